@@ -28,6 +28,9 @@ fn main() {
             name,
             close_on_exit,
             start_suspended,
+            login_shell,
+            shell,
+            env,
         })) = opts.command
         {
             let command_cli_action = CliAction::NewPane {
@@ -36,9 +39,25 @@ fn main() {
                 direction,
                 cwd,
                 floating,
+                snap: None,
+                width: None,
+                height: None,
+                aspect: None,
+                place_at: None,
                 name,
                 close_on_exit,
                 start_suspended,
+                login_shell,
+                shell,
+                watch: vec![],
+                output_socket: None,
+                pin_matching: None,
+                cwd_from_focus: false,
+                no_frame: false,
+                capture_exit: None,
+                env,
+                fixed_size: None,
+                reset_geometry: false,
             };
             commands::send_action_to_session(command_cli_action, opts.session, config);
             std::process::exit(0);
@@ -47,6 +66,7 @@ fn main() {
             file,
             direction,
             line_number,
+            column,
             floating,
             cwd,
         })) = opts.command
@@ -62,6 +82,7 @@ fn main() {
                 file,
                 direction,
                 line_number,
+                column,
                 floating,
                 cwd,
             };
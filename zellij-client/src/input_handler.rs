@@ -15,9 +15,16 @@ use zellij_utils::{
         options::Options,
     },
     ipc::{ClientToServerMsg, ExitReason},
+    position::Position,
     termwiz::input::InputEvent,
 };
 
+use std::time::{Duration, Instant};
+
+/// Left clicks at the same position within this interval of each other count as a double (or
+/// triple) click
+const MULTI_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
 #[derive(Debug, Clone, Copy)]
 enum HeldMouseButton {
     Left,
@@ -44,6 +51,7 @@ struct InputHandler {
     should_exit: bool,
     receive_input_instructions: Receiver<(InputInstruction, ErrorContext)>,
     holding_mouse: Option<HeldMouseButton>,
+    last_left_click: Option<(Position, Instant, usize)>, // usize - the click count
     mouse_mode_active: bool,
 }
 
@@ -68,6 +76,7 @@ impl InputHandler {
             should_exit: false,
             receive_input_instructions,
             holding_mouse: None,
+            last_left_click: None,
             mouse_mode_active: false,
         }
     }
@@ -209,7 +218,12 @@ impl InputHandler {
                     if self.holding_mouse.is_some() {
                         self.dispatch_action(Action::MouseHoldLeft(point), None);
                     } else {
-                        self.dispatch_action(Action::LeftClick(point), None);
+                        let action = match self.count_left_click(point) {
+                            2 => Action::LeftDoubleClick(point),
+                            3 => Action::LeftTripleClick(point),
+                            _ => Action::LeftClick(point),
+                        };
+                        self.dispatch_action(action, None);
                     }
                     self.holding_mouse = Some(HeldMouseButton::Left);
                 },
@@ -262,6 +276,20 @@ impl InputHandler {
             },
         }
     }
+    /// Returns how many times in a row the left button was clicked at this position, going back
+    /// to a single click after a triple click
+    fn count_left_click(&mut self, point: Position) -> usize {
+        let click_count = match self.last_left_click {
+            Some((last_point, last_click_time, click_count))
+                if last_point == point && last_click_time.elapsed() < MULTI_CLICK_INTERVAL =>
+            {
+                click_count % 3 + 1
+            },
+            _ => 1,
+        };
+        self.last_left_click = Some((point, Instant::now(), click_count));
+        click_count
+    }
     /// Dispatches an [`Action`].
     ///
     /// This function's body dictates what each [`Action`] actually does when
@@ -290,19 +318,28 @@ impl InputHandler {
                 self.exit(ExitReason::NormalDetached);
                 should_break = true;
             },
-            Action::SwitchToMode(mode) => {
+            Action::SwitchToMode(mode) | Action::SwitchToModeTimed(mode, _) => {
                 // this is an optimistic update, we should get a SwitchMode instruction from the
                 // server later that atomically changes the mode as well
                 self.mode = mode;
                 self.os_input
                     .send_to_server(ClientToServerMsg::Action(action, None));
             },
+            Action::EnterResizeLoop => {
+                // optimistic as well, leaving the loop is left to the server since only it knows
+                // which mode to return to
+                self.mode = InputMode::Resize;
+                self.os_input
+                    .send_to_server(ClientToServerMsg::Action(action, None));
+            },
             Action::CloseFocus
+            | Action::CloseFocusSmart
             | Action::ClearScreen
             | Action::NewPane(..)
             | Action::Run(_)
             | Action::ToggleFloatingPanes
             | Action::TogglePaneEmbedOrFloating
+            | Action::PeekPaneFloating
             | Action::NewTab(..)
             | Action::GoToNextTab
             | Action::GoToPreviousTab
@@ -317,6 +354,22 @@ impl InputHandler {
                 self.command_is_executing
                     .wait_until_input_thread_is_unblocked();
             },
+            Action::Sequence(actions) => {
+                for action in actions {
+                    if self.dispatch_action(action, client_id) {
+                        should_break = true;
+                        break;
+                    }
+                }
+            },
+            Action::Repeat(count, action) => {
+                for _ in 0..count {
+                    if self.dispatch_action((*action).clone(), client_id) {
+                        should_break = true;
+                        break;
+                    }
+                }
+            },
             Action::ToggleMouseMode => {
                 if self.mouse_mode_active {
                     self.os_input.disable_mouse().non_fatal();
@@ -1094,6 +1094,7 @@ impl std::fmt::Display for OldAction {
             Self::TogglePaneEmbedOrFloating => write!(f, "TogglePaneEmbedOrFloating"),
             Self::ToggleFloatingPanes => write!(f, "ToggleFloatingPanes"),
             Self::CloseFocus => write!(f, "CloseFocus"),
+            Self::PaneNameInput(bytes) if bytes == &[0] => write!(f, "ClearPaneName"),
             Self::PaneNameInput(bytes) => write!(
                 f,
                 "PaneNameInput {}",
@@ -1112,6 +1113,7 @@ impl std::fmt::Display for OldAction {
             Self::GoToTab(index) => write!(f, "GoToTab {}", index),
             Self::ToggleTab => write!(f, "ToggleTab"),
             // Self::TabNameInput(bytes) => write!(f, "TabNameInput {}", format!("{}", bytes.iter().map(|c| format!("{}", *c)).collect::<Vec<String>>().join(" "))),
+            Self::TabNameInput(bytes) if bytes == &[0] => write!(f, "ClearTabName"),
             Self::TabNameInput(bytes) => write!(
                 f,
                 "TabNameInput {}",
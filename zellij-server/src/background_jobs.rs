@@ -10,13 +10,22 @@ use std::time::{Duration, Instant};
 
 use crate::panes::PaneId;
 use crate::screen::ScreenInstruction;
+use crate::tab::copy_filter::{filter_through, COPY_FILTER_TIMEOUT};
 use crate::thread_bus::Bus;
+use crate::ClientId;
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum BackgroundJob {
     DisplayPaneError(Vec<PaneId>, String),
-    AnimatePluginLoading(u32),       // u32 - plugin_id
-    StopPluginLoadingAnimation(u32), // u32 - plugin_id
+    AnimatePluginLoading(u32),           // u32 - plugin_id
+    StopPluginLoadingAnimation(u32),     // u32 - plugin_id
+    RunTabTour(ClientId, Vec<u32>, u64), // Vec<u32> - tab indices, u64 - delay in ms
+    StopTabTour(ClientId),
+    TabTourEnded(ClientId),
+    ModeTimeout(ClientId, u64), // u64 - timeout in ms
+    PollPendingDetach(u64),     // u64 - interval in ms
+    // the filter command and the text to pipe through it
+    RunCopyFilter(String, String, PaneId, ClientId),
     Exit,
 }
 
@@ -28,6 +37,12 @@ impl From<&BackgroundJob> for BackgroundJobContext {
             BackgroundJob::StopPluginLoadingAnimation(..) => {
                 BackgroundJobContext::StopPluginLoadingAnimation
             },
+            BackgroundJob::RunTabTour(..) => BackgroundJobContext::RunTabTour,
+            BackgroundJob::StopTabTour(..) => BackgroundJobContext::StopTabTour,
+            BackgroundJob::TabTourEnded(..) => BackgroundJobContext::TabTourEnded,
+            BackgroundJob::ModeTimeout(..) => BackgroundJobContext::ModeTimeout,
+            BackgroundJob::PollPendingDetach(..) => BackgroundJobContext::PollPendingDetach,
+            BackgroundJob::RunCopyFilter(..) => BackgroundJobContext::RunCopyFilter,
             BackgroundJob::Exit => BackgroundJobContext::Exit,
         }
     }
@@ -40,6 +55,7 @@ pub(crate) fn background_jobs_main(bus: Bus<BackgroundJob>) -> Result<()> {
     let err_context = || "failed to write to pty".to_string();
     let mut running_jobs: HashMap<BackgroundJob, Instant> = HashMap::new();
     let mut loading_plugins: HashMap<u32, Arc<AtomicBool>> = HashMap::new(); // u32 - plugin_id
+    let mut tab_tours: HashMap<ClientId, Arc<AtomicBool>> = HashMap::new();
 
     loop {
         let (event, mut err_ctx) = bus.recv().with_context(err_context)?;
@@ -93,10 +109,100 @@ pub(crate) fn background_jobs_main(bus: Bus<BackgroundJob>) -> Result<()> {
                     loading_plugin.store(false, Ordering::SeqCst);
                 }
             },
+            BackgroundJob::RunTabTour(client_id, tab_indices, delay_ms) => {
+                // only one tour per client, starting a new one cancels the previous
+                if let Some(running_tour) = tab_tours.remove(&client_id) {
+                    running_tour.store(false, Ordering::SeqCst);
+                }
+                let tour_is_running = Arc::new(AtomicBool::new(true));
+                task::spawn({
+                    let senders = bus.senders.clone();
+                    let tour_is_running = tour_is_running.clone();
+                    async move {
+                        for (i, tab_index) in tab_indices.iter().enumerate() {
+                            if !tour_is_running.load(Ordering::SeqCst) {
+                                break;
+                            }
+                            let _ = senders.send_to_screen(ScreenInstruction::GoToTab(
+                                *tab_index,
+                                Some(client_id),
+                            ));
+                            if i + 1 < tab_indices.len() {
+                                task::sleep(std::time::Duration::from_millis(delay_ms)).await;
+                            }
+                        }
+                        tour_is_running.store(false, Ordering::SeqCst);
+                        let _ =
+                            senders.send_to_background_jobs(BackgroundJob::TabTourEnded(client_id));
+                    }
+                });
+                tab_tours.insert(client_id, tour_is_running);
+            },
+            BackgroundJob::StopTabTour(client_id) => {
+                if let Some(running_tour) = tab_tours.remove(&client_id) {
+                    running_tour.store(false, Ordering::SeqCst);
+                }
+            },
+            BackgroundJob::TabTourEnded(client_id) => {
+                // by now the client might have started another tour, which we keep
+                let tour_has_ended = tab_tours
+                    .get(&client_id)
+                    .map(|running_tour| !running_tour.load(Ordering::SeqCst))
+                    .unwrap_or(false);
+                if tour_has_ended {
+                    tab_tours.remove(&client_id);
+                }
+            },
+            BackgroundJob::ModeTimeout(client_id, timeout_ms) => {
+                // the screen keeps the deadline and decides whether the timer is still relevant
+                // once it elapses, so there's nothing to cancel here
+                task::spawn({
+                    let senders = bus.senders.clone();
+                    async move {
+                        task::sleep(std::time::Duration::from_millis(timeout_ms)).await;
+                        let _ = senders
+                            .send_to_screen(ScreenInstruction::ModeTimeoutElapsed(client_id));
+                    }
+                });
+            },
+            BackgroundJob::PollPendingDetach(interval_ms) => {
+                task::spawn({
+                    let senders = bus.senders.clone();
+                    async move {
+                        task::sleep(std::time::Duration::from_millis(interval_ms)).await;
+                        let _ = senders.send_to_screen(ScreenInstruction::CheckPendingDetach);
+                    }
+                });
+            },
+            BackgroundJob::RunCopyFilter(command, text, pane_id, client_id) => {
+                // the filter blocks until it exits or times out, so it gets a thread of its own
+                task::spawn_blocking({
+                    let senders = bus.senders.clone();
+                    move || match filter_through(&command, &text, COPY_FILTER_TIMEOUT) {
+                        Ok(filtered_text) => {
+                            let _ = senders.send_to_screen(ScreenInstruction::CopyFilteredText(
+                                filtered_text,
+                                client_id,
+                            ));
+                        },
+                        Err(err) => {
+                            Err::<(), _>(err).non_fatal();
+                            let _ =
+                                senders.send_to_background_jobs(BackgroundJob::DisplayPaneError(
+                                    vec![pane_id],
+                                    "COPY FILTER FAILED!".into(),
+                                ));
+                        },
+                    }
+                });
+            },
             BackgroundJob::Exit => {
                 for loading_plugin in loading_plugins.values() {
                     loading_plugin.store(false, Ordering::SeqCst);
                 }
+                for running_tour in tab_tours.values() {
+                    running_tour.store(false, Ordering::SeqCst);
+                }
                 return Ok(());
             },
         }
@@ -10,6 +10,7 @@ mod pty;
 mod pty_writer;
 mod route;
 mod screen;
+mod session_layout_metadata;
 mod terminal_bytes;
 mod thread_bus;
 mod ui;
@@ -17,9 +18,9 @@ mod ui;
 use background_jobs::{background_jobs_main, BackgroundJob};
 use log::info;
 use pty_writer::{pty_writer_main, PtyWriteInstruction};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::{
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{Arc, RwLock},
     thread,
 };
@@ -31,6 +32,7 @@ use wasmer::Store;
 
 use crate::{
     os_input_output::ServerOsApi,
+    panes::PaneId,
     plugins::{plugin_thread_main, PluginInstruction},
     pty::{get_default_shell, pty_thread_main, Pty, PtyInstruction},
     screen::{screen_thread_main, ScreenInstruction},
@@ -40,7 +42,10 @@ use route::route_thread_main;
 use zellij_utils::{
     channels::{self, ChannelWithContext, SenderWithContext},
     cli::CliArgs,
-    consts::{DEFAULT_SCROLL_BUFFER_SIZE, SCROLL_BUFFER_SIZE},
+    consts::{
+        DEFAULT_SCROLL_BUFFER_SIZE, MAX_SESSION_VARS, MAX_SESSION_VAR_NAME_LEN,
+        MAX_SESSION_VAR_VALUE_LEN, SCROLL_BUFFER_SIZE,
+    },
     data::{Event, PluginCapabilities},
     errors::{prelude::*, ContextType, ErrorInstruction, FatalError, ServerContext},
     input::{
@@ -78,6 +83,9 @@ pub enum ServerInstruction {
     ConnStatus(ClientId),
     ActiveClients(ClientId),
     Log(Vec<String>, ClientId),
+    RenameSession(String, ClientId), // String is the new session name
+    SetSessionVar(String, String, Option<PaneId>), // name, value, pane whose exit status it is
+    QuerySessionVar(String, ClientId),
 }
 
 impl From<&ServerInstruction> for ServerContext {
@@ -95,6 +103,9 @@ impl From<&ServerInstruction> for ServerContext {
             ServerInstruction::ConnStatus(..) => ServerContext::ConnStatus,
             ServerInstruction::ActiveClients(_) => ServerContext::ActiveClients,
             ServerInstruction::Log(..) => ServerContext::Log,
+            ServerInstruction::RenameSession(..) => ServerContext::RenameSession,
+            ServerInstruction::SetSessionVar(..) => ServerContext::SetSessionVar,
+            ServerInstruction::QuerySessionVar(..) => ServerContext::QuerySessionVar,
         }
     }
 }
@@ -177,12 +188,14 @@ macro_rules! send_to_client {
 #[derive(Clone, Debug, PartialEq)]
 pub(crate) struct SessionState {
     clients: HashMap<ClientId, Option<Size>>,
+    vars: BTreeMap<String, String>,
 }
 
 impl SessionState {
     pub fn new() -> Self {
         SessionState {
             clients: HashMap::new(),
+            vars: BTreeMap::new(),
         }
     }
     pub fn new_client(&mut self) -> ClientId {
@@ -231,9 +244,56 @@ impl SessionState {
     pub fn client_ids(&self) -> Vec<ClientId> {
         self.clients.keys().copied().collect()
     }
+    pub fn set_var(&mut self, name: String, value: String) -> Result<()> {
+        if name.is_empty() || name.len() > MAX_SESSION_VAR_NAME_LEN {
+            bail!("session variable name must be 1 to {MAX_SESSION_VAR_NAME_LEN} bytes long");
+        }
+        if value.len() > MAX_SESSION_VAR_VALUE_LEN {
+            bail!(
+                "value of session variable '{}' is longer than {} bytes",
+                name,
+                MAX_SESSION_VAR_VALUE_LEN
+            );
+        }
+        if !self.vars.contains_key(&name) && self.vars.len() >= MAX_SESSION_VARS {
+            bail!("cannot set '{name}', a session holds at most {MAX_SESSION_VARS} variables");
+        }
+        self.vars.insert(name, value);
+        Ok(())
+    }
+    pub fn get_var(&self, name: &str) -> Option<&String> {
+        self.vars.get(name)
+    }
+    /// Replaces the {{NAME}} placeholders in `text` with the value of the session variable NAME,
+    /// leaving those of unset variables as they are
+    pub fn expand_vars(&self, text: &str) -> String {
+        let mut expanded = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find("{{") {
+            let (before, placeholder) = rest.split_at(start);
+            expanded.push_str(before);
+            let value = placeholder.find("}}").and_then(|end| {
+                self.vars
+                    .get(&placeholder[2..end])
+                    .map(|value| (value, end))
+            });
+            match value {
+                Some((value, end)) => {
+                    expanded.push_str(value);
+                    rest = &placeholder[end + 2..];
+                },
+                None => {
+                    expanded.push_str("{{");
+                    rest = &placeholder[2..];
+                },
+            }
+        }
+        expanded.push_str(rest);
+        expanded
+    }
 }
 
-pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
+pub fn start_server(mut os_input: Box<dyn ServerOsApi>, mut socket_path: PathBuf) {
     info!("Starting Zellij server!");
 
     // preserve the current umask: read current value by setting to another mode, and then restoring it
@@ -364,6 +424,7 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                             floating_panes_layout,
                             tab_name,
                             swap_layouts,
+                            false,
                             client_id,
                         ))
                         .unwrap()
@@ -382,6 +443,17 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                         );
                     }
 
+                    for pinned_tab_index in layout.pinned_tab_indices() {
+                        session_data
+                            .read()
+                            .unwrap()
+                            .as_ref()
+                            .unwrap()
+                            .senders
+                            .send_to_screen(ScreenInstruction::PinTab(pinned_tab_index))
+                            .unwrap();
+                    }
+
                     if let Some(focused_tab_index) = layout.focused_tab_index() {
                         session_data
                             .read()
@@ -635,6 +707,66 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     session_state
                 );
             },
+            ServerInstruction::SetSessionVar(name, value, pane_id) => {
+                let set_var = session_state.write().unwrap().set_var(name, value);
+                if let Err(e) = set_var {
+                    // the exit status of a held pane is otherwise lost without a trace
+                    if let Some(pane_id) = pane_id {
+                        if let Some(session_data) = session_data.read().unwrap().as_ref() {
+                            let _ = session_data.senders.send_to_background_jobs(
+                                BackgroundJob::DisplayPaneError(
+                                    vec![pane_id],
+                                    "EXIT STATUS NOT SAVED!".into(),
+                                ),
+                            );
+                        }
+                    }
+                    Err::<(), _>(e)
+                        .context("failed to set session variable")
+                        .non_fatal();
+                }
+            },
+            ServerInstruction::QuerySessionVar(name, client_id) => {
+                // an unset variable is printed as nothing rather than as an error, so that
+                // scripts can compare it against an empty string
+                let value: Vec<String> = session_state
+                    .read()
+                    .unwrap()
+                    .get_var(&name)
+                    .cloned()
+                    .into_iter()
+                    .collect();
+                send_to_client!(
+                    client_id,
+                    os_input,
+                    ServerToClientMsg::Log(value),
+                    session_state
+                );
+            },
+            ServerInstruction::RenameSession(session_name, client_id) => {
+                match rename_session_socket(&socket_path, &session_name) {
+                    Ok(new_socket_path) => {
+                        socket_path = new_socket_path;
+                        envs::set_session_name(session_name.clone());
+                        session_data
+                            .read()
+                            .unwrap()
+                            .as_ref()
+                            .unwrap()
+                            .senders
+                            .send_to_screen(ScreenInstruction::RenameSession(session_name))
+                            .unwrap();
+                    },
+                    Err(error) => {
+                        send_to_client!(
+                            client_id,
+                            os_input,
+                            ServerToClientMsg::Log(vec![error]),
+                            session_state
+                        );
+                    },
+                }
+            },
         }
     }
 
@@ -651,6 +783,29 @@ pub struct SessionOptions {
     pub plugins: Option<PluginsConfig>,
 }
 
+/// Renames the socket of the session, which is what identifies the session to every other zellij
+/// command, returning its new path.
+fn rename_session_socket(socket_path: &Path, session_name: &str) -> Result<PathBuf, String> {
+    let new_socket_path = socket_path.with_file_name(session_name);
+    // linking (unlike renaming) fails if a session with the new name already exists, so that two
+    // sessions renamed at once cannot take each other's place
+    std::fs::hard_link(socket_path, &new_socket_path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::AlreadyExists => {
+            format!("A session named '{}' already exists", session_name)
+        },
+        _ => format!("Failed to rename session to '{}': {}", session_name, e),
+    })?;
+    if let Err(e) = std::fs::remove_file(socket_path) {
+        // keep the session listed under its old name only
+        drop(std::fs::remove_file(&new_socket_path));
+        return Err(format!(
+            "Failed to rename session to '{}': {}",
+            session_name, e
+        ));
+    }
+    Ok(new_socket_path)
+}
+
 fn init_session(
     os_input: Box<dyn ServerOsApi>,
     to_server: SenderWithContext<ServerInstruction>,
@@ -828,7 +983,7 @@ fn init_session(
                 Some(&to_plugin),
                 Some(&to_server),
                 Some(&to_pty_writer),
-                None,
+                Some(&to_background_jobs),
                 Some(os_input.clone()),
             );
             || background_jobs_main(background_jobs_bus).fatal()
@@ -868,3 +1023,7 @@ fn get_store() -> Store {
     log::info!("Compiling plugins using Singlepass");
     Store::new(&wasmer::Universal::new(wasmer::Singlepass::default()).engine())
 }
+
+#[cfg(test)]
+#[path = "./unit/server_tests.rs"]
+mod server_tests;
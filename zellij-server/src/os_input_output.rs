@@ -182,6 +182,7 @@ fn handle_openpty(
             }
             command
                 .args(&cmd.args)
+                .envs(&cmd.env)
                 .pre_exec(move || -> std::io::Result<()> {
                     if libc::login_tty(pid_secondary) != 0 {
                         panic!("failed to set controlling terminal");
@@ -286,7 +287,7 @@ fn spawn_terminal(
     // secondary fd
     let mut failover_cmd_args = None;
     let cmd = match terminal_action {
-        TerminalAction::OpenFile(mut file_to_open, line_number, cwd) => {
+        TerminalAction::OpenFile(mut file_to_open, line_number, column, cwd) => {
             if file_to_open.is_relative() {
                 if let Some(cwd) = cwd.as_ref() {
                     file_to_open = cwd.join(file_to_open);
@@ -309,20 +310,39 @@ fn spawn_terminal(
                 .into_string()
                 .expect("Not valid Utf8 Encoding");
             if let Some(line_number) = line_number {
-                if command.ends_with("vim")
-                    || command.ends_with("nvim")
-                    || command.ends_with("emacs")
+                if command.ends_with("vim") || command.ends_with("nvim") {
+                    failover_cmd_args = Some(vec![file_to_open.clone()]);
+                    match column {
+                        Some(column) => {
+                            args.push(format!("+call cursor({},{})", line_number, column))
+                        },
+                        None => args.push(format!("+{}", line_number)),
+                    }
+                    args.push(file_to_open);
+                } else if command.ends_with("emacs")
                     || command.ends_with("nano")
                     || command.ends_with("kak")
                 {
                     failover_cmd_args = Some(vec![file_to_open.clone()]);
-                    args.push(format!("+{}", line_number));
+                    match column {
+                        // nano separates the column with a comma rather than a colon
+                        Some(column) if command.ends_with("nano") => {
+                            args.push(format!("+{},{}", line_number, column))
+                        },
+                        Some(column) => args.push(format!("+{}:{}", line_number, column)),
+                        None => args.push(format!("+{}", line_number)),
+                    }
                     args.push(file_to_open);
                 } else if command.ends_with("hx") || command.ends_with("helix") {
                     // at the time of writing, helix only supports this syntax
                     // and it might be a good idea to leave this here anyway
                     // to keep supporting old versions
-                    args.push(format!("{}:{}", file_to_open, line_number));
+                    match column {
+                        Some(column) => {
+                            args.push(format!("{}:{}:{}", file_to_open, line_number, column))
+                        },
+                        None => args.push(format!("{}:{}", file_to_open, line_number)),
+                    }
                 } else {
                     args.push(file_to_open);
                 }
@@ -335,6 +355,14 @@ fn spawn_terminal(
                 cwd,
                 hold_on_close: false,
                 hold_on_start: false,
+                watch_paths: vec![],
+                output_socket: None,
+                pin_matching: None,
+                cwd_from_focus: false,
+                no_frame: false,
+                capture_exit: None,
+                env: BTreeMap::new(),
+                fixed_pty_size: None,
             }
         },
         TerminalAction::RunCommand(command) => command,
@@ -514,6 +542,11 @@ pub trait ServerOsApi: Send + Sync {
     fn clear_terminal_id(&self, terminal_id: u32) -> Result<()>;
     fn cache_resizes(&mut self) {}
     fn apply_cached_resizes(&mut self) {}
+    /// Whether a process other than the session leader (usually the shell) is in the foreground
+    /// of the terminal with ID `terminal_id`, eg. a command the shell is waiting on
+    fn has_foreground_process(&self, _terminal_id: u32) -> bool {
+        false
+    }
 }
 
 impl ServerOsApi for ServerOsInputOutput {
@@ -738,6 +771,23 @@ impl ServerOsApi for ServerOsInputOutput {
         default_palette()
     }
 
+    fn has_foreground_process(&self, terminal_id: u32) -> bool {
+        let fd = match self.terminal_id_to_raw_fd.lock() {
+            Ok(terminal_id_to_raw_fd) => terminal_id_to_raw_fd.get(&terminal_id).copied().flatten(),
+            Err(_) => None,
+        };
+        // the process we spawn in the terminal is the leader of its session, so if the
+        // foreground process group of the terminal is anything else, it's running a job
+        match fd {
+            Some(fd) => match (unistd::tcgetpgrp(fd), termios::tcgetsid(fd)) {
+                (Ok(foreground_process_group), Ok(session_id)) => {
+                    foreground_process_group != session_id
+                },
+                _ => false,
+            },
+            None => false,
+        }
+    }
     fn get_cwd(&self, pid: Pid) -> Option<PathBuf> {
         let mut system_info = System::new();
         // Update by minimizing information.
@@ -907,7 +907,10 @@ impl OutputBuffer {
         let mut terminal_characters: Vec<TerminalCharacter> = row.columns.iter().copied().collect();
         // pad row
         let row_width = row.width();
-        if row_width < viewport_width {
+        if row_width > viewport_width {
+            // lines are truncated rather than wrapped
+            terminal_characters.truncate(row.position_accounting_for_widechars(viewport_width));
+        } else if row_width < viewport_width {
             let mut padding = vec![EMPTY_TERMINAL_CHARACTER; viewport_width - row_width];
             terminal_characters.append(&mut padding);
         }
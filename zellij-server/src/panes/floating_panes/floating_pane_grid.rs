@@ -2,7 +2,9 @@ use crate::tab::{MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH};
 use crate::{panes::PaneId, tab::Pane};
 use std::cmp::Ordering;
 use std::collections::HashMap;
-use zellij_utils::data::{Direction, ResizeStrategy};
+use zellij_utils::data::{
+    AspectRatio, Direction, FloatingPanePlacement, FloatingSnap, ResizeStrategy,
+};
 use zellij_utils::errors::prelude::*;
 use zellij_utils::pane_size::{Dimension, PaneGeom, Size, Viewport};
 
@@ -72,6 +74,26 @@ impl<'a> FloatingPaneGrid<'a> {
             .with_context(err_context)
     }
 
+    /// Moves the top left corner of the pane to these coordinates, as far as the pane stays inside
+    /// the viewport
+    pub fn move_pane_to(&mut self, pane_id: PaneId, x: usize, y: usize) -> Result<()> {
+        let err_context = || format!("failed to move pane {pane_id:?} to ({x}, {y})");
+
+        let current_position = self
+            .panes
+            .borrow()
+            .get(&pane_id)
+            .map(|pane| pane.position_and_size())
+            .with_context(|| no_pane_id(&pane_id))
+            .with_context(err_context)?;
+        self.move_pane_by(
+            pane_id,
+            x as isize - current_position.x as isize,
+            y as isize - current_position.y as isize,
+        )
+        .with_context(err_context)
+    }
+
     fn set_pane_geom(&mut self, pane_id: PaneId, new_pane_geom: PaneGeom) -> Result<()> {
         let err_context = || {
             format!(
@@ -797,6 +819,67 @@ impl<'a> FloatingPaneGrid<'a> {
         };
         Some(previous_active_pane_id)
     }
+    pub fn snap_pane(&mut self, pane_id: PaneId, placement: &FloatingPanePlacement) -> Result<()> {
+        let new_pane_geom = snapped_geom(&self.viewport, placement);
+        self.set_pane_geom(pane_id, new_pane_geom)
+            .with_context(|| format!("failed to snap pane {pane_id:?} to {placement:?}"))
+    }
+    pub fn restore_pane_geom(&mut self, pane_id: PaneId, geom: PaneGeom) -> Result<()> {
+        // the viewport might have shrunk since we last saw this geometry
+        let mut new_pane_geom = geom;
+        let cols = geom
+            .cols
+            .as_usize()
+            .max(MIN_TERMINAL_WIDTH)
+            .min(self.viewport.cols);
+        let rows = geom
+            .rows
+            .as_usize()
+            .max(MIN_TERMINAL_HEIGHT)
+            .min(self.viewport.rows);
+        new_pane_geom.cols.set_inner(cols);
+        new_pane_geom.rows.set_inner(rows);
+        new_pane_geom.x = geom
+            .x
+            .max(self.viewport.x)
+            .min(self.viewport.x + self.viewport.cols - cols);
+        new_pane_geom.y = geom
+            .y
+            .max(self.viewport.y)
+            .min(self.viewport.y + self.viewport.rows - rows);
+        self.set_pane_geom(pane_id, new_pane_geom)
+            .with_context(|| format!("failed to restore pane {pane_id:?} to {geom:?}"))
+    }
+    pub fn set_pane_size(
+        &mut self,
+        pane_id: PaneId,
+        cols: Option<usize>,
+        rows: Option<usize>,
+    ) -> Result<()> {
+        let err_context = || format!("failed to set size of pane {pane_id:?}");
+        let mut new_pane_geom = self
+            .panes
+            .borrow()
+            .get(&pane_id)
+            .with_context(|| no_pane_id(&pane_id))
+            .with_context(err_context)?
+            .position_and_size();
+        // if the pane would no longer fit in the viewport, we move it back in rather than shrink it
+        if let Some(cols) = cols {
+            let cols = cols.max(1).min(self.viewport.cols);
+            let viewport_right_edge = self.viewport.x + self.viewport.cols;
+            new_pane_geom.x = new_pane_geom.x.min(viewport_right_edge - cols);
+            new_pane_geom.cols.set_inner(cols);
+        }
+        if let Some(rows) = rows {
+            let rows = rows.max(1).min(self.viewport.rows);
+            let viewport_bottom_edge = self.viewport.y + self.viewport.rows;
+            new_pane_geom.y = new_pane_geom.y.min(viewport_bottom_edge - rows);
+            new_pane_geom.rows.set_inner(rows);
+        }
+        self.set_pane_geom(pane_id, new_pane_geom)
+            .with_context(err_context)
+    }
     pub fn find_room_for_new_pane(&self) -> Option<PaneGeom> {
         let panes = self.panes.borrow();
         let pane_geoms: Vec<PaneGeom> = panes.values().map(|p| p.position_and_size()).collect();
@@ -919,6 +1002,77 @@ fn half_size_bottom_right_geom(space: &Viewport, offset: usize) -> PaneGeom {
     geom
 }
 
+fn snapped_geom(space: &Viewport, placement: &FloatingPanePlacement) -> PaneGeom {
+    let snap = placement.snap.unwrap_or(FloatingSnap::Center);
+    let default_cols = match snap {
+        FloatingSnap::Top | FloatingSnap::Bottom => space.cols,
+        FloatingSnap::Center => space.cols / 2,
+        _ => space.cols / 3,
+    };
+    let default_rows = match snap {
+        FloatingSnap::Left | FloatingSnap::Right => space.rows,
+        FloatingSnap::Top | FloatingSnap::Bottom => space.rows / 3,
+        _ => space.rows / 2,
+    };
+    let (cols, rows) = match placement.aspect {
+        Some(aspect) => {
+            // a single given side decides the size, with the other following the ratio
+            let (max_cols, max_rows) = match (placement.width, placement.height) {
+                (None, None) => (default_cols, default_rows),
+                (width, height) => (
+                    width.unwrap_or(space.cols).min(space.cols),
+                    height.unwrap_or(space.rows).min(space.rows),
+                ),
+            };
+            aspect_fitted_size(aspect, max_cols, max_rows)
+        },
+        None => (
+            placement.width.unwrap_or(default_cols),
+            placement.height.unwrap_or(default_rows),
+        ),
+    };
+    let cols = cols.max(MIN_TERMINAL_WIDTH).min(space.cols);
+    let rows = rows.max(MIN_TERMINAL_HEIGHT).min(space.rows);
+    let left_x = space.x;
+    let center_x = space.x + (space.cols - cols) / 2;
+    let right_x = space.x + space.cols - cols;
+    let top_y = space.y;
+    let center_y = space.y + (space.rows - rows) / 2;
+    let bottom_y = space.y + space.rows - rows;
+    let (x, y) = match snap {
+        FloatingSnap::Top => (center_x, top_y),
+        FloatingSnap::Bottom => (center_x, bottom_y),
+        FloatingSnap::Left => (left_x, center_y),
+        FloatingSnap::Right => (right_x, center_y),
+        FloatingSnap::TopLeft => (left_x, top_y),
+        FloatingSnap::TopRight => (right_x, top_y),
+        FloatingSnap::BottomLeft => (left_x, bottom_y),
+        FloatingSnap::BottomRight => (right_x, bottom_y),
+        FloatingSnap::Center => (center_x, center_y),
+    };
+    let mut geom = PaneGeom {
+        x,
+        y,
+        cols: Dimension::fixed(cols),
+        rows: Dimension::fixed(rows),
+        is_stacked: false,
+    };
+    geom.cols.set_inner(cols);
+    geom.rows.set_inner(rows);
+    geom
+}
+
+// the largest size with this ratio of columns to rows that fits in max_cols x max_rows
+fn aspect_fitted_size(aspect: AspectRatio, max_cols: usize, max_rows: usize) -> (usize, usize) {
+    let rows_for_max_cols = (max_cols * aspect.rows + aspect.cols / 2) / aspect.cols;
+    if rows_for_max_cols <= max_rows {
+        (max_cols, rows_for_max_cols)
+    } else {
+        let cols_for_max_rows = (max_rows * aspect.cols + aspect.rows / 2) / aspect.rows;
+        (cols_for_max_rows.min(max_cols), max_rows)
+    }
+}
+
 fn pane_geom_is_inside_viewport(viewport: &Viewport, geom: &PaneGeom) -> bool {
     geom.y >= viewport.y
         && geom.y + geom.rows.as_usize() <= viewport.y + viewport.rows
@@ -1,6 +1,6 @@
 mod floating_pane_grid;
 use zellij_utils::{
-    data::{Direction, PaneInfo, ResizeStrategy},
+    data::{Direction, FloatingPanePlacement, PaneInfo, ResizeStrategy},
     position::Position,
 };
 
@@ -45,6 +45,7 @@ pub struct FloatingPanes {
     session_is_mirrored: bool,
     desired_pane_positions: HashMap<PaneId, PaneGeom>, // this represents the positions of panes the user moved with intention, rather than by resizing the terminal window
     z_indices: Vec<PaneId>,
+    pinned_panes: HashSet<PaneId>, // always on top of the z_indices of the other panes
     active_panes: ActivePanes,
     show_panes: bool,
     pane_being_moved_with_mouse: Option<(PaneId, Position)>,
@@ -80,6 +81,7 @@ impl FloatingPanes {
             style,
             desired_pane_positions: HashMap::new(),
             z_indices: vec![],
+            pinned_panes: HashSet::new(),
             show_panes: false,
             active_panes: ActivePanes::new(&os_input),
             pane_being_moved_with_mouse: None,
@@ -101,11 +103,15 @@ impl FloatingPanes {
     pub fn pane_ids(&self) -> impl Iterator<Item = &PaneId> {
         self.panes.keys()
     }
+    pub fn pane_ids_from_bottom_to_top(&self) -> Vec<PaneId> {
+        self.z_indices.clone()
+    }
     pub fn add_pane(&mut self, pane_id: PaneId, pane: Box<dyn Pane>) {
         self.desired_pane_positions
             .insert(pane_id, pane.position_and_size());
         self.panes.insert(pane_id, pane);
         self.z_indices.push(pane_id);
+        self.raise_pinned_panes();
     }
     pub fn replace_active_pane(
         &mut self,
@@ -149,6 +155,10 @@ impl FloatingPanes {
                 Ok(removed_pane)
             });
 
+        if self.pinned_panes.remove(&pane_id) {
+            self.pinned_panes.insert(with_pane_id);
+        }
+
         // update the desired_pane_positions to relate to the new pane
         if let Some(desired_pane_position) = self.desired_pane_positions.remove(&pane_id) {
             self.desired_pane_positions
@@ -161,6 +171,7 @@ impl FloatingPanes {
     }
     pub fn remove_pane(&mut self, pane_id: PaneId) -> Option<Box<dyn Pane>> {
         self.z_indices.retain(|p_id| *p_id != pane_id);
+        self.pinned_panes.remove(&pane_id);
         self.desired_pane_positions.remove(&pane_id);
         self.panes.remove(&pane_id)
     }
@@ -236,6 +247,56 @@ impl FloatingPanes {
         );
         floating_pane_grid.find_room_for_new_pane()
     }
+    pub fn snap_pane(
+        &mut self,
+        pane_id: PaneId,
+        placement: &FloatingPanePlacement,
+        os_api: &mut Box<dyn ServerOsApi>,
+    ) -> Result<()> {
+        let err_context = || format!("failed to snap floating pane {pane_id:?}");
+        let display_area = *self.display_area.borrow();
+        let viewport = *self.viewport.borrow();
+        let mut floating_pane_grid = FloatingPaneGrid::new(
+            &mut self.panes,
+            &mut self.desired_pane_positions,
+            display_area,
+            viewport,
+        );
+        floating_pane_grid
+            .snap_pane(pane_id, placement)
+            .with_context(err_context)?;
+        if let Some(pane) = self.panes.get_mut(&pane_id) {
+            resize_pty!(pane, os_api, self.senders, self.character_cell_size)
+                .with_context(err_context)?;
+        }
+        self.set_force_render();
+        Ok(())
+    }
+    pub fn restore_pane_geom(
+        &mut self,
+        pane_id: PaneId,
+        geom: PaneGeom,
+        os_api: &mut Box<dyn ServerOsApi>,
+    ) -> Result<()> {
+        let err_context = || format!("failed to restore geometry of floating pane {pane_id:?}");
+        let display_area = *self.display_area.borrow();
+        let viewport = *self.viewport.borrow();
+        let mut floating_pane_grid = FloatingPaneGrid::new(
+            &mut self.panes,
+            &mut self.desired_pane_positions,
+            display_area,
+            viewport,
+        );
+        floating_pane_grid
+            .restore_pane_geom(pane_id, geom)
+            .with_context(err_context)?;
+        if let Some(pane) = self.panes.get_mut(&pane_id) {
+            resize_pty!(pane, os_api, self.senders, self.character_cell_size)
+                .with_context(err_context)?;
+        }
+        self.set_force_render();
+        Ok(())
+    }
     pub fn position_floating_pane_layout(
         &mut self,
         floating_pane_layout: &FloatingPaneLayout,
@@ -437,6 +498,40 @@ impl FloatingPanes {
         Ok(false)
     }
 
+    pub fn set_active_pane_size(
+        &mut self,
+        client_id: ClientId,
+        os_api: &mut Box<dyn ServerOsApi>,
+        cols: Option<usize>,
+        rows: Option<usize>,
+    ) -> Result<bool> {
+        // true => successfully resized
+        let err_context =
+            || format!("failed to set size of active floating pane for client {client_id}");
+
+        let display_area = *self.display_area.borrow();
+        let viewport = *self.viewport.borrow();
+        if let Some(active_floating_pane_id) = self.active_panes.get(&client_id) {
+            let mut floating_pane_grid = FloatingPaneGrid::new(
+                &mut self.panes,
+                &mut self.desired_pane_positions,
+                display_area,
+                viewport,
+            );
+            floating_pane_grid
+                .set_pane_size(*active_floating_pane_id, cols, rows)
+                .with_context(err_context)?;
+
+            for pane in self.panes.values_mut() {
+                resize_pty!(pane, os_api, self.senders, self.character_cell_size)
+                    .with_context(err_context)?;
+            }
+            self.set_force_render();
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
     fn set_pane_active_at(&mut self, pane_id: PaneId) {
         if let Some(pane) = self.panes.get_mut(&pane_id) {
             pane.set_active_at(Instant::now());
@@ -571,6 +666,25 @@ impl FloatingPanes {
             self.set_force_render();
         }
     }
+    pub fn move_active_pane_to(&mut self, client_id: ClientId, x: usize, y: usize) -> Result<()> {
+        let display_area = *self.display_area.borrow();
+        let viewport = *self.viewport.borrow();
+        if let Some(active_pane_id) = self.active_panes.get(&client_id) {
+            let mut floating_pane_grid = FloatingPaneGrid::new(
+                &mut self.panes,
+                &mut self.desired_pane_positions,
+                display_area,
+                viewport,
+            );
+            floating_pane_grid
+                .move_pane_to(*active_pane_id, x, y)
+                .with_context(|| {
+                    format!("failed to move active floating pane for client {client_id}")
+                })?;
+            self.set_force_render();
+        }
+        Ok(())
+    }
     pub fn move_active_pane_left(&mut self, client_id: ClientId) {
         let display_area = *self.display_area.borrow();
         let viewport = *self.viewport.borrow();
@@ -687,14 +801,96 @@ impl FloatingPanes {
         }
         self.z_indices.retain(|p_id| *p_id != pane_id);
         self.z_indices.push(pane_id);
+        self.raise_pinned_panes();
         self.set_pane_active_at(pane_id);
         self.set_force_render();
     }
+    /// Pins the pane on top of the unpinned panes (or unpins it), returns whether it is pinned now
+    pub fn toggle_pane_pinned(&mut self, pane_id: PaneId) -> bool {
+        let is_pinned = !self.pinned_panes.contains(&pane_id);
+        self.set_pane_pinned(pane_id, is_pinned);
+        is_pinned
+    }
+    pub fn set_pane_pinned(&mut self, pane_id: PaneId, is_pinned: bool) {
+        if !self.panes.contains_key(&pane_id) {
+            return;
+        }
+        if is_pinned {
+            self.pinned_panes.insert(pane_id);
+        } else {
+            self.pinned_panes.remove(&pane_id);
+        }
+        self.raise_pinned_panes();
+        self.set_force_render();
+    }
+    pub fn pane_is_pinned(&self, pane_id: PaneId) -> bool {
+        self.pinned_panes.contains(&pane_id)
+    }
+    fn raise_pinned_panes(&mut self) {
+        // a stable sort, so the pinned and unpinned panes keep their order among themselves
+        let pinned_panes = &self.pinned_panes;
+        self.z_indices
+            .sort_by_key(|pane_id| pinned_panes.contains(pane_id));
+    }
     pub fn focus_pane(&mut self, pane_id: PaneId, client_id: ClientId) {
         self.active_panes
             .insert(client_id, pane_id, &mut self.panes);
         self.focus_pane_for_all_clients(pane_id);
     }
+    /// Focuses the bottom-most selectable pane, which is raised to the top, so that doing this
+    /// repeatedly cycles through the panes in z-order
+    pub fn focus_next_pane(&mut self, client_id: ClientId) {
+        let active_pane_id = self.active_panes.get(&client_id).copied();
+        let next_pane_id = self
+            .z_indices
+            .iter()
+            .copied()
+            .filter(|pane_id| Some(*pane_id) != active_pane_id)
+            .find(|pane_id| {
+                self.panes
+                    .get(pane_id)
+                    .map(|pane| pane.selectable())
+                    .unwrap_or(false)
+            });
+        if let Some(next_pane_id) = next_pane_id {
+            self.focus_pane(next_pane_id, client_id);
+        }
+    }
+    /// Focuses the selectable pane right below the focused one (wrapping around to the top-most
+    /// pane) and lowers the focused pane to the bottom, reversing `focus_next_pane`
+    pub fn focus_previous_pane(&mut self, client_id: ClientId) {
+        let active_pane_id = match self.active_panes.get(&client_id).copied() {
+            Some(active_pane_id) => active_pane_id,
+            None => return,
+        };
+        let selectable_pane_ids: Vec<PaneId> = self
+            .z_indices
+            .iter()
+            .copied()
+            .filter(|pane_id| {
+                self.panes
+                    .get(pane_id)
+                    .map(|pane| pane.selectable())
+                    .unwrap_or(false)
+            })
+            .collect();
+        let previous_pane_id = selectable_pane_ids
+            .iter()
+            .position(|pane_id| *pane_id == active_pane_id)
+            .and_then(|position| {
+                let previous_position = position
+                    .checked_sub(1)
+                    .unwrap_or(selectable_pane_ids.len() - 1);
+                selectable_pane_ids.get(previous_position).copied()
+            })
+            .filter(|pane_id| *pane_id != active_pane_id);
+        if let Some(previous_pane_id) = previous_pane_id {
+            self.focus_pane(previous_pane_id, client_id);
+            self.z_indices.retain(|pane_id| *pane_id != active_pane_id);
+            self.z_indices.insert(0, active_pane_id);
+            self.raise_pinned_panes();
+        }
+    }
     pub fn focus_pane_if_client_not_focused(&mut self, pane_id: PaneId, client_id: ClientId) {
         if self.active_panes.get(&client_id).is_none() {
             self.focus_pane(pane_id, client_id)
@@ -813,6 +1009,7 @@ impl FloatingPanes {
     }
     pub fn drain(&mut self) -> BTreeMap<PaneId, Box<dyn Pane>> {
         self.z_indices.clear();
+        self.pinned_panes.clear();
         self.desired_pane_positions.clear();
         match self.panes.iter().next().map(|(pid, _p)| *pid) {
             Some(first_pid) => self.panes.split_off(&first_pid),
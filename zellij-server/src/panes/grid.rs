@@ -6,6 +6,7 @@ use std::rc::Rc;
 use unicode_width::UnicodeWidthChar;
 use zellij_utils::data::Style;
 use zellij_utils::errors::prelude::*;
+use zellij_utils::lazy_static::lazy_static;
 use zellij_utils::regex::Regex;
 
 use std::{
@@ -35,7 +36,7 @@ use crate::panes::link_handler::LinkHandler;
 use crate::panes::search::SearchResult;
 use crate::panes::selection::Selection;
 use crate::panes::terminal_character::{
-    AnsiCode, CharacterStyles, CharsetIndex, Cursor, CursorShape, StandardCharset,
+    AnsiCode, CharacterStyles, CharsetIndex, Cursor, CursorShape, LinkAnchor, StandardCharset,
     TerminalCharacter, EMPTY_TERMINAL_CHARACTER,
 };
 
@@ -119,6 +120,7 @@ fn transfer_rows_from_lines_above_to_viewport(
     lines_above: &mut VecDeque<Row>,
     viewport: &mut Vec<Row>,
     sixel_grid: &mut SixelGrid,
+    max_lines_above: usize,
     count: usize,
     max_viewport_width: usize,
 ) -> usize {
@@ -150,7 +152,7 @@ fn transfer_rows_from_lines_above_to_viewport(
     }
     if !next_lines.is_empty() {
         let excess_row = Row::from_rows(next_lines, 0);
-        bounded_push(lines_above, sixel_grid, excess_row);
+        bounded_push(lines_above, sixel_grid, max_lines_above, excess_row);
     }
     match usize::try_from(lines_added_to_viewport) {
         Ok(n) => n,
@@ -162,6 +164,7 @@ fn transfer_rows_from_viewport_to_lines_above(
     viewport: &mut Vec<Row>,
     lines_above: &mut VecDeque<Row>,
     sixel_grid: &mut SixelGrid,
+    max_lines_above: usize,
     count: usize,
     max_viewport_width: usize,
 ) -> isize {
@@ -184,7 +187,12 @@ fn transfer_rows_from_viewport_to_lines_above(
                 break; // no more rows
             }
         }
-        let dropped_line_width = bounded_push(lines_above, sixel_grid, next_lines.remove(0));
+        let dropped_line_width = bounded_push(
+            lines_above,
+            sixel_grid,
+            max_lines_above,
+            next_lines.remove(0),
+        );
         if let Some(width) = dropped_line_width {
             transferred_rows_count -=
                 calculate_row_display_height(width, max_viewport_width) as isize;
@@ -240,9 +248,19 @@ fn transfer_rows_from_lines_below_to_viewport(
     }
 }
 
-fn bounded_push(vec: &mut VecDeque<Row>, sixel_grid: &mut SixelGrid, value: Row) -> Option<usize> {
+fn bounded_push(
+    vec: &mut VecDeque<Row>,
+    sixel_grid: &mut SixelGrid,
+    max_size: usize,
+    value: Row,
+) -> Option<usize> {
+    if max_size == 0 {
+        // without a scrollback, the line is dropped as soon as it leaves the viewport
+        sixel_grid.offset_grid_top();
+        return Some(value.width());
+    }
     let mut dropped_line_width = None;
-    if vec.len() >= *SCROLL_BUFFER_SIZE.get().unwrap() {
+    if vec.len() >= max_size {
         let line = vec.pop_front();
         if let Some(line) = line {
             sixel_grid.offset_grid_top();
@@ -273,6 +291,12 @@ fn calculate_row_display_height(row_width: usize, viewport_width: usize) -> usiz
     (row_width as f64 / viewport_width as f64).ceil() as usize
 }
 
+// word boundaries for double click selection are whitespace and punctuation, except for
+// underscores which are commonly part of identifiers
+fn is_word_character(character: char) -> bool {
+    character.is_alphanumeric() || character == '_'
+}
+
 fn subtract_isize_from_usize(u: usize, i: isize) -> usize {
     if i.is_negative() {
         u - i.abs() as usize
@@ -354,7 +378,9 @@ pub struct Grid {
     pub sixel_scrolling: bool,      // DECSDM
     pub insert_mode: bool,
     pub disable_linewrap: bool,
-    pub new_line_mode: bool, // Automatic newline LNM
+    pub truncate_lines: bool, // set by the user rather than the application, see toggle_line_wrap
+    pub completed_lines: Option<Vec<Vec<TerminalCharacter>>>, // lines ended with a newline since last drained, if collected
+    pub new_line_mode: bool,                                  // Automatic newline LNM
     pub clear_viewport_before_rendering: bool,
     pub width: usize,
     pub height: usize,
@@ -365,6 +391,7 @@ pub struct Grid {
     pub link_handler: Rc<RefCell<LinkHandler>>,
     pub ring_bell: bool,
     scrollback_buffer_lines: usize,
+    scrollback_buffer_size: usize, // the most lines kept above the viewport
     pub mouse_mode: MouseMode,
     pub mouse_tracking: MouseTracking,
     pub focus_event_tracking: bool,
@@ -461,12 +488,11 @@ impl Grid {
         debug: bool,
     ) -> Self {
         let sixel_grid = SixelGrid::new(character_cell_size.clone(), sixel_image_store);
+        // .get_or_init() is used instead of .get().unwrap() to prevent
+        // unit tests from panicking where SCROLL_BUFFER_SIZE is uninitialized.
+        let scrollback_buffer_size = *SCROLL_BUFFER_SIZE.get_or_init(|| DEFAULT_SCROLL_BUFFER_SIZE);
         Grid {
-            lines_above: VecDeque::with_capacity(
-                // .get_or_init() is used instead of .get().unwrap() to prevent
-                // unit tests from panicking where SCROLL_BUFFER_SIZE is uninitialized.
-                *SCROLL_BUFFER_SIZE.get_or_init(|| DEFAULT_SCROLL_BUFFER_SIZE),
-            ),
+            lines_above: VecDeque::with_capacity(scrollback_buffer_size),
             viewport: vec![Row::new(columns).canonical()],
             lines_below: vec![],
             horizontal_tabstops: create_horizontal_tabstops(columns),
@@ -484,6 +510,8 @@ impl Grid {
             sixel_scrolling: false,
             insert_mode: false,
             disable_linewrap: false,
+            truncate_lines: false,
+            completed_lines: None,
             new_line_mode: false,
             alternate_screen_state: None,
             clear_viewport_before_rendering: false,
@@ -500,6 +528,7 @@ impl Grid {
             link_handler,
             ring_bell: false,
             scrollback_buffer_lines: 0,
+            scrollback_buffer_size,
             mouse_mode: MouseMode::default(),
             mouse_tracking: MouseTracking::default(),
             focus_event_tracking: false,
@@ -676,6 +705,7 @@ impl Grid {
                 &mut self.lines_above,
                 &mut self.viewport,
                 &mut self.sixel_grid,
+                self.scrollback_buffer_size,
                 1,
                 self.width,
             );
@@ -708,8 +738,12 @@ impl Grid {
                 last_line_above
             };
 
-            let dropped_line_width =
-                bounded_push(&mut self.lines_above, &mut self.sixel_grid, line_to_push_up);
+            let dropped_line_width = bounded_push(
+                &mut self.lines_above,
+                &mut self.sixel_grid,
+                self.scrollback_buffer_size,
+                line_to_push_up,
+            );
             if let Some(width) = dropped_line_width {
                 let dropped_line_height = calculate_row_display_height(width, self.width);
 
@@ -818,6 +852,12 @@ impl Grid {
                 if canonical_line.columns.is_empty() {
                     canonical_line_parts.push(Row::new(new_columns).canonical());
                 }
+                if self.truncate_lines && !canonical_line.columns.is_empty() {
+                    // the line is kept whole in a single row, only its beginning is displayed
+                    canonical_line.width = None;
+                    new_viewport_rows.push(canonical_line);
+                    continue;
+                }
                 while !canonical_line.columns.is_empty() {
                     let next_wrap = canonical_line.drain_until(new_columns);
                     // If the next character is wider than the grid (i.e. there is nothing in
@@ -849,15 +889,26 @@ impl Grid {
                     None
                 };
 
-            let new_cursor_x = (cursor_index_in_canonical_line / new_columns)
-                + (cursor_index_in_canonical_line % new_columns);
+            let new_cursor_x = if self.truncate_lines {
+                std::cmp::min(cursor_index_in_canonical_line, new_columns)
+            } else {
+                (cursor_index_in_canonical_line / new_columns)
+                    + (cursor_index_in_canonical_line % new_columns)
+            };
             let saved_cursor_x_coordinates = if let Some(saved_cursor_index_in_canonical_line) =
                 saved_cursor_index_in_canonical_line.as_ref()
             {
-                Some(
-                    (*saved_cursor_index_in_canonical_line / new_columns)
-                        + (*saved_cursor_index_in_canonical_line % new_columns),
-                )
+                if self.truncate_lines {
+                    Some(std::cmp::min(
+                        *saved_cursor_index_in_canonical_line,
+                        new_columns,
+                    ))
+                } else {
+                    Some(
+                        (*saved_cursor_index_in_canonical_line / new_columns)
+                            + (*saved_cursor_index_in_canonical_line % new_columns),
+                    )
+                }
             } else {
                 None
             };
@@ -870,6 +921,7 @@ impl Grid {
                         &mut self.lines_above,
                         &mut self.viewport,
                         &mut self.sixel_grid,
+                        self.scrollback_buffer_size,
                         row_count_to_transfer,
                         new_columns,
                     );
@@ -897,6 +949,7 @@ impl Grid {
                         &mut self.viewport,
                         &mut self.lines_above,
                         &mut self.sixel_grid,
+                        self.scrollback_buffer_size,
                         row_count_to_transfer,
                         new_columns,
                     );
@@ -935,6 +988,7 @@ impl Grid {
                         &mut self.lines_above,
                         &mut self.viewport,
                         &mut self.sixel_grid,
+                        self.scrollback_buffer_size,
                         row_count_to_transfer,
                         new_columns,
                     );
@@ -964,6 +1018,7 @@ impl Grid {
                             &mut self.viewport,
                             &mut self.lines_above,
                             &mut self.sixel_grid,
+                            self.scrollback_buffer_size,
                             row_count_to_transfer,
                             new_columns,
                         );
@@ -1121,6 +1176,32 @@ impl Grid {
         self.reset_terminal_state();
         self.mark_for_rerender();
     }
+    /// Discards the lines above the viewport, scrolling back to the bottom and keeping the
+    /// viewport itself
+    pub fn clear_scrollback(&mut self) {
+        self.reset_viewport();
+        self.clear_lines_above();
+        self.search_results = Default::default();
+        self.output_buffer.update_all_lines();
+        self.mark_for_rerender();
+    }
+    /// Changes the most lines kept above the viewport, dropping the oldest ones if there are
+    /// more than that already (0 keeps no scrollback at all)
+    pub fn set_scrollback_buffer_size(&mut self, lines: usize) {
+        self.reset_viewport();
+        self.scrollback_buffer_size = lines;
+        let excess_lines = self.lines_above.len().saturating_sub(lines);
+        if excess_lines > 0 {
+            self.lines_above.drain(..excess_lines);
+            for _ in 0..excess_lines {
+                self.sixel_grid.offset_grid_top();
+            }
+            self.scrollback_buffer_lines = self.recalculate_scrollback_buffer_count();
+            self.search_results = Default::default();
+            self.output_buffer.update_all_lines();
+            self.mark_for_rerender();
+        }
+    }
     /// Dumps all lines above terminal vieport and the viewport itself to a string
     pub fn dump_screen(&mut self, full: bool) -> String {
         let viewport: String = dump_screen!(self.viewport);
@@ -1134,6 +1215,47 @@ impl Grid {
         scrollback.push_str(&viewport);
         scrollback
     }
+    /// Same as [`Grid::dump_screen`], but keeps the colors and text attributes of the lines as
+    /// ANSI escape sequences
+    pub fn dump_screen_ansi(&mut self, full: bool) -> String {
+        let lines: Vec<&Row> = if full {
+            self.lines_above
+                .iter()
+                .chain(self.viewport.iter())
+                .collect()
+        } else {
+            self.viewport.iter().collect()
+        };
+        let mut buf = String::new();
+        let mut current_styles = CharacterStyles::default();
+        for (index, line) in lines.iter().enumerate() {
+            if line.is_canonical && index > 0 {
+                buf.push('\n');
+            }
+            // like the plain dump, leave out the empty cells at the end of the line
+            let line_length = line
+                .columns
+                .iter()
+                .rposition(|character| {
+                    character.character != ' '
+                        || !matches!(character.styles.background, None | Some(AnsiCode::Reset))
+                })
+                .map(|position| position + 1)
+                .unwrap_or(0);
+            for character in line.columns.iter().take(line_length) {
+                if let Some(new_styles) =
+                    current_styles.update_and_return_diff(&character.styles, None)
+                {
+                    buf.push_str(&new_styles.to_string());
+                }
+                buf.push(character.character);
+            }
+        }
+        if current_styles != CharacterStyles::default() {
+            buf.push_str("\u{1b}[m");
+        }
+        buf
+    }
     pub fn move_viewport_up(&mut self, count: usize) {
         for _ in 0..count {
             self.scroll_up_one_line();
@@ -1146,6 +1268,124 @@ impl Grid {
         }
         self.output_buffer.update_all_lines();
     }
+    /// Scrolls up until the closest prompt above the viewport (as marked by the shell with
+    /// OSC 133) is at its top, returns false without scrolling if there is no such prompt
+    pub fn move_viewport_to_previous_prompt(&mut self) -> bool {
+        let can_scroll = self.viewport.len() == self.height;
+        if !can_scroll || !self.lines_above.iter().any(|row| row.is_prompt) {
+            return false;
+        }
+        while !self.lines_above.is_empty() {
+            self.scroll_up_one_line();
+            if self.viewport.first().map(|row| row.is_prompt) == Some(true) {
+                break;
+            }
+        }
+        self.output_buffer.update_all_lines();
+        true
+    }
+    /// Scrolls down until the closest prompt below the top of the viewport (as marked by the
+    /// shell with OSC 133) is at its top or until we're back at the bottom, returns false without
+    /// scrolling if there is no such prompt
+    pub fn move_viewport_to_next_prompt(&mut self) -> bool {
+        let can_scroll = self.viewport.len() == self.height && !self.lines_below.is_empty();
+        let has_next_prompt = self
+            .viewport
+            .iter()
+            .skip(1)
+            .chain(self.lines_below.iter())
+            .any(|row| row.is_prompt);
+        if !can_scroll || !has_next_prompt {
+            return false;
+        }
+        while !self.lines_below.is_empty() {
+            self.scroll_down_one_line();
+            if self.viewport.first().map(|row| row.is_prompt) == Some(true) {
+                break;
+            }
+        }
+        self.output_buffer.update_all_lines();
+        true
+    }
+    /// Scrolls so that the given line (1 indexed, counting from the top of the scrollback) starts
+    /// at the top of the viewport, or gets as close to it as possible, returns false without
+    /// scrolling if there is no such line
+    pub fn move_viewport_to_line(&mut self, line_number: usize) -> bool {
+        let line_count = self.all_lines().len();
+        if line_number == 0 || line_number > line_count || self.viewport.len() != self.height {
+            return false;
+        }
+        let target_line = line_number - 1;
+        // the index of the line the top of the viewport is on, wrapped lines are split into
+        // several rows once they are in the viewport
+        let first_row_is_canonical = |grid: &Grid| {
+            grid.viewport
+                .first()
+                .map(|row| row.is_canonical)
+                .unwrap_or(true)
+        };
+        let mut top_line = self.lines_above.iter().filter(|r| r.is_canonical).count();
+        if !first_row_is_canonical(self) {
+            top_line = top_line.saturating_sub(1);
+        }
+        while top_line > target_line || (top_line == target_line && !first_row_is_canonical(self)) {
+            if self.lines_above.is_empty() {
+                break;
+            }
+            if first_row_is_canonical(self) {
+                top_line -= 1;
+            }
+            self.scroll_up_one_line();
+        }
+        while top_line < target_line && !self.lines_below.is_empty() {
+            self.scroll_down_one_line();
+            if first_row_is_canonical(self) {
+                top_line += 1;
+            }
+        }
+        self.output_buffer.update_all_lines();
+        true
+    }
+    /// The lines of the scrollback and viewport containing the needle, along with their 1 indexed
+    /// line number
+    pub fn lines_matching(&self, needle: &str) -> Vec<(usize, String)> {
+        if needle.is_empty() {
+            return vec![];
+        }
+        self.all_lines()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, line)| line.contains(needle))
+            .map(|(index, line)| (index + 1, line))
+            .collect()
+    }
+    fn all_lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = vec![];
+        for row in self
+            .lines_above
+            .iter()
+            .chain(self.viewport.iter())
+            .chain(self.lines_below.iter())
+        {
+            let text: String = row.columns.iter().map(|c| c.character).collect();
+            match lines.last_mut() {
+                Some(line) if !row.is_canonical => line.push_str(&text),
+                _ => lines.push(text),
+            }
+        }
+        for line in lines.iter_mut() {
+            line.truncate(line.trim_end().len());
+        }
+        lines
+    }
+    /// The text of the viewport line the position is on
+    pub fn line_at(&self, position: &Position) -> Option<String> {
+        if position.line.0 < 0 {
+            return None;
+        }
+        let row = self.viewport.get(position.line.0 as usize)?;
+        Some(row.columns.iter().map(|c| c.character).collect())
+    }
     pub fn reset_viewport(&mut self) {
         let max_lines_to_scroll = *SCROLL_BUFFER_SIZE.get().unwrap() * 2; // while not very elegant, this can prevent minor bugs from becoming showstoppers by sticking the whole app display in an endless loop
         let mut lines_scrolled = 0;
@@ -1344,6 +1584,14 @@ impl Grid {
             if self.disable_linewrap {
                 return;
             }
+            if self.truncate_lines && self.alternate_screen_state.is_none() {
+                // the overflowing part of the line is kept out of view so that it can be
+                // displayed if line wrapping is turned back on
+                if let Some(row) = self.viewport.get_mut(self.cursor.y) {
+                    row.push(terminal_character);
+                }
+                return;
+            }
             self.line_wrap();
         }
         self.add_character_at_cursor_position(terminal_character, false);
@@ -1443,6 +1691,12 @@ impl Grid {
         }
         self.output_buffer.update_line(self.cursor.y);
     }
+    fn mark_prompt_at_cursor(&mut self) {
+        self.pad_lines_until(self.cursor.y, EMPTY_TERMINAL_CHARACTER);
+        if let Some(row) = self.viewport.get_mut(self.cursor.y) {
+            row.is_prompt = true;
+        }
+    }
     fn pad_lines_until(&mut self, position: usize, pad_character: TerminalCharacter) {
         for _ in self.viewport.len()..=position {
             let columns = VecDeque::from(vec![pad_character; self.width]);
@@ -1653,12 +1907,51 @@ impl Grid {
         self.output_buffer.update_line(self.cursor.y);
     }
     fn add_newline(&mut self) {
+        if self.completed_lines.is_some() && self.alternate_screen_state.is_none() {
+            let line = self.characters_of_cursor_line();
+            if let Some(completed_lines) = self.completed_lines.as_mut() {
+                completed_lines.push(line);
+            }
+        }
         self.add_canonical_line();
         self.mark_for_rerender();
     }
+    /// The characters of the line the cursor is on, including the rows it wrapped from
+    fn characters_of_cursor_line(&self) -> Vec<TerminalCharacter> {
+        let cursor_row = self.cursor.y.min(self.viewport.len().saturating_sub(1));
+        let rows = match self.viewport.get(..=cursor_row) {
+            Some(rows) => rows,
+            None => return vec![],
+        };
+        let line_start = rows.iter().rposition(|row| row.is_canonical).unwrap_or(0);
+        rows[line_start..]
+            .iter()
+            .flat_map(|row| row.columns.iter().copied())
+            .collect()
+    }
+    /// Takes the lines completed since this was last called, if they are being collected
+    pub fn drain_completed_lines(&mut self) -> Vec<Vec<TerminalCharacter>> {
+        self.completed_lines
+            .as_mut()
+            .map(|completed_lines| completed_lines.drain(..).collect())
+            .unwrap_or_default()
+    }
     pub fn mark_for_rerender(&mut self) {
         self.should_render = true;
     }
+    /// Switches between wrapping lines that are wider than the grid and truncating them, and
+    /// reflows the lines in the viewport accordingly. Truncated lines keep their hidden part so
+    /// that wrapping them again shows it. Like when resizing, the bottom of the viewport stays in
+    /// place so that the scroll position is kept, and lines brought in from the scrollback are
+    /// wrapped as before.
+    pub fn toggle_line_wrap(&mut self) {
+        self.truncate_lines = !self.truncate_lines;
+        if self.alternate_screen_state.is_none() {
+            self.force_change_size(self.height, self.width);
+        }
+        self.output_buffer.update_all_lines();
+        self.mark_for_rerender();
+    }
     pub fn reset_terminal_state(&mut self) {
         self.lines_above = VecDeque::with_capacity(*SCROLL_BUFFER_SIZE.get().unwrap());
         self.lines_below = vec![];
@@ -1690,6 +1983,41 @@ impl Grid {
     fn set_preceding_character(&mut self, terminal_character: TerminalCharacter) {
         self.preceding_char = Some(terminal_character);
     }
+    /// The output of the last command that finished, ie. the lines between the last two prompts
+    /// marked by the shell (OSC 133), not including the line of the prompt itself
+    pub fn last_command_output(&self) -> Option<String> {
+        let rows: Vec<&Row> = self
+            .lines_above
+            .iter()
+            .chain(self.viewport.iter())
+            .chain(self.lines_below.iter())
+            .collect();
+        let mut prompt_indices = rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| row.is_prompt)
+            .map(|(index, _)| index)
+            .rev();
+        let last_prompt = prompt_indices.next()?;
+        let previous_prompt = prompt_indices.next()?;
+        let mut lines: Vec<String> = vec![];
+        // skip the rest of the prompt line if the command typed after it wrapped
+        for row in rows[previous_prompt + 1..last_prompt]
+            .iter()
+            .skip_while(|row| !row.is_canonical)
+        {
+            let text: String = row.columns.iter().map(|c| c.character).collect();
+            match lines.last_mut() {
+                Some(line) if !row.is_canonical => line.push_str(&text),
+                _ => lines.push(text),
+            }
+        }
+        if lines.is_empty() {
+            return None;
+        }
+        let lines: Vec<&str> = lines.iter().map(|line| line.trim_end()).collect();
+        Some(lines.join("\n"))
+    }
     pub fn start_selection(&mut self, start: &Position) {
         let old_selection = self.selection;
         self.selection.start(*start);
@@ -1716,6 +2044,85 @@ impl Grid {
         self.update_selected_lines(&old_selection, &self.selection.clone());
         self.mark_for_rerender();
     }
+    pub fn select_all(&mut self, viewport_only: bool) {
+        // selection lines are relative to the top of the viewport, scrollback lines are negative
+        let (first_line, last_line) = if viewport_only {
+            (0, self.height.saturating_sub(1))
+        } else {
+            (
+                -(self.lines_above.len() as i32),
+                self.height.saturating_sub(1) + self.lines_below.len(),
+            )
+        };
+        let old_selection = self.selection;
+        self.selection.start(Position::new(first_line, 0));
+        self.selection
+            .end(Position::new(last_line as i32, self.width as u16));
+        self.update_selected_lines(&old_selection, &self.selection.clone());
+        self.mark_for_rerender();
+    }
+    /// Selects the word at the given position of the viewport, ie. the characters around it up
+    /// to the nearest whitespace or punctuation. Does nothing if there is no word there.
+    pub fn select_word_at(&mut self, position: &Position) {
+        let row = match usize::try_from(position.line.0)
+            .ok()
+            .and_then(|line| self.viewport.get(line))
+        {
+            Some(row) => row,
+            None => return,
+        };
+        let mut character_columns = vec![]; // (terminal column, width, is part of a word)
+        let mut terminal_col = 0;
+        for terminal_character in &row.columns {
+            character_columns.push((
+                terminal_col,
+                terminal_character.width,
+                is_word_character(terminal_character.character),
+            ));
+            terminal_col += terminal_character.width;
+        }
+        let index = match character_columns
+            .iter()
+            .position(|(col, width, _)| (*col..col + width).contains(&position.column.0))
+        {
+            Some(index) if character_columns[index].2 => index,
+            _ => return,
+        };
+        let first = character_columns[..index]
+            .iter()
+            .rposition(|(_, _, is_word)| !is_word)
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let last = character_columns[index..]
+            .iter()
+            .position(|(_, _, is_word)| !is_word)
+            .map(|i| index + i - 1)
+            .unwrap_or(character_columns.len() - 1);
+        let (start_col, _, _) = character_columns[first];
+        let (last_col, last_width, _) = character_columns[last];
+        let old_selection = self.selection;
+        self.selection
+            .start(Position::new(position.line.0 as i32, start_col as u16));
+        self.selection.end(Position::new(
+            position.line.0 as i32,
+            (last_col + last_width) as u16,
+        ));
+        self.update_selected_lines(&old_selection, &self.selection.clone());
+        self.mark_for_rerender();
+    }
+    /// Selects the whole line at the given position of the viewport
+    pub fn select_line_at(&mut self, position: &Position) {
+        if position.line.0 < 0 || position.line.0 as usize >= self.height {
+            return;
+        }
+        let old_selection = self.selection;
+        self.selection
+            .start(Position::new(position.line.0 as i32, 0));
+        self.selection
+            .end(Position::new(position.line.0 as i32, self.width as u16));
+        self.update_selected_lines(&old_selection, &self.selection.clone());
+        self.mark_for_rerender();
+    }
     pub fn get_selected_text(&self) -> Option<String> {
         if self.selection.is_empty() {
             return None;
@@ -1747,7 +2154,7 @@ impl Grid {
                 Row::from_columns(VecDeque::from(vec![EMPTY_TERMINAL_CHARACTER; self.width]));
 
             // get the row from lines_above, viewport, or lines below depending on index
-            let row = if l < 0 && self.lines_above.len() > l.abs() as usize {
+            let row = if l < 0 && self.lines_above.len() >= l.abs() as usize {
                 let offset_from_end = l.abs();
                 &self.lines_above[self
                     .lines_above
@@ -1797,6 +2204,87 @@ impl Grid {
             Some(selection.join("\n"))
         }
     }
+    /// Turn CRLF line endings into LF and expand tabs to spaces up to the next tab stop
+    pub fn normalize_text(&self, text: &str) -> String {
+        text.replace("\r\n", "\n")
+            .split('\n')
+            .map(|line| {
+                let mut normalized_line = String::with_capacity(line.len());
+                let mut column = 0;
+                for character in line.chars() {
+                    if character == '\t' {
+                        let next_tabstop = self
+                            .horizontal_tabstops
+                            .range(column + 1..)
+                            .next()
+                            .copied()
+                            .unwrap_or(column + 1);
+                        for _ in column..next_tabstop {
+                            normalized_line.push(' ');
+                        }
+                        column = next_tabstop;
+                    } else {
+                        normalized_line.push(character);
+                        column += character.width().unwrap_or(0);
+                    }
+                }
+                normalized_line
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+    pub fn url_at(&self, position: &Position) -> Option<String> {
+        // position is relative to the viewport, we only look at the line it is on
+        if position.line.0 < 0 {
+            return None;
+        }
+        let row = self.viewport.get(position.line.0 as usize)?;
+        let mut terminal_col = 0;
+        let mut character_index = None;
+        for (i, terminal_character) in row.columns.iter().enumerate() {
+            if (terminal_col..terminal_col + terminal_character.width).contains(&position.column.0)
+            {
+                character_index = Some(i);
+                break;
+            }
+            terminal_col += terminal_character.width;
+        }
+        let character_index = character_index?;
+        if let Some(LinkAnchor::Start(link_index)) = row.columns[character_index].styles.link_anchor
+        {
+            // an explicit (OSC 8) link takes precedence over whatever text it is displayed as
+            // only with schemes we are willing to hand to the system opener, others (eg.
+            // `javascript:` or custom app handlers) fall through to the text below
+            if let Some(uri) = self.link_handler.borrow().uri(link_index) {
+                let scheme = uri.split_once(':').map(|(scheme, _)| scheme.to_lowercase());
+                if matches!(scheme.as_deref(), Some("http" | "https" | "ftp" | "file")) {
+                    return Some(uri.to_owned());
+                }
+            }
+        }
+        let mut line = String::new();
+        let mut byte_offset_at_position = 0;
+        for (i, terminal_character) in row.columns.iter().enumerate() {
+            if i == character_index {
+                byte_offset_at_position = line.len();
+            }
+            line.push(terminal_character.character);
+        }
+        lazy_static! {
+            static ref URL_REGEX: Regex =
+                Regex::new(r#"(?:(?:https?|ftp|file)://|www\.)[^\s<>"'`]+"#).unwrap();
+        }
+        URL_REGEX
+            .find_iter(&line)
+            .map(|found| {
+                let url = found.as_str().trim_end_matches(|c: char| {
+                    matches!(c, '.' | ',' | ';' | ':' | '!' | '?' | ')' | ']' | '}')
+                });
+                (found.start(), url)
+            })
+            .find(|(start, url)| (*start..start + url.len()).contains(&byte_offset_at_position))
+            .map(|(_, url)| url.to_owned())
+    }
     pub fn absolute_position_in_scrollback(&self) -> usize {
         self.lines_above.len() + self.cursor.y
     }
@@ -1827,6 +2315,7 @@ impl Grid {
             &mut self.viewport,
             &mut self.lines_above,
             &mut self.sixel_grid,
+            self.scrollback_buffer_size,
             count,
             self.width,
         );
@@ -2390,6 +2879,13 @@ impl Perform for Grid {
                 // TBD - reset text cursor color - currently unimplemented
             },
 
+            // Shell integration marks, we only keep track of where prompts start (A)
+            b"133" => {
+                if params.get(1).map(|mark| mark.starts_with(b"A")) == Some(true) {
+                    self.mark_prompt_at_cursor();
+                }
+            },
+
             _ => {
                 if self.debug {
                     log::warn!("Unhandled osc: {:?}", params);
@@ -3050,6 +3546,7 @@ impl AlternateScreenState {
 pub struct Row {
     pub columns: VecDeque<TerminalCharacter>,
     pub is_canonical: bool,
+    pub is_prompt: bool, // a shell prompt starts on this line (OSC 133)
     width: Option<usize>,
 }
 
@@ -3067,6 +3564,7 @@ impl Row {
         Row {
             columns: VecDeque::with_capacity(width),
             is_canonical: false,
+            is_prompt: false,
             width: None,
         }
     }
@@ -3074,6 +3572,7 @@ impl Row {
         Row {
             columns,
             is_canonical: false,
+            is_prompt: false,
             width: None,
         }
     }
@@ -3372,6 +3871,9 @@ impl Row {
         if !parts.is_empty() && self.is_canonical {
             parts.get_mut(0).unwrap().is_canonical = true;
         }
+        if !parts.is_empty() && self.is_prompt {
+            parts.get_mut(0).unwrap().is_prompt = true;
+        }
         if parts.is_empty() {
             parts.push(self.clone());
         }
@@ -0,0 +1,113 @@
+use super::svg_dump::{palette_color_to_hex, CellStyle};
+use crate::panes::terminal_character::CharacterStyles;
+use crate::panes::{Grid, Row};
+use zellij_utils::data::Palette;
+
+impl Grid {
+    /// Dumps the viewport (and optionally the scrollback) to a self-contained HTML page, keeping
+    /// colors and text attributes as inline styles resolved with the given palette
+    pub fn dump_screen_html(&self, full: bool, palette: &Palette) -> String {
+        let rows: Vec<&Row> = if full {
+            self.lines_above
+                .iter()
+                .chain(self.viewport.iter())
+                .collect()
+        } else {
+            self.viewport.iter().collect()
+        };
+        // like the text dump, rows that were wrapped are joined back into one line
+        let mut lines: Vec<Vec<&Row>> = vec![];
+        for row in rows {
+            match lines.last_mut() {
+                Some(line) if !row.is_canonical => line.push(row),
+                _ => lines.push(vec![row]),
+            }
+        }
+        let default_style = self.cell_style(&CharacterStyles::default(), palette);
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n</head>\n<body style=\"margin: 0; background-color: {}; color: {};\">\n<pre style=\"margin: 0; padding: 1em; font-family: monospace;\">",
+            palette_color_to_hex(palette.bg),
+            default_style.foreground
+        );
+        for line in lines {
+            self.push_html_line(&mut html, &line, &default_style, palette);
+            html.push('\n');
+        }
+        html.push_str("</pre>\n</body>\n</html>\n");
+        html
+    }
+    fn push_html_line(
+        &self,
+        html: &mut String,
+        rows: &[&Row],
+        default_style: &CellStyle,
+        palette: &Palette,
+    ) {
+        let characters: Vec<_> = rows.iter().flat_map(|row| row.columns.iter()).collect();
+        // trailing whitespace is left out unless it has a background of its own
+        let line_length = characters
+            .iter()
+            .rposition(|character| {
+                !character.character.is_whitespace()
+                    || self
+                        .cell_style(&character.styles, palette)
+                        .background
+                        .is_some()
+            })
+            .map(|position| position + 1)
+            .unwrap_or(0);
+        let mut spans: Vec<(CellStyle, String)> = vec![];
+        for character in characters.iter().take(line_length) {
+            let style = self.cell_style(&character.styles, palette);
+            match spans.last_mut() {
+                Some((span_style, text)) if span_style == &style => text.push(character.character),
+                _ => spans.push((style, character.character.to_string())),
+            }
+        }
+        for (style, text) in spans {
+            if &style == default_style {
+                html.push_str(&escape_html(&text));
+            } else {
+                html.push_str(&format!(
+                    "<span style=\"{}\">{}</span>",
+                    inline_style(&style),
+                    escape_html(&text)
+                ));
+            }
+        }
+    }
+}
+
+fn inline_style(style: &CellStyle) -> String {
+    let mut inline_style = format!("color: {};", style.foreground);
+    if let Some(background) = &style.background {
+        inline_style.push_str(&format!(" background-color: {};", background));
+    }
+    if style.bold {
+        inline_style.push_str(" font-weight: bold;");
+    }
+    if style.italic {
+        inline_style.push_str(" font-style: italic;");
+    }
+    if style.underline {
+        inline_style.push_str(" text-decoration: underline;");
+    }
+    if style.hidden {
+        inline_style.push_str(" visibility: hidden;");
+    }
+    inline_style
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
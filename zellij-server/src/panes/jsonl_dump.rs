@@ -0,0 +1,121 @@
+//! Dumps the screen as JSON lines, one object per line of text:
+//!
+//! ```text
+//! {"line_number":1,"text":"$ ls","style_runs":[{"start":0,"end":1,"foreground":"#00ff00","bold":true,"italic":false,"underline":false}]}
+//! ```
+//!
+//! - `line_number`: the position of the line in the dump, starting at 1 (lines that wrap on
+//!   screen are dumped as a single line)
+//! - `text`: the text of the line, without trailing whitespace
+//! - `style_runs`: left out if the whole line has the default style, otherwise the spans of
+//!   `text` that are styled, with `start` and `end` (exclusive) counted in characters, the
+//!   resolved `foreground` color, the `background` color (left out for the default background)
+//!   and the `bold`, `italic` and `underline` attributes
+//!
+//! New fields might be added, but the existing ones keep their names and meaning.
+
+use super::svg_dump::CellStyle;
+use crate::panes::terminal_character::CharacterStyles;
+use crate::panes::{Grid, Row};
+use serde::Serialize;
+use zellij_utils::data::Palette;
+
+#[derive(Serialize)]
+struct JsonLine {
+    line_number: usize,
+    text: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    style_runs: Vec<StyleRun>,
+}
+
+#[derive(Serialize)]
+struct StyleRun {
+    start: usize,
+    end: usize,
+    foreground: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    background: Option<String>,
+    bold: bool,
+    italic: bool,
+    underline: bool,
+}
+
+impl Grid {
+    /// Dumps the viewport (and optionally the scrollback) as JSON lines, resolving colors with
+    /// the given palette
+    pub fn dump_screen_jsonl(&self, full: bool, palette: &Palette) -> String {
+        let rows: Vec<&Row> = if full {
+            self.lines_above
+                .iter()
+                .chain(self.viewport.iter())
+                .collect()
+        } else {
+            self.viewport.iter().collect()
+        };
+        // like the text dump, rows that were wrapped are joined back into one line
+        let mut lines: Vec<Vec<&Row>> = vec![];
+        for row in rows {
+            match lines.last_mut() {
+                Some(line) if !row.is_canonical => line.push(row),
+                _ => lines.push(vec![row]),
+            }
+        }
+        let default_style = self.cell_style(&CharacterStyles::default(), palette);
+        let mut dump = String::new();
+        for (index, line) in lines.iter().enumerate() {
+            let json_line = self.json_line(index + 1, line, &default_style, palette);
+            if let Ok(json_line) = serde_json::to_string(&json_line) {
+                dump.push_str(&json_line);
+                dump.push('\n');
+            }
+        }
+        dump
+    }
+    fn json_line(
+        &self,
+        line_number: usize,
+        rows: &[&Row],
+        default_style: &CellStyle,
+        palette: &Palette,
+    ) -> JsonLine {
+        let characters: Vec<_> = rows.iter().flat_map(|row| row.columns.iter()).collect();
+        let text_length = characters
+            .iter()
+            .rposition(|character| !character.character.is_whitespace())
+            .map(|position| position + 1)
+            .unwrap_or(0);
+        let mut text = String::new();
+        let mut style_runs: Vec<StyleRun> = vec![];
+        let mut current_style: Option<CellStyle> = None;
+        for (position, character) in characters.iter().take(text_length).enumerate() {
+            text.push(character.character);
+            let style = self.cell_style(&character.styles, palette);
+            if &style == default_style {
+                current_style = None;
+                continue;
+            }
+            match style_runs.last_mut() {
+                Some(style_run) if current_style.as_ref() == Some(&style) => {
+                    style_run.end = position + 1;
+                },
+                _ => {
+                    style_runs.push(StyleRun {
+                        start: position,
+                        end: position + 1,
+                        foreground: style.foreground.clone(),
+                        background: style.background.clone(),
+                        bold: style.bold,
+                        italic: style.italic,
+                        underline: style.underline,
+                    });
+                    current_style = Some(style);
+                },
+            }
+        }
+        JsonLine {
+            line_number,
+            text,
+            style_runs,
+        }
+    }
+}
@@ -49,6 +49,10 @@ impl LinkHandler {
         }
     }
 
+    pub fn uri(&self, index: u16) -> Option<&str> {
+        self.links.get(&index).map(|link| link.uri.as_str())
+    }
+
     pub fn output_osc8(&self, link_anchor: Option<LinkAnchor>) -> Option<String> {
         link_anchor.and_then(|link| match link {
             LinkAnchor::Start(index) => {
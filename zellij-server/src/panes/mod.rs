@@ -7,8 +7,12 @@ pub mod terminal_character;
 
 mod active_panes;
 mod floating_panes;
+mod html_dump;
+mod jsonl_dump;
+mod pinned_lines;
 mod plugin_pane;
 mod search;
+mod svg_dump;
 mod terminal_pane;
 mod tiled_panes;
 
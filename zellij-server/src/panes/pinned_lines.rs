@@ -0,0 +1,64 @@
+//! Lines of a terminal pane's output that match a pattern, kept pinned in a header above the
+//! rest of the pane so that they stay visible while the output scrolls underneath them.
+
+use crate::output::CharacterChunk;
+use crate::panes::terminal_character::{TerminalCharacter, EMPTY_TERMINAL_CHARACTER};
+use std::collections::VecDeque;
+use zellij_utils::regex::Regex;
+
+/// The most lines kept pinned, the oldest one is dropped when another one matches
+pub const MAX_PINNED_LINES: usize = 5;
+
+pub struct PinnedLines {
+    pattern: Regex,
+    lines: VecDeque<Vec<TerminalCharacter>>,
+}
+
+impl PinnedLines {
+    pub fn new(pattern: Regex) -> Self {
+        PinnedLines {
+            pattern,
+            lines: VecDeque::with_capacity(MAX_PINNED_LINES),
+        }
+    }
+    /// Pins `line` if its text matches the pattern, returns true if it was pinned
+    pub fn pin_if_matching(&mut self, line: Vec<TerminalCharacter>) -> bool {
+        let text: String = line.iter().map(|c| c.character).collect();
+        if !self.pattern.is_match(text.trim_end()) {
+            return false;
+        }
+        if self.lines.len() == MAX_PINNED_LINES {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+        true
+    }
+    pub fn pinned_count(&self) -> usize {
+        self.lines.len()
+    }
+    /// Renders the most recent `rows` pinned lines from `y` downwards, truncated or padded to
+    /// `columns`
+    pub fn render(&self, x: usize, y: usize, rows: usize, columns: usize) -> Vec<CharacterChunk> {
+        self.lines
+            .iter()
+            .skip(self.lines.len().saturating_sub(rows))
+            .enumerate()
+            .map(|(line_index, line)| {
+                let mut characters = Vec::with_capacity(columns);
+                let mut line_width = 0;
+                for character in line {
+                    if line_width + character.width > columns {
+                        break;
+                    }
+                    line_width += character.width;
+                    characters.push(*character);
+                }
+                characters.resize(
+                    characters.len() + columns.saturating_sub(line_width),
+                    EMPTY_TERMINAL_CHARACTER,
+                );
+                CharacterChunk::new(characters, x, y + line_index)
+            })
+            .collect()
+    }
+}
@@ -13,7 +13,7 @@ use crate::ui::{
 use crate::ClientId;
 use std::cell::RefCell;
 use std::rc::Rc;
-use zellij_utils::pane_size::{Offset, SizeInPixels};
+use zellij_utils::pane_size::{Offset, SizeConstraints, SizeInPixels};
 use zellij_utils::position::Position;
 use zellij_utils::{
     channels::SenderWithContext,
@@ -53,6 +53,7 @@ pub(crate) struct PluginPane {
     pub selectable: bool,
     pub geom: PaneGeom,
     pub geom_override: Option<PaneGeom>,
+    pub size_constraints: SizeConstraints,
     pub content_offset: Offset,
     pub send_plugin_instructions: SenderWithContext<PluginInstruction>,
     pub active_at: Instant,
@@ -101,6 +102,7 @@ impl PluginPane {
             selectable: true,
             geom: position_and_size,
             geom_override: None,
+            size_constraints: SizeConstraints::default(),
             send_plugin_instructions,
             active_at: Instant::now(),
             frame: HashMap::new(),
@@ -210,6 +212,12 @@ impl Pane for PluginPane {
     fn geom_override(&self) -> Option<PaneGeom> {
         self.geom_override
     }
+    fn size_constraints(&self) -> SizeConstraints {
+        self.size_constraints
+    }
+    fn set_size_constraints(&mut self, size_constraints: SizeConstraints) {
+        self.size_constraints = size_constraints;
+    }
     fn should_render(&self) -> bool {
         // set should_render for all clients
         self.should_render.values().any(|v| *v)
@@ -358,9 +366,6 @@ impl Pane for PluginPane {
     }
     fn update_name(&mut self, name: &str) {
         match name {
-            "\0" => {
-                self.pane_name = String::new();
-            },
             "\u{007F}" | "\u{0008}" => {
                 //delete and backspace keys
                 self.pane_name.pop();
@@ -370,6 +375,9 @@ impl Pane for PluginPane {
             },
         }
     }
+    fn clear_name(&mut self) {
+        self.pane_name = String::new();
+    }
     fn pid(&self) -> PaneId {
         PaneId::Plugin(self.pid)
     }
@@ -442,6 +450,9 @@ impl Pane for PluginPane {
     fn clear_screen(&mut self) {
         // do nothing
     }
+    fn clear_scrollback(&mut self) {
+        // do nothing
+    }
     fn clear_scroll(&mut self) {
         // noop
     }
@@ -5,6 +5,7 @@ use std::borrow::Cow;
 use std::fmt::Debug;
 use zellij_utils::input::actions::SearchDirection;
 use zellij_utils::position::Position;
+use zellij_utils::regex::{Regex, RegexBuilder};
 
 // If char is neither alphanumeric nor an underscore do we consider it a word-boundary
 fn is_word_boundary(x: &Option<char>) -> bool {
@@ -98,6 +99,13 @@ pub struct SearchResult {
     pub whole_word_only: bool, // TODO
     // Jump from the bottom to the top (or vice versa), if we run out of lines to search
     pub wrap_search: bool,
+    // Interpret the needle as a regular expression rather than a literal
+    pub regex: bool,
+    // Only search the viewport rather than scrolling through the whole scrollback, this is much
+    // faster in huge buffers
+    pub visible_only: bool,
+    // The needle compiled with the above options (None if it is not a valid regular expression)
+    needle_regex: Option<Regex>,
 }
 
 impl SearchResult {
@@ -141,7 +149,29 @@ impl SearchResult {
     }
 
     pub fn has_modifiers_set(&self) -> bool {
-        self.wrap_search || self.whole_word_only || self.case_insensitive
+        self.wrap_search
+            || self.whole_word_only
+            || self.case_insensitive
+            || self.regex
+            || self.visible_only
+    }
+
+    /// Needs to be called whenever the needle or one of the options it is compiled with changes
+    pub(crate) fn update_needle_regex(&mut self) {
+        self.needle_regex = if self.regex && !self.needle.is_empty() {
+            let pattern = if self.whole_word_only {
+                format!(r"\b(?:{})\b", self.needle)
+            } else {
+                self.needle.clone()
+            };
+            // an invalid regex (eg. one that is still being typed) simply doesn't match anything
+            RegexBuilder::new(&pattern)
+                .case_insensitive(self.case_insensitive)
+                .build()
+                .ok()
+        } else {
+            None
+        };
     }
 
     fn check_if_haystack_char_matches_needle(
@@ -181,6 +211,9 @@ impl SearchResult {
         if self.needle.is_empty() || row.columns.is_empty() {
             return res;
         }
+        if self.regex {
+            return self.search_row_with_regex(ridx, row, tail);
+        }
 
         let mut tailit = tail.iter();
         let mut source = SearchSource::Main(row); // Where we currently get the haystack-characters from
@@ -279,6 +312,59 @@ impl SearchResult {
         res
     }
 
+    /// Like `search_row`, but matching the needle as a regular expression against the row and its
+    /// tail joined together. Only matches starting in `row` itself are returned, those starting in
+    /// the tail will be found when searching the tail rows.
+    fn search_row_with_regex(&self, ridx: usize, row: &Row, tail: &[&Row]) -> Vec<Selection> {
+        let mut res = Vec::new();
+        let needle_regex = match &self.needle_regex {
+            Some(needle_regex) => needle_regex,
+            None => return res,
+        };
+        let mut haystack = String::new();
+        // the byte offset of each character in the haystack along with its position in the grid
+        let mut character_positions = Vec::new();
+        for (line_offset, line) in std::iter::once(row).chain(tail.iter().copied()).enumerate() {
+            for (column, terminal_character) in line.columns.iter().enumerate() {
+                character_positions.push((
+                    haystack.len(),
+                    Position::new((ridx + line_offset) as i32, column as u16),
+                ));
+                haystack.push(terminal_character.character);
+            }
+        }
+        let position_at = |byte_offset: usize| {
+            character_positions
+                .binary_search_by_key(&byte_offset, |(offset, _)| *offset)
+                .ok()
+                .map(|index| character_positions[index].1)
+        };
+        for found in needle_regex.find_iter(&haystack) {
+            if found.as_str().is_empty() {
+                // eg. `a*`, there's nothing to highlight
+                continue;
+            }
+            let last_character_offset =
+                found.end() - found.as_str().chars().last().map_or(0, |c| c.len_utf8());
+            match (
+                position_at(found.start()),
+                position_at(last_character_offset),
+            ) {
+                (Some(start), Some(last)) if start.line() as usize == ridx => {
+                    let mut selection = Selection::default();
+                    selection.start(start);
+                    selection.end(Position::new(
+                        last.line() as i32,
+                        (last.column() + 1) as u16,
+                    ));
+                    res.push(selection);
+                },
+                _ => break,
+            }
+        }
+        res
+    }
+
     pub(crate) fn move_active_selection_to_next(&mut self) {
         if let Some(active_idx) = self.active {
             self.active = self
@@ -412,14 +498,15 @@ impl Grid {
 
     pub fn set_search_string(&mut self, needle: &str) {
         self.search_results.needle = needle.to_string();
+        self.search_results.update_needle_regex();
         self.search_viewport();
         // If the current viewport does not contain any hits,
         // we jump around until we find something. Starting
         // going backwards.
-        if self.search_results.selections.is_empty() {
+        if self.search_results.selections.is_empty() && !self.search_results.visible_only {
             self.search_up();
         }
-        if self.search_results.selections.is_empty() {
+        if self.search_results.selections.is_empty() && !self.search_results.visible_only {
             self.search_down();
         }
         // We still don't want to pre-select anything at this stage
@@ -452,8 +539,71 @@ impl Grid {
         }
     }
 
+    /// The number of matches of the search term in the whole buffer
+    pub fn search_match_count(&self) -> usize {
+        self.search_all_lines().len()
+    }
+
+    /// Scrolls so that the match with the given (0 indexed) position among all the matches in the
+    /// buffer is at the top of the viewport (or as close to it as possible) and makes it the
+    /// active one, returns false without scrolling if there is no such match
+    pub fn move_viewport_to_search_match(&mut self, match_index: usize) -> bool {
+        if match_index >= self.search_match_count() {
+            return false;
+        }
+        // the lines below the viewport are only wrapped to its width once they are scrolled into
+        // it, so the matches are searched for from the bottom of the buffer where there are none
+        self.reset_viewport();
+        let search_match = match self.search_all_lines().get(match_index) {
+            Some(search_match) => *search_match,
+            None => return false,
+        };
+        let match_line = search_match.start.line() as usize;
+        self.move_viewport_up(self.lines_above.len().saturating_sub(match_line));
+        for line in self.search_results.selections.drain(..) {
+            self.output_buffer
+                .update_lines(line.start.line() as usize, line.end.line() as usize);
+        }
+        self.search_viewport();
+        let lines_above = self.lines_above.len();
+        self.search_results.active = self
+            .search_results
+            .selections
+            .iter()
+            .find(|selection| {
+                selection.start.line() as usize + lines_above == match_line
+                    && selection.start.column() == search_match.start.column()
+            })
+            .or_else(|| self.search_results.selections.first())
+            .copied();
+        self.is_scrolled = true;
+        true
+    }
+
+    /// Searches the scrollback, viewport and the lines below it, with the lines of the matches
+    /// counted from the top of the scrollback
+    fn search_all_lines(&self) -> Vec<Selection> {
+        let rows: Vec<&Row> = self
+            .lines_above
+            .iter()
+            .chain(self.viewport.iter())
+            .chain(self.lines_below.iter())
+            .collect();
+        let mut matches = vec![];
+        for (ridx, row) in rows.iter().enumerate() {
+            let tail: Vec<&Row> = rows[ridx + 1..]
+                .iter()
+                .take_while(|row| !row.is_canonical)
+                .copied()
+                .collect();
+            matches.append(&mut self.search_results.search_row(ridx, row, &tail));
+        }
+        matches
+    }
+
     pub fn toggle_search_case_sensitivity(&mut self) {
         self.search_results.case_insensitive = !self.search_results.case_insensitive;
+        self.search_results.update_needle_regex();
         for line in self.search_results.selections.drain(..) {
             self.output_buffer
                 .update_lines(line.start.line() as usize, line.end.line() as usize);
@@ -469,6 +619,7 @@ impl Grid {
 
     pub fn toggle_search_whole_words(&mut self) {
         self.search_results.whole_word_only = !self.search_results.whole_word_only;
+        self.search_results.update_needle_regex();
         for line in self.search_results.selections.drain(..) {
             self.output_buffer
                 .update_lines(line.start.line() as usize, line.end.line() as usize);
@@ -479,6 +630,23 @@ impl Grid {
         self.search_results.unset_active_selection_if_nonexistent();
     }
 
+    pub fn toggle_search_regex(&mut self) {
+        self.search_results.regex = !self.search_results.regex;
+        self.search_results.update_needle_regex();
+        for line in self.search_results.selections.drain(..) {
+            self.output_buffer
+                .update_lines(line.start.line() as usize, line.end.line() as usize);
+        }
+        self.search_results.active = None;
+        self.search_viewport();
+        // Maybe the selection we had is now gone
+        self.search_results.unset_active_selection_if_nonexistent();
+    }
+
+    pub fn toggle_search_visible_only(&mut self) {
+        self.search_results.visible_only = !self.search_results.visible_only;
+    }
+
     fn search_scrollbuffer(&mut self, dir: SearchDirection) {
         let first_sel = self.search_results.selections.first();
         let last_sel = self.search_results.selections.last();
@@ -497,6 +665,9 @@ impl Grid {
         if search_viewport_for_the_first_time || search_viewport_again {
             // We can stay in the viewport and just move the active selection
             self.search_viewport_again(search_viewport_for_the_first_time, dir);
+        } else if self.search_results.visible_only {
+            // We're not allowed to move the viewport, so we cycle back to its other end
+            self.search_viewport_cycle(dir);
         } else {
             // Need to move the viewport
             let found_something = self.search_viewport_move(dir);
@@ -537,6 +708,25 @@ impl Grid {
         }
     }
 
+    fn search_viewport_cycle(&mut self, dir: SearchDirection) {
+        if let Some(previous_active) = self.search_results.active {
+            self.output_buffer.update_lines(
+                previous_active.start.line() as usize,
+                previous_active.end.line() as usize,
+            );
+        }
+        self.search_results.active = match dir {
+            SearchDirection::Up => self.search_results.selections.last().cloned(),
+            SearchDirection::Down => self.search_results.selections.first().cloned(),
+        };
+        if let Some(new_active) = self.search_results.active {
+            self.output_buffer.update_lines(
+                new_active.start.line() as usize,
+                new_active.end.line() as usize,
+            );
+        }
+    }
+
     fn search_reached_opposite_end(&mut self, dir: SearchDirection) -> bool {
         match dir {
             SearchDirection::Up => self.lines_above.is_empty(),
@@ -0,0 +1,272 @@
+use crate::panes::terminal_character::{AnsiCode, CharacterStyles, NamedColor};
+use crate::panes::{Grid, Row};
+use zellij_utils::data::{Palette, PaletteColor};
+
+const CELL_WIDTH: usize = 9;
+const CELL_HEIGHT: usize = 18;
+const FONT_SIZE: usize = 15;
+const BASELINE_OFFSET: usize = 14;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct CellStyle {
+    pub foreground: String,
+    pub background: Option<String>, // None means the default background
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub hidden: bool,
+}
+
+struct Run {
+    x: usize,
+    width: usize,
+    style: CellStyle,
+    text: String,
+}
+
+impl Run {
+    fn has_wide_chars(&self) -> bool {
+        self.width != self.text.chars().count()
+    }
+}
+
+impl Grid {
+    /// Dumps the viewport (and optionally the scrollback) to an SVG image, resolving the default
+    /// and named colors with the given palette
+    pub fn dump_screen_svg(&self, full: bool, palette: &Palette) -> String {
+        let rows: Vec<&Row> = if full {
+            self.lines_above
+                .iter()
+                .chain(self.viewport.iter())
+                .collect()
+        } else {
+            self.viewport.iter().collect()
+        };
+        let svg_width = self.width * CELL_WIDTH;
+        let svg_height = rows.len() * CELL_HEIGHT;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{svg_width}\" height=\"{svg_height}\" viewBox=\"0 0 {svg_width} {svg_height}\" font-family=\"monospace\" font-size=\"{FONT_SIZE}\">\n"
+        );
+        svg.push_str(&format!(
+            "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+            palette_color_to_hex(palette.bg)
+        ));
+        for (y, row) in rows.iter().enumerate() {
+            for run in self.row_to_runs(row, palette) {
+                push_run(&mut svg, &run, y);
+            }
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+    fn row_to_runs(&self, row: &Row, palette: &Palette) -> Vec<Run> {
+        let mut runs: Vec<Run> = vec![];
+        let mut x = 0;
+        for character in row.columns.iter() {
+            let style = self.cell_style(&character.styles, palette);
+            match runs.last_mut() {
+                // wide characters get a run of their own so that the text after them stays
+                // aligned to the grid
+                Some(run)
+                    if run.style == style && character.width == 1 && !run.has_wide_chars() =>
+                {
+                    run.text.push(character.character);
+                    run.width += 1;
+                },
+                _ => runs.push(Run {
+                    x,
+                    width: character.width,
+                    style,
+                    text: character.character.to_string(),
+                }),
+            }
+            x += character.width;
+        }
+        runs
+    }
+    pub(super) fn cell_style(&self, styles: &CharacterStyles, palette: &Palette) -> CellStyle {
+        let is_on = |code: Option<AnsiCode>| code == Some(AnsiCode::On);
+        let mut foreground = styles
+            .foreground
+            .and_then(|code| self.ansi_code_to_hex(code, palette))
+            .unwrap_or_else(|| palette_color_to_hex(palette.fg));
+        let mut background = styles
+            .background
+            .and_then(|code| self.ansi_code_to_hex(code, palette));
+        if is_on(styles.reverse) {
+            let reversed_foreground =
+                background.unwrap_or_else(|| palette_color_to_hex(palette.bg));
+            background = Some(foreground);
+            foreground = reversed_foreground;
+        }
+        CellStyle {
+            foreground,
+            background,
+            bold: is_on(styles.bold),
+            italic: is_on(styles.italic),
+            underline: is_on(styles.underline),
+            hidden: is_on(styles.hidden),
+        }
+    }
+    fn ansi_code_to_hex(&self, ansi_code: AnsiCode, palette: &Palette) -> Option<String> {
+        match ansi_code {
+            AnsiCode::RgbCode(rgb) => Some(rgb_to_hex(rgb)),
+            AnsiCode::NamedColor(named_color) => Some(palette_color_to_hex(named_palette_color(
+                named_color,
+                palette,
+            ))),
+            AnsiCode::ColorIndex(color_index) => {
+                let changed_color = self
+                    .changed_colors
+                    .as_ref()
+                    .and_then(|changed_colors| changed_colors[color_index as usize]);
+                match changed_color {
+                    Some(AnsiCode::RgbCode(rgb)) => Some(rgb_to_hex(rgb)),
+                    _ if color_index < 16 => Some(palette_color_to_hex(named_palette_color(
+                        index_to_named_color(color_index),
+                        palette,
+                    ))),
+                    _ => Some(rgb_to_hex(eight_bit_to_rgb(color_index))),
+                }
+            },
+            AnsiCode::On | AnsiCode::Reset => None,
+        }
+    }
+}
+
+fn push_run(svg: &mut String, run: &Run, y: usize) {
+    let x_px = run.x * CELL_WIDTH;
+    let y_px = y * CELL_HEIGHT;
+    if let Some(background) = &run.style.background {
+        svg.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+            x_px,
+            y_px,
+            run.width * CELL_WIDTH,
+            CELL_HEIGHT,
+            background
+        ));
+    }
+    if run.style.hidden || run.text.trim().is_empty() {
+        return;
+    }
+    let mut attributes = format!(
+        "x=\"{}\" y=\"{}\" fill=\"{}\"",
+        x_px,
+        y_px + BASELINE_OFFSET,
+        run.style.foreground
+    );
+    if run.style.bold {
+        attributes.push_str(" font-weight=\"bold\"");
+    }
+    if run.style.italic {
+        attributes.push_str(" font-style=\"italic\"");
+    }
+    if run.style.underline {
+        attributes.push_str(" text-decoration=\"underline\"");
+    }
+    svg.push_str(&format!(
+        "<text {} xml:space=\"preserve\">{}</text>\n",
+        attributes,
+        escape_xml(&run.text)
+    ));
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn named_palette_color(named_color: NamedColor, palette: &Palette) -> PaletteColor {
+    // themes do not define bright variants, so they share the regular colors
+    match named_color {
+        NamedColor::Black | NamedColor::BrightBlack => palette.black,
+        NamedColor::Red | NamedColor::BrightRed => palette.red,
+        NamedColor::Green | NamedColor::BrightGreen => palette.green,
+        NamedColor::Yellow | NamedColor::BrightYellow => palette.yellow,
+        NamedColor::Blue | NamedColor::BrightBlue => palette.blue,
+        NamedColor::Magenta | NamedColor::BrightMagenta => palette.magenta,
+        NamedColor::Cyan | NamedColor::BrightCyan => palette.cyan,
+        NamedColor::White | NamedColor::BrightWhite => palette.white,
+    }
+}
+
+fn index_to_named_color(color_index: u8) -> NamedColor {
+    match color_index {
+        0 => NamedColor::Black,
+        1 => NamedColor::Red,
+        2 => NamedColor::Green,
+        3 => NamedColor::Yellow,
+        4 => NamedColor::Blue,
+        5 => NamedColor::Magenta,
+        6 => NamedColor::Cyan,
+        7 => NamedColor::White,
+        8 => NamedColor::BrightBlack,
+        9 => NamedColor::BrightRed,
+        10 => NamedColor::BrightGreen,
+        11 => NamedColor::BrightYellow,
+        12 => NamedColor::BrightBlue,
+        13 => NamedColor::BrightMagenta,
+        14 => NamedColor::BrightCyan,
+        _ => NamedColor::BrightWhite,
+    }
+}
+
+pub(super) fn palette_color_to_hex(palette_color: PaletteColor) -> String {
+    match palette_color {
+        PaletteColor::Rgb(rgb) => rgb_to_hex(rgb),
+        PaletteColor::EightBit(color_index) => rgb_to_hex(eight_bit_to_rgb(color_index)),
+    }
+}
+
+fn rgb_to_hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// The standard xterm 256 color table
+fn eight_bit_to_rgb(color_index: u8) -> (u8, u8, u8) {
+    const SYSTEM_COLORS: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (205, 0, 0),
+        (0, 205, 0),
+        (205, 205, 0),
+        (0, 0, 238),
+        (205, 0, 205),
+        (0, 205, 205),
+        (229, 229, 229),
+        (127, 127, 127),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (92, 92, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ];
+    const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    match color_index {
+        0..=15 => SYSTEM_COLORS[color_index as usize],
+        16..=231 => {
+            let index = color_index - 16;
+            (
+                CUBE_LEVELS[(index / 36) as usize],
+                CUBE_LEVELS[((index / 6) % 6) as usize],
+                CUBE_LEVELS[(index % 6) as usize],
+            )
+        },
+        _ => {
+            let level = 8 + (color_index - 232) * 10;
+            (level, level, level)
+        },
+    }
+}
@@ -3,6 +3,7 @@ use crate::panes::sixel::SixelImageStore;
 use crate::panes::LinkHandler;
 use crate::panes::{
     grid::Grid,
+    pinned_lines::PinnedLines,
     terminal_character::{render_first_run_banner, TerminalCharacter, EMPTY_TERMINAL_CHARACTER},
 };
 use crate::pty::VteBytes;
@@ -20,8 +21,10 @@ use zellij_utils::{
     errors::prelude::*,
     input::layout::Run,
     pane_size::PaneGeom,
+    pane_size::SizeConstraints,
     pane_size::SizeInPixels,
     position::Position,
+    regex::Regex,
     shared::make_terminal_title,
     vte,
 };
@@ -37,8 +40,8 @@ const UP_ARROW: &[u8] = &[27, 91, 65];
 const DOWN_ARROW: &[u8] = &[27, 91, 66];
 const HOME_KEY: &[u8] = &[27, 91, 72];
 const END_KEY: &[u8] = &[27, 91, 70];
-const BRACKETED_PASTE_BEGIN: &[u8] = &[27, 91, 50, 48, 48, 126];
-const BRACKETED_PASTE_END: &[u8] = &[27, 91, 50, 48, 49, 126];
+pub const BRACKETED_PASTE_BEGIN: &[u8] = &[27, 91, 50, 48, 48, 126];
+pub const BRACKETED_PASTE_END: &[u8] = &[27, 91, 50, 48, 49, 126];
 const ENTER_NEWLINE: &[u8] = &[10];
 const ENTER_CARRIAGE_RETURN: &[u8] = &[13];
 const SPACE: &[u8] = &[32];
@@ -95,6 +98,7 @@ pub struct TerminalPane {
     pub selectable: bool,
     pub geom: PaneGeom,
     pub geom_override: Option<PaneGeom>,
+    pub size_constraints: SizeConstraints,
     pub active_at: Instant,
     pub style: Style,
     vte_parser: vte::Parser,
@@ -114,6 +118,8 @@ pub struct TerminalPane {
     // held on startup and can possibly be used to display some errors
     pane_frame_color_override: Option<(PaletteColor, Option<String>)>,
     invoked_with: Option<Run>,
+    pinned_lines: Option<PinnedLines>, // output lines pinned above the grid, see RunCommand::pin_matching
+    fixed_size: Option<(usize, usize)>, // (columns, rows) of the grid, see RunCommand::fixed_pty_size
 }
 
 impl Pane for TerminalPane {
@@ -133,7 +139,7 @@ impl Pane for TerminalPane {
         self.get_x() + self.content_offset.left
     }
     fn get_content_y(&self) -> usize {
-        self.get_y() + self.content_offset.top
+        self.get_y() + self.content_offset.top + self.pinned_rows()
     }
     fn get_content_columns(&self) -> usize {
         // content columns might differ from the pane's columns if the pane has a frame
@@ -146,6 +152,11 @@ impl Pane for TerminalPane {
         // in that case they would be 2 less
         self.get_rows()
             .saturating_sub(self.content_offset.top + self.content_offset.bottom)
+            .saturating_sub(self.pinned_rows())
+    }
+    fn get_pty_size(&self) -> (usize, usize) {
+        self.fixed_size
+            .unwrap_or_else(|| (self.get_content_columns(), self.get_content_rows()))
     }
     fn reset_size_and_position_override(&mut self) {
         self.geom_override = None;
@@ -165,6 +176,7 @@ impl Pane for TerminalPane {
         for &byte in &bytes {
             self.vte_parser.advance(&mut self.grid, byte);
         }
+        self.pin_matching_lines();
     }
     fn cursor_coordinates(&self) -> Option<(usize, usize)> {
         // (x, y)
@@ -173,8 +185,14 @@ impl Pane for TerminalPane {
             return None;
         }
         let Offset { top, left, .. } = self.content_offset;
+        let top = top + self.pinned_rows();
         self.grid
             .cursor_coordinates()
+            .filter(|(x, y)| {
+                // a grid of a fixed size might be cropped, hiding the cursor
+                self.fixed_size.is_none()
+                    || (*x < self.get_content_columns() && *y < self.get_content_rows())
+            })
             .map(|(x, y)| (x + left, y + top))
     }
     fn adjust_input_to_terminal(&mut self, input_bytes: Vec<u8>) -> Option<AdjustedInput> {
@@ -182,15 +200,10 @@ impl Pane for TerminalPane {
         // needs to be adjusted.
         // here we match against those cases - if need be, we adjust the input and if not
         // we send back the original input
-        if let Some((_exit_status, _is_first_run, run_command)) = &self.is_held {
+        if self.is_held.is_some() {
             match input_bytes.as_slice() {
                 ENTER_CARRIAGE_RETURN | ENTER_NEWLINE | SPACE => {
-                    let run_command = run_command.clone();
-                    self.is_held = None;
-                    self.grid.reset_terminal_state();
-                    self.set_should_render(true);
-                    self.remove_banner();
-                    Some(AdjustedInput::ReRunCommandInThisPane(run_command))
+                    self.rerun().map(AdjustedInput::ReRunCommandInThisPane)
                 },
                 CTRL_C => Some(AdjustedInput::CloseThisPane),
                 _ => None,
@@ -265,6 +278,12 @@ impl Pane for TerminalPane {
     fn geom_override(&self) -> Option<PaneGeom> {
         self.geom_override
     }
+    fn size_constraints(&self) -> SizeConstraints {
+        self.size_constraints
+    }
+    fn set_size_constraints(&mut self, size_constraints: SizeConstraints) {
+        self.size_constraints = size_constraints;
+    }
     fn should_render(&self) -> bool {
         self.grid.should_render
     }
@@ -296,8 +315,23 @@ impl Pane for TerminalPane {
                 return Ok(None);
             }
             match self.grid.render(content_x, content_y, &self.style) {
-                Ok(rendered_assets) => {
+                Ok(mut rendered_assets) => {
                     self.set_should_render(false);
+                    if let (Some(_), Some((character_chunks, _, _))) =
+                        (self.fixed_size, rendered_assets.as_mut())
+                    {
+                        self.letterbox(character_chunks, content_x, content_y, rows, columns);
+                    }
+                    if let (Some(pinned_lines), Some((character_chunks, _, _))) =
+                        (&self.pinned_lines, rendered_assets.as_mut())
+                    {
+                        character_chunks.append(&mut pinned_lines.render(
+                            content_x,
+                            self.get_y() + self.content_offset.top,
+                            self.pinned_rows(),
+                            columns,
+                        ));
+                    }
                     return Ok(rendered_assets);
                 },
                 e => return e,
@@ -346,6 +380,12 @@ impl Pane for TerminalPane {
                 if self.grid.search_results.wrap_search {
                     modifiers.push("w")
                 }
+                if self.grid.search_results.regex {
+                    modifiers.push("r")
+                }
+                if self.grid.search_results.visible_only {
+                    modifiers.push("v")
+                }
                 modifier_text.push_str(&modifiers.join(", "));
                 modifier_text.push(']');
             }
@@ -442,9 +482,6 @@ impl Pane for TerminalPane {
     }
     fn update_name(&mut self, name: &str) {
         match name {
-            TERMINATING_STRING => {
-                self.pane_name = String::new();
-            },
             DELETE_KEY | BACKSPACE_KEY => {
                 self.pane_name.pop();
             },
@@ -454,6 +491,10 @@ impl Pane for TerminalPane {
         }
         self.set_should_render(true);
     }
+    fn clear_name(&mut self) {
+        self.pane_name = String::new();
+        self.set_should_render(true);
+    }
     fn pid(&self) -> PaneId {
         PaneId::Terminal(self.pid)
     }
@@ -497,12 +538,33 @@ impl Pane for TerminalPane {
         self.geom.y -= count;
         self.reflow_lines();
     }
-    fn dump_screen(&mut self, _client_id: ClientId, full: bool) -> String {
-        self.grid.dump_screen(full)
+    fn dump_screen(&mut self, _client_id: ClientId, full: bool, preserve_ansi: bool) -> String {
+        if preserve_ansi {
+            self.grid.dump_screen_ansi(full)
+        } else {
+            self.grid.dump_screen(full)
+        }
+    }
+    fn dump_screen_svg(&mut self, _client_id: ClientId, full: bool) -> String {
+        self.grid.dump_screen_svg(full, &self.style.colors)
+    }
+    fn dump_screen_jsonl(&mut self, _client_id: ClientId, full: bool) -> String {
+        self.grid.dump_screen_jsonl(full, &self.style.colors)
+    }
+    fn dump_screen_html(&mut self, _client_id: ClientId, full: bool) -> String {
+        self.grid.dump_screen_html(full, &self.style.colors)
     }
     fn clear_screen(&mut self) {
         self.grid.clear_screen()
     }
+    fn clear_scrollback(&mut self) {
+        self.grid.clear_scrollback();
+        self.set_should_render(true);
+    }
+    fn set_scrollback_lines(&mut self, lines: usize) {
+        self.grid.set_scrollback_buffer_size(lines);
+        self.set_should_render(true);
+    }
     fn scroll_up(&mut self, count: usize, _client_id: ClientId) {
         self.grid.move_viewport_up(count);
         self.set_should_render(true);
@@ -515,9 +577,34 @@ impl Pane for TerminalPane {
         self.grid.reset_viewport();
         self.set_should_render(true);
     }
+    fn scroll_to_previous_prompt(&mut self) -> bool {
+        let found_prompt = self.grid.move_viewport_to_previous_prompt();
+        self.set_should_render(true);
+        found_prompt
+    }
+    fn scroll_to_next_prompt(&mut self) -> bool {
+        let found_prompt = self.grid.move_viewport_to_next_prompt();
+        self.set_should_render(true);
+        found_prompt
+    }
+    fn scroll_to_line(&mut self, line_number: usize) -> bool {
+        let found_line = self.grid.move_viewport_to_line(line_number);
+        self.set_should_render(true);
+        found_line
+    }
+    fn lines_matching(&self, needle: &str) -> Vec<(usize, String)> {
+        self.grid.lines_matching(needle)
+    }
+    fn line_at(&self, position: &Position) -> Option<String> {
+        self.grid.line_at(position)
+    }
     fn is_scrolled(&self) -> bool {
         self.grid.is_scrolled
     }
+    fn toggle_line_wrap(&mut self) {
+        self.grid.toggle_line_wrap();
+        self.set_should_render(true);
+    }
 
     fn active_at(&self) -> Instant {
         self.active_at
@@ -572,10 +659,39 @@ impl Pane for TerminalPane {
         self.grid.reset_selection();
     }
 
+    fn select_all(&mut self, viewport_only: bool) {
+        self.grid.select_all(viewport_only);
+        self.set_should_render(true);
+    }
+
+    fn select_word_at(&mut self, position: &Position) {
+        self.grid.select_word_at(position);
+        self.set_should_render(true);
+    }
+
+    fn select_line_at(&mut self, position: &Position) {
+        self.grid.select_line_at(position);
+        self.set_should_render(true);
+    }
+
     fn get_selected_text(&self) -> Option<String> {
         self.grid.get_selected_text()
     }
 
+    fn get_selected_text_normalized(&self) -> Option<String> {
+        self.grid
+            .get_selected_text()
+            .map(|text| self.grid.normalize_text(&text))
+    }
+
+    fn url_at(&self, position: &Position) -> Option<String> {
+        self.grid.url_at(position)
+    }
+
+    fn last_command_output(&self) -> Option<String> {
+        self.grid.last_command_output()
+    }
+
     fn set_frame(&mut self, _frame: bool) {
         self.frame.clear();
     }
@@ -689,10 +805,32 @@ impl Pane for TerminalPane {
     fn toggle_search_wrap(&mut self) {
         self.grid.toggle_search_wrap();
     }
+    fn toggle_search_regex(&mut self) {
+        self.grid.toggle_search_regex();
+        self.set_should_render(true);
+    }
+    fn toggle_search_visible_only(&mut self) {
+        self.grid.toggle_search_visible_only();
+        self.set_should_render(true);
+    }
     fn clear_search(&mut self) {
         self.grid.clear_search();
         self.search_term.clear();
     }
+    fn search_match_count(&self) -> Option<usize> {
+        if self.search_term.is_empty() {
+            return None;
+        }
+        Some(self.grid.search_match_count())
+    }
+    fn search_jump_to_match(&mut self, match_index: usize) {
+        if self.search_term.is_empty() {
+            return; // No-op
+        }
+        if self.grid.move_viewport_to_search_match(match_index) {
+            self.set_should_render(true);
+        }
+    }
     fn is_alternate_mode_active(&self) -> bool {
         self.grid.is_alternate_mode_active()
     }
@@ -703,6 +841,16 @@ impl Pane for TerminalPane {
         }
         self.set_should_render(true);
     }
+    fn rerun(&mut self) -> Option<RunCommand> {
+        self.is_held
+            .take()
+            .map(|(_exit_status, _is_first_run, run_command)| {
+                self.grid.reset_terminal_state();
+                self.set_should_render(true);
+                self.remove_banner();
+                run_command
+            })
+    }
     fn add_red_pane_frame_color_override(&mut self, error_text: Option<String>) {
         self.pane_frame_color_override = Some((self.style.colors.red, error_text));
     }
@@ -766,9 +914,27 @@ impl TerminalPane {
     ) -> TerminalPane {
         let initial_pane_title =
             initial_pane_title.unwrap_or_else(|| format!("Pane #{}", pane_index));
-        let grid = Grid::new(
-            position_and_size.rows.as_usize(),
+        let pinned_lines = match &invoked_with {
+            Some(Run::Command(RunCommand {
+                pin_matching: Some(pin_matching),
+                ..
+            })) => Regex::new(pin_matching).ok().map(PinnedLines::new),
+            _ => None,
+        };
+        let fixed_size = match &invoked_with {
+            Some(Run::Command(RunCommand {
+                fixed_pty_size: Some((columns, rows)),
+                ..
+            })) => Some((*columns as usize, *rows as usize)),
+            _ => None,
+        };
+        let (grid_columns, grid_rows) = fixed_size.unwrap_or((
             position_and_size.cols.as_usize(),
+            position_and_size.rows.as_usize(),
+        ));
+        let mut grid = Grid::new(
+            grid_rows,
+            grid_columns,
             terminal_emulator_colors,
             terminal_emulator_color_codes,
             link_handler,
@@ -776,6 +942,9 @@ impl TerminalPane {
             sixel_image_store,
             debug,
         );
+        if pinned_lines.is_some() {
+            grid.completed_lines = Some(vec![]);
+        }
         TerminalPane {
             frame: HashMap::new(),
             content_offset: Offset::default(),
@@ -784,6 +953,7 @@ impl TerminalPane {
             selectable: true,
             geom: position_and_size,
             geom_override: None,
+            size_constraints: SizeConstraints::default(),
             vte_parser: vte::Parser::new(),
             active_at: Instant::now(),
             style,
@@ -799,6 +969,8 @@ impl TerminalPane {
             banner: None,
             pane_frame_color_override: None,
             invoked_with,
+            pinned_lines,
+            fixed_size,
         }
     }
     pub fn get_x(&self) -> usize {
@@ -826,8 +998,7 @@ impl TerminalPane {
         }
     }
     fn reflow_lines(&mut self) {
-        let rows = self.get_content_rows();
-        let cols = self.get_content_columns();
+        let (cols, rows) = self.get_pty_size();
         self.grid.force_change_size(rows, cols);
         if self.banner.is_some() {
             self.grid.reset_terminal_state();
@@ -846,6 +1017,73 @@ impl TerminalPane {
         }
         self.grid.cursor_coordinates()
     }
+    /// Fits the lines rendered from a grid of a fixed size (see RunCommand::fixed_pty_size) to the
+    /// content area of the pane, cropping them where the area is smaller than the grid and
+    /// blanking the area around them where it is larger
+    fn letterbox(
+        &self,
+        character_chunks: &mut Vec<CharacterChunk>,
+        content_x: usize,
+        content_y: usize,
+        rows: usize,
+        columns: usize,
+    ) {
+        character_chunks.retain(|chunk| chunk.y < content_y + rows);
+        for chunk in character_chunks.iter_mut() {
+            let mut line_width = 0;
+            let visible_characters = chunk
+                .terminal_characters
+                .iter()
+                .take_while(|character| {
+                    line_width += character.width;
+                    line_width <= columns
+                })
+                .count();
+            chunk.terminal_characters.truncate(visible_characters);
+            let visible_width: usize = chunk
+                .terminal_characters
+                .iter()
+                .map(|character| character.width)
+                .sum();
+            chunk.terminal_characters.resize(
+                visible_characters + columns.saturating_sub(visible_width),
+                EMPTY_TERMINAL_CHARACTER,
+            );
+        }
+        for line_index in self.grid.height..rows {
+            character_chunks.push(CharacterChunk::new(
+                vec![EMPTY_TERMINAL_CHARACTER; columns],
+                content_x,
+                content_y + line_index,
+            ));
+        }
+    }
+    /// The rows taken by pinned lines above the grid, leaving at least one row for the grid
+    fn pinned_rows(&self) -> usize {
+        let rows = self
+            .get_rows()
+            .saturating_sub(self.content_offset.top + self.content_offset.bottom);
+        self.pinned_lines
+            .as_ref()
+            .map(|pinned_lines| pinned_lines.pinned_count().min(rows.saturating_sub(1)))
+            .unwrap_or(0)
+    }
+    fn pin_matching_lines(&mut self) {
+        let pinned_rows = self.pinned_rows();
+        let mut pinned_a_line = false;
+        if let Some(pinned_lines) = self.pinned_lines.as_mut() {
+            for line in self.grid.drain_completed_lines() {
+                pinned_a_line |= pinned_lines.pin_if_matching(line);
+            }
+        }
+        if pinned_a_line {
+            if self.pinned_rows() != pinned_rows {
+                // the grid makes room for the new pinned line
+                self.reflow_lines();
+            }
+            self.render_full_viewport();
+        }
+    }
     fn render_first_run_banner(&mut self) {
         let columns = self.get_content_columns();
         let rows = self.get_content_rows();
@@ -18,13 +18,13 @@ use crate::{
 };
 use stacked_panes::StackedPanes;
 use zellij_utils::{
-    data::{Direction, ModeInfo, PaneInfo, ResizeStrategy, Style},
+    data::{Direction, ModeInfo, PaneInfo, Resize, ResizeStrategy, Style},
     errors::prelude::*,
     input::{
         command::RunCommand,
         layout::{Run, RunPlugin, SplitDirection},
     },
-    pane_size::{Offset, PaneGeom, Size, SizeInPixels, Viewport},
+    pane_size::{Dimension, Offset, PaneGeom, Size, SizeConstraints, SizeInPixels, Viewport},
 };
 
 use std::{
@@ -172,6 +172,10 @@ impl TiledPanes {
         self.add_pane(pane_id, pane, should_relayout);
     }
     pub fn has_room_for_new_pane(&mut self) -> bool {
+        if self.panes.is_empty() {
+            // an empty tab, the new pane will take up the whole viewport
+            return true;
+        }
         let cursor_height_width_ratio = self.cursor_height_width_ratio();
         let pane_grid = TiledPaneGrid::new(
             &mut self.panes,
@@ -185,6 +189,18 @@ impl TiledPanes {
         has_room_for_new_pane || pane_grid.has_room_for_new_stacked_pane()
     }
     fn add_pane(&mut self, pane_id: PaneId, mut pane: Box<dyn Pane>, should_relayout: bool) {
+        if self.panes.is_empty() {
+            let viewport = *self.viewport.borrow();
+            let mut pane_geom = PaneGeom::default();
+            pane_geom.x = viewport.x;
+            pane_geom.y = viewport.y;
+            pane_geom.cols.set_inner(viewport.cols);
+            pane_geom.rows.set_inner(viewport.rows);
+            pane.set_geom(pane_geom);
+            self.panes.insert(pane_id, pane);
+            self.reapply_pane_frames();
+            return;
+        }
         let cursor_height_width_ratio = self.cursor_height_width_ratio();
         let mut pane_grid = TiledPaneGrid::new(
             &mut self.panes,
@@ -415,6 +431,160 @@ impl TiledPanes {
             self.relayout(SplitDirection::Horizontal);
         }
     }
+    /// Insert a pane on the `direction` side of the tab, splitting the whole tab at `fraction` of
+    /// its width or height (measured from its left or top edge) rather than splitting one pane.
+    ///
+    /// Only the flexible panes are squeezed to make room, fixed size panes along the edges of the
+    /// tab (eg. the tab-bar and status-bar) stay in place. Returns false without changing anything
+    /// if there is no room for the new pane.
+    pub fn insert_pane_at_fraction(
+        &mut self,
+        pane_id: PaneId,
+        mut pane: Box<dyn Pane>,
+        direction: Direction,
+        fraction: f64,
+    ) -> bool {
+        let splits_columns = matches!(direction, Direction::Left | Direction::Right);
+        let (flexible, fixed): (Vec<(PaneId, PaneGeom)>, Vec<(PaneId, PaneGeom)>) = self
+            .panes
+            .iter()
+            .map(|(id, pane)| (*id, pane.position_and_size()))
+            .partition(|(_, geom)| {
+                geom.is_stacked || (geom.rows.is_percent() && geom.cols.is_percent())
+            });
+        if flexible.is_empty() || (!splits_columns && flexible.iter().any(|(_, g)| g.is_stacked)) {
+            // stacks can only be squeezed sideways
+            return false;
+        }
+        let area_x = flexible.iter().map(|(_, g)| g.x).min().unwrap_or(0);
+        let area_y = flexible.iter().map(|(_, g)| g.y).min().unwrap_or(0);
+        let area_end_x = flexible
+            .iter()
+            .map(|(_, g)| g.x + g.cols.as_usize())
+            .max()
+            .unwrap_or(0);
+        let area_end_y = flexible
+            .iter()
+            .map(|(_, g)| g.y + g.rows.as_usize())
+            .max()
+            .unwrap_or(0);
+        let overlaps_area = |g: &PaneGeom| {
+            g.x < area_end_x
+                && g.x + g.cols.as_usize() > area_x
+                && g.y < area_end_y
+                && g.y + g.rows.as_usize() > area_y
+        };
+        if fixed.iter().any(|(_, g)| overlaps_area(g)) {
+            return false;
+        }
+        // the share of the tab's flexible space the area takes up in each direction
+        let area_percent = |along_columns: bool| {
+            let percent: f64 = flexible
+                .iter()
+                .filter(|(_, g)| {
+                    if along_columns {
+                        g.y == area_y
+                    } else {
+                        g.x == area_x
+                    }
+                })
+                .filter_map(|(_, g)| {
+                    if along_columns {
+                        g.cols.as_percent()
+                    } else {
+                        g.rows.as_percent()
+                    }
+                })
+                .sum();
+            if percent > 0.0 {
+                percent
+            } else {
+                100.0
+            }
+        };
+        let (cols_percent, rows_percent) = (area_percent(true), area_percent(false));
+
+        let (start, end, min_size) = if splits_columns {
+            (area_x, area_end_x, MIN_TERMINAL_WIDTH)
+        } else {
+            (area_y, area_end_y, MIN_TERMINAL_HEIGHT)
+        };
+        let length = end - start;
+        if length < min_size * 2 {
+            return false;
+        }
+        let split_at = (start + (length as f64 * fraction).round() as usize)
+            .clamp(start + min_size, end - min_size);
+        let new_pane_is_first = matches!(direction, Direction::Left | Direction::Up);
+        let (existing_start, existing_end, new_start, new_end) = if new_pane_is_first {
+            (split_at, end, start, split_at)
+        } else {
+            (start, split_at, split_at, end)
+        };
+        let existing_ratio = (existing_end - existing_start) as f64 / length as f64;
+        let scale = |position: usize| {
+            existing_start + ((position - start) as f64 * existing_ratio).round() as usize
+        };
+
+        let mut squeezed_geoms = Vec::with_capacity(flexible.len());
+        for (id, mut geom) in flexible {
+            let (position, size) = if splits_columns {
+                (&mut geom.x, &mut geom.cols)
+            } else {
+                (&mut geom.y, &mut geom.rows)
+            };
+            let new_position = scale(*position);
+            let new_size = scale(*position + size.as_usize()) - new_position;
+            if new_size < std::cmp::min(size.as_usize(), min_size) {
+                return false;
+            }
+            *position = new_position;
+            if let Some(percent) = size.as_percent() {
+                size.set_percent(percent * existing_ratio);
+            }
+            size.set_inner(new_size);
+            squeezed_geoms.push((id, geom));
+        }
+        let new_pane_geom = if splits_columns {
+            let mut cols = Dimension::percent(cols_percent * (1.0 - existing_ratio));
+            cols.set_inner(new_end - new_start);
+            let mut rows = Dimension::percent(rows_percent);
+            rows.set_inner(area_end_y - area_y);
+            PaneGeom {
+                x: new_start,
+                y: area_y,
+                cols,
+                rows,
+                is_stacked: false,
+            }
+        } else {
+            let mut cols = Dimension::percent(cols_percent);
+            cols.set_inner(area_end_x - area_x);
+            let mut rows = Dimension::percent(rows_percent * (1.0 - existing_ratio));
+            rows.set_inner(new_end - new_start);
+            PaneGeom {
+                x: area_x,
+                y: new_start,
+                cols,
+                rows,
+                is_stacked: false,
+            }
+        };
+
+        for (id, geom) in squeezed_geoms {
+            if let Some(pane) = self.panes.get_mut(&id) {
+                pane.set_geom(geom);
+            }
+        }
+        pane.set_geom(new_pane_geom);
+        self.panes.insert(pane_id, pane);
+        self.relayout(if splits_columns {
+            SplitDirection::Horizontal
+        } else {
+            SplitDirection::Vertical
+        });
+        true
+    }
     pub fn focus_pane_for_all_clients(&mut self, pane_id: PaneId) {
         let connected_clients: Vec<ClientId> =
             self.connected_clients.borrow().iter().copied().collect();
@@ -844,6 +1014,271 @@ impl TiledPanes {
         Ok(())
     }
 
+    pub fn set_active_pane_size(
+        &mut self,
+        client_id: ClientId,
+        cols: Option<usize>,
+        rows: Option<usize>,
+    ) -> Result<()> {
+        let err_context =
+            || format!("failed to set size of active tiled pane for client {client_id}");
+
+        let active_pane_id = match self.get_active_pane_id(client_id) {
+            Some(active_pane_id) => active_pane_id,
+            None => return Ok(()),
+        };
+        let viewport = *self.viewport.borrow();
+        for (target_size, is_horizontal) in [(cols, true), (rows, false)] {
+            let (current_size, available_size) = {
+                let pane = self.panes.get(&active_pane_id).with_context(err_context)?;
+                if is_horizontal {
+                    (pane.cols(), viewport.cols)
+                } else {
+                    (pane.rows(), viewport.rows)
+                }
+            };
+            let target_size = match target_size {
+                Some(target_size) => target_size.max(1).min(available_size),
+                None => continue,
+            };
+            if target_size == current_size || available_size == 0 {
+                continue;
+            }
+            let (resize, change_by_cells) = if target_size > current_size {
+                (Resize::Increase, target_size - current_size)
+            } else {
+                (Resize::Decrease, current_size - target_size)
+            };
+            // pane sizes are percentages of the viewport
+            let change_by = change_by_cells as f64 / available_size as f64 * 100.0;
+            // we first try to move the right (bottom) border and if that's not possible, the left
+            // (top) border
+            let directions = if is_horizontal {
+                [Direction::Right, Direction::Left]
+            } else {
+                [Direction::Down, Direction::Up]
+            };
+            let mut pane_grid = TiledPaneGrid::new(
+                &mut self.panes,
+                &self.panes_to_hide,
+                *self.display_area.borrow(),
+                viewport,
+            );
+            for direction in directions {
+                let strategy = ResizeStrategy {
+                    resize,
+                    direction: Some(direction),
+                    invert_on_boundaries: false,
+                };
+                match pane_grid.change_pane_size(&active_pane_id, &strategy, (change_by, change_by))
+                {
+                    Ok(true) => break,
+                    Ok(false) => continue,
+                    Err(err) => match err.downcast_ref::<ZellijError>() {
+                        Some(ZellijError::CantResizeFixedPanes { .. })
+                        | Some(ZellijError::PaneSizeUnchanged) => continue,
+                        _ => return Err(err).with_context(err_context),
+                    },
+                }
+            }
+        }
+        for pane in self.panes.values_mut() {
+            resize_pty!(pane, self.os_api, self.senders, self.character_cell_size)
+                .with_context(|| format!("failed to resize PTY in pane {:?}", pane.pid()))?;
+        }
+        self.reset_boundaries();
+        self.set_force_render();
+        Ok(())
+    }
+
+    pub fn set_active_pane_size_in_percent(
+        &mut self,
+        client_id: ClientId,
+        width: Option<u8>,
+        height: Option<u8>,
+    ) -> Result<()> {
+        let err_context =
+            || format!("failed to set size of active tiled pane in percent for client {client_id}");
+
+        let active_pane_id = match self.get_active_pane_id(client_id) {
+            Some(active_pane_id) => active_pane_id,
+            None => return Ok(()),
+        };
+        let viewport = *self.viewport.borrow();
+        for (target_percent, is_horizontal) in [(width, true), (height, false)] {
+            let target_percent = match target_percent {
+                Some(target_percent) => target_percent.clamp(1, 100) as usize,
+                None => continue,
+            };
+            let (position, current_size, viewport_position, available_size) = {
+                let pane = self.panes.get(&active_pane_id).with_context(err_context)?;
+                if is_horizontal {
+                    (pane.x(), pane.cols(), viewport.x, viewport.cols)
+                } else {
+                    (pane.y(), pane.rows(), viewport.y, viewport.rows)
+                }
+            };
+            let target_size = ((available_size * target_percent) as f64 / 100.0).round() as usize;
+            let target_size = target_size.max(1);
+            if target_size == current_size || available_size == 0 {
+                continue;
+            }
+            let (resize, change_by_cells) = if target_size > current_size {
+                (Resize::Increase, target_size - current_size)
+            } else {
+                (Resize::Decrease, current_size - target_size)
+            };
+            // the change is split between both sides of the pane, proportionally to the space the
+            // panes on each side of it take up
+            let space_before = position.saturating_sub(viewport_position);
+            let space_after =
+                (viewport_position + available_size).saturating_sub(position + current_size);
+            if space_before + space_after == 0 {
+                continue;
+            }
+            let change_after = change_by_cells * space_after / (space_before + space_after);
+            let (direction_after, direction_before) = if is_horizontal {
+                (Direction::Right, Direction::Left)
+            } else {
+                (Direction::Down, Direction::Up)
+            };
+            let changed_after = self
+                .change_active_pane_size_as_far_as_possible(
+                    &active_pane_id,
+                    resize,
+                    direction_after,
+                    change_after,
+                    available_size,
+                )
+                .with_context(err_context)?;
+            // whatever could not be taken from (given to) the panes after the pane is taken from
+            // (given to) the ones before it
+            self.change_active_pane_size_as_far_as_possible(
+                &active_pane_id,
+                resize,
+                direction_before,
+                change_by_cells - changed_after,
+                available_size,
+            )
+            .with_context(err_context)?;
+        }
+        for pane in self.panes.values_mut() {
+            resize_pty!(pane, self.os_api, self.senders, self.character_cell_size)
+                .with_context(|| format!("failed to resize PTY in pane {:?}", pane.pid()))?;
+        }
+        self.reset_boundaries();
+        self.set_force_render();
+        Ok(())
+    }
+
+    // returns the amount of cells the pane was actually changed by, which is less than requested
+    // if the panes next to it would otherwise be smaller than their minimum size
+    fn change_active_pane_size_as_far_as_possible(
+        &mut self,
+        pane_id: &PaneId,
+        resize: Resize,
+        direction: Direction,
+        change_by_cells: usize,
+        available_size: usize,
+    ) -> Result<usize> {
+        let mut pane_grid = TiledPaneGrid::new(
+            &mut self.panes,
+            &self.panes_to_hide,
+            *self.display_area.borrow(),
+            *self.viewport.borrow(),
+        );
+        let strategy = ResizeStrategy {
+            resize,
+            direction: Some(direction),
+            invert_on_boundaries: false,
+        };
+        for cells in (1..=change_by_cells).rev() {
+            // pane sizes are percentages of the viewport
+            let change_by = cells as f64 / available_size as f64 * 100.0;
+            match pane_grid.change_pane_size(pane_id, &strategy, (change_by, change_by)) {
+                Ok(true) => return Ok(cells),
+                Ok(false) => return Ok(0), // no panes on this side
+                Err(err) => match err.downcast_ref::<ZellijError>() {
+                    Some(ZellijError::CantResizeFixedPanes { .. })
+                    | Some(ZellijError::PaneSizeUnchanged) => continue,
+                    _ => return Err(err),
+                },
+            }
+        }
+        Ok(0)
+    }
+
+    pub fn balance_panes_axis(&mut self, vertical: bool) -> Result<()> {
+        let direction = if vertical {
+            SplitDirection::Vertical
+        } else {
+            SplitDirection::Horizontal
+        };
+        let mut pane_grid = TiledPaneGrid::new(
+            &mut self.panes,
+            &self.panes_to_hide,
+            *self.display_area.borrow(),
+            *self.viewport.borrow(),
+        );
+        match pane_grid.balance_panes(direction) {
+            Ok(_) => {},
+            Err(err) => match err.downcast_ref::<ZellijError>() {
+                Some(ZellijError::PaneSizeUnchanged) => {}, // already balanced
+                _ => return Err(err),
+            },
+        }
+        for pane in self.panes.values_mut() {
+            resize_pty!(pane, self.os_api, self.senders, self.character_cell_size)
+                .with_context(|| format!("failed to resize PTY in pane {:?}", pane.pid()))?;
+        }
+        self.reset_boundaries();
+        self.set_force_render();
+        Ok(())
+    }
+
+    pub fn set_pane_size_constraints(
+        &mut self,
+        pane_id: PaneId,
+        size_constraints: SizeConstraints,
+    ) -> Result<()> {
+        let err_context = || format!("failed to set size constraints of pane {pane_id:?}");
+        self.panes
+            .get_mut(&pane_id)
+            .with_context(err_context)?
+            .set_size_constraints(size_constraints);
+        let display_area = *self.display_area.borrow();
+        let mut pane_grid = TiledPaneGrid::new(
+            &mut self.panes,
+            &self.panes_to_hide,
+            display_area,
+            *self.viewport.borrow(),
+        );
+        for (direction, space) in [
+            (SplitDirection::Horizontal, display_area.cols),
+            (SplitDirection::Vertical, display_area.rows),
+        ] {
+            match pane_grid.layout(direction, space) {
+                Ok(_) => {},
+                Err(err) => match err.downcast_ref::<ZellijError>() {
+                    Some(ZellijError::PaneSizeUnchanged) => {}, // already within constraints
+                    _ => return Err(err).with_context(err_context),
+                },
+            }
+        }
+        for pane in self.panes.values_mut() {
+            resize_pty!(pane, self.os_api, self.senders, self.character_cell_size)
+                .with_context(|| format!("failed to resize PTY in pane {:?}", pane.pid()))?;
+        }
+        self.reset_boundaries();
+        self.set_force_render();
+        Ok(())
+    }
+
+    pub fn balance_panes(&mut self) -> Result<()> {
+        self.balance_panes_axis(false)?;
+        self.balance_panes_axis(true)
+    }
+
     pub fn focus_next_pane(&mut self, client_id: ClientId) {
         let connected_clients: Vec<ClientId> =
             { self.connected_clients.borrow().iter().copied().collect() };
@@ -1243,6 +1678,49 @@ impl TiledPanes {
         current_position.set_should_render(true);
         self.set_pane_frames(self.draw_pane_frames);
     }
+    pub fn rotate_panes(&mut self, backwards: bool) {
+        let mut pane_ids: Vec<PaneId> = self
+            .panes
+            .iter()
+            .filter(|(_, p)| p.selectable())
+            .map(|(p_id, _)| *p_id)
+            .collect();
+        if pane_ids.len() < 2 {
+            return;
+        }
+        pane_ids.sort_by(|a_id, b_id| {
+            let a_pane = self.panes.get(a_id).unwrap();
+            let b_pane = self.panes.get(b_id).unwrap();
+            if a_pane.y() == b_pane.y() {
+                a_pane.x().cmp(&b_pane.x())
+            } else {
+                a_pane.y().cmp(&b_pane.y())
+            }
+        });
+        let slots: Vec<(PaneGeom, Option<PaneGeom>)> = pane_ids
+            .iter()
+            .filter_map(|p_id| self.panes.get(p_id))
+            .map(|p| (p.position_and_size(), p.geom_override()))
+            .collect();
+        let pane_count = pane_ids.len();
+        for (index, pane_id) in pane_ids.iter().enumerate() {
+            let slot_index = if backwards {
+                (index + pane_count - 1) % pane_count
+            } else {
+                (index + 1) % pane_count
+            };
+            let (geom, geom_override) = slots[slot_index];
+            if let Some(pane) = self.panes.get_mut(pane_id) {
+                pane.set_geom(geom);
+                if let Some(geom) = geom_override {
+                    pane.set_geom_override(geom);
+                }
+                resize_pty!(pane, self.os_api, self.senders, self.character_cell_size).unwrap();
+                pane.set_should_render(true);
+            }
+        }
+        self.set_pane_frames(self.draw_pane_frames);
+    }
     pub fn move_active_pane_down(&mut self, client_id: ClientId) {
         if let Some(active_pane_id) = self.get_active_pane_id(client_id) {
             let mut pane_grid = TiledPaneGrid::new(
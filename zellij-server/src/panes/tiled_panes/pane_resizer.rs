@@ -3,7 +3,7 @@ use crate::{panes::PaneId, tab::Pane};
 use cassowary::{
     strength::{REQUIRED, STRONG},
     Expression, Solver, Variable,
-    WeightedRelation::EQ,
+    WeightedRelation::{EQ, GE, LE},
 };
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
@@ -14,6 +14,12 @@ use zellij_utils::{
     pane_size::{Constraint, Dimension, PaneGeom},
 };
 
+// size constraints of individual panes are preferred over keeping the ratios between panes, with
+// minimum sizes winning over maximum ones - neither is required so that panes overflow rather than
+// failing to lay out when there's not enough room for them
+const MIN_SIZE_STRENGTH: f64 = STRONG * 100.0;
+const MAX_SIZE_STRENGTH: f64 = STRONG * 10.0;
+
 pub struct PaneResizer<'a> {
     panes: Rc<RefCell<HashMap<PaneId, &'a mut Box<dyn Pane>>>>,
     vars: HashMap<PaneId, Variable>,
@@ -29,6 +35,8 @@ struct Span {
     pos: usize,
     size: Dimension,
     size_var: Variable,
+    min_size: Option<usize>,
+    max_size: Option<usize>,
 }
 
 type Grid = Vec<Vec<Span>>;
@@ -261,6 +269,7 @@ impl<'a> PaneResizer<'a> {
             }
         }?;
         let size_var = *self.vars.get(&pane.pid()).unwrap();
+        let size_constraints = pane.size_constraints();
         match direction {
             SplitDirection::Horizontal => Some(Span {
                 pid: pane.pid(),
@@ -268,6 +277,8 @@ impl<'a> PaneResizer<'a> {
                 pos: position_and_size.x,
                 size: position_and_size.cols,
                 size_var,
+                min_size: size_constraints.min_cols,
+                max_size: size_constraints.max_cols,
             }),
             SplitDirection::Vertical => Some(Span {
                 pid: pane.pid(),
@@ -275,6 +286,8 @@ impl<'a> PaneResizer<'a> {
                 pos: position_and_size.y,
                 size: position_and_size.rows,
                 size_var,
+                min_size: size_constraints.min_rows,
+                max_size: size_constraints.max_rows,
             }),
         }
     }
@@ -305,6 +318,12 @@ fn constrain_spans(space: usize, spans: &[Span]) -> HashSet<cassowary::Constrain
             Constraint::Percent(p) => constraints
                 .insert((span.size_var / new_flex_space as f64) | EQ(STRONG) | (p / 100.0)),
         };
+        if let Some(min_size) = span.min_size {
+            constraints.insert(span.size_var | GE(MIN_SIZE_STRENGTH) | min_size as f64);
+        }
+        if let Some(max_size) = span.max_size {
+            constraints.insert(span.size_var | LE(MAX_SIZE_STRENGTH) | max_size as f64);
+        }
     }
 
     constraints
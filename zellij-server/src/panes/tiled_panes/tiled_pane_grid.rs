@@ -9,7 +9,7 @@ use zellij_utils::data::{Direction, Resize, ResizeStrategy};
 use zellij_utils::{
     errors::prelude::*,
     input::layout::SplitDirection,
-    pane_size::{Dimension, PaneGeom, Size, Viewport},
+    pane_size::{Dimension, PaneGeom, Size, SizeConstraints, Viewport},
 };
 
 use std::cell::RefCell;
@@ -54,6 +54,52 @@ impl<'a> TiledPaneGrid<'a> {
         let mut pane_resizer = PaneResizer::new(self.panes.clone());
         pane_resizer.layout(direction, space)
     }
+    /// Even out the sizes of panes along the given direction, following the splits that
+    /// currently make up the layout and leaving the other direction untouched.
+    ///
+    /// The layout is taken apart into nested rows and columns, the flexible parts of each split
+    /// get an equal share of the space given to it while fixed parts keep their size. Layouts
+    /// that cannot be taken apart this way or that contain stacked panes are left untouched.
+    pub fn balance_panes(&mut self, direction: SplitDirection) -> Result<()> {
+        let err_context = || format!("failed to balance panes in {direction:?}");
+        let panes: Vec<(PaneId, PaneGeom)> = self
+            .panes
+            .borrow()
+            .iter()
+            .map(|(pane_id, pane)| (*pane_id, pane.current_geom()))
+            .collect();
+        if panes.len() < 2 || panes.iter().any(|(_, geom)| geom.is_stacked) {
+            return Ok(());
+        }
+        let (cols, rows, space) = match direction {
+            SplitDirection::Horizontal => (Some(100.0), None, self.display_area.cols),
+            SplitDirection::Vertical => (None, Some(100.0), self.display_area.rows),
+        };
+        let mut balanced_sizes = HashMap::new();
+        if !balanced_sizes_of_split(panes, cols, rows, &mut balanced_sizes) {
+            return Ok(());
+        }
+        {
+            let mut panes = self.panes.borrow_mut();
+            for (pane_id, (cols, rows)) in balanced_sizes {
+                if let Some(pane) = panes.get_mut(&pane_id) {
+                    let mut geom = pane.current_geom();
+                    if let Some(cols) = cols {
+                        geom.cols.set_percent(cols);
+                    }
+                    if let Some(rows) = rows {
+                        geom.rows.set_percent(rows);
+                    }
+                    pane.set_geom(geom);
+                }
+            }
+        }
+        let mut pane_resizer = PaneResizer::new(self.panes.clone());
+        pane_resizer
+            .layout(direction, space)
+            .with_context(err_context)?;
+        Ok(())
+    }
     fn get_pane_geom(&self, pane_id: &PaneId) -> Option<PaneGeom> {
         let panes = self.panes.borrow();
         let pane_to_check = panes.get(pane_id)?;
@@ -180,6 +226,12 @@ impl<'a> TiledPaneGrid<'a> {
             if direction.is_horizontal() {
                 match strategy.resize {
                     Resize::Increase => {
+                        if !self
+                            .can_increase_pane_width(pane_id, change_by.0 as f64)
+                            .with_context(err_context)?
+                        {
+                            return Ok(false);
+                        }
                         for id in pane_ids {
                             if !self
                                 .can_reduce_pane_width(&id, change_by.0 as f64)
@@ -190,13 +242,28 @@ impl<'a> TiledPaneGrid<'a> {
                         }
                         Ok(true)
                     },
-                    Resize::Decrease => self
-                        .can_reduce_pane_width(pane_id, change_by.0 as f64)
-                        .with_context(err_context),
+                    Resize::Decrease => {
+                        for id in pane_ids {
+                            if !self
+                                .can_increase_pane_width(&id, change_by.0 as f64)
+                                .with_context(err_context)?
+                            {
+                                return Ok(false);
+                            }
+                        }
+                        self.can_reduce_pane_width(pane_id, change_by.0 as f64)
+                            .with_context(err_context)
+                    },
                 }
             } else {
                 match strategy.resize {
                     Resize::Increase => {
+                        if !self
+                            .can_increase_pane_height(pane_id, change_by.1 as f64)
+                            .with_context(err_context)?
+                        {
+                            return Ok(false);
+                        }
                         for id in pane_ids {
                             if !self
                                 .can_reduce_pane_height(&id, change_by.1 as f64)
@@ -207,9 +274,18 @@ impl<'a> TiledPaneGrid<'a> {
                         }
                         Ok(true)
                     },
-                    Resize::Decrease => self
-                        .can_reduce_pane_height(pane_id, change_by.1 as f64)
-                        .with_context(err_context),
+                    Resize::Decrease => {
+                        for id in pane_ids {
+                            if !self
+                                .can_increase_pane_height(&id, change_by.1 as f64)
+                                .with_context(err_context)?
+                            {
+                                return Ok(false);
+                            }
+                        }
+                        self.can_reduce_pane_height(pane_id, change_by.1 as f64)
+                            .with_context(err_context)
+                    },
                 }
             }
         } else {
@@ -521,9 +597,13 @@ impl<'a> TiledPaneGrid<'a> {
             .get_pane_geom(pane_id)
             .with_context(|| no_pane_id(pane_id))
             .with_context(err_context)?;
+        let min_terminal_width = std::cmp::max(
+            MIN_TERMINAL_WIDTH,
+            self.pane_size_constraints(pane_id).min_cols.unwrap_or(0),
+        );
         let current_fixed_cols = pane.cols.as_usize();
         let will_reduce_by = ((self.display_area.cols as f64 / 100.0) * reduce_by) as usize;
-        if current_fixed_cols.saturating_sub(will_reduce_by) < MIN_TERMINAL_WIDTH {
+        if current_fixed_cols.saturating_sub(will_reduce_by) < min_terminal_width {
             Ok(false)
         } else if let Some(cols) = pane.cols.as_percent() {
             Ok(cols - reduce_by >= RESIZE_PERCENT)
@@ -543,7 +623,10 @@ impl<'a> TiledPaneGrid<'a> {
         let min_terminal_height = if pane.is_stacked {
             StackedPanes::new(self.panes.clone()).min_stack_height(pane_id)?
         } else {
-            MIN_TERMINAL_HEIGHT
+            std::cmp::max(
+                MIN_TERMINAL_HEIGHT,
+                self.pane_size_constraints(pane_id).min_rows.unwrap_or(0),
+            )
         };
         let current_fixed_rows = pane.rows.as_usize();
         let will_reduce_by = ((self.display_area.rows as f64 / 100.0) * reduce_by) as usize;
@@ -555,6 +638,45 @@ impl<'a> TiledPaneGrid<'a> {
             Ok(false)
         }
     }
+    fn can_increase_pane_width(&self, pane_id: &PaneId, increase_by: f64) -> Result<bool> {
+        let err_context = || {
+            format!("failed to determine if pane {pane_id:?} can increase width by {increase_by} %")
+        };
+
+        let pane = self
+            .get_pane_geom(pane_id)
+            .with_context(|| no_pane_id(pane_id))
+            .with_context(err_context)?;
+        let will_increase_by = ((self.display_area.cols as f64 / 100.0) * increase_by) as usize;
+        match self.pane_size_constraints(pane_id).max_cols {
+            Some(max_cols) => Ok(pane.cols.as_usize() + will_increase_by <= max_cols),
+            None => Ok(true),
+        }
+    }
+    fn can_increase_pane_height(&self, pane_id: &PaneId, increase_by: f64) -> Result<bool> {
+        let err_context = || {
+            format!(
+                "failed to determine if pane {pane_id:?} can increase height by {increase_by} %"
+            )
+        };
+
+        let pane = self
+            .get_pane_geom(pane_id)
+            .with_context(|| no_pane_id(pane_id))
+            .with_context(err_context)?;
+        let will_increase_by = ((self.display_area.rows as f64 / 100.0) * increase_by) as usize;
+        match self.pane_size_constraints(pane_id).max_rows {
+            Some(max_rows) => Ok(pane.rows.as_usize() + will_increase_by <= max_rows),
+            None => Ok(true),
+        }
+    }
+    fn pane_size_constraints(&self, pane_id: &PaneId) -> SizeConstraints {
+        self.panes
+            .borrow()
+            .get(pane_id)
+            .map(|pane| pane.size_constraints())
+            .unwrap_or_default()
+    }
 
     fn reduce_pane_height(&mut self, id: &PaneId, percent: f64) {
         if self.can_reduce_pane_height(id, percent).unwrap() {
@@ -1416,3 +1538,87 @@ pub fn split(direction: SplitDirection, rect: &PaneGeom) -> Option<(PaneGeom, Pa
         None
     }
 }
+
+type BalancedSizes = HashMap<PaneId, (Option<f64>, Option<f64>)>;
+
+// Splits the panes into the parts lying one after the other along the given axis, returns a
+// single part if no cut goes through all of them
+fn parts_along_axis(
+    mut panes: Vec<(PaneId, PaneGeom)>,
+    along_x: bool,
+) -> Vec<Vec<(PaneId, PaneGeom)>> {
+    let span = |geom: &PaneGeom| {
+        if along_x {
+            (geom.x, geom.x + geom.cols.as_usize())
+        } else {
+            (geom.y, geom.y + geom.rows.as_usize())
+        }
+    };
+    panes.sort_by_key(|(pane_id, geom)| (span(geom).0, *pane_id));
+    let mut parts = vec![];
+    let mut current_part: Vec<(PaneId, PaneGeom)> = vec![];
+    let mut current_end = 0;
+    for (pane_id, geom) in panes {
+        let (start, end) = span(&geom);
+        if !current_part.is_empty() && start >= current_end {
+            parts.push(std::mem::take(&mut current_part));
+        }
+        current_end = if current_part.is_empty() {
+            end
+        } else {
+            std::cmp::max(current_end, end)
+        };
+        current_part.push((pane_id, geom));
+    }
+    if !current_part.is_empty() {
+        parts.push(current_part);
+    }
+    parts
+}
+
+// Fills in the balanced (cols, rows) percentages of the panes given the share of the screen
+// available to them (None meaning the panes keep their size in that direction), returns false if
+// the panes cannot be split into rows and columns
+fn balanced_sizes_of_split(
+    panes: Vec<(PaneId, PaneGeom)>,
+    cols: Option<f64>,
+    rows: Option<f64>,
+    balanced_sizes: &mut BalancedSizes,
+) -> bool {
+    if panes.len() == 1 {
+        let (pane_id, _) = panes[0];
+        balanced_sizes.insert(pane_id, (cols, rows));
+        return true;
+    }
+    for along_x in [true, false] {
+        let parts = parts_along_axis(panes.clone(), along_x);
+        if parts.len() < 2 {
+            continue;
+        }
+        let is_flexible = |part: &Vec<(PaneId, PaneGeom)>| {
+            part.iter().all(|(_, geom)| {
+                if along_x {
+                    geom.cols.is_percent()
+                } else {
+                    geom.rows.is_percent()
+                }
+            })
+        };
+        let flexible_part_count = parts.iter().filter(|part| is_flexible(part)).count();
+        let available = if along_x { cols } else { rows };
+        let share = available.map(|available| available / flexible_part_count.max(1) as f64);
+        for part in parts {
+            let part_share = if is_flexible(&part) { share } else { None };
+            let (part_cols, part_rows) = if along_x {
+                (part_share, rows)
+            } else {
+                (cols, part_share)
+            };
+            if !balanced_sizes_of_split(part, part_cols, part_rows, balanced_sizes) {
+                return false;
+            }
+        }
+        return true;
+    }
+    false
+}
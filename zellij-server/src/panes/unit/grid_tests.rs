@@ -2768,3 +2768,554 @@ pub fn cursor_hide_persists_through_alternate_screen() {
         "Cursor still shown away from alternate screen"
     );
 }
+
+#[test]
+pub fn url_at_detects_plain_text_and_osc8_urls() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let character_cell_size = Rc::new(RefCell::new(Some(SizeInPixels {
+        width: 8,
+        height: 21,
+    })));
+    let debug = false;
+    let mut grid = Grid::new(
+        30,
+        112,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        character_cell_size,
+        sixel_image_store,
+        debug,
+    );
+
+    let content = "see https://zellij.dev/docs. for more\n\r\u{1b}]8;;https://example.com/hidden\u{1b}\\click here\u{1b}]8;;\u{1b}\\ and more";
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(
+        grid.url_at(&Position::new(0, 10)),
+        Some("https://zellij.dev/docs".to_owned()),
+        "plain text url detected without trailing punctuation"
+    );
+    assert_eq!(grid.url_at(&Position::new(0, 1)), None, "no url before it");
+    assert_eq!(
+        grid.url_at(&Position::new(0, 27)),
+        None,
+        "trailing punctuation is not part of the url"
+    );
+    assert_eq!(
+        grid.url_at(&Position::new(1, 3)),
+        Some("https://example.com/hidden".to_owned()),
+        "osc8 link detected"
+    );
+    assert_eq!(
+        grid.url_at(&Position::new(1, 12)),
+        None,
+        "no url after the osc8 link"
+    );
+}
+
+#[test]
+pub fn url_at_detects_file_and_bare_www_urls() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let character_cell_size = Rc::new(RefCell::new(Some(SizeInPixels {
+        width: 8,
+        height: 21,
+    })));
+    let debug = false;
+    let mut grid = Grid::new(
+        30,
+        112,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        character_cell_size,
+        sixel_image_store,
+        debug,
+    );
+
+    let content = "open file:///tmp/report.html or www.zellij.dev, then quit";
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(
+        grid.url_at(&Position::new(0, 8)),
+        Some("file:///tmp/report.html".to_owned()),
+        "file url detected"
+    );
+    assert_eq!(
+        grid.url_at(&Position::new(0, 35)),
+        Some("www.zellij.dev".to_owned()),
+        "bare www url detected without trailing punctuation"
+    );
+    assert_eq!(
+        grid.url_at(&Position::new(0, 30)),
+        None,
+        "no url between them"
+    );
+}
+
+#[test]
+pub fn select_word_and_line_at_position() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let character_cell_size = Rc::new(RefCell::new(Some(SizeInPixels {
+        width: 8,
+        height: 21,
+    })));
+    let debug = false;
+    let mut grid = Grid::new(
+        30,
+        112,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        character_cell_size,
+        sixel_image_store,
+        debug,
+    );
+
+    let content = "let my_var = (first.second);\n\rnext line";
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    grid.select_word_at(&Position::new(0, 6));
+    assert_eq!(
+        grid.get_selected_text(),
+        Some("my_var".to_owned()),
+        "underscores are part of a word"
+    );
+    grid.select_word_at(&Position::new(0, 16));
+    assert_eq!(
+        grid.get_selected_text(),
+        Some("first".to_owned()),
+        "punctuation ends a word"
+    );
+    grid.reset_selection();
+    grid.select_word_at(&Position::new(0, 3));
+    assert_eq!(grid.get_selected_text(), None, "no word on whitespace");
+    grid.select_line_at(&Position::new(1, 2));
+    assert_eq!(
+        grid.get_selected_text(),
+        Some("next line".to_owned()),
+        "whole line selected"
+    );
+}
+
+#[test]
+pub fn move_viewport_to_search_match_by_index() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        5,
+        20,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    let mut content = String::new();
+    for line in 0..20 {
+        if [3, 8, 15].contains(&line) {
+            content.push_str(&format!("needle {}\n\r", line));
+        } else {
+            content.push_str(&format!("line {}\n\r", line));
+        }
+    }
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    grid.set_search_string("needle");
+    assert_eq!(
+        grid.search_match_count(),
+        3,
+        "all matches in the buffer are counted"
+    );
+    let top_line = |grid: &mut Grid| grid.dump_screen(false).lines().next().unwrap().to_owned();
+    assert!(grid.move_viewport_to_search_match(1));
+    assert_eq!(
+        top_line(&mut grid),
+        "needle 8",
+        "scrolled to the second match"
+    );
+    assert_eq!(
+        grid.search_results.active.map(|active| active.start.line()),
+        Some(0),
+        "the match is the active one"
+    );
+    assert!(grid.move_viewport_to_search_match(0));
+    assert_eq!(
+        top_line(&mut grid),
+        "needle 3",
+        "scrolled to the first match"
+    );
+    assert!(
+        !grid.move_viewport_to_search_match(3),
+        "there is no fourth match"
+    );
+    assert_eq!(top_line(&mut grid), "needle 3", "did not scroll");
+}
+
+#[test]
+pub fn move_viewport_between_osc_133_prompt_marks() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        5,
+        20,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    let mut content = String::new();
+    for command in ["first", "second"] {
+        content.push_str(&format!("\u{1b}]133;A\u{7}$ {}\n\r", command));
+        for line in 0..8 {
+            content.push_str(&format!("{} output {}\n\r", command, line));
+        }
+    }
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    let top_line = |grid: &mut Grid| grid.dump_screen(false).lines().next().unwrap().to_owned();
+    assert!(grid.move_viewport_to_previous_prompt());
+    assert_eq!(
+        top_line(&mut grid),
+        "$ second",
+        "scrolled to the last prompt"
+    );
+    assert!(grid.move_viewport_to_previous_prompt());
+    assert_eq!(
+        top_line(&mut grid),
+        "$ first",
+        "scrolled to the prompt before it"
+    );
+    assert!(
+        !grid.move_viewport_to_previous_prompt(),
+        "no prompt above the first one"
+    );
+    assert!(grid.move_viewport_to_next_prompt());
+    assert_eq!(
+        top_line(&mut grid),
+        "$ second",
+        "scrolled back down a prompt"
+    );
+    assert!(
+        !grid.move_viewport_to_next_prompt(),
+        "no prompt below the last one"
+    );
+}
+
+#[test]
+pub fn lines_matching_a_search_and_moving_the_viewport_to_them() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        5,
+        20,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    let mut content = String::new();
+    for line in 1..=20 {
+        if line % 7 == 0 {
+            content.push_str(&format!("needle on line {}\n\r", line));
+        } else {
+            content.push_str(&format!("hay on line {}\n\r", line));
+        }
+    }
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(
+        grid.lines_matching("needle"),
+        vec![
+            (7, "needle on line 7".to_owned()),
+            (14, "needle on line 14".to_owned())
+        ],
+        "matching lines with their line numbers"
+    );
+    let top_line = |grid: &mut Grid| grid.dump_screen(false).lines().next().unwrap().to_owned();
+    assert!(grid.move_viewport_to_line(7));
+    assert_eq!(
+        top_line(&mut grid),
+        "needle on line 7",
+        "scrolled up to the line"
+    );
+    assert!(grid.move_viewport_to_line(14));
+    assert_eq!(
+        top_line(&mut grid),
+        "needle on line 14",
+        "scrolled down to the line"
+    );
+    assert!(!grid.move_viewport_to_line(100), "no such line");
+}
+
+#[test]
+pub fn last_command_output_between_osc_133_prompt_marks() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        5,
+        20,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    let content = "no marks yet\n\r\u{1b}]133;A\u{7}$ ls\n\r";
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(
+        grid.last_command_output(),
+        None,
+        "no output without two prompts"
+    );
+    let content = "one\n\rtwo\n\rthree\n\rfour\n\r\u{1b}]133;A\u{7}$ ";
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(
+        grid.last_command_output(),
+        Some("one\ntwo\nthree\nfour".to_owned()),
+        "output between the last two prompts, including scrollback"
+    );
+}
+
+#[test]
+pub fn toggle_line_wrap_reflows_long_lines() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        5,
+        10,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    let row_texts = |grid: &Grid| -> Vec<String> {
+        grid.viewport
+            .iter()
+            .map(|row| row.columns.iter().map(|c| c.character).collect())
+            .collect()
+    };
+    let content = "0123456789abcde\n\rshort";
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(
+        &row_texts(&grid)[..3],
+        &["0123456789", "abcde", "short"],
+        "long line is wrapped"
+    );
+    grid.toggle_line_wrap();
+    assert_eq!(
+        &row_texts(&grid)[..2],
+        &["0123456789abcde", "short"],
+        "long line is kept whole in a single row when truncated"
+    );
+    let content = "\n\rABCDEFGHIJKL";
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(
+        &row_texts(&grid)[..3],
+        &["0123456789abcde", "short", "ABCDEFGHIJKL"],
+        "new output past the edge is kept in the same row"
+    );
+    grid.toggle_line_wrap();
+    assert_eq!(
+        &row_texts(&grid)[..5],
+        &["0123456789", "abcde", "short", "ABCDEFGHIJ", "KL"],
+        "lines are wrapped again"
+    );
+}
+
+#[test]
+pub fn set_scrollback_buffer_size() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        10,
+        20,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    let mut write_lines = |grid: &mut Grid, count: usize| {
+        for i in 0..count {
+            for byte in format!("line {}\n\r", i).as_bytes() {
+                vte_parser.advance(grid, *byte);
+            }
+        }
+    };
+    write_lines(&mut grid, 30);
+    assert_eq!(grid.scrollback_position_and_length(), (0, 21));
+    grid.move_viewport_up(3);
+    grid.set_scrollback_buffer_size(5);
+    assert_eq!(
+        grid.scrollback_position_and_length(),
+        (0, 5),
+        "excess lines dropped and scrolled back to the bottom"
+    );
+    write_lines(&mut grid, 10);
+    assert_eq!(
+        grid.scrollback_position_and_length(),
+        (0, 5),
+        "new lines are kept to the limit"
+    );
+    grid.set_scrollback_buffer_size(0);
+    write_lines(&mut grid, 10);
+    assert_eq!(
+        grid.scrollback_position_and_length(),
+        (0, 0),
+        "no scrollback kept"
+    );
+}
+
+#[test]
+pub fn select_all() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        10,
+        20,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    for i in 0..15 {
+        for byte in format!("line {}\n\r", i).as_bytes() {
+            vte_parser.advance(&mut grid, *byte);
+        }
+    }
+    grid.select_all(false);
+    let text = grid.get_selected_text().unwrap();
+    assert!(
+        text.starts_with("line 0\nline 1\n"),
+        "selection starts at the top of the scrollback"
+    );
+    assert!(
+        text.contains("line 13\nline 14"),
+        "selection includes the viewport"
+    );
+
+    grid.select_all(true);
+    let text = grid.get_selected_text().unwrap();
+    assert!(
+        text.starts_with("line 6\nline 7\n"),
+        "selection starts at the top of the viewport"
+    );
+    assert!(
+        text.contains("line 13\nline 14"),
+        "selection includes the viewport"
+    );
+
+    grid.reset_selection();
+    assert_eq!(grid.get_selected_text(), None, "selection cleared");
+}
+
+#[test]
+pub fn normalize_text() {
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let grid = Grid::new(
+        10,
+        20,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    assert_eq!(
+        grid.normalize_text("a\tb\r\nc"),
+        "a       b\nc",
+        "tab expanded to the next tab stop and CRLF turned into LF"
+    );
+    assert_eq!(
+        grid.normalize_text("abcdefghijklmnop\tq"),
+        "abcdefghijklmnop q",
+        "a tab past the last tab stop becomes a single space"
+    );
+    assert_eq!(grid.normalize_text("no\ttabs\nhere"), "no      tabs\nhere");
+}
+
+#[test]
+pub fn url_at_ignores_osc8_links_with_other_schemes() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let character_cell_size = Rc::new(RefCell::new(Some(SizeInPixels {
+        width: 8,
+        height: 21,
+    })));
+    let debug = false;
+    let mut grid = Grid::new(
+        30,
+        112,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        character_cell_size,
+        sixel_image_store,
+        debug,
+    );
+
+    let content = "\u{1b}]8;;javascript:alert(1)\u{1b}\\click here\u{1b}]8;;\u{1b}\\ or \u{1b}]8;;FTP://example.com/file\u{1b}\\there\u{1b}]8;;\u{1b}\\";
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(
+        grid.url_at(&Position::new(0, 2)),
+        None,
+        "javascript link is not opened"
+    );
+    assert_eq!(
+        grid.url_at(&Position::new(0, 14)),
+        Some("FTP://example.com/file".to_owned()),
+        "scheme is matched case insensitively"
+    );
+}
@@ -415,3 +415,86 @@ pub fn searching_whole_word_case_insensitive() {
         format!("{:?}", terminal_pane.grid)
     );
 }
+
+#[test]
+pub fn searching_with_regex() {
+    let mut terminal_pane = create_pane();
+    terminal_pane.handle_pty_bytes(
+        "\n\rerror: disk full\n\rwarn: disk almost full\n\rinfo: all good\n\r"
+            .as_bytes()
+            .to_vec(),
+    );
+    terminal_pane.update_search_term("error|warn");
+    assert!(
+        terminal_pane.grid.search_results.selections.is_empty(),
+        "Needle is searched for literally by default"
+    );
+
+    terminal_pane.toggle_search_regex();
+    let selections = &terminal_pane.grid.search_results.selections;
+    assert_eq!(selections.len(), 2, "Both alternatives found");
+    assert_eq!(
+        (selections[0].start.column(), selections[0].end.column()),
+        (0, 5),
+        "First match covers 'error'"
+    );
+    assert_eq!(
+        (selections[1].start.column(), selections[1].end.column()),
+        (0, 4),
+        "Second match covers 'warn'"
+    );
+    assert_eq!(
+        selections[1].start.line(),
+        selections[0].start.line() + 1,
+        "Matches are on consecutive lines"
+    );
+}
+
+#[test]
+pub fn searching_with_invalid_regex_finds_nothing() {
+    let mut terminal_pane = create_pane();
+    terminal_pane.update_search_term("tortor(");
+    terminal_pane.toggle_search_regex();
+    assert!(
+        terminal_pane.grid.search_results.selections.is_empty(),
+        "Invalid regex has no matches"
+    );
+    terminal_pane.search_up();
+    terminal_pane.search_down();
+    assert!(
+        terminal_pane.grid.search_results.active.is_none(),
+        "Nothing to select"
+    );
+}
+
+#[test]
+pub fn searching_visible_only_cycles_within_viewport() {
+    let mut terminal_pane = create_pane();
+    terminal_pane.update_search_term("tortor");
+    terminal_pane.toggle_search_visible_only();
+    let viewport_selections = terminal_pane.grid.search_results.selections.clone();
+    assert!(
+        !viewport_selections.is_empty(),
+        "Needle found in the viewport"
+    );
+
+    for _ in 0..viewport_selections.len() {
+        terminal_pane.search_up();
+    }
+    assert_eq!(
+        terminal_pane.grid.search_results.active,
+        viewport_selections.first().cloned(),
+        "Reached the topmost match in the viewport"
+    );
+
+    terminal_pane.search_up();
+    assert_eq!(
+        terminal_pane.grid.search_results.selections, viewport_selections,
+        "Viewport was not scrolled to search the scrollback"
+    );
+    assert_eq!(
+        terminal_pane.grid.search_results.active,
+        viewport_selections.last().cloned(),
+        "Cycled back to the bottommost match in the viewport"
+    );
+}
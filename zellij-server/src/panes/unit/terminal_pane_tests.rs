@@ -1,13 +1,16 @@
 use super::super::TerminalPane;
+use crate::panes::pinned_lines::MAX_PINNED_LINES;
 use crate::panes::sixel::SixelImageStore;
 use crate::panes::LinkHandler;
 use crate::tab::Pane;
 use ::insta::assert_snapshot;
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::rc::Rc;
 use zellij_utils::{
     data::{Palette, Style},
+    input::{command::RunCommand, layout::Run},
     pane_size::{Offset, PaneGeom, SizeInPixels},
     position::Position,
 };
@@ -692,3 +695,145 @@ pub fn frameless_pane_position_is_on_frame() {
     assert!(!terminal_pane.position_is_on_frame(&Position::new(30, 130)));
     assert!(!terminal_pane.position_is_on_frame(&Position::new(30, 131)));
 }
+
+#[test]
+pub fn lines_matching_pattern_are_pinned_above_the_output() {
+    let mut fake_win_size = PaneGeom::default();
+    fake_win_size.cols.set_inner(121);
+    fake_win_size.rows.set_inner(20);
+
+    let pid = 1;
+    let style = Style::default();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_colors = Rc::new(RefCell::new(Palette::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let invoked_with = Run::Command(RunCommand {
+        command: PathBuf::from("tail"),
+        pin_matching: Some("^ERROR".to_owned()),
+        ..Default::default()
+    });
+    let mut terminal_pane = TerminalPane::new(
+        pid,
+        fake_win_size,
+        style,
+        0,
+        String::new(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        terminal_emulator_colors,
+        terminal_emulator_color_codes,
+        None,
+        Some(invoked_with),
+        debug,
+    ); // 0 is the pane index
+    let mut text_to_fill_pane = String::new();
+    for i in 0..30 {
+        writeln!(&mut text_to_fill_pane, "\rline {}", i + 1).unwrap();
+        if i % 4 == 0 {
+            writeln!(&mut text_to_fill_pane, "\rERROR {}", i + 1).unwrap();
+        }
+    }
+    terminal_pane.handle_pty_bytes(text_to_fill_pane.into_bytes());
+
+    assert_eq!(
+        terminal_pane.get_content_rows(),
+        20 - MAX_PINNED_LINES,
+        "grid makes room for the pinned lines"
+    );
+    let (character_chunks, _, _) = terminal_pane.render(None).unwrap().unwrap();
+    let pinned_lines: Vec<String> = (0..MAX_PINNED_LINES)
+        .map(|y| {
+            let chunk = character_chunks
+                .iter()
+                .find(|chunk| chunk.y == y)
+                .expect("pinned line rendered");
+            let text: String = chunk
+                .terminal_characters
+                .iter()
+                .map(|c| c.character)
+                .collect();
+            text.trim_end().to_owned()
+        })
+        .collect();
+    assert_eq!(
+        pinned_lines,
+        vec!["ERROR 13", "ERROR 17", "ERROR 21", "ERROR 25", "ERROR 29"],
+        "only the most recent matching lines are pinned"
+    );
+}
+
+#[test]
+pub fn pane_with_fixed_pty_size_is_letterboxed() {
+    let mut fake_win_size = PaneGeom::default();
+    fake_win_size.cols.set_inner(20);
+    fake_win_size.rows.set_inner(6);
+
+    let pid = 1;
+    let style = Style::default();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_colors = Rc::new(RefCell::new(Palette::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let invoked_with = Run::Command(RunCommand {
+        command: PathBuf::from("htop"),
+        fixed_pty_size: Some((10, 3)),
+        ..Default::default()
+    });
+    let mut terminal_pane = TerminalPane::new(
+        pid,
+        fake_win_size,
+        style,
+        0,
+        String::new(),
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        terminal_emulator_colors,
+        terminal_emulator_color_codes,
+        None,
+        Some(invoked_with),
+        debug,
+    ); // 0 is the pane index
+    terminal_pane.set_geom(fake_win_size);
+    terminal_pane.handle_pty_bytes("0123456789abc".as_bytes().to_vec());
+
+    assert_eq!(terminal_pane.get_pty_size(), (10, 3), "pty keeps its size");
+    let (character_chunks, _, _) = terminal_pane.render(None).unwrap().unwrap();
+    let rendered_lines: Vec<String> = (0..6)
+        .map(|y| {
+            let chunk = character_chunks
+                .iter()
+                .find(|chunk| chunk.y == y)
+                .expect("line rendered");
+            assert_eq!(chunk.terminal_characters.len(), 20, "line spans the pane");
+            chunk
+                .terminal_characters
+                .iter()
+                .map(|c| c.character)
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        rendered_lines
+            .iter()
+            .map(|line| line.trim_end())
+            .collect::<Vec<&str>>(),
+        vec!["0123456789", "abc", "", "", "", ""],
+        "output wraps at the fixed width and the rest of the pane is blank"
+    );
+
+    let mut smaller_win_size = PaneGeom::default();
+    smaller_win_size.cols.set_inner(5);
+    smaller_win_size.rows.set_inner(2);
+    terminal_pane.set_geom(smaller_win_size);
+    assert_eq!(terminal_pane.get_pty_size(), (10, 3), "resize is ignored");
+    let (character_chunks, _, _) = terminal_pane.render(None).unwrap().unwrap();
+    assert!(
+        character_chunks
+            .iter()
+            .all(|chunk| chunk.y < 2 && chunk.terminal_characters.len() == 5),
+        "output is cropped to the pane"
+    );
+}
@@ -349,6 +349,7 @@ pub fn load_new_plugin_from_hd() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -407,6 +408,7 @@ pub fn plugin_workers() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -468,6 +470,7 @@ pub fn plugin_workers_persist_state() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -541,6 +544,7 @@ pub fn can_subscribe_to_hd_events() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -605,6 +609,7 @@ pub fn switch_to_mode_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -666,6 +671,7 @@ pub fn new_tabs_with_layout_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -741,6 +747,7 @@ pub fn new_tab_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -802,6 +809,7 @@ pub fn go_to_next_tab_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -862,6 +870,7 @@ pub fn go_to_previous_tab_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -922,6 +931,7 @@ pub fn resize_focused_pane_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -982,6 +992,7 @@ pub fn resize_focused_pane_with_direction_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -1042,6 +1053,7 @@ pub fn focus_next_pane_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -1102,6 +1114,7 @@ pub fn focus_previous_pane_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -1162,6 +1175,7 @@ pub fn move_focus_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -1222,6 +1236,7 @@ pub fn move_focus_or_tab_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -1282,6 +1297,7 @@ pub fn edit_scrollback_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -1342,6 +1358,7 @@ pub fn write_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -1402,6 +1419,7 @@ pub fn write_chars_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -1462,6 +1480,7 @@ pub fn toggle_tab_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -1522,6 +1541,7 @@ pub fn move_pane_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -1582,6 +1602,7 @@ pub fn move_pane_with_direction_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -1642,6 +1663,7 @@ pub fn clear_screen_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -1702,6 +1724,7 @@ pub fn scroll_up_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -1762,6 +1785,7 @@ pub fn scroll_down_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -1822,6 +1846,7 @@ pub fn scroll_to_top_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -1882,6 +1907,7 @@ pub fn scroll_to_bottom_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -1942,6 +1968,7 @@ pub fn page_scroll_up_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -2002,6 +2029,7 @@ pub fn page_scroll_down_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -2062,6 +2090,7 @@ pub fn toggle_focus_fullscreen_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -2122,6 +2151,7 @@ pub fn toggle_pane_frames_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -2182,6 +2212,7 @@ pub fn toggle_pane_embed_or_eject_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -2242,6 +2273,7 @@ pub fn undo_rename_pane_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -2302,6 +2334,7 @@ pub fn close_focus_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -2362,6 +2395,7 @@ pub fn toggle_active_tab_sync_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -2422,6 +2456,7 @@ pub fn close_focused_tab_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -2482,6 +2517,7 @@ pub fn undo_rename_tab_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -2542,6 +2578,7 @@ pub fn previous_swap_layout_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -2602,6 +2639,7 @@ pub fn next_swap_layout_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -2662,6 +2700,7 @@ pub fn go_to_tab_name_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -2722,6 +2761,7 @@ pub fn focus_or_create_tab_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -2782,6 +2822,7 @@ pub fn go_to_tab() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -2842,6 +2883,7 @@ pub fn start_or_reload_plugin() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -2902,6 +2944,7 @@ pub fn quit_zellij_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -2962,6 +3005,7 @@ pub fn detach_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -3022,6 +3066,7 @@ pub fn open_file_floating_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -3082,6 +3127,7 @@ pub fn open_file_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -3142,6 +3188,7 @@ pub fn open_file_with_line_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -3202,6 +3249,7 @@ pub fn open_file_with_line_floating_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -3262,6 +3310,7 @@ pub fn open_terminal_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -3322,6 +3371,7 @@ pub fn open_terminal_floating_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -3382,6 +3432,7 @@ pub fn open_command_pane_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -3442,6 +3493,7 @@ pub fn open_command_pane_floating_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -3502,6 +3554,7 @@ pub fn switch_to_tab_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -3562,6 +3615,7 @@ pub fn hide_self_plugin_command() {
     let run_plugin = RunPlugin {
         _allow_exec_host_cmd: false,
         location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
     };
     let tab_index = 1;
     let client_id = 1;
@@ -5,7 +5,7 @@ use crate::route::route_action;
 use log::{debug, warn};
 use serde::{de::DeserializeOwned, Serialize};
 use std::{
-    collections::HashSet,
+    collections::{BTreeMap, HashSet},
     path::PathBuf,
     process,
     sync::{Arc, Mutex},
@@ -25,7 +25,7 @@ use zellij_utils::{
     errors::prelude::*,
     input::{
         actions::Action,
-        command::{RunCommand, RunCommandAction, TerminalAction},
+        command::{PaneRunAction, RunCommand, RunCommandAction, TerminalAction},
         layout::{Layout, RunPlugin, RunPluginLocation},
         plugins::PluginType,
     },
@@ -42,6 +42,8 @@ macro_rules! apply_action {
             $env.plugin_env.client_attributes.clone(),
             $env.plugin_env.default_shell.clone(),
             $env.plugin_env.default_layout.clone(),
+            // plugins are not given the session variables, so their chars are written as is
+            None,
         ) {
             log::error!("{}: {:?}", $error_message(), e);
         }
@@ -240,7 +242,7 @@ fn host_open_file(env: &ForeignFunctionEnv) {
                 )
             };
             let floating = false;
-            let action = Action::EditFile(path, None, None, None, floating); // TODO: add cwd
+            let action = Action::EditFile(path, None, None, None, None, floating); // TODO: add cwd
             apply_action!(action, error_msg, env);
             Ok(())
         })
@@ -258,7 +260,7 @@ fn host_open_file_floating(env: &ForeignFunctionEnv) {
         .and_then(|path| {
             let error_msg = || format!("failed to open file in plugin {}", env.plugin_env.name());
             let floating = true;
-            let action = Action::EditFile(path, None, None, None, floating); // TODO: add cwd
+            let action = Action::EditFile(path, None, None, None, None, floating); // TODO: add cwd
             apply_action!(action, error_msg, env);
             Ok(())
         })
@@ -276,7 +278,7 @@ fn host_open_file_with_line(env: &ForeignFunctionEnv) {
         .and_then(|(path, line)| {
             let error_msg = || format!("failed to open file in plugin {}", env.plugin_env.name());
             let floating = false;
-            let action = Action::EditFile(path, Some(line), None, None, floating); // TODO: add cwd
+            let action = Action::EditFile(path, Some(line), None, None, None, floating); // TODO: add cwd
             apply_action!(action, error_msg, env);
             Ok(())
         })
@@ -294,7 +296,7 @@ fn host_open_file_with_line_floating(env: &ForeignFunctionEnv) {
         .and_then(|(path, line)| {
             let error_msg = || format!("failed to open file in plugin {}", env.plugin_env.name());
             let floating = true;
-            let action = Action::EditFile(path, Some(line), None, None, floating); // TODO: add cwd
+            let action = Action::EditFile(path, Some(line), None, None, None, floating); // TODO: add cwd
             apply_action!(action, error_msg, env);
             Ok(())
         })
@@ -317,8 +319,10 @@ fn host_open_terminal(env: &ForeignFunctionEnv) {
                 .clone()
                 .unwrap_or_else(|| TerminalAction::RunCommand(RunCommand::default()));
             default_shell.change_cwd(path);
-            let run_command_action: Option<RunCommandAction> = match default_shell {
-                TerminalAction::RunCommand(run_command) => Some(run_command.into()),
+            let run_command_action: Option<PaneRunAction> = match default_shell {
+                TerminalAction::RunCommand(run_command) => {
+                    Some(PaneRunAction::Command(run_command.into()))
+                },
                 _ => None,
             };
             let action = Action::NewTiledPane(None, run_command_action, None);
@@ -344,11 +348,13 @@ fn host_open_terminal_floating(env: &ForeignFunctionEnv) {
                 .clone()
                 .unwrap_or_else(|| TerminalAction::RunCommand(RunCommand::default()));
             default_shell.change_cwd(path);
-            let run_command_action: Option<RunCommandAction> = match default_shell {
-                TerminalAction::RunCommand(run_command) => Some(run_command.into()),
+            let run_command_action: Option<PaneRunAction> = match default_shell {
+                TerminalAction::RunCommand(run_command) => {
+                    Some(PaneRunAction::Command(run_command.into()))
+                },
                 _ => None,
             };
-            let action = Action::NewFloatingPane(run_command_action, None);
+            let action = Action::NewFloatingPane(run_command_action, None, None);
             apply_action!(action, error_msg, env);
             Ok(())
         })
@@ -377,8 +383,21 @@ fn host_open_command_pane(env: &ForeignFunctionEnv) {
                 direction,
                 hold_on_close,
                 hold_on_start,
+                watch_paths: vec![],
+                output_socket: None,
+                pin_matching: None,
+                cwd_from_focus: false,
+                no_frame: false,
+                capture_exit: None,
+                env: BTreeMap::new(),
+                fixed_pty_size: None,
+                run_in_shell: None,
             };
-            let action = Action::NewTiledPane(direction, Some(run_command_action), name);
+            let action = Action::NewTiledPane(
+                direction,
+                Some(PaneRunAction::Command(run_command_action)),
+                name,
+            );
             apply_action!(action, error_msg, env);
             Ok(())
         })
@@ -402,8 +421,21 @@ fn host_open_command_pane_floating(env: &ForeignFunctionEnv) {
                 direction,
                 hold_on_close,
                 hold_on_start,
+                watch_paths: vec![],
+                output_socket: None,
+                pin_matching: None,
+                cwd_from_focus: false,
+                no_frame: false,
+                capture_exit: None,
+                env: BTreeMap::new(),
+                fixed_pty_size: None,
+                run_in_shell: None,
             };
-            let action = Action::NewFloatingPane(Some(run_command_action), name);
+            let action = Action::NewFloatingPane(
+                Some(PaneRunAction::Command(run_command_action)),
+                name,
+                None,
+            );
             apply_action!(action, error_msg, env);
             Ok(())
         })
@@ -579,6 +611,8 @@ fn host_new_tabs_with_layout(env: &ForeignFunctionEnv) {
                     None,
                     None,
                     None,
+                    false,
+                    false,
                 );
                 tabs_to_open.push(action);
             } else {
@@ -589,6 +623,8 @@ fn host_new_tabs_with_layout(env: &ForeignFunctionEnv) {
                         None,
                         None,
                         tab_name,
+                        false,
+                        false,
                     );
                     tabs_to_open.push(action);
                 }
@@ -603,7 +639,7 @@ fn host_new_tabs_with_layout(env: &ForeignFunctionEnv) {
 }
 
 fn host_new_tab(env: &ForeignFunctionEnv) {
-    let action = Action::NewTab(None, vec![], None, None, None);
+    let action = Action::NewTab(None, vec![], None, None, None, false, false);
     let error_msg = || format!("Failed to open new tab");
     apply_action!(action, error_msg, env);
 }
@@ -957,6 +993,7 @@ fn host_start_or_reload_plugin(env: &ForeignFunctionEnv) {
             let run_plugin = RunPlugin {
                 location: run_plugin_location,
                 _allow_exec_host_cmd: false,
+                configuration: Default::default(),
             };
             let action = Action::StartOrReloadPlugin(run_plugin);
             apply_action!(action, error_msg, env);
@@ -3,14 +3,26 @@ use crate::{
     panes::PaneId,
     plugins::PluginInstruction,
     screen::ScreenInstruction,
+    session_layout_metadata::SessionLayoutMetadata,
     thread_bus::{Bus, ThreadSenders},
     ClientId, ServerInstruction,
 };
 use async_std::task::{self, JoinHandle};
-use std::{collections::HashMap, os::unix::io::RawFd, path::PathBuf};
+use std::time::Duration;
+use std::{
+    collections::{BTreeMap, HashMap},
+    os::unix::io::RawFd,
+    path::PathBuf,
+};
 use zellij_utils::nix::unistd::Pid;
+use zellij_utils::notify_debouncer_full::{
+    new_debouncer,
+    notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher},
+    DebounceEventResult, Debouncer, FileIdMap,
+};
 use zellij_utils::{
     async_std,
+    data::{Direction, FloatingPanePlacement, PaneListEntry, PaneListFormat},
     errors::prelude::*,
     errors::{ContextType, PtyContext},
     input::{
@@ -19,6 +31,8 @@ use zellij_utils::{
     },
 };
 
+const WATCH_DEBOUNCE_DURATION_MS: u64 = 500;
+
 pub type VteBytes = Vec<u8>;
 pub type TabIndex = u32;
 
@@ -36,6 +50,7 @@ pub enum PtyInstruction {
         Option<bool>,
         Option<String>,
         ClientOrTabIndex,
+        Option<FloatingPanePlacement>,
     ), // bool (if Some) is
     // should_float, String is an optional pane name
     OpenInPlaceEditor(PathBuf, Option<usize>, ClientId), // Option<usize> is the optional line number
@@ -45,6 +60,13 @@ pub enum PtyInstruction {
     SpawnTerminalHorizontally(Option<TerminalAction>, Option<String>, ClientId), // String is an
     // optional pane
     // name
+    SpawnTerminalAtFraction(
+        Option<TerminalAction>,
+        Option<String>,
+        Direction,
+        f64,
+        ClientId,
+    ), // String is an optional pane name, f64 is the fraction of the tab to split at
     UpdateActivePane(Option<PaneId>, ClientId),
     GoToTab(TabIndex, ClientId),
     NewTab(
@@ -59,6 +81,10 @@ pub enum PtyInstruction {
     ClosePane(PaneId),
     CloseTab(Vec<PaneId>),
     ReRunCommandInPane(PaneId, RunCommand),
+    KillCommandInPane(PaneId),
+    DumpLayout(SessionLayoutMetadata, Option<String>, ClientId), // String is an optional file to
+    // write the layout to
+    ListPanes(Vec<PaneListEntry>, PaneListFormat, ClientId),
     Exit,
 }
 
@@ -69,12 +95,16 @@ impl From<&PtyInstruction> for PtyContext {
             PtyInstruction::OpenInPlaceEditor(..) => PtyContext::OpenInPlaceEditor,
             PtyInstruction::SpawnTerminalVertically(..) => PtyContext::SpawnTerminalVertically,
             PtyInstruction::SpawnTerminalHorizontally(..) => PtyContext::SpawnTerminalHorizontally,
+            PtyInstruction::SpawnTerminalAtFraction(..) => PtyContext::SpawnTerminalAtFraction,
             PtyInstruction::UpdateActivePane(..) => PtyContext::UpdateActivePane,
             PtyInstruction::GoToTab(..) => PtyContext::GoToTab,
             PtyInstruction::ClosePane(_) => PtyContext::ClosePane,
             PtyInstruction::CloseTab(_) => PtyContext::CloseTab,
             PtyInstruction::NewTab(..) => PtyContext::NewTab,
             PtyInstruction::ReRunCommandInPane(..) => PtyContext::ReRunCommandInPane,
+            PtyInstruction::KillCommandInPane(..) => PtyContext::KillCommandInPane,
+            PtyInstruction::DumpLayout(..) => PtyContext::DumpLayout,
+            PtyInstruction::ListPanes(..) => PtyContext::ListPanes,
             PtyInstruction::Exit => PtyContext::Exit,
         }
     }
@@ -87,6 +117,7 @@ pub(crate) struct Pty {
     debug_to_file: bool,
     task_handles: HashMap<u32, JoinHandle<()>>, // terminal_id to join-handle
     default_editor: Option<PathBuf>,
+    command_watchers: HashMap<u32, Debouncer<RecommendedWatcher, FileIdMap>>, // terminal_id
 }
 
 pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
@@ -99,6 +130,7 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                 should_float,
                 name,
                 client_or_tab_index,
+                floating_pane_placement,
             ) => {
                 let err_context =
                     || format!("failed to spawn terminal for {:?}", client_or_tab_index);
@@ -116,7 +148,11 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     .with_context(err_context)
                 {
                     Ok((pid, starts_held)) => {
-                        let hold_for_command = if starts_held { run_command } else { None };
+                        let hold_for_command = if starts_held {
+                            run_command.clone()
+                        } else {
+                            None
+                        };
                         pty.bus
                             .senders
                             .send_to_screen(ScreenInstruction::NewPane(
@@ -125,8 +161,11 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                                 should_float,
                                 hold_for_command,
                                 client_or_tab_index,
+                                floating_pane_placement,
                             ))
                             .with_context(err_context)?;
+                        remove_pane_frame_if_requested(&pty.bus.senders, pid, run_command.as_ref())
+                            .with_context(err_context)?;
                     },
                     Err(err) => match err.downcast_ref::<ZellijError>() {
                         Some(ZellijError::CommandNotFound { terminal_id, .. }) => {
@@ -140,6 +179,7 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                                         should_float,
                                         hold_for_command,
                                         client_or_tab_index,
+                                        floating_pane_placement,
                                     ))
                                     .with_context(err_context)?;
                                 if let Some(run_command) = run_command {
@@ -166,7 +206,7 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     || format!("failed to open in-place editor for client {}", client_id);
 
                 match pty.spawn_terminal(
-                    Some(TerminalAction::OpenFile(temp_file, line_number, None)),
+                    Some(TerminalAction::OpenFile(temp_file, line_number, None, None)),
                     ClientOrTabIndex::ClientId(client_id),
                 ) {
                     Ok((pid, _starts_held)) => {
@@ -200,7 +240,11 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     .with_context(err_context)
                 {
                     Ok((pid, starts_held)) => {
-                        let hold_for_command = if starts_held { run_command } else { None };
+                        let hold_for_command = if starts_held {
+                            run_command.clone()
+                        } else {
+                            None
+                        };
                         pty.bus
                             .senders
                             .send_to_screen(ScreenInstruction::VerticalSplit(
@@ -210,6 +254,8 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                                 client_id,
                             ))
                             .with_context(err_context)?;
+                        remove_pane_frame_if_requested(&pty.bus.senders, pid, run_command.as_ref())
+                            .with_context(err_context)?;
                     },
                     Err(err) => match err.downcast_ref::<ZellijError>() {
                         Some(ZellijError::CommandNotFound { terminal_id, .. }) => {
@@ -271,7 +317,11 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     .with_context(err_context)
                 {
                     Ok((pid, starts_held)) => {
-                        let hold_for_command = if starts_held { run_command } else { None };
+                        let hold_for_command = if starts_held {
+                            run_command.clone()
+                        } else {
+                            None
+                        };
                         pty.bus
                             .senders
                             .send_to_screen(ScreenInstruction::HorizontalSplit(
@@ -281,6 +331,8 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                                 client_id,
                             ))
                             .with_context(err_context)?;
+                        remove_pane_frame_if_requested(&pty.bus.senders, pid, run_command.as_ref())
+                            .with_context(err_context)?;
                     },
                     Err(err) => match err.downcast_ref::<ZellijError>() {
                         Some(ZellijError::CommandNotFound { terminal_id, .. }) => {
@@ -325,6 +377,96 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     },
                 }
             },
+            PtyInstruction::SpawnTerminalAtFraction(
+                terminal_action,
+                name,
+                direction,
+                fraction,
+                client_id,
+            ) => {
+                let err_context = || {
+                    format!(
+                        "failed to spawn terminal at {fraction} of the tab for client {client_id}"
+                    )
+                };
+
+                let (hold_on_close, run_command, pane_title) = match &terminal_action {
+                    Some(TerminalAction::RunCommand(run_command)) => (
+                        run_command.hold_on_close,
+                        Some(run_command.clone()),
+                        Some(name.unwrap_or_else(|| run_command.to_string())),
+                    ),
+                    _ => (false, None, name),
+                };
+                match pty
+                    .spawn_terminal(terminal_action, ClientOrTabIndex::ClientId(client_id))
+                    .with_context(err_context)
+                {
+                    Ok((pid, starts_held)) => {
+                        let hold_for_command = if starts_held {
+                            run_command.clone()
+                        } else {
+                            None
+                        };
+                        pty.bus
+                            .senders
+                            .send_to_screen(ScreenInstruction::NewPaneAtFraction(
+                                PaneId::Terminal(pid),
+                                pane_title,
+                                hold_for_command,
+                                direction,
+                                fraction,
+                                client_id,
+                            ))
+                            .with_context(err_context)?;
+                        remove_pane_frame_if_requested(&pty.bus.senders, pid, run_command.as_ref())
+                            .with_context(err_context)?;
+                    },
+                    Err(err) => match err.downcast_ref::<ZellijError>() {
+                        Some(ZellijError::CommandNotFound { terminal_id, .. }) => {
+                            if hold_on_close {
+                                let hold_for_command = None; // we do not hold an "error" pane
+                                pty.bus
+                                    .senders
+                                    .send_to_screen(ScreenInstruction::NewPaneAtFraction(
+                                        PaneId::Terminal(*terminal_id),
+                                        pane_title,
+                                        hold_for_command,
+                                        direction,
+                                        fraction,
+                                        client_id,
+                                    ))
+                                    .with_context(err_context)?;
+                                if let Some(run_command) = run_command {
+                                    pty.bus
+                                        .senders
+                                        .send_to_screen(ScreenInstruction::PtyBytes(
+                                            *terminal_id,
+                                            format!(
+                                                "Command not found: {}",
+                                                run_command.command.display()
+                                            )
+                                            .as_bytes()
+                                            .to_vec(),
+                                        ))
+                                        .with_context(err_context)?;
+                                    pty.bus
+                                        .senders
+                                        .send_to_screen(ScreenInstruction::HoldPane(
+                                            PaneId::Terminal(*terminal_id),
+                                            Some(2), // exit status
+                                            run_command,
+                                            None,
+                                            None,
+                                        ))
+                                        .with_context(err_context)?;
+                                }
+                            }
+                        },
+                        _ => Err::<(), _>(err).non_fatal(),
+                    },
+                }
+            },
             PtyInstruction::UpdateActivePane(pane_id, client_id) => {
                 pty.set_active_pane(pane_id, client_id);
             },
@@ -420,6 +562,18 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     },
                 }
             },
+            PtyInstruction::KillCommandInPane(pane_id) => {
+                pty.kill_command_in_pane(pane_id)
+                    .with_context(|| format!("failed to kill command in pane {:?}", pane_id))?;
+            },
+            PtyInstruction::DumpLayout(session_layout_metadata, file, client_id) => {
+                pty.dump_layout(session_layout_metadata, file, client_id)
+                    .with_context(|| format!("failed to dump layout for client {client_id}"))?;
+            },
+            PtyInstruction::ListPanes(pane_list, format, client_id) => {
+                pty.list_panes(pane_list, format, client_id)
+                    .with_context(|| format!("failed to list panes for client {client_id}"))?;
+            },
             PtyInstruction::Exit => break,
         }
     }
@@ -439,6 +593,7 @@ impl Pty {
             debug_to_file,
             task_handles: HashMap::new(),
             default_editor,
+            command_watchers: HashMap::new(),
         }
     }
     pub fn get_default_terminal(
@@ -453,7 +608,12 @@ impl Pty {
                         TerminalAction::RunCommand(ref mut command) => {
                             command.cwd = Some(cwd);
                         },
-                        TerminalAction::OpenFile(ref _file, _line_number, ref mut edit_cwd) => {
+                        TerminalAction::OpenFile(
+                            ref _file,
+                            _line_number,
+                            _column,
+                            ref mut edit_cwd,
+                        ) => {
                             match edit_cwd.as_mut() {
                                 Some(edit_cwd) => {
                                     *edit_cwd = cwd.join(&edit_cwd);
@@ -475,29 +635,112 @@ impl Pty {
                     cwd, // note: this might also be filled by the calling function, eg. spawn_terminal
                     hold_on_close: false,
                     hold_on_start: false,
+                    watch_paths: vec![],
+                    output_socket: None,
+                    pin_matching: None,
+                    cwd_from_focus: false,
+                    no_frame: false,
+                    capture_exit: None,
+                    env: BTreeMap::new(),
+                    fixed_pty_size: None,
                 })
             },
         }
     }
     fn fill_cwd(&self, terminal_action: &mut TerminalAction, client_id: ClientId) {
         if let TerminalAction::RunCommand(run_command) = terminal_action {
-            if run_command.cwd.is_none() {
-                run_command.cwd = self
-                    .active_panes
-                    .get(&client_id)
-                    .and_then(|pane| match pane {
-                        PaneId::Plugin(..) => None,
-                        PaneId::Terminal(id) => self.id_to_child_pid.get(id),
-                    })
-                    .and_then(|&id| {
-                        self.bus
-                            .os_input
-                            .as_ref()
-                            .and_then(|input| input.get_cwd(Pid::from_raw(id)))
-                    });
+            if run_command.cwd_from_focus {
+                // cli clients have no focused pane of their own, so we use that of the first
+                // connected client, keeping the cwd they sent along if there is none
+                let client_id = if self.active_panes.contains_key(&client_id) {
+                    Some(client_id)
+                } else {
+                    self.active_panes.keys().min().copied()
+                };
+                if let Some(cwd) = client_id.and_then(|c| self.get_focused_pane_cwd(c)) {
+                    run_command.cwd = Some(cwd);
+                }
+            } else if run_command.cwd.is_none() {
+                run_command.cwd = self.get_focused_pane_cwd(client_id);
             };
         };
     }
+    fn get_focused_pane_cwd(&self, client_id: ClientId) -> Option<PathBuf> {
+        self.active_panes
+            .get(&client_id)
+            .and_then(|pane| match pane {
+                PaneId::Plugin(..) => None,
+                PaneId::Terminal(id) => self.id_to_child_pid.get(id),
+            })
+            .and_then(|&id| {
+                self.bus
+                    .os_input
+                    .as_ref()
+                    .and_then(|input| input.get_cwd(Pid::from_raw(id)))
+            })
+    }
+    pub fn dump_layout(
+        &mut self,
+        mut session_layout_metadata: SessionLayoutMetadata,
+        file: Option<String>,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to dump layout for client {client_id}");
+
+        let id_to_child_pid = &self.id_to_child_pid;
+        let os_input = self.bus.os_input.as_ref();
+        session_layout_metadata.update_terminal_cwds(|terminal_id| {
+            id_to_child_pid
+                .get(&terminal_id)
+                .and_then(|&id| os_input.and_then(|input| input.get_cwd(Pid::from_raw(id))))
+        });
+        let kdl_layout = Layout::from(session_layout_metadata).to_kdl();
+        match file {
+            Some(file) => {
+                if let Some(os_input) = self.bus.os_input.as_mut() {
+                    os_input
+                        .write_to_file(kdl_layout, Some(file))
+                        .with_context(err_context)
+                        .non_fatal();
+                }
+                self.bus
+                    .senders
+                    .send_to_server(ServerInstruction::UnblockInputThread)
+                    .with_context(err_context)
+            },
+            None => self
+                .bus
+                .senders
+                .send_to_server(ServerInstruction::Log(vec![kdl_layout], client_id))
+                .with_context(err_context),
+        }
+    }
+    pub fn list_panes(
+        &mut self,
+        mut pane_list: Vec<PaneListEntry>,
+        format: PaneListFormat,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to list panes for client {client_id}");
+
+        let os_input = self.bus.os_input.as_ref();
+        for pane in pane_list.iter_mut().filter(|pane| !pane.is_plugin) {
+            pane.cwd = self
+                .id_to_child_pid
+                .get(&pane.id)
+                .and_then(|&id| os_input.and_then(|input| input.get_cwd(Pid::from_raw(id))));
+        }
+        let lines = match format {
+            PaneListFormat::Json => {
+                vec![serde_json::to_string(&pane_list).with_context(err_context)?]
+            },
+            PaneListFormat::Plain => pane_list.iter().map(pane_list_line).collect(),
+        };
+        self.bus
+            .senders
+            .send_to_server(ServerInstruction::Log(lines, client_id))
+            .with_context(err_context)
+    }
     pub fn spawn_terminal(
         &mut self,
         terminal_action: Option<TerminalAction>,
@@ -518,11 +761,14 @@ impl Pty {
                 terminal_action.unwrap_or_else(|| self.get_default_terminal(None, None))
             },
         };
-        let (hold_on_start, hold_on_close) = match &terminal_action {
-            TerminalAction::RunCommand(run_command) => {
-                (run_command.hold_on_start, run_command.hold_on_close)
-            },
-            _ => (false, false),
+        let (hold_on_start, hold_on_close, watch_paths, output_socket) = match &terminal_action {
+            TerminalAction::RunCommand(run_command) => (
+                run_command.hold_on_start,
+                run_command.hold_on_close,
+                run_command.watch_paths.clone(),
+                run_command.output_socket.clone(),
+            ),
+            _ => (false, false, vec![], None),
         };
 
         if hold_on_start {
@@ -535,12 +781,14 @@ impl Pty {
                 .context("couldn't get mutable reference to OS interface")
                 .and_then(|os_input| os_input.reserve_terminal_id())
                 .with_context(err_context)?;
+            self.watch_command_paths(terminal_id, &watch_paths);
             return Ok((terminal_id, starts_held));
         }
 
         let quit_cb = Box::new({
             let senders = self.bus.senders.clone();
-            move |pane_id, exit_status, command| {
+            move |pane_id, exit_status, command: RunCommand| {
+                capture_exit_status(&senders, pane_id, exit_status, &command);
                 if hold_on_close {
                     let _ = senders.send_to_screen(ScreenInstruction::HoldPane(
                         pane_id,
@@ -577,6 +825,7 @@ impl Pty {
             let debug_to_file = self.debug_to_file;
             async move {
                 TerminalBytes::new(pid_primary, senders, os_input, debug_to_file, terminal_id)
+                    .with_output_socket(output_socket)
                     .listen()
                     .await
                     .with_context(|| err_context(terminal_id))
@@ -586,6 +835,7 @@ impl Pty {
 
         self.task_handles.insert(terminal_id, terminal_bytes);
         self.id_to_child_pid.insert(terminal_id, child_fd);
+        self.watch_command_paths(terminal_id, &watch_paths);
         let starts_held = false;
         Ok((terminal_id, starts_held))
     }
@@ -681,6 +931,9 @@ impl Pty {
                             .with_context(err_context)?
                             .clone();
                         let debug_to_file = self.debug_to_file;
+                        let output_socket = run_command
+                            .as_ref()
+                            .and_then(|run_command| run_command.output_socket.clone());
                         async move {
                             TerminalBytes::new(
                                 pid_primary,
@@ -689,6 +942,7 @@ impl Pty {
                                 debug_to_file,
                                 terminal_id,
                             )
+                            .with_output_socket(output_socket)
                             .listen()
                             .await
                             .context("failed to spawn terminals for layout")
@@ -744,7 +998,8 @@ impl Pty {
                 let hold_on_close = command.hold_on_close;
                 let quit_cb = Box::new({
                     let senders = self.bus.senders.clone();
-                    move |pane_id, exit_status, command| {
+                    move |pane_id, exit_status, command: RunCommand| {
+                        capture_exit_status(&senders, pane_id, exit_status, &command);
                         if hold_on_close {
                             let _ = senders.send_to_screen(ScreenInstruction::HoldPane(
                                 pane_id,
@@ -849,7 +1104,7 @@ impl Pty {
                     .context("no OS I/O interface found")
                     .with_context(err_context)?
                     .spawn_terminal(
-                        TerminalAction::OpenFile(path_to_file, line_number, cwd),
+                        TerminalAction::OpenFile(path_to_file, line_number, None, cwd),
                         quit_cb,
                         self.default_editor.clone(),
                     )
@@ -899,6 +1154,9 @@ impl Pty {
         match id {
             PaneId::Terminal(id) => {
                 self.task_handles.remove(&id);
+                if let Some(watcher) = self.command_watchers.remove(&id) {
+                    watcher.stop_nonblocking();
+                }
                 if let Some(child_fd) = self.id_to_child_pid.remove(&id) {
                     task::block_on(async {
                         let err_context = || format!("failed to run async task for pane {id}");
@@ -927,6 +1185,33 @@ impl Pty {
         }
         Ok(())
     }
+    pub fn kill_command_in_pane(&mut self, id: PaneId) -> Result<()> {
+        // the pane itself stays open, its command exiting will hold or close it as usual
+        if let PaneId::Terminal(id) = id {
+            if let Some(child_fd) = self.id_to_child_pid.get(&id) {
+                self.bus
+                    .os_input
+                    .as_ref()
+                    .context("no OS I/O interface found")
+                    .and_then(|os_input| os_input.kill(Pid::from_raw(*child_fd)))
+                    .with_context(|| format!("failed to kill command in terminal {id}"))?;
+            }
+        }
+        Ok(())
+    }
+    fn watch_command_paths(&mut self, terminal_id: u32, watch_paths: &[PathBuf]) {
+        if watch_paths.is_empty() || self.command_watchers.contains_key(&terminal_id) {
+            return;
+        }
+        match watch_paths_for_command(self.bus.senders.clone(), terminal_id, watch_paths) {
+            Ok(watcher) => {
+                self.command_watchers.insert(terminal_id, watcher);
+            },
+            Err(e) => Err::<(), _>(e)
+                .with_context(|| format!("failed to watch paths for terminal {terminal_id}"))
+                .non_fatal(),
+        }
+    }
     pub fn close_tab(&mut self, ids: Vec<PaneId>) -> Result<()> {
         for id in ids {
             self.close_pane(id)
@@ -952,9 +1237,11 @@ impl Pty {
                 let _ = self.id_to_child_pid.remove(&id); // if all is wlel, this shouldn't be here
 
                 let hold_on_close = run_command.hold_on_close;
+                let output_socket = run_command.output_socket.clone();
                 let quit_cb = Box::new({
                     let senders = self.bus.senders.clone();
-                    move |pane_id, exit_status, command| {
+                    move |pane_id, exit_status, command: RunCommand| {
+                        capture_exit_status(&senders, pane_id, exit_status, &command);
                         if hold_on_close {
                             let _ = senders.send_to_screen(ScreenInstruction::HoldPane(
                                 pane_id,
@@ -992,6 +1279,7 @@ impl Pty {
                     let debug_to_file = self.debug_to_file;
                     async move {
                         TerminalBytes::new(pid_primary, senders, os_input, debug_to_file, id)
+                            .with_output_socket(output_socket)
                             .listen()
                             .await
                             .with_context(|| err_context(pane_id))
@@ -1019,6 +1307,40 @@ impl Drop for Pty {
     }
 }
 
+fn remove_pane_frame_if_requested(
+    senders: &ThreadSenders,
+    terminal_id: u32,
+    run_command: Option<&RunCommand>,
+) -> Result<()> {
+    if run_command
+        .map(|run_command| run_command.no_frame)
+        .unwrap_or(false)
+    {
+        senders
+            .send_to_screen(ScreenInstruction::RemovePaneFrame(PaneId::Terminal(
+                terminal_id,
+            )))
+            .with_context(|| format!("failed to remove the frame of terminal {terminal_id}"))?;
+    }
+    Ok(())
+}
+
+/// Stores the exit status of a command in the session variable it asked for, if any
+fn capture_exit_status(
+    senders: &ThreadSenders,
+    pane_id: PaneId,
+    exit_status: Option<i32>,
+    command: &RunCommand,
+) {
+    if let Some(var_name) = &command.capture_exit {
+        let _ = senders.send_to_server(ServerInstruction::SetSessionVar(
+            var_name.clone(),
+            exit_status.map(|s| s.to_string()).unwrap_or_default(),
+            Some(pane_id),
+        ));
+    }
+}
+
 fn send_command_not_found_to_screen(
     senders: ThreadSenders,
     terminal_id: u32,
@@ -1046,6 +1368,59 @@ fn send_command_not_found_to_screen(
     Ok(())
 }
 
+fn watch_paths_for_command(
+    senders: ThreadSenders,
+    terminal_id: u32,
+    watch_paths: &[PathBuf],
+) -> Result<Debouncer<RecommendedWatcher, FileIdMap>> {
+    let mut debouncer = new_debouncer(
+        Duration::from_millis(WATCH_DEBOUNCE_DURATION_MS),
+        None,
+        move |result: DebounceEventResult| match result {
+            Ok(events) => {
+                let paths_changed = events.iter().any(|event| {
+                    matches!(
+                        event.kind,
+                        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                    )
+                });
+                if paths_changed {
+                    let _ = senders.send_to_screen(ScreenInstruction::RestartWatchedCommand(
+                        PaneId::Terminal(terminal_id),
+                    ));
+                }
+            },
+            Err(errors) => errors
+                .iter()
+                .for_each(|error| log::error!("watch error: {error:?}")),
+        },
+    )?;
+    for path in watch_paths {
+        debouncer
+            .watcher()
+            .watch(path, RecursiveMode::Recursive)
+            .with_context(|| format!("failed to watch {}", path.display()))?;
+    }
+    Ok(debouncer)
+}
+
+/// A line of `zellij action query-pane-list`, eg.
+/// `terminal_1 tab=1 floating=false focused=true title="vim" cwd="/home/user" command="vim"`
+fn pane_list_line(pane: &PaneListEntry) -> String {
+    let pane_type = if pane.is_plugin { "plugin" } else { "terminal" };
+    let mut line = format!(
+        "{}_{} tab={} floating={} focused={} title={:?}",
+        pane_type, pane.id, pane.tab_index, pane.is_floating, pane.is_focused, pane.title
+    );
+    if let Some(cwd) = &pane.cwd {
+        line.push_str(&format!(" cwd={:?}", cwd));
+    }
+    if let Some(command) = &pane.command {
+        line.push_str(&format!(" command={:?}", command));
+    }
+    line
+}
+
 pub fn get_default_shell() -> PathBuf {
     PathBuf::from(std::env::var("SHELL").unwrap_or_else(|_| {
         log::warn!("Cannot read SHELL env, falling back to use /bin/sh");
@@ -3,25 +3,28 @@ use std::sync::{Arc, RwLock};
 
 use crate::thread_bus::ThreadSenders;
 use crate::{
+    background_jobs::BackgroundJob,
     os_input_output::ServerOsApi,
+    panes::{PaneId, BRACKETED_PASTE_BEGIN, BRACKETED_PASTE_END},
     plugins::PluginInstruction,
-    pty::{ClientOrTabIndex, PtyInstruction},
+    pty::{get_default_shell, ClientOrTabIndex, PtyInstruction},
     screen::ScreenInstruction,
     ServerInstruction, SessionMetaData, SessionState,
 };
 use zellij_utils::{
     channels::SenderWithContext,
-    data::{Direction, Event, PluginCapabilities, ResizeStrategy},
+    data::{Direction, Event, InputMode, PluginCapabilities, ResizeStrategy},
     errors::prelude::*,
     input::{
         actions::{Action, SearchDirection, SearchOption},
-        command::TerminalAction,
+        command::{PaneRunAction, RunCommand, TerminalAction},
         get_mode_info,
         layout::Layout,
     },
     ipc::{
         ClientAttributes, ClientToServerMsg, ExitReason, IpcReceiverWithContext, ServerToClientMsg,
     },
+    pane_size::SizeConstraints,
 };
 
 use crate::ClientId;
@@ -34,6 +37,7 @@ pub(crate) fn route_action(
     client_attributes: ClientAttributes,
     default_shell: Option<TerminalAction>,
     default_layout: Box<Layout>,
+    session_state: Option<Arc<RwLock<SessionState>>>,
 ) -> Result<bool> {
     let mut should_break = false;
     let err_context = || format!("failed to route action for client {client_id}");
@@ -54,12 +58,27 @@ pub(crate) fn route_action(
         },
     }
 
+    // any input resets (or cancels, depending on configuration) a pending timed mode switch
+    if !matches!(
+        action,
+        Action::MouseHoldLeft(..) | Action::MouseHoldRight(..) | Action::SwitchToModeTimed(..)
+    ) {
+        senders
+            .send_to_screen(ScreenInstruction::ResetModeTimer(client_id))
+            .with_context(err_context)?;
+    }
+
     match action {
         Action::ToggleTab => {
             senders
                 .send_to_screen(ScreenInstruction::ToggleTab(client_id))
                 .with_context(err_context)?;
         },
+        Action::RestoreTabFocus => {
+            senders
+                .send_to_screen(ScreenInstruction::RestoreTabFocus(client_id))
+                .with_context(err_context)?;
+        },
         Action::Write(val) => {
             senders
                 .send_to_screen(ScreenInstruction::ClearScroll(client_id))
@@ -68,14 +87,49 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::WriteCharacter(val, client_id))
                 .with_context(err_context)?;
         },
+        Action::WriteToPanesMatching(command_filter, val) => {
+            senders
+                .send_to_screen(ScreenInstruction::WriteToPanesMatching(
+                    command_filter,
+                    val,
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
         Action::WriteChars(val) => {
             senders
                 .send_to_screen(ScreenInstruction::ClearScroll(client_id))
                 .with_context(err_context)?;
-            let val = val.into_bytes();
+            let val = match &session_state {
+                Some(session_state) => session_state
+                    .read()
+                    .to_anyhow()
+                    .with_context(err_context)?
+                    .expand_vars(&val),
+                None => val,
+            };
             senders
-                .send_to_screen(ScreenInstruction::WriteCharacter(val, client_id))
+                .send_to_screen(ScreenInstruction::WriteCharacter(
+                    val.into_bytes(),
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::PasteText(val) => {
+            senders
+                .send_to_screen(ScreenInstruction::ClearScroll(client_id))
                 .with_context(err_context)?;
+            // the markers are written on their own so that terminal panes which did not ask for
+            // bracketed paste drop them, the same way they do when pasting through the terminal
+            for bytes in [
+                BRACKETED_PASTE_BEGIN.to_vec(),
+                val.into_bytes(),
+                BRACKETED_PASTE_END.to_vec(),
+            ] {
+                senders
+                    .send_to_screen(ScreenInstruction::WriteCharacter(bytes, client_id))
+                    .with_context(err_context)?;
+            }
         },
         Action::SwitchToMode(mode) => {
             let attrs = &client_attributes;
@@ -100,6 +154,81 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::Render)
                 .with_context(err_context)?;
         },
+        Action::ResetFloatingPaneGeometry(pane_name) => {
+            senders
+                .send_to_screen(ScreenInstruction::ResetFloatingPaneGeometry(pane_name))
+                .with_context(err_context)?;
+        },
+        Action::SetRepeatCount(count) => {
+            senders
+                .send_to_screen(ScreenInstruction::SetRepeatCount(count, client_id))
+                .with_context(err_context)?;
+        },
+        Action::ShowModeHelp(mode) => {
+            senders
+                .send_to_screen(ScreenInstruction::ShowModeHelp(
+                    mode,
+                    client_attributes.keybinds.keybinds_for_mode(&mode),
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::SwitchToModeTimed(mode, timeout_ms) => {
+            senders
+                .send_to_screen(ScreenInstruction::ChangeModeTimed(
+                    get_mode_info(mode, &client_attributes, capabilities),
+                    timeout_ms,
+                    client_id,
+                ))
+                .with_context(err_context)?;
+            senders
+                .send_to_screen(ScreenInstruction::Render)
+                .with_context(err_context)?;
+        },
+        Action::EnterResizeLoop => {
+            senders
+                .send_to_screen(ScreenInstruction::EnterResizeLoop(
+                    get_mode_info(InputMode::Resize, &client_attributes, capabilities),
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::ExitResizeLoop => {
+            senders
+                .send_to_screen(ScreenInstruction::ExitResizeLoop(client_id))
+                .with_context(err_context)?;
+        },
+        Action::SetModeReportSocket(socket_path) => {
+            senders
+                .send_to_screen(ScreenInstruction::SetModeReportSocket(socket_path))
+                .with_context(err_context)?;
+        },
+        Action::SetPaneSize(cols, rows) => {
+            senders
+                .send_to_screen(ScreenInstruction::SetPaneSize(cols, rows, client_id))
+                .with_context(err_context)?;
+        },
+        Action::ResizePaneToPercent { width, height } => {
+            senders
+                .send_to_screen(ScreenInstruction::ResizePaneToPercent(
+                    width, height, client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::SetPaneSizeConstraints(min_cols, max_cols, min_rows, max_rows) => {
+            let size_constraints = SizeConstraints {
+                min_cols,
+                max_cols,
+                min_rows,
+                max_rows,
+            };
+            senders
+                .send_to_screen(ScreenInstruction::SetPaneSizeConstraints(
+                    size_constraints,
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
         Action::Resize(resize, direction) => {
             let screen_instr =
                 ScreenInstruction::Resize(client_id, ResizeStrategy::new(resize, direction));
@@ -122,6 +251,16 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::FocusPreviousPane(client_id))
                 .with_context(err_context)?;
         },
+        Action::FocusNextFloatingPane => {
+            senders
+                .send_to_screen(ScreenInstruction::FocusNextFloatingPane(client_id))
+                .with_context(err_context)?;
+        },
+        Action::FocusPreviousFloatingPane => {
+            senders
+                .send_to_screen(ScreenInstruction::FocusPreviousFloatingPane(client_id))
+                .with_context(err_context)?;
+        },
         Action::MoveFocus(direction) => {
             let screen_instr = match direction {
                 Direction::Left => ScreenInstruction::MoveFocusLeft(client_id),
@@ -133,6 +272,27 @@ pub(crate) fn route_action(
                 .send_to_screen(screen_instr)
                 .with_context(err_context)?;
         },
+        Action::FocusPaneWithId(terminal_id, should_switch_tab) => {
+            senders
+                .send_to_screen(ScreenInstruction::FocusPaneWithId(
+                    PaneId::Terminal(terminal_id),
+                    should_switch_tab,
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::CycleFocusByCommand(command) => {
+            senders
+                .send_to_screen(ScreenInstruction::CycleFocusByCommand(command, client_id))
+                .with_context(err_context)?;
+        },
+        Action::FocusPaneByName(name, all_tabs) => {
+            senders
+                .send_to_screen(ScreenInstruction::FocusPaneByName(
+                    name, all_tabs, client_id,
+                ))
+                .with_context(err_context)?;
+        },
         Action::MoveFocusOrTab(direction) => {
             let screen_instr = match direction {
                 Direction::Left => ScreenInstruction::MoveFocusLeftOrPreviousTab(client_id),
@@ -156,19 +316,77 @@ pub(crate) fn route_action(
                 .send_to_screen(screen_instr)
                 .with_context(err_context)?;
         },
+        Action::RotatePanesForward => {
+            senders
+                .send_to_screen(ScreenInstruction::RotatePanes(false, client_id))
+                .with_context(err_context)?;
+        },
+        Action::RotatePanesBackward => {
+            senders
+                .send_to_screen(ScreenInstruction::RotatePanes(true, client_id))
+                .with_context(err_context)?;
+        },
         Action::MovePaneBackwards => {
             senders
                 .send_to_screen(ScreenInstruction::MovePaneBackwards(client_id))
                 .with_context(err_context)?;
         },
+        Action::MoveFloatingPaneTo { x, y } => {
+            senders
+                .send_to_screen(ScreenInstruction::MoveFloatingPaneTo(x, y, client_id))
+                .with_context(err_context)?;
+        },
+        Action::ResizeFloatingPaneTo { cols, rows } => {
+            senders
+                .send_to_screen(ScreenInstruction::ResizeFloatingPaneTo(
+                    cols, rows, client_id,
+                ))
+                .with_context(err_context)?;
+        },
         Action::ClearScreen => {
             senders
                 .send_to_screen(ScreenInstruction::ClearScreen(client_id))
                 .with_context(err_context)?;
         },
-        Action::DumpScreen(val, full) => {
+        Action::ClearScrollback => {
+            senders
+                .send_to_screen(ScreenInstruction::ClearScrollback(client_id))
+                .with_context(err_context)?;
+        },
+        Action::SetScrollbackLines(lines) => {
+            senders
+                .send_to_screen(ScreenInstruction::SetScrollbackLines(lines, client_id))
+                .with_context(err_context)?;
+        },
+        Action::DumpScreen(val, full, preserve_ansi, header) => {
+            senders
+                .send_to_screen(ScreenInstruction::DumpScreen(
+                    val,
+                    client_id,
+                    full,
+                    preserve_ansi,
+                    header,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::DumpScreenSvg(val, full) => {
             senders
-                .send_to_screen(ScreenInstruction::DumpScreen(val, client_id, full))
+                .send_to_screen(ScreenInstruction::DumpScreenSvg(val, client_id, full))
+                .with_context(err_context)?;
+        },
+        Action::DumpScreenJsonl(val, full) => {
+            senders
+                .send_to_screen(ScreenInstruction::DumpScreenJsonl(val, client_id, full))
+                .with_context(err_context)?;
+        },
+        Action::DumpScreenHtml(val, full) => {
+            senders
+                .send_to_screen(ScreenInstruction::DumpScreenHtml(val, client_id, full))
+                .with_context(err_context)?;
+        },
+        Action::DumpLayout(path) => {
+            senders
+                .send_to_screen(ScreenInstruction::DumpLayout(path, client_id))
                 .with_context(err_context)?;
         },
         Action::EditScrollback => {
@@ -186,11 +404,21 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::ScrollUpAt(point, client_id))
                 .with_context(err_context)?;
         },
+        Action::ScrollUpBy(lines) => {
+            senders
+                .send_to_screen(ScreenInstruction::ScrollUpBy(lines, client_id))
+                .with_context(err_context)?;
+        },
         Action::ScrollDown => {
             senders
                 .send_to_screen(ScreenInstruction::ScrollDown(client_id))
                 .with_context(err_context)?;
         },
+        Action::ScrollDownBy(lines) => {
+            senders
+                .send_to_screen(ScreenInstruction::ScrollDownBy(lines, client_id))
+                .with_context(err_context)?;
+        },
         Action::ScrollDownAt(point) => {
             senders
                 .send_to_screen(ScreenInstruction::ScrollDownAt(point, client_id))
@@ -206,6 +434,11 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::ScrollToTop(client_id))
                 .with_context(err_context)?;
         },
+        Action::ScrollToLine(line) => {
+            senders
+                .send_to_screen(ScreenInstruction::ScrollToLine(line, client_id))
+                .with_context(err_context)?;
+        },
         Action::PageScrollUp => {
             senders
                 .send_to_screen(ScreenInstruction::PageScrollUp(client_id))
@@ -216,6 +449,21 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::PageScrollDown(client_id))
                 .with_context(err_context)?;
         },
+        Action::MoveToPreviousPrompt => {
+            senders
+                .send_to_screen(ScreenInstruction::MoveToPreviousPrompt(client_id))
+                .with_context(err_context)?;
+        },
+        Action::MoveToNextPrompt => {
+            senders
+                .send_to_screen(ScreenInstruction::MoveToNextPrompt(client_id))
+                .with_context(err_context)?;
+        },
+        Action::TogglePaneLineWrap => {
+            senders
+                .send_to_screen(ScreenInstruction::TogglePaneLineWrap(client_id))
+                .with_context(err_context)?;
+        },
         Action::HalfPageScrollUp => {
             senders
                 .send_to_screen(ScreenInstruction::HalfPageScrollUp(client_id))
@@ -231,11 +479,34 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::ToggleActiveTerminalFullscreen(client_id))
                 .with_context(err_context)?;
         },
+        Action::ToggleFullscreenForPane(terminal_id) => {
+            senders
+                .send_to_screen(ScreenInstruction::TogglePaneFullscreen(
+                    PaneId::Terminal(terminal_id),
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
         Action::TogglePaneFrames => {
             senders
                 .send_to_screen(ScreenInstruction::TogglePaneFrames)
                 .with_context(err_context)?;
         },
+        Action::ToggleFocusedPaneFrame => {
+            senders
+                .send_to_screen(ScreenInstruction::ToggleFocusedPaneFrame(client_id))
+                .with_context(err_context)?;
+        },
+        Action::ToggleZenMode => {
+            senders
+                .send_to_screen(ScreenInstruction::ToggleZenMode)
+                .with_context(err_context)?;
+        },
+        Action::ToggleRuler => {
+            senders
+                .send_to_screen(ScreenInstruction::ToggleRuler)
+                .with_context(err_context)?;
+        },
         Action::NewPane(direction, name) => {
             let shell = default_shell.clone();
             let pty_instr = match direction {
@@ -257,13 +528,34 @@ pub(crate) fn route_action(
                     None,
                     name,
                     ClientOrTabIndex::ClientId(client_id),
+                    None,
                 ),
             };
             senders.send_to_pty(pty_instr).with_context(err_context)?;
         },
-        Action::EditFile(path_to_file, line_number, cwd, split_direction, should_float) => {
+        Action::DuplicatePane(direction) => {
+            senders
+                .send_to_screen(ScreenInstruction::DuplicatePane(
+                    direction,
+                    false,
+                    default_shell.clone(),
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::DuplicateFloatingPane => {
+            senders
+                .send_to_screen(ScreenInstruction::DuplicatePane(
+                    None,
+                    true,
+                    default_shell.clone(),
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::EditFile(path_to_file, line_number, column, cwd, split_direction, should_float) => {
             let title = format!("Editing: {}", path_to_file.display());
-            let open_file = TerminalAction::OpenFile(path_to_file, line_number, cwd);
+            let open_file = TerminalAction::OpenFile(path_to_file, line_number, column, cwd);
             let pty_instr = match (split_direction, should_float) {
                 (Some(Direction::Left), false) => {
                     PtyInstruction::SpawnTerminalVertically(Some(open_file), Some(title), client_id)
@@ -287,6 +579,7 @@ pub(crate) fn route_action(
                     Some(should_float),
                     Some(title),
                     ClientOrTabIndex::ClientId(client_id),
+                    None,
                 ),
             };
             senders.send_to_pty(pty_instr).with_context(err_context)?;
@@ -308,25 +601,22 @@ pub(crate) fn route_action(
                 )))
                 .with_context(err_context)?;
         },
-        Action::NewFloatingPane(run_command, name) => {
+        Action::NewFloatingPane(run_command, name, placement) => {
             let should_float = true;
-            let run_cmd = run_command
-                .map(|cmd| TerminalAction::RunCommand(cmd.into()))
-                .or_else(|| default_shell.clone());
+            let run_cmd = terminal_action_for(run_command, &default_shell);
             senders
                 .send_to_pty(PtyInstruction::SpawnTerminal(
                     run_cmd,
                     Some(should_float),
                     name,
                     ClientOrTabIndex::ClientId(client_id),
+                    placement,
                 ))
                 .with_context(err_context)?;
         },
         Action::NewTiledPane(direction, run_command, name) => {
             let should_float = false;
-            let run_cmd = run_command
-                .map(|cmd| TerminalAction::RunCommand(cmd.into()))
-                .or_else(|| default_shell.clone());
+            let run_cmd = terminal_action_for(run_command, &default_shell);
             let pty_instr = match direction {
                 Some(Direction::Left) => {
                     PtyInstruction::SpawnTerminalVertically(run_cmd, name, client_id)
@@ -346,15 +636,45 @@ pub(crate) fn route_action(
                     Some(should_float),
                     name,
                     ClientOrTabIndex::ClientId(client_id),
+                    None,
                 ),
             };
             senders.send_to_pty(pty_instr).with_context(err_context)?;
         },
+        Action::NewPaneAtFraction(direction, percent, run_command, name) => {
+            let run_cmd = terminal_action_for(run_command, &default_shell);
+            let fraction = f64::from(percent) / 100.0;
+            senders
+                .send_to_pty(PtyInstruction::SpawnTerminalAtFraction(
+                    run_cmd, name, direction, fraction, client_id,
+                ))
+                .with_context(err_context)?;
+        },
         Action::TogglePaneEmbedOrFloating => {
             senders
                 .send_to_screen(ScreenInstruction::TogglePaneEmbedOrFloating(client_id))
                 .with_context(err_context)?;
         },
+        Action::EmbedAllFloatingPanes => {
+            senders
+                .send_to_screen(ScreenInstruction::EmbedAllFloatingPanes(client_id))
+                .with_context(err_context)?;
+        },
+        Action::FloatAllTiledPanes => {
+            senders
+                .send_to_screen(ScreenInstruction::FloatAllTiledPanes(client_id))
+                .with_context(err_context)?;
+        },
+        Action::PeekPaneFloating => {
+            senders
+                .send_to_screen(ScreenInstruction::PeekPaneFloating(client_id))
+                .with_context(err_context)?;
+        },
+        Action::TogglePanePinned => {
+            senders
+                .send_to_screen(ScreenInstruction::TogglePanePinned(client_id))
+                .with_context(err_context)?;
+        },
         Action::ToggleFloatingPanes => {
             senders
                 .send_to_screen(ScreenInstruction::ToggleFloatingPanes(
@@ -368,6 +688,11 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::UpdatePaneName(c, client_id))
                 .with_context(err_context)?;
         },
+        Action::ClearPaneName => {
+            senders
+                .send_to_screen(ScreenInstruction::ClearPaneName(client_id))
+                .with_context(err_context)?;
+        },
         Action::UndoRenamePane => {
             senders
                 .send_to_screen(ScreenInstruction::UndoRenamePane(client_id))
@@ -394,6 +719,7 @@ pub(crate) fn route_action(
                     None,
                     None,
                     ClientOrTabIndex::ClientId(client_id),
+                    None,
                 ),
             };
             senders.send_to_pty(pty_instr).with_context(err_context)?;
@@ -403,14 +729,34 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::CloseFocusedPane(client_id))
                 .with_context(err_context)?;
         },
+        Action::CloseFocusSmart => {
+            senders
+                .send_to_screen(ScreenInstruction::CloseFocusedPaneSmart(client_id))
+                .with_context(err_context)?;
+        },
         Action::NewTab(
             tab_layout,
             floating_panes_layout,
             swap_tiled_layouts,
             swap_floating_layouts,
             tab_name,
+            empty,
+            inherit_cwd,
         ) => {
-            let shell = default_shell.clone();
+            let mut shell = default_shell.clone();
+            if inherit_cwd {
+                // the pty thread then starts the panes in the cwd of the focused pane (that of the
+                // first connected client if this is a cli client)
+                let mut run_command = match shell {
+                    Some(TerminalAction::RunCommand(run_command)) => run_command,
+                    _ => RunCommand {
+                        command: get_default_shell(),
+                        ..Default::default()
+                    },
+                };
+                run_command.cwd_from_focus = true;
+                shell = Some(TerminalAction::RunCommand(run_command));
+            }
             let swap_tiled_layouts =
                 swap_tiled_layouts.unwrap_or_else(|| default_layout.swap_tiled_layouts.clone());
             let swap_floating_layouts = swap_floating_layouts
@@ -423,10 +769,21 @@ pub(crate) fn route_action(
                     floating_panes_layout,
                     tab_name,
                     (swap_tiled_layouts, swap_floating_layouts),
+                    empty,
                     client_id,
                 ))
                 .with_context(err_context)?;
         },
+        Action::FocusPaneAtIndex(index) => {
+            senders
+                .send_to_screen(ScreenInstruction::FocusPaneAtIndex(index, client_id))
+                .with_context(err_context)?;
+        },
+        Action::SetClientLayout(layout_path) => {
+            senders
+                .send_to_screen(ScreenInstruction::SetClientLayout(layout_path, client_id))
+                .with_context(err_context)?;
+        },
         Action::GoToNextTab => {
             senders
                 .send_to_screen(ScreenInstruction::SwitchTabNext(client_id))
@@ -437,11 +794,66 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::SwitchTabPrev(client_id))
                 .with_context(err_context)?;
         },
+        Action::MoveTabLeft => {
+            senders
+                .send_to_screen(ScreenInstruction::MoveTabLeft(client_id))
+                .with_context(err_context)?;
+        },
+        Action::MoveTabRight => {
+            senders
+                .send_to_screen(ScreenInstruction::MoveTabRight(client_id))
+                .with_context(err_context)?;
+        },
+        Action::BreakPane => {
+            senders
+                .send_to_screen(ScreenInstruction::BreakPane(client_id))
+                .with_context(err_context)?;
+        },
+        Action::BreakPaneLeft => {
+            senders
+                .send_to_screen(ScreenInstruction::BreakPaneLeft(client_id))
+                .with_context(err_context)?;
+        },
+        Action::BreakPaneRight => {
+            senders
+                .send_to_screen(ScreenInstruction::BreakPaneRight(client_id))
+                .with_context(err_context)?;
+        },
+        Action::MoveTabToIndex(tab_position) => {
+            senders
+                .send_to_screen(ScreenInstruction::MoveTabToIndex(tab_position, client_id))
+                .with_context(err_context)?;
+        },
+        Action::MovePaneToTab(tab_position) => {
+            senders
+                .send_to_screen(ScreenInstruction::MovePaneToTab(tab_position, client_id))
+                .with_context(err_context)?;
+        },
+        Action::ToggleOverlayTab(tab_position) => {
+            senders
+                .send_to_screen(ScreenInstruction::ToggleOverlayTab(tab_position, client_id))
+                .with_context(err_context)?;
+        },
+        Action::RestartPaneCommand => {
+            senders
+                .send_to_screen(ScreenInstruction::RestartPaneCommand(client_id))
+                .with_context(err_context)?;
+        },
         Action::ToggleActiveSyncTab => {
             senders
                 .send_to_screen(ScreenInstruction::ToggleActiveSyncTab(client_id))
                 .with_context(err_context)?;
         },
+        Action::TogglePinTab => {
+            senders
+                .send_to_screen(ScreenInstruction::TogglePinTab(client_id))
+                .with_context(err_context)?;
+        },
+        Action::ToggleActiveSyncSession => {
+            senders
+                .send_to_screen(ScreenInstruction::ToggleActiveSyncSession)
+                .with_context(err_context)?;
+        },
         Action::CloseTab => {
             senders
                 .send_to_screen(ScreenInstruction::CloseTab(client_id))
@@ -452,6 +864,14 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::GoToTab(i, Some(client_id)))
                 .with_context(err_context)?;
         },
+        Action::GoToTabOfPane(terminal_id) => {
+            senders
+                .send_to_screen(ScreenInstruction::GoToTabOfPane(
+                    PaneId::Terminal(terminal_id),
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
         Action::GoToTabName(name, create) => {
             let shell = default_shell.clone();
             let swap_tiled_layouts = default_layout.swap_tiled_layouts.clone();
@@ -471,6 +891,11 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::UpdateTabName(c, client_id))
                 .with_context(err_context)?;
         },
+        Action::ClearTabName => {
+            senders
+                .send_to_screen(ScreenInstruction::ClearTabName(client_id))
+                .with_context(err_context)?;
+        },
         Action::UndoRenameTab => {
             senders
                 .send_to_screen(ScreenInstruction::UndoRenameTab(client_id))
@@ -488,11 +913,26 @@ pub(crate) fn route_action(
                 .with_context(err_context)?;
             should_break = true;
         },
+        Action::DetachWhenIdle(timeout_secs) => {
+            senders
+                .send_to_screen(ScreenInstruction::DetachWhenIdle(timeout_secs, client_id))
+                .with_context(err_context)?;
+        },
         Action::LeftClick(point) => {
             senders
                 .send_to_screen(ScreenInstruction::LeftClick(point, client_id))
                 .with_context(err_context)?;
         },
+        Action::LeftDoubleClick(point) => {
+            senders
+                .send_to_screen(ScreenInstruction::LeftDoubleClick(point, client_id))
+                .with_context(err_context)?;
+        },
+        Action::LeftTripleClick(point) => {
+            senders
+                .send_to_screen(ScreenInstruction::LeftTripleClick(point, client_id))
+                .with_context(err_context)?;
+        },
         Action::RightClick(point) => {
             senders
                 .send_to_screen(ScreenInstruction::RightClick(point, client_id))
@@ -538,6 +978,36 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::Copy(client_id))
                 .with_context(err_context)?;
         },
+        Action::CopyNormalized => {
+            senders
+                .send_to_screen(ScreenInstruction::CopyNormalized(client_id))
+                .with_context(err_context)?;
+        },
+        Action::SelectAll(viewport_only) => {
+            senders
+                .send_to_screen(ScreenInstruction::SelectAll(viewport_only, client_id))
+                .with_context(err_context)?;
+        },
+        Action::OpenUrlUnderCursor(position) => {
+            senders
+                .send_to_screen(ScreenInstruction::OpenUrlUnderCursor(position, client_id))
+                .with_context(err_context)?;
+        },
+        Action::CopyUrlUnderCursor(position) => {
+            senders
+                .send_to_screen(ScreenInstruction::CopyUrlUnderCursor(position, client_id))
+                .with_context(err_context)?;
+        },
+        Action::CopyThrough(command) => {
+            senders
+                .send_to_screen(ScreenInstruction::CopyThrough(command, client_id))
+                .with_context(err_context)?;
+        },
+        Action::CopyLastCommandOutput => {
+            senders
+                .send_to_screen(ScreenInstruction::CopyLastCommandOutput(client_id))
+                .with_context(err_context)?;
+        },
         Action::Confirm => {
             senders
                 .send_to_screen(ScreenInstruction::ConfirmPrompt(client_id))
@@ -548,7 +1018,6 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::DenyPrompt(client_id))
                 .with_context(err_context)?;
         },
-        #[allow(clippy::single_match)]
         Action::SkipConfirm(action) => match *action {
             Action::Quit => {
                 senders
@@ -556,6 +1025,11 @@ pub(crate) fn route_action(
                     .with_context(err_context)?;
                 should_break = true;
             },
+            Action::CloseFocus | Action::CloseFocusSmart => {
+                senders
+                    .send_to_screen(ScreenInstruction::CloseFocusedPane(client_id))
+                    .with_context(err_context)?;
+            },
             _ => {},
         },
         Action::NoOp => {},
@@ -573,6 +1047,21 @@ pub(crate) fn route_action(
                 .send_to_screen(instruction)
                 .with_context(err_context)?;
         },
+        Action::SearchToQuickfix(needle) => {
+            senders
+                .send_to_screen(ScreenInstruction::SearchToQuickfix(needle, client_id))
+                .with_context(err_context)?;
+        },
+        Action::SearchJumpToMatch(match_index) => {
+            senders
+                .send_to_screen(ScreenInstruction::SearchJumpToMatch(match_index, client_id))
+                .with_context(err_context)?;
+        },
+        Action::QuerySearchMatchCount => {
+            senders
+                .send_to_screen(ScreenInstruction::QuerySearchMatchCount(client_id))
+                .with_context(err_context)?;
+        },
         Action::SearchToggleOption(o) => {
             let instruction = match o {
                 SearchOption::CaseSensitivity => {
@@ -580,6 +1069,8 @@ pub(crate) fn route_action(
                 },
                 SearchOption::WholeWord => ScreenInstruction::SearchToggleWholeWord(client_id),
                 SearchOption::Wrap => ScreenInstruction::SearchToggleWrap(client_id),
+                SearchOption::Regex => ScreenInstruction::SearchToggleRegex(client_id),
+                SearchOption::VisibleOnly => ScreenInstruction::SearchToggleVisibleOnly(client_id),
             };
             senders
                 .send_to_screen(instruction)
@@ -596,11 +1087,61 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::NextSwapLayout(client_id))
                 .with_context(err_context)?;
         },
+        Action::GoToSwapLayout(index) => {
+            senders
+                .send_to_screen(ScreenInstruction::GoToSwapLayout(index, client_id))
+                .with_context(err_context)?;
+        },
+        Action::ApplySwapLayoutByName(name) => {
+            senders
+                .send_to_screen(ScreenInstruction::ApplySwapLayoutByName(name, client_id))
+                .with_context(err_context)?;
+        },
         Action::QueryTabNames => {
             senders
                 .send_to_screen(ScreenInstruction::QueryTabNames(client_id))
                 .with_context(err_context)?;
         },
+        Action::QueryPaneList(format) => {
+            senders
+                .send_to_screen(ScreenInstruction::QueryPaneList(format, client_id))
+                .with_context(err_context)?;
+        },
+        Action::QueryClipboardInfo => {
+            senders
+                .send_to_screen(ScreenInstruction::QueryClipboardInfo(client_id))
+                .with_context(err_context)?;
+        },
+        Action::SetSessionVar(name, value) => match &session_state {
+            // set right away so that the actions following this one already see the variable
+            Some(session_state) => {
+                let set_var = session_state
+                    .write()
+                    .to_anyhow()
+                    .with_context(err_context)?
+                    .set_var(name, value);
+                if let Err(e) = set_var {
+                    senders
+                        .send_to_server(ServerInstruction::Log(vec![e.to_string()], client_id))
+                        .with_context(err_context)?;
+                }
+            },
+            None => {
+                senders
+                    .send_to_server(ServerInstruction::SetSessionVar(name, value, None))
+                    .with_context(err_context)?;
+            },
+        },
+        Action::QuerySessionVar(name) => {
+            senders
+                .send_to_server(ServerInstruction::QuerySessionVar(name, client_id))
+                .with_context(err_context)?;
+        },
+        Action::RenameSession(name) => {
+            senders
+                .send_to_server(ServerInstruction::RenameSession(name, client_id))
+                .with_context(err_context)?;
+        },
         Action::NewTiledPluginPane(run_plugin, name) => {
             senders
                 .send_to_screen(ScreenInstruction::NewTiledPluginPane(
@@ -629,6 +1170,78 @@ pub(crate) fn route_action(
                 ))
                 .with_context(err_context)?;
         },
+        Action::StartTabTour(tab_indices, delay_ms) => {
+            senders
+                .send_to_background_jobs(BackgroundJob::RunTabTour(
+                    client_id,
+                    tab_indices,
+                    delay_ms,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::StopTabTour => {
+            senders
+                .send_to_background_jobs(BackgroundJob::StopTabTour(client_id))
+                .with_context(err_context)?;
+        },
+        Action::BalancePanesAxis(vertical) => {
+            senders
+                .send_to_screen(ScreenInstruction::BalancePanesAxis(vertical, client_id))
+                .with_context(err_context)?;
+        },
+        Action::BalancePanes => {
+            senders
+                .send_to_screen(ScreenInstruction::BalancePanes(client_id))
+                .with_context(err_context)?;
+        },
+        Action::ToggleDefaultSplitDirection => {
+            senders
+                .send_to_screen(ScreenInstruction::ToggleDefaultSplitDirection)
+                .with_context(err_context)?;
+        },
+        Action::FocusMostRecentlyActivePane => {
+            senders
+                .send_to_screen(ScreenInstruction::FocusMostRecentlyActivePane(client_id))
+                .with_context(err_context)?;
+        },
+        Action::Sequence(actions) => {
+            for action in actions {
+                let should_break_after_action = route_action(
+                    action,
+                    client_id,
+                    senders.clone(),
+                    capabilities,
+                    client_attributes.clone(),
+                    default_shell.clone(),
+                    default_layout.clone(),
+                    session_state.clone(),
+                )
+                .with_context(err_context)?;
+                if should_break_after_action {
+                    should_break = true;
+                    break;
+                }
+            }
+        },
+        Action::Repeat(count, action) => {
+            for _ in 0..count {
+                let should_break_after_action = route_action(
+                    (*action).clone(),
+                    client_id,
+                    senders.clone(),
+                    capabilities,
+                    client_attributes.clone(),
+                    default_shell.clone(),
+                    default_layout.clone(),
+                    session_state.clone(),
+                )
+                .with_context(err_context)?;
+                if should_break_after_action {
+                    should_break = true;
+                    break;
+                }
+            }
+        },
     }
     Ok(should_break)
 }
@@ -649,6 +1262,35 @@ macro_rules! send_to_screen_or_retry_queue {
     }};
 }
 
+fn terminal_action_for(
+    pane_run: Option<PaneRunAction>,
+    default_shell: &Option<TerminalAction>,
+) -> Option<TerminalAction> {
+    match pane_run {
+        Some(PaneRunAction::Command(run_command)) => {
+            Some(TerminalAction::RunCommand(run_command.into()))
+        },
+        // the pane stays a shell pane rather than a command pane (eg. `new-pane --no-frame`)
+        Some(PaneRunAction::DefaultShell(shell_options)) => {
+            let mut shell = match default_shell {
+                Some(TerminalAction::RunCommand(shell)) => shell.clone(),
+                _ => RunCommand {
+                    command: get_default_shell(),
+                    ..Default::default()
+                },
+            };
+            if shell_options.cwd.is_some() {
+                shell.cwd = shell_options.cwd;
+            }
+            shell.cwd_from_focus = shell_options.cwd_from_focus;
+            shell.no_frame = shell_options.no_frame;
+            shell.fixed_pty_size = shell_options.fixed_pty_size;
+            Some(TerminalAction::RunCommand(shell))
+        },
+        None => default_shell.clone(),
+    }
+}
+
 pub(crate) fn route_thread_main(
     session_data: Arc<RwLock<Option<SessionMetaData>>>,
     session_state: Arc<RwLock<SessionState>>,
@@ -674,7 +1316,9 @@ pub(crate) fn route_thread_main(
                         ClientToServerMsg::Action(action, maybe_client_id) => {
                             let client_id = maybe_client_id.unwrap_or(client_id);
                             if let Some(rlocked_sessions) = rlocked_sessions.as_ref() {
-                                if let Action::SwitchToMode(input_mode) = action {
+                                if let Action::SwitchToMode(input_mode)
+                                | Action::SwitchToModeTimed(input_mode, _) = action
+                                {
                                     let send_res = os_input.send_to_client(
                                         client_id,
                                         ServerToClientMsg::SwitchToMode(input_mode),
@@ -693,6 +1337,7 @@ pub(crate) fn route_thread_main(
                                     rlocked_sessions.client_attributes.clone(),
                                     rlocked_sessions.default_shell.clone(),
                                     rlocked_sessions.layout.clone(),
+                                    Some(session_state.clone()),
                                 )? {
                                     should_break = true;
                                 }
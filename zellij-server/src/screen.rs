@@ -2,20 +2,27 @@
 
 use std::cell::RefCell;
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
+use std::os::unix::net::UnixStream;
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::str;
+use std::time::{Duration, Instant};
 
-use zellij_utils::data::{Direction, PaneManifest, Resize, ResizeStrategy};
+use zellij_utils::data::{
+    ClipboardInfo, CopyDestination, Direction, FloatingPanePlacement, PaneListEntry,
+    PaneListFormat, PaneManifest, Resize, ResizeStrategy,
+};
 use zellij_utils::errors::prelude::*;
 use zellij_utils::input::command::RunCommand;
-use zellij_utils::input::options::Clipboard;
-use zellij_utils::pane_size::{Size, SizeInPixels};
+use zellij_utils::input::options::{Clipboard, TimedModeKeypress};
+use zellij_utils::pane_size::{PaneGeom, Size, SizeConstraints, SizeInPixels};
 use zellij_utils::{
+    input::actions::Action,
     input::command::TerminalAction,
     input::layout::{
-        FloatingPaneLayout, Run, RunPlugin, RunPluginLocation, SwapFloatingLayout, SwapTiledLayout,
-        TiledPaneLayout,
+        FloatingPaneLayout, Layout, Run, RunPlugin, RunPluginLocation, SplitDirection,
+        SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout,
     },
     position::Position,
 };
@@ -24,21 +31,29 @@ use crate::panes::alacritty_functions::xparse_color;
 use crate::panes::terminal_character::AnsiCode;
 
 use crate::{
+    background_jobs::BackgroundJob,
     output::Output,
     panes::sixel::SixelImageStore,
     panes::PaneId,
     plugins::PluginInstruction,
     pty::{ClientOrTabIndex, PtyInstruction, VteBytes},
+    session_layout_metadata::SessionLayoutMetadata,
     tab::Tab,
     thread_bus::Bus,
     ui::{
         loading_indication::LoadingIndication,
-        overlay::{Overlay, OverlayWindow},
+        overlay::{
+            mode_help::ModeHelp,
+            prompt::{generate_close_pane_prompt, PromptInstruction},
+            Overlay, OverlayType, OverlayWindow, Overlayable,
+        },
     },
     ClientId, ServerInstruction,
 };
 use zellij_utils::{
-    data::{Event, InputMode, ModeInfo, Palette, PaletteColor, PluginCapabilities, Style, TabInfo},
+    data::{
+        Event, InputMode, Key, ModeInfo, Palette, PaletteColor, PluginCapabilities, Style, TabInfo,
+    },
     errors::{ContextType, ScreenContext},
     input::{get_mode_info, options::Options},
     ipc::{ClientAttributes, PixelDimensions, ServerToClientMsg},
@@ -138,47 +153,96 @@ pub enum ScreenInstruction {
         Option<ShouldFloat>,
         HoldForCommand,
         ClientOrTabIndex,
+        Option<FloatingPanePlacement>,
     ),
     OpenInPlaceEditor(PaneId, ClientId),
+    RemovePaneFrame(PaneId),
     TogglePaneEmbedOrFloating(ClientId),
+    EmbedAllFloatingPanes(ClientId),
+    FloatAllTiledPanes(ClientId),
+    PeekPaneFloating(ClientId),
+    TogglePanePinned(ClientId),
+    BalancePanesAxis(bool, ClientId), // bool => vertical
+    BalancePanes(ClientId),
+    FocusMostRecentlyActivePane(ClientId),
+    ToggleDefaultSplitDirection,
+    WriteToPanesMatching(String, Vec<u8>, ClientId), // String is the command filter
     ToggleFloatingPanes(ClientId, Option<TerminalAction>),
     HorizontalSplit(PaneId, Option<InitialTitle>, HoldForCommand, ClientId),
+    NewPaneAtFraction(
+        PaneId,
+        Option<InitialTitle>,
+        HoldForCommand,
+        Direction,
+        f64,
+        ClientId,
+    ), // f64 is the fraction of the tab to split at
     VerticalSplit(PaneId, Option<InitialTitle>, HoldForCommand, ClientId),
     WriteCharacter(Vec<u8>, ClientId),
     Resize(ClientId, ResizeStrategy),
+    SetPaneSize(Option<usize>, Option<usize>, ClientId), // (cols, rows)
+    ResizePaneToPercent(Option<u8>, Option<u8>, ClientId), // (width, height)
+    SetPaneSizeConstraints(SizeConstraints, ClientId),
     SwitchFocus(ClientId),
     FocusNextPane(ClientId),
     FocusPreviousPane(ClientId),
+    FocusNextFloatingPane(ClientId),
+    FocusPreviousFloatingPane(ClientId),
     MoveFocusLeft(ClientId),
     MoveFocusLeftOrPreviousTab(ClientId),
     MoveFocusDown(ClientId),
     MoveFocusUp(ClientId),
     MoveFocusRight(ClientId),
     MoveFocusRightOrNextTab(ClientId),
+    FocusPaneWithId(PaneId, bool, ClientId), // bool => should switch to the tab of the pane
+    CycleFocusByCommand(String, ClientId),
+    FocusPaneByName(String, bool, ClientId), // bool => search across all tabs
+    FocusPaneAtIndex(usize, ClientId),       // usize is the 1-based index in tiling order
+    SetClientLayout(PathBuf, ClientId),
     MovePane(ClientId),
     MovePaneBackwards(ClientId),
+    RotatePanes(bool, ClientId),                  // bool -> backwards
+    MoveFloatingPaneTo(usize, usize, ClientId),   // (x, y)
+    ResizeFloatingPaneTo(usize, usize, ClientId), // (cols, rows)
     MovePaneUp(ClientId),
     MovePaneDown(ClientId),
     MovePaneRight(ClientId),
     MovePaneLeft(ClientId),
     Exit,
     ClearScreen(ClientId),
-    DumpScreen(String, ClientId, bool),
+    ClearScrollback(ClientId),
+    SetScrollbackLines(usize, ClientId),
+    DumpScreen(String, ClientId, bool, bool, bool), // bools are full, preserve_ansi and header
+    DumpScreenSvg(String, ClientId, bool),
+    DumpScreenJsonl(String, ClientId, bool),
+    DumpScreenHtml(String, ClientId, bool),
+    DumpLayout(Option<String>, ClientId),
     EditScrollback(ClientId),
     ScrollUp(ClientId),
     ScrollUpAt(Position, ClientId),
+    ScrollUpBy(usize, ClientId),
     ScrollDown(ClientId),
     ScrollDownAt(Position, ClientId),
+    ScrollDownBy(usize, ClientId),
     ScrollToBottom(ClientId),
     ScrollToTop(ClientId),
+    ScrollToLine(usize, ClientId),
     PageScrollUp(ClientId),
+    MoveToPreviousPrompt(ClientId),
+    MoveToNextPrompt(ClientId),
+    TogglePaneLineWrap(ClientId),
     PageScrollDown(ClientId),
     HalfPageScrollUp(ClientId),
     HalfPageScrollDown(ClientId),
     ClearScroll(ClientId),
     CloseFocusedPane(ClientId),
+    CloseFocusedPaneSmart(ClientId),
     ToggleActiveTerminalFullscreen(ClientId),
+    TogglePaneFullscreen(PaneId, ClientId),
     TogglePaneFrames,
+    ToggleFocusedPaneFrame(ClientId),
+    ToggleRuler,
+    ToggleZenMode,
     SetSelectable(PaneId, bool, usize),
     ClosePane(PaneId, Option<ClientId>),
     HoldPane(
@@ -188,7 +252,9 @@ pub enum ScreenInstruction {
         Option<usize>,
         Option<ClientId>,
     ), // Option<i32> is the exit status, Option<usize> is the tab_index
+    RestartWatchedCommand(PaneId),
     UpdatePaneName(Vec<u8>, ClientId),
+    ClearPaneName(ClientId),
     UndoRenamePane(ClientId),
     NewTab(
         Option<PathBuf>,
@@ -197,6 +263,7 @@ pub enum ScreenInstruction {
         Vec<FloatingPaneLayout>,
         Option<String>,
         (Vec<SwapTiledLayout>, Vec<SwapFloatingLayout>), // swap layouts
+        bool,                                            // create the tab without any panes
         ClientId,
     ),
     ApplyLayout(
@@ -210,9 +277,24 @@ pub enum ScreenInstruction {
     ),
     SwitchTabNext(ClientId),
     SwitchTabPrev(ClientId),
+    MoveTabLeft(ClientId),
+    MoveTabRight(ClientId),
+    MoveTabToIndex(u32, ClientId), // u32 - 1-based position to move the tab to
+    BreakPane(ClientId),
+    BreakPaneLeft(ClientId),
+    BreakPaneRight(ClientId),
+    MovePaneToTab(u32, ClientId),    // u32 - 1-based position of the tab
+    ToggleOverlayTab(u32, ClientId), // u32 - 1-based position of the tab
     ToggleActiveSyncTab(ClientId),
+    ToggleActiveSyncSession,
+    TogglePinTab(ClientId),
+    PinTab(usize), // usize - 0-based position of the tab
+    RestartPaneCommand(ClientId),
+    DuplicatePane(Option<Direction>, bool, Option<TerminalAction>, ClientId), // bool -> should float,
+    // TerminalAction -> the default shell, opened when the focused pane is not a command pane
     CloseTab(ClientId),
     GoToTab(u32, Option<ClientId>), // this Option is a hacky workaround, please do not copy this behaviour
+    GoToTabOfPane(PaneId, ClientId),
     GoToTabName(
         String,
         (Vec<SwapTiledLayout>, Vec<SwapFloatingLayout>), // swap layouts
@@ -221,7 +303,9 @@ pub enum ScreenInstruction {
         Option<ClientId>,
     ),
     ToggleTab(ClientId),
+    RestoreTabFocus(ClientId),
     UpdateTabName(Vec<u8>, ClientId),
+    ClearTabName(ClientId),
     UndoRenameTab(ClientId),
     TerminalResize(Size),
     TerminalPixelDimensions(PixelDimensions),
@@ -230,7 +314,15 @@ pub enum ScreenInstruction {
     TerminalColorRegisters(Vec<(usize, String)>),
     ChangeMode(ModeInfo, ClientId),
     ChangeModeForAllClients(ModeInfo),
+    ChangeModeTimed(ModeInfo, u64, ClientId), // u64 - timeout in ms
+    ResetModeTimer(ClientId),
+    ModeTimeoutElapsed(ClientId),
+    EnterResizeLoop(ModeInfo, ClientId),
+    ExitResizeLoop(ClientId),
+    SetModeReportSocket(PathBuf),
     LeftClick(Position, ClientId),
+    LeftDoubleClick(Position, ClientId),
+    LeftTripleClick(Position, ClientId),
     RightClick(Position, ClientId),
     MiddleClick(Position, ClientId),
     LeftMouseRelease(Position, ClientId),
@@ -240,10 +332,25 @@ pub enum ScreenInstruction {
     MouseHoldRight(Position, ClientId),
     MouseHoldMiddle(Position, ClientId),
     Copy(ClientId),
+    CopyNormalized(ClientId),
+    SelectAll(bool, ClientId), // bool -> viewport only
+    CopyUrlUnderCursor(Position, ClientId),
+    OpenUrlUnderCursor(Position, ClientId),
+    CopyLastCommandOutput(ClientId),
+    CopyThrough(String, ClientId), // String is the filter command
+    CopyFilteredText(String, ClientId),
+    SearchToQuickfix(Vec<u8>, ClientId),
+    SearchJumpToMatch(usize, ClientId),
+    QuerySearchMatchCount(ClientId),
+    DetachWhenIdle(u64, ClientId), // u64 is the timeout in seconds
+    CheckPendingDetach,
     AddClient(ClientId),
     RemoveClient(ClientId),
     AddOverlay(Overlay, ClientId),
     RemoveOverlay(ClientId),
+    ShowModeHelp(InputMode, Vec<(Key, Vec<Action>)>, ClientId),
+    SetRepeatCount(u32, ClientId),
+    ResetFloatingPaneGeometry(String),
     ConfirmPrompt(ClientId),
     DenyPrompt(ClientId),
     UpdateSearch(Vec<u8>, ClientId),
@@ -252,11 +359,18 @@ pub enum ScreenInstruction {
     SearchToggleCaseSensitivity(ClientId),
     SearchToggleWholeWord(ClientId),
     SearchToggleWrap(ClientId),
+    SearchToggleRegex(ClientId),
+    SearchToggleVisibleOnly(ClientId),
     AddRedPaneFrameColorOverride(Vec<PaneId>, Option<String>), // Option<String> => optional error text
     ClearPaneFrameColorOverride(Vec<PaneId>),
     PreviousSwapLayout(ClientId),
     NextSwapLayout(ClientId),
+    GoToSwapLayout(usize, ClientId),
+    ApplySwapLayoutByName(String, ClientId),
     QueryTabNames(ClientId),
+    QueryPaneList(PaneListFormat, ClientId),
+    RenameSession(String),
+    QueryClipboardInfo(ClientId),
     NewTiledPluginPane(RunPluginLocation, Option<String>, ClientId), // Option<String> is
     // optional pane title
     NewFloatingPluginPane(RunPluginLocation, Option<String>, ClientId), // Option<String> is an
@@ -277,6 +391,30 @@ pub enum ScreenInstruction {
     SuppressPane(PaneId, ClientId),
 }
 
+impl ScreenInstruction {
+    /// The client of instructions that are performed as many times as the repeat count (see
+    /// [`Action::SetRepeatCount`]) says
+    fn count_aware_client_id(&self) -> Option<ClientId> {
+        match self {
+            ScreenInstruction::ScrollUp(client_id)
+            | ScreenInstruction::ScrollUpBy(_, client_id)
+            | ScreenInstruction::ScrollDown(client_id)
+            | ScreenInstruction::ScrollDownBy(_, client_id)
+            | ScreenInstruction::FocusNextPane(client_id)
+            | ScreenInstruction::FocusPreviousPane(client_id)
+            | ScreenInstruction::FocusNextFloatingPane(client_id)
+            | ScreenInstruction::FocusPreviousFloatingPane(client_id)
+            | ScreenInstruction::MoveFocusLeft(client_id)
+            | ScreenInstruction::MoveFocusDown(client_id)
+            | ScreenInstruction::MoveFocusUp(client_id)
+            | ScreenInstruction::MoveFocusRight(client_id)
+            | ScreenInstruction::SwitchTabNext(client_id)
+            | ScreenInstruction::SwitchTabPrev(client_id) => Some(*client_id),
+            _ => None,
+        }
+    }
+}
+
 impl From<&ScreenInstruction> for ScreenContext {
     fn from(screen_instruction: &ScreenInstruction) -> Self {
         match *screen_instruction {
@@ -285,11 +423,26 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::Render => ScreenContext::Render,
             ScreenInstruction::NewPane(..) => ScreenContext::NewPane,
             ScreenInstruction::OpenInPlaceEditor(..) => ScreenContext::OpenInPlaceEditor,
+            ScreenInstruction::RemovePaneFrame(..) => ScreenContext::RemovePaneFrame,
             ScreenInstruction::TogglePaneEmbedOrFloating(..) => {
                 ScreenContext::TogglePaneEmbedOrFloating
             },
+            ScreenInstruction::EmbedAllFloatingPanes(..) => ScreenContext::EmbedAllFloatingPanes,
+            ScreenInstruction::FloatAllTiledPanes(..) => ScreenContext::FloatAllTiledPanes,
+            ScreenInstruction::PeekPaneFloating(..) => ScreenContext::PeekPaneFloating,
+            ScreenInstruction::TogglePanePinned(..) => ScreenContext::TogglePanePinned,
+            ScreenInstruction::BalancePanesAxis(..) => ScreenContext::BalancePanesAxis,
+            ScreenInstruction::BalancePanes(..) => ScreenContext::BalancePanes,
+            ScreenInstruction::FocusMostRecentlyActivePane(..) => {
+                ScreenContext::FocusMostRecentlyActivePane
+            },
+            ScreenInstruction::ToggleDefaultSplitDirection => {
+                ScreenContext::ToggleDefaultSplitDirection
+            },
+            ScreenInstruction::WriteToPanesMatching(..) => ScreenContext::WriteToPanesMatching,
             ScreenInstruction::ToggleFloatingPanes(..) => ScreenContext::ToggleFloatingPanes,
             ScreenInstruction::HorizontalSplit(..) => ScreenContext::HorizontalSplit,
+            ScreenInstruction::NewPaneAtFraction(..) => ScreenContext::NewPaneAtFraction,
             ScreenInstruction::VerticalSplit(..) => ScreenContext::VerticalSplit,
             ScreenInstruction::WriteCharacter(..) => ScreenContext::WriteCharacter,
             ScreenInstruction::Resize(.., strategy) => match strategy {
@@ -316,9 +469,16 @@ impl From<&ScreenInstruction> for ScreenContext {
                     None => ScreenContext::ResizeDecreaseAll,
                 },
             },
+            ScreenInstruction::SetPaneSize(..) => ScreenContext::SetPaneSize,
+            ScreenInstruction::ResizePaneToPercent(..) => ScreenContext::ResizePaneToPercent,
+            ScreenInstruction::SetPaneSizeConstraints(..) => ScreenContext::SetPaneSizeConstraints,
             ScreenInstruction::SwitchFocus(..) => ScreenContext::SwitchFocus,
             ScreenInstruction::FocusNextPane(..) => ScreenContext::FocusNextPane,
             ScreenInstruction::FocusPreviousPane(..) => ScreenContext::FocusPreviousPane,
+            ScreenInstruction::FocusNextFloatingPane(..) => ScreenContext::FocusNextFloatingPane,
+            ScreenInstruction::FocusPreviousFloatingPane(..) => {
+                ScreenContext::FocusPreviousFloatingPane
+            },
             ScreenInstruction::MoveFocusLeft(..) => ScreenContext::MoveFocusLeft,
             ScreenInstruction::MoveFocusLeftOrPreviousTab(..) => {
                 ScreenContext::MoveFocusLeftOrPreviousTab
@@ -326,46 +486,83 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::MoveFocusDown(..) => ScreenContext::MoveFocusDown,
             ScreenInstruction::MoveFocusUp(..) => ScreenContext::MoveFocusUp,
             ScreenInstruction::MoveFocusRight(..) => ScreenContext::MoveFocusRight,
+            ScreenInstruction::FocusPaneWithId(..) => ScreenContext::FocusPaneWithId,
+            ScreenInstruction::CycleFocusByCommand(..) => ScreenContext::CycleFocusByCommand,
+            ScreenInstruction::FocusPaneByName(..) => ScreenContext::FocusPaneByName,
+            ScreenInstruction::FocusPaneAtIndex(..) => ScreenContext::FocusPaneAtIndex,
+            ScreenInstruction::SetClientLayout(..) => ScreenContext::SetClientLayout,
             ScreenInstruction::MoveFocusRightOrNextTab(..) => {
                 ScreenContext::MoveFocusRightOrNextTab
             },
             ScreenInstruction::MovePane(..) => ScreenContext::MovePane,
             ScreenInstruction::MovePaneBackwards(..) => ScreenContext::MovePaneBackwards,
+            ScreenInstruction::RotatePanes(..) => ScreenContext::RotatePanes,
+            ScreenInstruction::MoveFloatingPaneTo(..) => ScreenContext::MoveFloatingPaneTo,
+            ScreenInstruction::ResizeFloatingPaneTo(..) => ScreenContext::ResizeFloatingPaneTo,
             ScreenInstruction::MovePaneDown(..) => ScreenContext::MovePaneDown,
             ScreenInstruction::MovePaneUp(..) => ScreenContext::MovePaneUp,
             ScreenInstruction::MovePaneRight(..) => ScreenContext::MovePaneRight,
             ScreenInstruction::MovePaneLeft(..) => ScreenContext::MovePaneLeft,
             ScreenInstruction::Exit => ScreenContext::Exit,
             ScreenInstruction::ClearScreen(..) => ScreenContext::ClearScreen,
+            ScreenInstruction::ClearScrollback(..) => ScreenContext::ClearScrollback,
+            ScreenInstruction::SetScrollbackLines(..) => ScreenContext::SetScrollbackLines,
             ScreenInstruction::DumpScreen(..) => ScreenContext::DumpScreen,
+            ScreenInstruction::DumpScreenSvg(..) => ScreenContext::DumpScreenSvg,
+            ScreenInstruction::DumpScreenJsonl(..) => ScreenContext::DumpScreenJsonl,
+            ScreenInstruction::DumpScreenHtml(..) => ScreenContext::DumpScreenHtml,
+            ScreenInstruction::DumpLayout(..) => ScreenContext::DumpLayout,
             ScreenInstruction::EditScrollback(..) => ScreenContext::EditScrollback,
             ScreenInstruction::ScrollUp(..) => ScreenContext::ScrollUp,
+            ScreenInstruction::ScrollUpBy(..) => ScreenContext::ScrollUpBy,
             ScreenInstruction::ScrollDown(..) => ScreenContext::ScrollDown,
+            ScreenInstruction::ScrollDownBy(..) => ScreenContext::ScrollDownBy,
             ScreenInstruction::ScrollToBottom(..) => ScreenContext::ScrollToBottom,
             ScreenInstruction::ScrollToTop(..) => ScreenContext::ScrollToTop,
+            ScreenInstruction::ScrollToLine(..) => ScreenContext::ScrollToLine,
             ScreenInstruction::PageScrollUp(..) => ScreenContext::PageScrollUp,
+            ScreenInstruction::MoveToPreviousPrompt(..) => ScreenContext::MoveToPreviousPrompt,
+            ScreenInstruction::MoveToNextPrompt(..) => ScreenContext::MoveToNextPrompt,
+            ScreenInstruction::TogglePaneLineWrap(..) => ScreenContext::TogglePaneLineWrap,
             ScreenInstruction::PageScrollDown(..) => ScreenContext::PageScrollDown,
             ScreenInstruction::HalfPageScrollUp(..) => ScreenContext::HalfPageScrollUp,
             ScreenInstruction::HalfPageScrollDown(..) => ScreenContext::HalfPageScrollDown,
             ScreenInstruction::ClearScroll(..) => ScreenContext::ClearScroll,
             ScreenInstruction::CloseFocusedPane(..) => ScreenContext::CloseFocusedPane,
+            ScreenInstruction::CloseFocusedPaneSmart(..) => ScreenContext::CloseFocusedPaneSmart,
+            ScreenInstruction::TogglePaneFullscreen(..) => ScreenContext::TogglePaneFullscreen,
             ScreenInstruction::ToggleActiveTerminalFullscreen(..) => {
                 ScreenContext::ToggleActiveTerminalFullscreen
             },
             ScreenInstruction::TogglePaneFrames => ScreenContext::TogglePaneFrames,
+            ScreenInstruction::ToggleFocusedPaneFrame(..) => ScreenContext::ToggleFocusedPaneFrame,
+            ScreenInstruction::ToggleRuler => ScreenContext::ToggleRuler,
+            ScreenInstruction::ToggleZenMode => ScreenContext::ToggleZenMode,
             ScreenInstruction::SetSelectable(..) => ScreenContext::SetSelectable,
             ScreenInstruction::ClosePane(..) => ScreenContext::ClosePane,
             ScreenInstruction::HoldPane(..) => ScreenContext::HoldPane,
+            ScreenInstruction::RestartWatchedCommand(..) => ScreenContext::RestartWatchedCommand,
             ScreenInstruction::UpdatePaneName(..) => ScreenContext::UpdatePaneName,
+            ScreenInstruction::ClearPaneName(..) => ScreenContext::ClearPaneName,
             ScreenInstruction::UndoRenamePane(..) => ScreenContext::UndoRenamePane,
             ScreenInstruction::NewTab(..) => ScreenContext::NewTab,
             ScreenInstruction::ApplyLayout(..) => ScreenContext::ApplyLayout,
             ScreenInstruction::SwitchTabNext(..) => ScreenContext::SwitchTabNext,
             ScreenInstruction::SwitchTabPrev(..) => ScreenContext::SwitchTabPrev,
+            ScreenInstruction::MoveTabLeft(..) => ScreenContext::MoveTabLeft,
+            ScreenInstruction::MoveTabRight(..) => ScreenContext::MoveTabRight,
+            ScreenInstruction::MoveTabToIndex(..) => ScreenContext::MoveTabToIndex,
+            ScreenInstruction::BreakPane(..) => ScreenContext::BreakPane,
+            ScreenInstruction::BreakPaneLeft(..) => ScreenContext::BreakPaneLeft,
+            ScreenInstruction::BreakPaneRight(..) => ScreenContext::BreakPaneRight,
+            ScreenInstruction::MovePaneToTab(..) => ScreenContext::MovePaneToTab,
+            ScreenInstruction::ToggleOverlayTab(..) => ScreenContext::ToggleOverlayTab,
             ScreenInstruction::CloseTab(..) => ScreenContext::CloseTab,
             ScreenInstruction::GoToTab(..) => ScreenContext::GoToTab,
+            ScreenInstruction::GoToTabOfPane(..) => ScreenContext::GoToTabOfPane,
             ScreenInstruction::GoToTabName(..) => ScreenContext::GoToTabName,
             ScreenInstruction::UpdateTabName(..) => ScreenContext::UpdateTabName,
+            ScreenInstruction::ClearTabName(..) => ScreenContext::ClearTabName,
             ScreenInstruction::UndoRenameTab(..) => ScreenContext::UndoRenameTab,
             ScreenInstruction::TerminalResize(..) => ScreenContext::TerminalResize,
             ScreenInstruction::TerminalPixelDimensions(..) => {
@@ -382,10 +579,23 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::ChangeModeForAllClients(..) => {
                 ScreenContext::ChangeModeForAllClients
             },
+            ScreenInstruction::ChangeModeTimed(..) => ScreenContext::ChangeModeTimed,
+            ScreenInstruction::EnterResizeLoop(..) => ScreenContext::EnterResizeLoop,
+            ScreenInstruction::ExitResizeLoop(..) => ScreenContext::ExitResizeLoop,
+            ScreenInstruction::ResetModeTimer(..) => ScreenContext::ResetModeTimer,
+            ScreenInstruction::ModeTimeoutElapsed(..) => ScreenContext::ModeTimeoutElapsed,
+            ScreenInstruction::SetModeReportSocket(..) => ScreenContext::SetModeReportSocket,
             ScreenInstruction::ToggleActiveSyncTab(..) => ScreenContext::ToggleActiveSyncTab,
+            ScreenInstruction::TogglePinTab(..) => ScreenContext::TogglePinTab,
+            ScreenInstruction::PinTab(..) => ScreenContext::PinTab,
+            ScreenInstruction::ToggleActiveSyncSession => ScreenContext::ToggleActiveSyncSession,
+            ScreenInstruction::RestartPaneCommand(..) => ScreenContext::RestartPaneCommand,
+            ScreenInstruction::DuplicatePane(..) => ScreenContext::DuplicatePane,
             ScreenInstruction::ScrollUpAt(..) => ScreenContext::ScrollUpAt,
             ScreenInstruction::ScrollDownAt(..) => ScreenContext::ScrollDownAt,
             ScreenInstruction::LeftClick(..) => ScreenContext::LeftClick,
+            ScreenInstruction::LeftDoubleClick(..) => ScreenContext::LeftDoubleClick,
+            ScreenInstruction::LeftTripleClick(..) => ScreenContext::LeftTripleClick,
             ScreenInstruction::RightClick(..) => ScreenContext::RightClick,
             ScreenInstruction::MiddleClick(..) => ScreenContext::MiddleClick,
             ScreenInstruction::LeftMouseRelease(..) => ScreenContext::LeftMouseRelease,
@@ -395,11 +605,29 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::MouseHoldRight(..) => ScreenContext::MouseHoldRight,
             ScreenInstruction::MouseHoldMiddle(..) => ScreenContext::MouseHoldMiddle,
             ScreenInstruction::Copy(..) => ScreenContext::Copy,
+            ScreenInstruction::CopyNormalized(..) => ScreenContext::CopyNormalized,
+            ScreenInstruction::SelectAll(..) => ScreenContext::SelectAll,
+            ScreenInstruction::CopyUrlUnderCursor(..) => ScreenContext::CopyUrlUnderCursor,
+            ScreenInstruction::OpenUrlUnderCursor(..) => ScreenContext::OpenUrlUnderCursor,
+            ScreenInstruction::CopyLastCommandOutput(..) => ScreenContext::CopyLastCommandOutput,
+            ScreenInstruction::CopyThrough(..) => ScreenContext::CopyThrough,
+            ScreenInstruction::CopyFilteredText(..) => ScreenContext::CopyFilteredText,
+            ScreenInstruction::SearchToQuickfix(..) => ScreenContext::SearchToQuickfix,
+            ScreenInstruction::SearchJumpToMatch(..) => ScreenContext::SearchJumpToMatch,
+            ScreenInstruction::QuerySearchMatchCount(..) => ScreenContext::QuerySearchMatchCount,
+            ScreenInstruction::DetachWhenIdle(..) => ScreenContext::DetachWhenIdle,
+            ScreenInstruction::CheckPendingDetach => ScreenContext::CheckPendingDetach,
             ScreenInstruction::ToggleTab(..) => ScreenContext::ToggleTab,
+            ScreenInstruction::RestoreTabFocus(..) => ScreenContext::RestoreTabFocus,
             ScreenInstruction::AddClient(..) => ScreenContext::AddClient,
             ScreenInstruction::RemoveClient(..) => ScreenContext::RemoveClient,
             ScreenInstruction::AddOverlay(..) => ScreenContext::AddOverlay,
             ScreenInstruction::RemoveOverlay(..) => ScreenContext::RemoveOverlay,
+            ScreenInstruction::ShowModeHelp(..) => ScreenContext::ShowModeHelp,
+            ScreenInstruction::SetRepeatCount(..) => ScreenContext::SetRepeatCount,
+            ScreenInstruction::ResetFloatingPaneGeometry(..) => {
+                ScreenContext::ResetFloatingPaneGeometry
+            },
             ScreenInstruction::ConfirmPrompt(..) => ScreenContext::ConfirmPrompt,
             ScreenInstruction::DenyPrompt(..) => ScreenContext::DenyPrompt,
             ScreenInstruction::UpdateSearch(..) => ScreenContext::UpdateSearch,
@@ -410,6 +638,10 @@ impl From<&ScreenInstruction> for ScreenContext {
             },
             ScreenInstruction::SearchToggleWholeWord(..) => ScreenContext::SearchToggleWholeWord,
             ScreenInstruction::SearchToggleWrap(..) => ScreenContext::SearchToggleWrap,
+            ScreenInstruction::SearchToggleRegex(..) => ScreenContext::SearchToggleRegex,
+            ScreenInstruction::SearchToggleVisibleOnly(..) => {
+                ScreenContext::SearchToggleVisibleOnly
+            },
             ScreenInstruction::AddRedPaneFrameColorOverride(..) => {
                 ScreenContext::AddRedPaneFrameColorOverride
             },
@@ -418,7 +650,12 @@ impl From<&ScreenInstruction> for ScreenContext {
             },
             ScreenInstruction::PreviousSwapLayout(..) => ScreenContext::PreviousSwapLayout,
             ScreenInstruction::NextSwapLayout(..) => ScreenContext::NextSwapLayout,
+            ScreenInstruction::GoToSwapLayout(..) => ScreenContext::GoToSwapLayout,
+            ScreenInstruction::ApplySwapLayoutByName(..) => ScreenContext::ApplySwapLayoutByName,
             ScreenInstruction::QueryTabNames(..) => ScreenContext::QueryTabNames,
+            ScreenInstruction::QueryPaneList(..) => ScreenContext::QueryPaneList,
+            ScreenInstruction::RenameSession(..) => ScreenContext::RenameSession,
+            ScreenInstruction::QueryClipboardInfo(..) => ScreenContext::QueryClipboardInfo,
             ScreenInstruction::NewTiledPluginPane(..) => ScreenContext::NewTiledPluginPane,
             ScreenInstruction::NewFloatingPluginPane(..) => ScreenContext::NewFloatingPluginPane,
             ScreenInstruction::StartOrReloadPluginPane(..) => {
@@ -463,6 +700,14 @@ impl CopyOptions {
         }
     }
 
+    pub(crate) fn copy_destination(&self) -> CopyDestination {
+        match (&self.command, self.clipboard) {
+            (Some(_), _) => CopyDestination::Command,
+            (None, Clipboard::Primary) => CopyDestination::Primary,
+            (None, Clipboard::System) => CopyDestination::System,
+        }
+    }
+
     #[cfg(test)]
     pub(crate) fn default() -> Self {
         Self {
@@ -499,12 +744,49 @@ pub(crate) struct Screen {
     default_mode_info: ModeInfo, // TODO: restructure ModeInfo to prevent this duplication
     style: Style,
     draw_pane_frames: bool,
+    ruler_is_visible: bool,
     auto_layout: bool,
     session_is_mirrored: bool,
     copy_options: CopyOptions,
+    clipboard_info: Rc<RefCell<Option<ClipboardInfo>>>, // the last copy to the clipboard
+    floating_pane_geometries: Rc<RefCell<HashMap<String, PaneGeom>>>, // the last geometry of closed floating panes, by name
+    repeat_counts: HashMap<ClientId, u32>,
+    timed_modes: HashMap<ClientId, TimedMode>,
+    timed_mode_keypress: TimedModeKeypress,
+    resize_loops: HashMap<ClientId, ModeInfo>, // the mode to return to when leaving the loop
+    resize_loop_step: usize,
+    sync_session_is_active: bool, // input is sent to all panes in all tabs, regardless of tab sync
+    default_split_direction: Option<SplitDirection>, // None means the biggest available space
+    mode_report_socket: Option<PathBuf>, // a unix socket the name of every mode switched to is written to
+    pane_frames_before_zen_mode: Option<bool>, // Some while in zen mode
+    pending_detach: Option<PendingDetach>,
+    overlay_tab: Option<OverlayTab>,
     debug: bool,
 }
 
+/// The first pane of one tab, shown floating on top of another tab until it is toggled off
+struct OverlayTab {
+    source_tab_index: usize,
+    target_tab_index: usize,
+    pane_id: PaneId,
+}
+
+/// A detach deferred until no pane has a command running anymore, or until it times out
+struct PendingDetach {
+    client_ids: Vec<ClientId>,
+    requested_by: ClientId,
+    deadline: Instant,
+}
+
+const PENDING_DETACH_POLL_INTERVAL_MS: u64 = 500;
+
+/// A mode switched to with a timeout, after which the client reverts to `revert_to`
+struct TimedMode {
+    revert_to: ModeInfo,
+    timeout_ms: u64,
+    deadline: Instant,
+}
+
 impl Screen {
     /// Creates and returns a new [`Screen`].
     pub fn new(
@@ -516,6 +798,8 @@ impl Screen {
         auto_layout: bool,
         session_is_mirrored: bool,
         copy_options: CopyOptions,
+        timed_mode_keypress: TimedModeKeypress,
+        resize_loop_step: usize,
         debug: bool,
     ) -> Self {
         Screen {
@@ -536,9 +820,23 @@ impl Screen {
             mode_info: BTreeMap::new(),
             default_mode_info: mode_info,
             draw_pane_frames,
+            ruler_is_visible: false,
             auto_layout,
             session_is_mirrored,
             copy_options,
+            clipboard_info: Rc::new(RefCell::new(None)),
+            floating_pane_geometries: Rc::new(RefCell::new(HashMap::new())),
+            repeat_counts: HashMap::new(),
+            timed_modes: HashMap::new(),
+            timed_mode_keypress,
+            resize_loops: HashMap::new(),
+            resize_loop_step,
+            sync_session_is_active: false,
+            default_split_direction: None,
+            mode_report_socket: None,
+            pending_detach: None,
+            pane_frames_before_zen_mode: None,
+            overlay_tab: None,
             debug,
         }
     }
@@ -658,6 +956,16 @@ impl Screen {
         )
         };
 
+        let new_tab_is_overlay_source = self
+            .overlay_tab
+            .as_ref()
+            .and_then(|overlay_tab| self.tabs.get(&overlay_tab.source_tab_index))
+            .map(|source_tab| source_tab.position == new_tab_pos)
+            .unwrap_or(false);
+        if new_tab_is_overlay_source {
+            // its first pane should be back in place when we get there
+            self.hide_overlay_tab().with_context(err_context)?;
+        }
         if let Some(new_tab) = self.tabs.values().find(|t| t.position == new_tab_pos) {
             match self.get_active_tab(client_id) {
                 Ok(current_tab) => {
@@ -803,177 +1111,969 @@ impl Screen {
         Ok(())
     }
 
-    pub fn go_to_tab(&mut self, tab_index: usize, client_id: ClientId) -> Result<()> {
-        self.switch_active_tab(tab_index.saturating_sub(1), None, client_id)
+    /// Moves this [`Screen`]'s active [`Tab`] one position to the left, wrapping around to the
+    /// last position if it is the first tab.
+    pub fn move_active_tab_left(&mut self, client_id: ClientId) -> Result<()> {
+        self.move_active_tab(client_id, true)
     }
 
-    pub fn go_to_tab_name(&mut self, name: String, client_id: ClientId) -> Result<bool> {
-        self.switch_active_tab_name(name, client_id)
+    /// Moves this [`Screen`]'s active [`Tab`] one position to the right, wrapping around to the
+    /// first position if it is the last tab.
+    pub fn move_active_tab_right(&mut self, client_id: ClientId) -> Result<()> {
+        self.move_active_tab(client_id, false)
     }
 
-    fn close_tab_at_index(&mut self, tab_index: usize) -> Result<()> {
-        let err_context = || format!("failed to close tab at index {tab_index:?}");
+    /// Moves this [`Screen`]'s active [`Tab`] to the given (1-based) position, shifting the tabs
+    /// in between by one. Positions past the first or last tab move it to that end.
+    pub fn move_active_tab_to_position(
+        &mut self,
+        tab_position: u32,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || {
+            format!("failed to move active tab to position {tab_position} for client {client_id}")
+        };
 
-        let mut tab_to_close = self.tabs.remove(&tab_index).with_context(err_context)?;
-        let pane_ids = tab_to_close.get_all_pane_ids();
-        // below we don't check the result of sending the CloseTab instruction to the pty thread
-        // because this might be happening when the app is closing, at which point the pty thread
-        // has already closed and this would result in an error
-        self.bus
-            .senders
-            .send_to_pty(PtyInstruction::CloseTab(pane_ids))
-            .with_context(err_context)?;
-        if self.tabs.is_empty() {
-            self.active_tab_indices.clear();
-            self.bus
-                .senders
-                .send_to_server(ServerInstruction::Render(None))
-                .with_context(err_context)
+        let client_id = if self.get_active_tab(client_id).is_ok() {
+            Some(client_id)
         } else {
-            let client_mode_infos_in_closed_tab = tab_to_close.drain_connected_clients(None);
-            self.move_clients_from_closed_tab(client_mode_infos_in_closed_tab)
-                .with_context(err_context)?;
-            let visible_tab_indices: HashSet<usize> =
-                self.active_tab_indices.values().copied().collect();
-            for t in self.tabs.values_mut() {
-                if visible_tab_indices.contains(&t.index) {
-                    t.set_force_render();
-                    t.visible(true).with_context(err_context)?;
-                }
-                if t.position > tab_to_close.position {
-                    t.position -= 1;
-                }
+            self.get_first_client_id()
+        };
+        let tab_count = self.tabs.len();
+        if let Some(client_id) = client_id {
+            let active_tab_pos = self
+                .get_active_tab(client_id)
+                .with_context(err_context)?
+                .position;
+            let new_tab_pos = (tab_position as usize)
+                .saturating_sub(1)
+                .min(tab_count.saturating_sub(1));
+            if new_tab_pos == active_tab_pos {
+                return Ok(());
+            }
+            for tab in self.tabs.values_mut() {
+                tab.position = if tab.position == active_tab_pos {
+                    new_tab_pos
+                } else if (new_tab_pos..active_tab_pos).contains(&tab.position) {
+                    // moving left, the tabs it passes shift one position to the right
+                    tab.position + 1
+                } else if (active_tab_pos + 1..=new_tab_pos).contains(&tab.position) {
+                    tab.position - 1
+                } else {
+                    tab.position
+                };
             }
             self.report_tab_state().with_context(err_context)?;
             self.report_pane_state().with_context(err_context)?;
-            self.render().with_context(err_context)
         }
+        Ok(())
     }
 
-    // Closes the client_id's focused tab
-    pub fn close_tab(&mut self, client_id: ClientId) -> Result<()> {
-        let err_context = || format!("failed to close tab for client {client_id:?}");
+    fn move_active_tab(&mut self, client_id: ClientId, to_the_left: bool) -> Result<()> {
+        let err_context = || format!("failed to move active tab for client {client_id}");
 
         let client_id = if self.get_active_tab(client_id).is_ok() {
             Some(client_id)
         } else {
             self.get_first_client_id()
         };
-
-        match client_id {
-            Some(client_id) => {
-                let active_tab_index = *self
-                    .active_tab_indices
-                    .get(&client_id)
-                    .with_context(err_context)?;
-                self.close_tab_at_index(active_tab_index)
-                    .with_context(err_context)
-            },
-            None => Ok(()),
+        let tab_count = self.tabs.len();
+        if let Some(client_id) = client_id {
+            let active_tab_pos = self
+                .get_active_tab(client_id)
+                .with_context(err_context)?
+                .position;
+            if tab_count < 2 {
+                return Ok(());
+            }
+            let last_tab_pos = tab_count - 1;
+            let wraps_around = if to_the_left {
+                active_tab_pos == 0
+            } else {
+                active_tab_pos == last_tab_pos
+            };
+            let new_tab_pos = match (to_the_left, wraps_around) {
+                (true, true) => last_tab_pos,
+                (true, false) => active_tab_pos - 1,
+                (false, true) => 0,
+                (false, false) => active_tab_pos + 1,
+            };
+            for tab in self.tabs.values_mut() {
+                tab.position = if tab.position == active_tab_pos {
+                    new_tab_pos
+                } else if wraps_around && to_the_left {
+                    // every other tab shifts one position to the left to make room at the end
+                    tab.position - 1
+                } else if wraps_around {
+                    tab.position + 1
+                } else if tab.position == new_tab_pos {
+                    active_tab_pos
+                } else {
+                    tab.position
+                };
+            }
+            self.report_tab_state().with_context(err_context)?;
+            self.report_pane_state().with_context(err_context)?;
         }
+        Ok(())
     }
 
-    pub fn resize_to_screen(&mut self, new_screen_size: Size) -> Result<()> {
-        let err_context = || format!("failed to resize to screen size: {new_screen_size:#?}");
+    /// Creates a new [`Tab`] without any panes in it and focuses it, returning its index.
+    pub fn new_empty_tab(
+        &mut self,
+        tab_name: Option<String>,
+        client_id: ClientId,
+    ) -> Result<usize> {
+        let err_context = || format!("failed to create empty tab for client {client_id}");
 
-        self.size = new_screen_size;
-        for tab in self.tabs.values_mut() {
-            tab.resize_whole_tab(new_screen_size)
-                .with_context(err_context)?;
-            tab.set_force_render();
+        let tab_index = self.get_new_tab_index();
+        // the swap layouts we get are those of the full layout, and assume it has panes (eg. a
+        // tab-bar) that an empty tab does not
+        self.new_tab(tab_index, (vec![], vec![]), tab_name, client_id)
+            .with_context(err_context)?;
+        if let Some(tab) = self.tabs.get_mut(&tab_index) {
+            tab.set_awaiting_first_pane();
         }
-        self.report_pane_state().with_context(err_context)?;
-        self.render().with_context(err_context)
+        // there are no panes to spawn, so we apply the (empty) layout right away rather than
+        // waiting for the plugin and pty threads
+        self.apply_layout(
+            TiledPaneLayout::default(),
+            vec![],
+            vec![],
+            vec![],
+            HashMap::new(),
+            tab_index,
+            client_id,
+        )
+        .with_context(err_context)?;
+        Ok(tab_index)
     }
 
-    pub fn update_pixel_dimensions(&mut self, pixel_dimensions: PixelDimensions) {
-        self.pixel_dimensions.merge(pixel_dimensions);
-        if let Some(character_cell_size) = self.pixel_dimensions.character_cell_size {
-            *self.character_cell_size.borrow_mut() = Some(character_cell_size);
-        } else if let Some(text_area_size) = self.pixel_dimensions.text_area_size {
-            let character_cell_size_height = text_area_size.height / self.size.rows;
-            let character_cell_size_width = text_area_size.width / self.size.cols;
-            let character_cell_size = SizeInPixels {
-                height: character_cell_size_height,
-                width: character_cell_size_width,
-            };
-            *self.character_cell_size.borrow_mut() = Some(character_cell_size);
+    /// Moves the focused pane of this client to a new [`Tab`] of its own.
+    pub fn break_pane(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to break pane to a new tab for client {client_id}");
+
+        let active_tab = self
+            .get_active_tab_mut(client_id)
+            .with_context(err_context)?;
+        if active_tab.focused_pane_is_last_tiled_pane(client_id) {
+            // this pane is already all there is to its tab, or leaving the tab with only
+            // floating panes would close it
+            return Ok(());
         }
+        let (pane_id, pane, is_floating) = match active_tab.extract_focused_pane(client_id) {
+            Some(extracted_pane) => extracted_pane,
+            None => return Ok(()),
+        };
+        let new_tab_index = self
+            .new_empty_tab(None, client_id)
+            .with_context(err_context)?;
+        self.tabs
+            .get_mut(&new_tab_index)
+            .with_context(err_context)?
+            .add_pane_from_another_tab(pane_id, pane, is_floating, client_id)
+            .with_context(err_context)?;
+        self.report_tab_state().with_context(err_context)?;
+        self.report_pane_state().with_context(err_context)?;
+        Ok(())
     }
 
-    pub fn update_terminal_background_color(&mut self, background_color_instruction: String) {
-        if let Some(AnsiCode::RgbCode((r, g, b))) =
-            xparse_color(background_color_instruction.as_bytes())
-        {
-            let bg_palette_color = PaletteColor::Rgb((r, g, b));
-            self.terminal_emulator_colors.borrow_mut().bg = bg_palette_color;
-        }
+    /// Moves the focused pane of this client to the [`Tab`] to the left or right of the active
+    /// one, creating a new tab in that position if there is none.
+    pub fn break_pane_to_adjacent_tab(
+        &mut self,
+        client_id: ClientId,
+        to_the_left: bool,
+    ) -> Result<()> {
+        let err_context = || format!("failed to break pane to adjacent tab for client {client_id}");
+
+        let active_tab = self.get_active_tab(client_id).with_context(err_context)?;
+        let (source_tab_index, source_tab_position) = (active_tab.index, active_tab.position);
+        let target_tab_position = if to_the_left {
+            source_tab_position.checked_sub(1)
+        } else {
+            Some(source_tab_position + 1)
+        };
+        let target_tab_index = target_tab_position.and_then(|position| {
+            self.tabs
+                .values()
+                .find(|tab| tab.position == position)
+                .map(|tab| tab.index)
+        });
+        let target_tab_index = match target_tab_index {
+            Some(target_tab_index) => target_tab_index,
+            None => {
+                self.break_pane(client_id).with_context(err_context)?;
+                let active_tab_position = self
+                    .get_active_tab(client_id)
+                    .with_context(err_context)?
+                    .position;
+                if to_the_left && active_tab_position != source_tab_position {
+                    // new tabs are created last, so moving it right wraps it around to be first
+                    self.move_active_tab_right(client_id)
+                        .with_context(err_context)?;
+                }
+                return Ok(());
+            },
+        };
+        self.move_focused_pane_between_tabs(source_tab_index, target_tab_index, client_id)
+            .with_context(err_context)
     }
 
-    pub fn update_terminal_foreground_color(&mut self, foreground_color_instruction: String) {
-        if let Some(AnsiCode::RgbCode((r, g, b))) =
-            xparse_color(foreground_color_instruction.as_bytes())
+    /// Moves the focused pane of this client to the [`Tab`] at the given (1-based) position,
+    /// appending it to that tab's tiled panes or keeping it floating if it was floating.
+    pub fn move_pane_to_tab(&mut self, tab_position: usize, client_id: ClientId) -> Result<()> {
+        let err_context = || {
+            format!("failed to move pane to tab at position {tab_position} for client {client_id}")
+        };
+        let client_id = match self.client_id_or_first(client_id) {
+            Some(client_id) => client_id,
+            None => return Ok(()),
+        };
+
+        let source_tab_index = self
+            .get_active_tab(client_id)
+            .with_context(err_context)?
+            .index;
+        let target_tab_index = match self
+            .tabs
+            .values()
+            .find(|tab| tab.position + 1 == tab_position)
         {
-            let fg_palette_color = PaletteColor::Rgb((r, g, b));
-            self.terminal_emulator_colors.borrow_mut().fg = fg_palette_color;
+            Some(target_tab) => target_tab.index,
+            None => {
+                return Err(ZellijError::NoTabAtPosition {
+                    position: tab_position,
+                    tab_count: self.tabs.len(),
+                })
+                .with_context(err_context);
+            },
+        };
+        if target_tab_index == source_tab_index {
+            return Ok(());
         }
+        self.move_focused_pane_between_tabs(source_tab_index, target_tab_index, client_id)
+            .with_context(err_context)
     }
 
-    pub fn update_terminal_color_registers(&mut self, color_registers: Vec<(usize, String)>) {
-        let mut terminal_emulator_color_codes = self.terminal_emulator_color_codes.borrow_mut();
-        for (color_register, color_sequence) in color_registers {
-            terminal_emulator_color_codes.insert(color_register, color_sequence);
+    /// Tells the client that sent a request naming a tab that does not exist, other errors are
+    /// only logged.
+    fn report_missing_tab(&self, result: Result<()>, client_id: ClientId) -> Result<()> {
+        let err = match result {
+            Ok(()) => return Ok(()),
+            Err(err) => err,
+        };
+        match err.downcast_ref::<ZellijError>() {
+            Some(error @ ZellijError::NoTabAtPosition { .. }) => self
+                .bus
+                .senders
+                .send_to_server(ServerInstruction::Log(vec![error.to_string()], client_id))
+                .context("failed to report missing tab"),
+            _ => {
+                Err::<(), _>(err).non_fatal();
+                Ok(())
+            },
         }
     }
 
-    /// Renders this [`Screen`], which amounts to rendering its active [`Tab`].
-    pub fn render(&mut self) -> Result<()> {
-        let err_context = "failed to render screen";
+    /// Moves the focused pane of this client from one existing [`Tab`] to another and switches
+    /// the client to the tab it moved to, closing the source tab if the pane was all it had.
+    fn move_focused_pane_between_tabs(
+        &mut self,
+        source_tab_index: usize,
+        target_tab_index: usize,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || {
+            format!(
+                "failed to move pane from tab {source_tab_index} to tab {target_tab_index} for client {client_id}"
+            )
+        };
 
-        let mut output = Output::new(
-            self.sixel_image_store.clone(),
-            self.character_cell_size.clone(),
-        );
-        let mut tabs_to_close = vec![];
-        for (tab_index, tab) in &mut self.tabs {
-            if tab.has_selectable_tiled_panes() {
-                tab.render(&mut output).context(err_context)?;
-            } else if !tab.is_pending() {
-                tabs_to_close.push(*tab_index);
-            }
+        let source_tab = self
+            .tabs
+            .get_mut(&source_tab_index)
+            .with_context(err_context)?;
+        let focused_pane_is_last_tiled_pane = source_tab.focused_pane_is_last_tiled_pane(client_id);
+        if focused_pane_is_last_tiled_pane && source_tab.has_floating_panes() {
+            // leaving only floating panes behind would close them along with the tab
+            return Ok(());
         }
-        for tab_index in tabs_to_close {
-            self.close_tab_at_index(tab_index).context(err_context)?;
+        let pane_is_floating = source_tab
+            .get_active_pane_id(client_id)
+            .map(|pane_id| source_tab.is_floating_pane(pane_id))
+            .unwrap_or(false);
+        let target_tab = self
+            .tabs
+            .get_mut(&target_tab_index)
+            .with_context(err_context)?;
+        if !pane_is_floating && !target_tab.has_room_for_new_tiled_pane() {
+            log::error!("No room for pane in tab {}", target_tab_index);
+            return Ok(());
         }
-        if output.is_dirty() {
-            let serialized_output = output.serialize().context(err_context)?;
-            self.bus
-                .senders
-                .send_to_server(ServerInstruction::Render(Some(serialized_output)))
-                .context(err_context)
+        let (pane_id, pane, is_floating) = match self
+            .tabs
+            .get_mut(&source_tab_index)
+            .with_context(err_context)?
+            .extract_focused_pane(client_id)
+        {
+            Some(extracted_pane) => extracted_pane,
+            None => return Ok(()),
+        };
+        let target_tab_position = self
+            .tabs
+            .get(&target_tab_index)
+            .with_context(err_context)?
+            .position;
+        self.switch_active_tab(target_tab_position, None, client_id)
+            .with_context(err_context)?;
+        self.tabs
+            .get_mut(&target_tab_index)
+            .with_context(err_context)?
+            .add_pane_from_another_tab(pane_id, pane, is_floating, client_id)
+            .with_context(err_context)?;
+        if focused_pane_is_last_tiled_pane {
+            self.close_tab_at_index(source_tab_index)
+                .with_context(err_context)?;
         } else {
-            Ok(())
+            self.report_tab_state().with_context(err_context)?;
+            self.report_pane_state().with_context(err_context)?;
         }
+        Ok(())
     }
 
-    /// Returns a mutable reference to this [`Screen`]'s tabs.
-    pub fn get_tabs_mut(&mut self) -> &mut BTreeMap<usize, Tab> {
-        &mut self.tabs
-    }
+    pub fn toggle_overlay_tab(&mut self, tab_position: usize, client_id: ClientId) -> Result<()> {
+        let err_context = || {
+            format!(
+                "failed to toggle overlay of tab at position {tab_position} for client {client_id}"
+            )
+        };
+        let client_id = match self.client_id_or_first(client_id) {
+            Some(client_id) => client_id,
+            None => return Ok(()),
+        };
 
-    /// Returns an immutable reference to this [`Screen`]'s active [`Tab`].
-    pub fn get_active_tab(&self, client_id: ClientId) -> Result<&Tab> {
-        match self.active_tab_indices.get(&client_id) {
-            Some(tab) => self
-                .tabs
-                .get(tab)
-                .ok_or_else(|| anyhow!("active tab {} does not exist", tab)),
-            None => Err(anyhow!("active tab not found for client {:?}", client_id)),
+        let source_tab_index = match self
+            .tabs
+            .values()
+            .find(|tab| tab.position + 1 == tab_position)
+        {
+            Some(source_tab) => source_tab.index,
+            None => {
+                return Err(ZellijError::NoTabAtPosition {
+                    position: tab_position,
+                    tab_count: self.tabs.len(),
+                })
+                .with_context(err_context);
+            },
+        };
+        let toggled_off = self
+            .overlay_tab
+            .as_ref()
+            .map(|overlay_tab| overlay_tab.source_tab_index == source_tab_index)
+            .unwrap_or(false);
+        // only one tab is shown as an overlay at a time
+        self.hide_overlay_tab().with_context(err_context)?;
+        if toggled_off {
+            return Ok(());
         }
-    }
 
-    pub fn get_first_client_id(&self) -> Option<ClientId> {
-        self.active_tab_indices.keys().next().copied()
+        let target_tab_index = self
+            .get_active_tab(client_id)
+            .with_context(err_context)?
+            .index;
+        if target_tab_index == source_tab_index {
+            // the tab is already in view
+            return Ok(());
+        }
+        let (pane_id, pane) = match self
+            .tabs
+            .get_mut(&source_tab_index)
+            .with_context(err_context)?
+            .lend_first_pane()
+            .with_context(err_context)?
+        {
+            Some(lent_pane) => lent_pane,
+            None => return Ok(()),
+        };
+        self.tabs
+            .get_mut(&target_tab_index)
+            .with_context(err_context)?
+            .show_overlay_pane(pane_id, pane, client_id)
+            .with_context(err_context)?;
+        self.overlay_tab = Some(OverlayTab {
+            source_tab_index,
+            target_tab_index,
+            pane_id,
+        });
+        self.report_pane_state().with_context(err_context)
+    }
+
+    /// Places the pane shown with [`Screen::toggle_overlay_tab`] back in the tab it came from.
+    fn hide_overlay_tab(&mut self) -> Result<()> {
+        let err_context = || "failed to hide overlay tab";
+
+        let overlay_tab = match self.overlay_tab.take() {
+            Some(overlay_tab) => overlay_tab,
+            None => return Ok(()),
+        };
+        let pane = self
+            .tabs
+            .get_mut(&overlay_tab.target_tab_index)
+            .and_then(|target_tab| target_tab.hide_overlay_pane(overlay_tab.pane_id));
+        match (self.tabs.get_mut(&overlay_tab.source_tab_index), pane) {
+            (Some(source_tab), Some(pane)) => {
+                source_tab
+                    .return_lent_pane(pane)
+                    .with_context(err_context)?;
+            },
+            _ => {
+                log::error!(
+                    "Could not return overlay pane {:?} to tab {}",
+                    overlay_tab.pane_id,
+                    overlay_tab.source_tab_index
+                );
+            },
+        }
+        self.report_pane_state().with_context(err_context)
+    }
+
+    fn is_overlay_tab_pane(&self, pane_id: PaneId) -> bool {
+        self.overlay_tab
+            .as_ref()
+            .map(|overlay_tab| overlay_tab.pane_id == pane_id)
+            .unwrap_or(false)
+    }
+
+    /// Opens a new pane running the same command as the focused pane (with its cwd, env and hold
+    /// flags), or the default shell in the same cwd if the focused pane is not a command pane.
+    pub fn duplicate_pane(
+        &mut self,
+        direction: Option<Direction>,
+        should_float: bool,
+        default_shell: Option<TerminalAction>,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to duplicate focused pane for client {client_id}");
+        let client_id = match self.client_id_or_first(client_id) {
+            Some(client_id) => client_id,
+            None => return Ok(()),
+        };
+        let terminal_action = self
+            .get_active_tab(client_id)
+            .with_context(err_context)?
+            .get_active_pane_run_command(client_id)
+            .map(TerminalAction::RunCommand)
+            .or(default_shell);
+        // the pty thread fills in the cwd of the focused pane when the command has none
+        let pty_instr = match (direction, should_float) {
+            (Some(Direction::Left), false) | (Some(Direction::Right), false) => {
+                PtyInstruction::SpawnTerminalVertically(terminal_action, None, client_id)
+            },
+            (Some(Direction::Up), false) | (Some(Direction::Down), false) => {
+                PtyInstruction::SpawnTerminalHorizontally(terminal_action, None, client_id)
+            },
+            (None, _) | (_, true) => PtyInstruction::SpawnTerminal(
+                terminal_action,
+                Some(should_float),
+                None,
+                ClientOrTabIndex::ClientId(client_id),
+                None,
+            ),
+        };
+        self.bus
+            .senders
+            .send_to_pty(pty_instr)
+            .with_context(err_context)
+    }
+
+    /// The panes of all tabs in tab order, without their cwd which only the pty thread knows
+    fn pane_list(&self) -> Vec<PaneListEntry> {
+        let mut tabs: Vec<&Tab> = self.tabs.values().collect();
+        tabs.sort_by_key(|tab| tab.position);
+        tabs.iter()
+            .flat_map(|tab| {
+                tab.pane_infos()
+                    .into_iter()
+                    .filter(|pane_info| !pane_info.is_suppressed)
+                    .map(|pane_info| PaneListEntry {
+                        id: pane_info.id,
+                        is_plugin: pane_info.is_plugin,
+                        title: pane_info.title,
+                        tab_index: tab.position + 1,
+                        is_floating: pane_info.is_floating,
+                        is_focused: pane_info.is_focused,
+                        cwd: None,
+                        command: pane_info.terminal_command,
+                    })
+            })
+            .collect()
+    }
+
+    pub fn go_to_tab(&mut self, tab_index: usize, client_id: ClientId) -> Result<()> {
+        self.switch_active_tab(tab_index.saturating_sub(1), None, client_id)
+    }
+
+    pub fn go_to_tab_name(&mut self, name: String, client_id: ClientId) -> Result<bool> {
+        self.switch_active_tab_name(name, client_id)
+    }
+
+    pub fn go_to_tab_of_pane(&mut self, pane_id: PaneId, client_id: ClientId) -> Result<bool> {
+        // true => found and focused, false => not
+        let err_context = || format!("failed to go to tab of pane {pane_id:?}");
+        let tab_position_and_index = self
+            .tabs
+            .values()
+            .find(|tab| tab.get_all_pane_ids().contains(&pane_id))
+            .map(|tab| (tab.position, tab.index));
+        match tab_position_and_index {
+            Some((tab_position, tab_index)) => {
+                self.switch_active_tab(tab_position, None, client_id)
+                    .with_context(err_context)?;
+                self.tabs
+                    .get_mut(&tab_index)
+                    .with_context(err_context)?
+                    .focus_pane_with_id(pane_id, false, client_id)
+                    .with_context(err_context)?;
+                self.report_tab_state().with_context(err_context)?;
+                self.report_pane_state().with_context(err_context)?;
+                Ok(true)
+            },
+            None => Ok(false),
+        }
+    }
+
+    /// Focuses the pane with this id, which does nothing if it is not in the active tab (unless we
+    /// should switch to its tab) or doesn't exist.
+    pub fn focus_pane_with_id(
+        &mut self,
+        pane_id: PaneId,
+        should_switch_tab: bool,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to focus pane {pane_id:?}");
+        if should_switch_tab {
+            self.go_to_tab_of_pane(pane_id, client_id)
+                .with_context(err_context)?;
+        } else if let Ok(active_tab) = self.get_active_tab_mut(client_id) {
+            if active_tab.get_all_pane_ids().contains(&pane_id) {
+                active_tab
+                    .focus_pane_with_id(pane_id, false, client_id)
+                    .with_context(err_context)?;
+                self.report_pane_state().with_context(err_context)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Focuses the next pane whose command contains `command`, going through the panes of all
+    /// tabs ordered by tab position and then by pane id and wrapping around after the last one.
+    pub fn cycle_focus_by_command(&mut self, command: &str, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to cycle focus to panes running '{command}'");
+        let client_id = match self.client_id_or_first(client_id) {
+            Some(client_id) => client_id,
+            None => return Ok(()),
+        };
+        let mut tabs: Vec<&Tab> = self.tabs.values().collect();
+        tabs.sort_by_key(|tab| tab.position);
+        let matching_panes: Vec<(usize, PaneId)> = tabs
+            .iter()
+            .flat_map(|tab| {
+                tab.pane_ids_with_command(command)
+                    .into_iter()
+                    .map(|pane_id| (tab.position, pane_id))
+            })
+            .collect();
+        let focused_pane = self.get_active_tab(client_id).ok().and_then(|tab| {
+            tab.get_active_pane_id(client_id)
+                .map(|pane_id| (tab.position, pane_id))
+        });
+        let next_pane = matching_panes
+            .iter()
+            .find(|pane| focused_pane.map_or(true, |focused_pane| **pane > focused_pane))
+            .or_else(|| matching_panes.first())
+            .copied();
+        if let Some((_tab_position, pane_id)) = next_pane {
+            self.focus_pane_with_id(pane_id, true, client_id)
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+
+    /// Switches to the tab of the pane with this id and toggles it fullscreen, focusing it first.
+    /// Does nothing if there is no such pane.
+    pub fn toggle_pane_fullscreen(&mut self, pane_id: PaneId, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to toggle fullscreen for pane {pane_id:?}");
+        let client_id = match self.client_id_or_first(client_id) {
+            Some(client_id) => client_id,
+            None => return Ok(()),
+        };
+        let tab_position = self
+            .tabs
+            .values()
+            .find(|tab| tab.get_all_pane_ids().contains(&pane_id))
+            .map(|tab| tab.position);
+        if let Some(tab_position) = tab_position {
+            self.switch_active_tab(tab_position, None, client_id)
+                .with_context(err_context)?;
+            self.get_active_tab_mut(client_id)
+                .and_then(|tab| tab.toggle_pane_fullscreen(pane_id, client_id))
+                .with_context(err_context)?;
+            self.report_tab_state().with_context(err_context)?;
+            self.report_pane_state().with_context(err_context)?;
+        }
+        Ok(())
+    }
+
+    /// Focuses the first pane named `name`, looking only in the active tab unless `all_tabs` is
+    /// set (in which case tabs are searched in order). Does nothing if there is no such pane.
+    pub fn focus_pane_by_name(
+        &mut self,
+        name: &str,
+        all_tabs: bool,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to focus pane named '{name}'");
+        let client_id = match self.client_id_or_first(client_id) {
+            Some(client_id) => client_id,
+            None => return Ok(()),
+        };
+        let pane_id = if all_tabs {
+            let mut tabs: Vec<&Tab> = self.tabs.values().collect();
+            tabs.sort_by_key(|tab| tab.position);
+            tabs.iter()
+                .find_map(|tab| tab.pane_ids_with_name(name).first().copied())
+        } else {
+            self.get_active_tab(client_id)
+                .ok()
+                .and_then(|tab| tab.pane_ids_with_name(name).first().copied())
+        };
+        if let Some(pane_id) = pane_id {
+            self.focus_pane_with_id(pane_id, all_tabs, client_id)
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+
+    /// Rearranges the panes of the client's active tab according to the layout at `layout_path`.
+    ///
+    /// Pane positions are shared by everyone looking at a tab, so the layout is only applied if
+    /// no other client is currently viewing it.
+    pub fn set_client_layout(&mut self, layout_path: PathBuf, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to set layout {layout_path:?} for client {client_id}");
+        let client_id = match self.client_id_or_first(client_id) {
+            Some(client_id) => client_id,
+            None => return Ok(()),
+        };
+        let (path_to_raw_layout, raw_layout, swap_layouts) =
+            Layout::stringified_from_path_or_default(Some(&layout_path), None)
+                .map_err(|e| anyhow!("Failed to load layout: {:?}", e))
+                .with_context(err_context)?;
+        let layout = Layout::from_str(
+            &raw_layout,
+            path_to_raw_layout,
+            swap_layouts.as_ref().map(|(f, p)| (f.as_str(), p.as_str())),
+            None,
+        )
+        .map_err(|e| anyhow!("Failed to parse layout: {:?}", e))
+        .with_context(err_context)?;
+        let (tiled_panes_layout, floating_panes_layout) = match layout.tabs().into_iter().next() {
+            Some((_tab_name, tiled_panes_layout, floating_panes_layout)) => {
+                (tiled_panes_layout, floating_panes_layout)
+            },
+            None => layout.template.unwrap_or_default(),
+        };
+        let active_tab = self
+            .get_active_tab_mut(client_id)
+            .with_context(err_context)?;
+        if active_tab.has_other_connected_clients(client_id) {
+            return Err(anyhow!(
+                "other clients are viewing this tab and pane positions are shared between them"
+            ))
+            .with_context(err_context);
+        }
+        active_tab
+            .apply_client_layout(tiled_panes_layout, floating_panes_layout, client_id)
+            .with_context(err_context)
+    }
+
+    pub fn dump_layout(&self, file: Option<String>, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to dump layout for client {client_id}");
+        let layout_client_id = self.client_id_or_first(client_id);
+        let mut tabs: Vec<&Tab> = self.tabs.values().collect();
+        tabs.sort_by_key(|tab| tab.position);
+        let session_layout_metadata = SessionLayoutMetadata {
+            focused_tab_index: layout_client_id
+                .and_then(|layout_client_id| self.get_active_tab(layout_client_id).ok())
+                .map(|tab| tab.position),
+            tabs: tabs
+                .iter()
+                .map(|tab| tab.layout_metadata(layout_client_id.unwrap_or(client_id)))
+                .collect(),
+        };
+        self.bus
+            .senders
+            .send_to_pty(PtyInstruction::DumpLayout(
+                session_layout_metadata,
+                file,
+                client_id,
+            ))
+            .with_context(err_context)
+    }
+
+    pub fn dump_screen_to_client(
+        &mut self,
+        client_id: ClientId,
+        full: bool,
+        preserve_ansi: bool,
+        header: bool,
+    ) -> Result<()> {
+        let err_context = || format!("failed to dump screen to client {client_id}");
+        let dump_client_id = self.client_id_or_first(client_id);
+        let dump = dump_client_id
+            .and_then(|dump_client_id| {
+                self.get_active_tab_mut(dump_client_id)
+                    .ok()?
+                    .active_terminal_screen_dump(dump_client_id, full, preserve_ansi, header)
+            })
+            .unwrap_or_default();
+        self.bus
+            .senders
+            .send_to_server(ServerInstruction::Log(vec![dump], client_id))
+            .with_context(err_context)
+    }
+
+    pub fn report_search_match_count(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to report search match count to client {client_id}");
+        let search_client_id = self.client_id_or_first(client_id);
+        let match_count = search_client_id.and_then(|search_client_id| {
+            self.get_active_tab_mut(search_client_id)
+                .ok()?
+                .search_match_count(search_client_id)
+        });
+        match match_count {
+            Some(match_count) => self
+                .bus
+                .senders
+                .send_to_server(ServerInstruction::Log(
+                    vec![match_count.to_string()],
+                    client_id,
+                ))
+                .with_context(err_context),
+            None => Ok(()), // there is no active search
+        }
+    }
+
+    fn close_tab_at_index(&mut self, tab_index: usize) -> Result<()> {
+        let err_context = || format!("failed to close tab at index {tab_index:?}");
+
+        let tab_has_overlay = self
+            .overlay_tab
+            .as_ref()
+            .map(|o| o.source_tab_index == tab_index || o.target_tab_index == tab_index)
+            .unwrap_or(false);
+        if tab_has_overlay {
+            self.hide_overlay_tab().with_context(err_context)?;
+        }
+        let mut tab_to_close = self.tabs.remove(&tab_index).with_context(err_context)?;
+        let pane_ids = tab_to_close.get_all_pane_ids();
+        // below we don't check the result of sending the CloseTab instruction to the pty thread
+        // because this might be happening when the app is closing, at which point the pty thread
+        // has already closed and this would result in an error
+        self.bus
+            .senders
+            .send_to_pty(PtyInstruction::CloseTab(pane_ids))
+            .with_context(err_context)?;
+        if self.tabs.is_empty() {
+            self.active_tab_indices.clear();
+            self.bus
+                .senders
+                .send_to_server(ServerInstruction::Render(None))
+                .with_context(err_context)
+        } else {
+            let client_mode_infos_in_closed_tab = tab_to_close.drain_connected_clients(None);
+            self.move_clients_from_closed_tab(client_mode_infos_in_closed_tab)
+                .with_context(err_context)?;
+            let visible_tab_indices: HashSet<usize> =
+                self.active_tab_indices.values().copied().collect();
+            for t in self.tabs.values_mut() {
+                if visible_tab_indices.contains(&t.index) {
+                    t.set_force_render();
+                    t.visible(true).with_context(err_context)?;
+                }
+                if t.position > tab_to_close.position {
+                    t.position -= 1;
+                }
+            }
+            self.report_tab_state().with_context(err_context)?;
+            self.report_pane_state().with_context(err_context)?;
+            self.render().with_context(err_context)
+        }
+    }
+
+    // Closes the client_id's focused tab
+    pub fn close_tab(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to close tab for client {client_id:?}");
+
+        let client_id = if self.get_active_tab(client_id).is_ok() {
+            Some(client_id)
+        } else {
+            self.get_first_client_id()
+        };
+
+        match client_id {
+            Some(client_id) => {
+                let active_tab_index = *self
+                    .active_tab_indices
+                    .get(&client_id)
+                    .with_context(err_context)?;
+                if self
+                    .tabs
+                    .get(&active_tab_index)
+                    .map(|tab| tab.is_pinned())
+                    .unwrap_or(false)
+                {
+                    log::info!("Not closing tab {} because it is pinned", active_tab_index);
+                    return Ok(());
+                }
+                self.close_tab_at_index(active_tab_index)
+                    .with_context(err_context)
+            },
+            None => Ok(()),
+        }
+    }
+
+    pub fn pin_tab_at_position(&mut self, position: usize) {
+        if let Some(tab) = self.tabs.values_mut().find(|tab| tab.position == position) {
+            tab.set_pinned(true);
+        }
+    }
+
+    pub fn resize_to_screen(&mut self, new_screen_size: Size) -> Result<()> {
+        let err_context = || format!("failed to resize to screen size: {new_screen_size:#?}");
+
+        self.size = new_screen_size;
+        for tab in self.tabs.values_mut() {
+            tab.resize_whole_tab(new_screen_size)
+                .with_context(err_context)?;
+            tab.set_force_render();
+        }
+        self.report_pane_state().with_context(err_context)?;
+        self.render().with_context(err_context)
+    }
+
+    pub fn update_pixel_dimensions(&mut self, pixel_dimensions: PixelDimensions) {
+        self.pixel_dimensions.merge(pixel_dimensions);
+        if let Some(character_cell_size) = self.pixel_dimensions.character_cell_size {
+            *self.character_cell_size.borrow_mut() = Some(character_cell_size);
+        } else if let Some(text_area_size) = self.pixel_dimensions.text_area_size {
+            let character_cell_size_height = text_area_size.height / self.size.rows;
+            let character_cell_size_width = text_area_size.width / self.size.cols;
+            let character_cell_size = SizeInPixels {
+                height: character_cell_size_height,
+                width: character_cell_size_width,
+            };
+            *self.character_cell_size.borrow_mut() = Some(character_cell_size);
+        }
+    }
+
+    pub fn update_terminal_background_color(&mut self, background_color_instruction: String) {
+        if let Some(AnsiCode::RgbCode((r, g, b))) =
+            xparse_color(background_color_instruction.as_bytes())
+        {
+            let bg_palette_color = PaletteColor::Rgb((r, g, b));
+            self.terminal_emulator_colors.borrow_mut().bg = bg_palette_color;
+        }
+    }
+
+    pub fn update_terminal_foreground_color(&mut self, foreground_color_instruction: String) {
+        if let Some(AnsiCode::RgbCode((r, g, b))) =
+            xparse_color(foreground_color_instruction.as_bytes())
+        {
+            let fg_palette_color = PaletteColor::Rgb((r, g, b));
+            self.terminal_emulator_colors.borrow_mut().fg = fg_palette_color;
+        }
+    }
+
+    pub fn update_terminal_color_registers(&mut self, color_registers: Vec<(usize, String)>) {
+        let mut terminal_emulator_color_codes = self.terminal_emulator_color_codes.borrow_mut();
+        for (color_register, color_sequence) in color_registers {
+            terminal_emulator_color_codes.insert(color_register, color_sequence);
+        }
+    }
+
+    /// Renders this [`Screen`], which amounts to rendering its active [`Tab`].
+    pub fn render(&mut self) -> Result<()> {
+        let err_context = "failed to render screen";
+
+        let mut output = Output::new(
+            self.sixel_image_store.clone(),
+            self.character_cell_size.clone(),
+        );
+        if !self.overlay.overlay_stack.is_empty() {
+            // this goes before the tabs' own instructions so that they get to place the cursor
+            // after the overlays were drawn
+            let overlays = self
+                .overlay
+                .generate_overlay(self.size)
+                .context(err_context)?;
+            let connected_clients: Vec<ClientId> =
+                self.connected_clients.borrow().iter().copied().collect();
+            output.add_post_vte_instruction_to_multiple_clients(
+                connected_clients.into_iter(),
+                &overlays,
+            );
+        }
+        let mut tabs_to_close = vec![];
+        for (tab_index, tab) in &mut self.tabs {
+            if tab.has_selectable_tiled_panes() || tab.is_empty() {
+                tab.render(&mut output).context(err_context)?;
+            } else if !tab.is_pending() {
+                tabs_to_close.push(*tab_index);
+            }
+        }
+        for tab_index in tabs_to_close {
+            self.close_tab_at_index(tab_index).context(err_context)?;
+        }
+        if output.is_dirty() {
+            let serialized_output = output.serialize().context(err_context)?;
+            self.bus
+                .senders
+                .send_to_server(ServerInstruction::Render(Some(serialized_output)))
+                .context(err_context)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns a mutable reference to this [`Screen`]'s tabs.
+    pub fn get_tabs_mut(&mut self) -> &mut BTreeMap<usize, Tab> {
+        &mut self.tabs
+    }
+
+    /// Returns an immutable reference to this [`Screen`]'s active [`Tab`].
+    pub fn get_active_tab(&self, client_id: ClientId) -> Result<&Tab> {
+        match self.active_tab_indices.get(&client_id) {
+            Some(tab) => self
+                .tabs
+                .get(tab)
+                .ok_or_else(|| anyhow!("active tab {} does not exist", tab)),
+            None => Err(anyhow!("active tab not found for client {:?}", client_id)),
+        }
+    }
+
+    pub fn get_first_client_id(&self) -> Option<ClientId> {
+        self.active_tab_indices.keys().next().copied()
+    }
+
+    /// Cli clients are not attached to any tab, so they act on behalf of the first attached
+    /// client instead.
+    fn client_id_or_first(&self, client_id: ClientId) -> Option<ClientId> {
+        if self.active_tab_indices.contains_key(&client_id) {
+            Some(client_id)
+        } else {
+            self.get_first_client_id()
+        }
     }
 
     /// Returns an immutable reference to this [`Screen`]'s previous active [`Tab`].
@@ -1010,6 +2110,79 @@ impl Screen {
         &mut self.overlay.overlay_stack
     }
 
+    pub fn set_repeat_count(&mut self, count: u32, client_id: ClientId) {
+        let client_id = self.client_id_or_first(client_id).unwrap_or(client_id);
+        if count > 1 {
+            self.repeat_counts.insert(client_id, count);
+        } else {
+            self.repeat_counts.remove(&client_id);
+        }
+    }
+
+    fn take_repeat_count(&mut self, client_id: ClientId) -> u32 {
+        let client_id = self.client_id_or_first(client_id).unwrap_or(client_id);
+        self.repeat_counts.remove(&client_id).unwrap_or(1)
+    }
+
+    pub fn show_mode_help(&mut self, mode_help: ModeHelp) {
+        self.hide_mode_help();
+        self.overlay
+            .overlay_stack
+            .push(Overlay::new(OverlayType::ModeHelp(mode_help)));
+    }
+
+    pub fn mode_help_is_visible(&self) -> bool {
+        self.overlay
+            .overlay_stack
+            .iter()
+            .any(|overlay| matches!(overlay.overlay_type, OverlayType::ModeHelp(..)))
+    }
+
+    pub fn prompt_is_visible(&self) -> bool {
+        self.overlay
+            .overlay_stack
+            .iter()
+            .any(|overlay| matches!(overlay.overlay_type, OverlayType::Prompt(..)))
+    }
+
+    /// Dismisses the topmost overlay and carries out what the prompt in it asked for, if anything
+    pub fn answer_prompt(&mut self, confirmed: bool) -> Result<()> {
+        let overlay = self.get_active_overlays_mut().pop();
+        let instruction = overlay.and_then(|overlay| match confirmed {
+            true => overlay.prompt_confirm(),
+            false => overlay.prompt_deny(),
+        });
+        // redraw whatever the prompt was covering
+        for tab in self.tabs.values_mut() {
+            tab.set_force_render();
+        }
+        match instruction.map(|instruction| *instruction) {
+            Some(PromptInstruction::Server(instruction)) => self
+                .bus
+                .senders
+                .send_to_server(instruction)
+                .context("failed to answer prompt"),
+            Some(PromptInstruction::Screen(instruction)) => self
+                .bus
+                .senders
+                .send_to_screen(instruction)
+                .context("failed to answer prompt"),
+            None => Ok(()),
+        }
+    }
+
+    pub fn hide_mode_help(&mut self) {
+        if self.mode_help_is_visible() {
+            self.overlay
+                .overlay_stack
+                .retain(|overlay| !matches!(overlay.overlay_type, OverlayType::ModeHelp(..)));
+            // redraw whatever the mode help was covering
+            for tab in self.tabs.values_mut() {
+                tab.set_force_render();
+            }
+        }
+    }
+
     /// Returns a mutable reference to this [`Screen`]'s indexed [`Tab`].
     pub fn get_indexed_tab_mut(&mut self, tab_index: usize) -> Option<&mut Tab> {
         self.get_tabs_mut().get_mut(&tab_index)
@@ -1036,7 +2209,7 @@ impl Screen {
         let tab_name = tab_name.unwrap_or_else(|| String::new());
 
         let position = self.tabs.len();
-        let tab = Tab::new(
+        let mut tab = Tab::new(
             tab_index,
             position,
             tab_name,
@@ -1058,11 +2231,16 @@ impl Screen {
             self.session_is_mirrored,
             client_id,
             self.copy_options.clone(),
+            self.clipboard_info.clone(),
+            self.floating_pane_geometries.clone(),
             self.terminal_emulator_colors.clone(),
             self.terminal_emulator_color_codes.clone(),
             swap_layouts,
             self.debug,
         );
+        if self.ruler_is_visible {
+            tab.set_ruler_visibility(true);
+        }
         self.tabs.insert(tab_index, tab);
         Ok(())
     }
@@ -1122,6 +2300,7 @@ impl Screen {
         };
 
         // apply the layout to the new tab
+        let zen_mode_is_active = self.pane_frames_before_zen_mode.is_some();
         self.tabs
             .get_mut(&tab_index)
             .context("couldn't find tab with index {tab_index}")
@@ -1134,6 +2313,9 @@ impl Screen {
                     new_plugin_ids,
                     client_id,
                 )?;
+                if zen_mode_is_active {
+                    tab.hide_chrome()?;
+                }
                 tab.update_input_modes()?;
                 tab.visible(true)?;
                 if let Some(drained_clients) = drained_clients {
@@ -1236,6 +2418,7 @@ impl Screen {
                     is_swap_layout_dirty,
                 });
             }
+            tab_data.sort_by_key(|tab_info| tab_info.position);
             plugin_updates.push((None, Some(*client_id), Event::TabUpdate(tab_data)));
         }
         self.bus
@@ -1278,9 +2461,6 @@ impl Screen {
                 match self.get_active_tab_mut(client_id) {
                     Ok(active_tab) => {
                         match s {
-                            "\0" => {
-                                active_tab.name = String::new();
-                            },
                             "\u{007F}" | "\u{0008}" => {
                                 // delete and backspace keys
                                 active_tab.name.pop();
@@ -1303,6 +2483,29 @@ impl Screen {
             None => Ok(()),
         }
     }
+    pub fn clear_active_tab_name(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to clear active tab name for client {client_id}");
+
+        let client_id = if self.get_active_tab(client_id).is_ok() {
+            Some(client_id)
+        } else {
+            self.get_first_client_id()
+        };
+        match client_id {
+            Some(client_id) => {
+                match self.get_active_tab_mut(client_id) {
+                    Ok(active_tab) => {
+                        active_tab.name = String::new();
+                        self.report_tab_state().with_context(err_context)?;
+                    },
+                    Err(err) => Err::<(), _>(err).with_context(err_context).non_fatal(),
+                };
+                Ok(())
+            },
+            None => Ok(()),
+        }
+    }
+
     pub fn undo_active_rename_tab(&mut self, client_id: ClientId) -> Result<()> {
         let err_context = || format!("failed to undo active tab rename for client {}", client_id);
 
@@ -1329,7 +2532,7 @@ impl Screen {
         }
     }
 
-    pub fn change_mode(&mut self, mode_info: ModeInfo, client_id: ClientId) -> Result<()> {
+    pub fn change_mode(&mut self, mut mode_info: ModeInfo, client_id: ClientId) -> Result<()> {
         let previous_mode = self
             .mode_info
             .get(&client_id)
@@ -1361,6 +2564,11 @@ impl Screen {
             }
         }
 
+        if previous_mode != mode_info.mode {
+            self.repeat_counts.remove(&client_id);
+            self.report_mode(mode_info.mode);
+        }
+
         if mode_info.mode == InputMode::RenameTab {
             if let Ok(active_tab) = self.get_active_tab_mut(client_id) {
                 active_tab.prev_name = active_tab.name.clone();
@@ -1377,6 +2585,19 @@ impl Screen {
             }
         }
 
+        // the mode info we get from the route thread does not know about the session sync
+        if self.sync_session_is_active {
+            mode_info.is_sync_session_active = true;
+            self.bus
+                .senders
+                .send_to_plugin(PluginInstruction::Update(vec![(
+                    None,
+                    Some(client_id),
+                    Event::ModeUpdate(mode_info.clone()),
+                )]))
+                .with_context(err_context)?;
+        }
+
         self.style = mode_info.style;
         self.mode_info.insert(client_id, mode_info.clone());
         for tab in self.tabs.values_mut() {
@@ -1391,6 +2612,319 @@ impl Screen {
 
         Ok(())
     }
+    pub fn toggle_sync_session(&mut self) -> Result<()> {
+        let err_context = || "failed to toggle session sync";
+
+        self.sync_session_is_active = !self.sync_session_is_active;
+        self.default_mode_info.is_sync_session_active = self.sync_session_is_active;
+        let mut plugin_updates = vec![];
+        for (client_id, mode_info) in self.mode_info.iter_mut() {
+            mode_info.is_sync_session_active = self.sync_session_is_active;
+            for tab in self.tabs.values_mut() {
+                tab.change_mode_info(mode_info.clone(), *client_id);
+            }
+            plugin_updates.push((None, Some(*client_id), Event::ModeUpdate(mode_info.clone())));
+        }
+        self.bus
+            .senders
+            .send_to_plugin(PluginInstruction::Update(plugin_updates))
+            .with_context(err_context)
+    }
+    fn write_to_all_terminals_in_session(
+        &mut self,
+        bytes: Vec<u8>,
+        client_id: ClientId,
+    ) -> Result<bool> {
+        // returns true if a UI update should be triggered, like writing to the panes of a tab
+        let mut should_trigger_ui_change = false;
+        for tab in self.tabs.values_mut() {
+            if tab
+                .write_to_terminals_on_current_tab(bytes.clone(), client_id)
+                .context("failed to write to all terminals in session")?
+            {
+                should_trigger_ui_change = true;
+            }
+        }
+        Ok(should_trigger_ui_change)
+    }
+    /// Toggle zen mode in all tabs: hide the tab bar, status bar and pane frames together, or
+    /// restore them exactly as they were before entering it
+    pub fn toggle_zen_mode(&mut self) -> Result<()> {
+        let err_context = || "failed to toggle zen mode";
+        match self.pane_frames_before_zen_mode.take() {
+            Some(draw_pane_frames) => {
+                self.draw_pane_frames = draw_pane_frames;
+                for tab in self.tabs.values_mut() {
+                    tab.show_chrome().with_context(err_context)?;
+                    tab.set_pane_frames(draw_pane_frames);
+                }
+            },
+            None => {
+                self.pane_frames_before_zen_mode = Some(self.draw_pane_frames);
+                self.draw_pane_frames = false;
+                for tab in self.tabs.values_mut() {
+                    tab.hide_chrome().with_context(err_context)?;
+                    tab.set_pane_frames(false);
+                }
+            },
+        }
+        Ok(())
+    }
+    /// Write the name of `mode` as a line to the mode report socket, if one was set.
+    ///
+    /// Reporting is best effort: if nothing is listening on the socket (or it went away) the
+    /// report is silently dropped.
+    fn report_mode(&self, mode: InputMode) {
+        if let Some(socket_path) = &self.mode_report_socket {
+            if let Ok(mut stream) = UnixStream::connect(socket_path) {
+                let _ = stream.set_nonblocking(true);
+                let _ = stream.write_all(format!("{:?}\n", mode).to_lowercase().as_bytes());
+            }
+        }
+    }
+    pub fn rename_session(&mut self, session_name: String) -> Result<()> {
+        let err_context = || format!("failed to rename session to {session_name}");
+
+        self.default_mode_info.session_name = Some(session_name.clone());
+        let connected_client_ids: Vec<ClientId> = self.active_tab_indices.keys().copied().collect();
+        for client_id in connected_client_ids {
+            let mut mode_info = self
+                .mode_info
+                .get(&client_id)
+                .unwrap_or(&self.default_mode_info)
+                .clone();
+            mode_info.session_name = Some(session_name.clone());
+            self.bus
+                .senders
+                .send_to_plugin(PluginInstruction::Update(vec![(
+                    None,
+                    Some(client_id),
+                    Event::ModeUpdate(mode_info.clone()),
+                )]))
+                .with_context(err_context)?;
+            self.change_mode(mode_info, client_id)
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+    pub fn change_mode_timed(
+        &mut self,
+        mode_info: ModeInfo,
+        timeout_ms: u64,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || {
+            format!(
+                "failed to switch to mode '{:?}' for {timeout_ms}ms for client {client_id}",
+                mode_info.mode
+            )
+        };
+
+        // chaining timed switches should revert to the mode we were in before the first one
+        let revert_to = match self.timed_modes.remove(&client_id) {
+            Some(timed_mode) => timed_mode.revert_to,
+            None => self
+                .mode_info
+                .get(&client_id)
+                .unwrap_or(&self.default_mode_info)
+                .clone(),
+        };
+        self.bus
+            .senders
+            .send_to_plugin(PluginInstruction::Update(vec![(
+                None,
+                Some(client_id),
+                Event::ModeUpdate(mode_info.clone()),
+            )]))
+            .with_context(err_context)?;
+        self.change_mode(mode_info, client_id)
+            .with_context(err_context)?;
+        self.timed_modes.insert(
+            client_id,
+            TimedMode {
+                revert_to,
+                timeout_ms,
+                deadline: Instant::now() + Duration::from_millis(timeout_ms),
+            },
+        );
+        self.bus
+            .senders
+            .send_to_background_jobs(BackgroundJob::ModeTimeout(client_id, timeout_ms))
+            .with_context(err_context)
+    }
+    pub fn reset_mode_timer(&mut self, client_id: ClientId) {
+        match self.timed_mode_keypress {
+            TimedModeKeypress::Cancel => {
+                self.timed_modes.remove(&client_id);
+            },
+            TimedModeKeypress::Extend => {
+                if let Some(timed_mode) = self.timed_modes.get_mut(&client_id) {
+                    timed_mode.deadline =
+                        Instant::now() + Duration::from_millis(timed_mode.timeout_ms);
+                }
+            },
+        }
+    }
+    pub fn detach_when_idle(&mut self, timeout_secs: u64, client_id: ClientId) -> Result<()> {
+        // cli clients are not attached to the session, so they detach everyone who is
+        let client_ids = if self.active_tab_indices.contains_key(&client_id) {
+            vec![client_id]
+        } else {
+            self.connected_clients.borrow().iter().copied().collect()
+        };
+        let busy_pane_count = self.panes_with_running_process_count();
+        if busy_pane_count > 0 {
+            let message = format!(
+                "Waiting for {} pane(s) to finish running before detaching (at most {}s)",
+                busy_pane_count, timeout_secs
+            );
+            log::info!("{}", message);
+            self.bus
+                .senders
+                .send_to_server(ServerInstruction::Log(vec![message], client_id))
+                .context("failed to report pending detach")?;
+        }
+        self.pending_detach = Some(PendingDetach {
+            client_ids,
+            requested_by: client_id,
+            deadline: Instant::now() + Duration::from_secs(timeout_secs),
+        });
+        self.check_pending_detach()
+    }
+    pub fn check_pending_detach(&mut self) -> Result<()> {
+        let err_context = || "failed to check pending detach";
+
+        let busy_pane_count = self.panes_with_running_process_count();
+        let timed_out = match &self.pending_detach {
+            Some(pending_detach) => Instant::now() >= pending_detach.deadline,
+            // the detach already happened
+            None => return Ok(()),
+        };
+        if busy_pane_count > 0 && !timed_out {
+            return self
+                .bus
+                .senders
+                .send_to_background_jobs(BackgroundJob::PollPendingDetach(
+                    PENDING_DETACH_POLL_INTERVAL_MS,
+                ))
+                .with_context(err_context);
+        }
+        if let Some(pending_detach) = self.pending_detach.take() {
+            let message = if busy_pane_count > 0 {
+                format!(
+                    "Timed out waiting for {} pane(s) to finish running, detaching anyway",
+                    busy_pane_count
+                )
+            } else {
+                "All commands finished running, detaching".to_owned()
+            };
+            log::info!("{}", message);
+            self.bus
+                .senders
+                .send_to_server(ServerInstruction::Log(
+                    vec![message],
+                    pending_detach.requested_by,
+                ))
+                .with_context(err_context)?;
+            self.bus
+                .senders
+                .send_to_server(ServerInstruction::DetachSession(pending_detach.client_ids))
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+    fn panes_with_running_process_count(&self) -> usize {
+        self.tabs
+            .values()
+            .map(|tab| tab.panes_with_running_process_count())
+            .sum()
+    }
+    pub fn mode_timeout_elapsed(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to revert timed mode for client {client_id}");
+
+        let remaining = match self.timed_modes.get(&client_id) {
+            Some(timed_mode) => timed_mode
+                .deadline
+                .saturating_duration_since(Instant::now()),
+            // the timer was cancelled or the mode was changed in the meantime
+            None => return Ok(()),
+        };
+        if !remaining.is_zero() {
+            // the timer was extended, check back once the new deadline is reached
+            return self
+                .bus
+                .senders
+                .send_to_background_jobs(BackgroundJob::ModeTimeout(
+                    client_id,
+                    remaining.as_millis() as u64,
+                ))
+                .with_context(err_context);
+        }
+        if let Some(timed_mode) = self.timed_modes.remove(&client_id) {
+            self.bus
+                .senders
+                .send_to_plugin(PluginInstruction::Update(vec![(
+                    None,
+                    Some(client_id),
+                    Event::ModeUpdate(timed_mode.revert_to.clone()),
+                )]))
+                .with_context(err_context)?;
+            self.change_mode(timed_mode.revert_to, client_id)
+                .with_context(err_context)?;
+            self.render().with_context(err_context)?;
+        }
+        Ok(())
+    }
+    pub fn enter_resize_loop(&mut self, mode_info: ModeInfo, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to enter resize loop for client {client_id}");
+
+        // re-entering the loop should still return to the mode we were in before the first time
+        let revert_to = match self.resize_loops.remove(&client_id) {
+            Some(revert_to) => revert_to,
+            None => self
+                .mode_info
+                .get(&client_id)
+                .unwrap_or(&self.default_mode_info)
+                .clone(),
+        };
+        self.timed_modes.remove(&client_id);
+        self.bus
+            .senders
+            .send_to_plugin(PluginInstruction::Update(vec![(
+                None,
+                Some(client_id),
+                Event::ModeUpdate(mode_info.clone()),
+            )]))
+            .with_context(err_context)?;
+        self.change_mode(mode_info, client_id)
+            .with_context(err_context)?;
+        self.resize_loops.insert(client_id, revert_to);
+        Ok(())
+    }
+    pub fn exit_resize_loop(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to exit resize loop for client {client_id}");
+
+        if let Some(revert_to) = self.resize_loops.remove(&client_id) {
+            self.bus
+                .senders
+                .send_to_plugin(PluginInstruction::Update(vec![(
+                    None,
+                    Some(client_id),
+                    Event::ModeUpdate(revert_to.clone()),
+                )]))
+                .with_context(err_context)?;
+            self.change_mode(revert_to, client_id)
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+    fn resize_steps(&self, client_id: ClientId) -> usize {
+        if self.resize_loops.contains_key(&client_id) {
+            self.resize_loop_step
+        } else {
+            1
+        }
+    }
     pub fn change_mode_for_all_clients(&mut self, mode_info: ModeInfo) -> Result<()> {
         let err_context = || {
             format!(
@@ -1475,6 +3009,12 @@ impl Screen {
         self.report_pane_state().with_context(err_context)?;
         Ok(())
     }
+    pub fn toggle_default_split_direction(&mut self) {
+        self.default_split_direction = match self.default_split_direction {
+            Some(SplitDirection::Vertical) => Some(SplitDirection::Horizontal),
+            _ => Some(SplitDirection::Vertical),
+        };
+    }
     pub fn toggle_tab(&mut self, client_id: ClientId) -> Result<()> {
         let tab = self
             .get_previous_tab(client_id)
@@ -1564,6 +3104,8 @@ pub(crate) fn screen_thread_main(
         auto_layout,
         session_is_mirrored,
         copy_options,
+        config_options.timed_mode_keypress.unwrap_or_default(),
+        config_options.resize_loop_step.unwrap_or(1).max(1),
         debug,
     );
 
@@ -1578,6 +3120,17 @@ pub(crate) fn screen_thread_main(
             .context("failed to receive event on channel")?;
         err_ctx.add_call(ContextType::Screen((&event).into()));
 
+        if let Some(client_id) = event.count_aware_client_id() {
+            // we handle the instruction itself below, and queue up the rest of the repetitions
+            for _ in 1..screen.take_repeat_count(client_id) {
+                screen
+                    .bus
+                    .senders
+                    .send_to_screen(event.clone())
+                    .context("failed to repeat screen instruction")?;
+            }
+        }
+
         match event {
             ScreenInstruction::PtyBytes(pid, vte_bytes) => {
                 let all_tabs = screen.get_tabs_mut();
@@ -1611,16 +3164,66 @@ pub(crate) fn screen_thread_main(
                 should_float,
                 hold_for_command,
                 client_or_tab_index,
+                floating_pane_placement,
             ) => {
+                // an explicit placement wins over the geometry this pane had when last closed
+                let restored_geom = initial_pane_title
+                    .as_ref()
+                    .filter(|_| should_float == Some(true) && floating_pane_placement.is_none())
+                    .and_then(|pane_name| {
+                        screen
+                            .floating_pane_geometries
+                            .borrow()
+                            .get(pane_name)
+                            .copied()
+                    });
                 match client_or_tab_index {
                     ClientOrTabIndex::ClientId(client_id) => {
-                        active_tab_and_connected_client_id!(screen, client_id, |tab: &mut Tab,
-                                                            client_id: ClientId| tab .new_pane(pid,
-                                                                                               initial_pane_title,
-                                                                                               should_float,
-                                                                                               None,
-                                                                                               Some(client_id)),
-                                                                                               ?);
+                        let split_direction = screen.default_split_direction.filter(|_| {
+                            should_float != Some(true) && matches!(pid, PaneId::Terminal(_))
+                        });
+                        active_tab_and_connected_client_id!(
+                            screen,
+                            client_id,
+                            |tab: &mut Tab, client_id: ClientId| match split_direction {
+                                Some(SplitDirection::Vertical)
+                                    if !tab.are_floating_panes_visible() && !tab.is_empty() =>
+                                {
+                                    tab.vertical_split(pid, initial_pane_title, client_id)
+                                },
+                                Some(SplitDirection::Horizontal)
+                                    if !tab.are_floating_panes_visible() && !tab.is_empty() =>
+                                {
+                                    tab.horizontal_split(pid, initial_pane_title, client_id)
+                                },
+                                _ => tab.new_pane(
+                                    pid,
+                                    initial_pane_title,
+                                    should_float,
+                                    None,
+                                    Some(client_id)
+                                ),
+                            },
+                            ?
+                        );
+                        if let Some(floating_pane_placement) = floating_pane_placement {
+                            active_tab_and_connected_client_id!(
+                                screen,
+                                client_id,
+                                |tab: &mut Tab, _client_id: ClientId| tab
+                                    .snap_floating_pane(pid, &floating_pane_placement),
+                                ?
+                            );
+                        }
+                        if let Some(restored_geom) = restored_geom {
+                            active_tab_and_connected_client_id!(
+                                screen,
+                                client_id,
+                                |tab: &mut Tab, _client_id: ClientId| tab
+                                    .restore_floating_pane_geom(pid, restored_geom),
+                                ?
+                            );
+                        }
                         if let Some(hold_for_command) = hold_for_command {
                             let is_first_run = true;
                             active_tab_and_connected_client_id!(
@@ -1644,6 +3247,12 @@ pub(crate) fn screen_thread_main(
                                 None,
                                 None,
                             )?;
+                            if let Some(floating_pane_placement) = floating_pane_placement {
+                                active_tab.snap_floating_pane(pid, &floating_pane_placement)?;
+                            }
+                            if let Some(restored_geom) = restored_geom {
+                                active_tab.restore_floating_pane_geom(pid, restored_geom)?;
+                            }
                             if let Some(hold_for_command) = hold_for_command {
                                 let is_first_run = true;
                                 active_tab.hold_pane(pid, None, is_first_run, hold_for_command);
@@ -1659,6 +3268,15 @@ pub(crate) fn screen_thread_main(
 
                 screen.render()?;
             },
+            ScreenInstruction::RemovePaneFrame(pane_id) => {
+                for tab in screen.tabs.values_mut() {
+                    if tab.has_pane_with_pid(&pane_id) {
+                        tab.remove_pane_frame(pane_id)?;
+                        break;
+                    }
+                }
+                screen.render()?;
+            },
             ScreenInstruction::OpenInPlaceEditor(pid, client_id) => {
                 active_tab!(screen, client_id, |tab: &mut Tab| tab
                     .suppress_active_pane(pid, client_id), ?);
@@ -1677,16 +3295,111 @@ pub(crate) fn screen_thread_main(
 
                 screen.render()?;
             },
+            ScreenInstruction::EmbedAllFloatingPanes(client_id) => {
+                active_tab_and_connected_client_id!(screen, client_id, |tab: &mut Tab, client_id: ClientId| tab
+                    .embed_all_floating_panes(client_id), ?);
+                screen.unblock_input()?;
+                screen.report_tab_state()?;
+                screen.report_pane_state()?;
+
+                screen.render()?;
+            },
+            ScreenInstruction::FloatAllTiledPanes(client_id) => {
+                active_tab_and_connected_client_id!(screen, client_id, |tab: &mut Tab, client_id: ClientId| tab
+                    .float_all_tiled_panes(client_id), ?);
+                screen.unblock_input()?;
+                screen.report_tab_state()?;
+                screen.report_pane_state()?;
+
+                screen.render()?;
+            },
+            ScreenInstruction::PeekPaneFloating(client_id) => {
+                active_tab_and_connected_client_id!(screen, client_id, |tab: &mut Tab, client_id: ClientId| tab
+                    .peek_pane_floating(client_id), ?);
+                screen.unblock_input()?;
+                screen.report_tab_state()?;
+                screen.report_pane_state()?;
+
+                screen.render()?;
+            },
+            ScreenInstruction::TogglePanePinned(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.toggle_active_pane_pinned(client_id)
+                );
+                screen.unblock_input()?;
+                screen.render()?;
+            },
+            ScreenInstruction::BalancePanesAxis(vertical, client_id) => {
+                active_tab_and_connected_client_id!(screen, client_id, |tab: &mut Tab, _client_id: ClientId| tab
+                    .balance_panes_axis(vertical), ?);
+                screen.unblock_input()?;
+                screen.report_pane_state()?;
+                screen.render()?;
+            },
+            ScreenInstruction::BalancePanes(client_id) => {
+                active_tab_and_connected_client_id!(screen, client_id, |tab: &mut Tab, _client_id: ClientId| tab
+                    .balance_panes(), ?);
+                screen.unblock_input()?;
+                screen.report_pane_state()?;
+                screen.render()?;
+            },
+            ScreenInstruction::ToggleDefaultSplitDirection => {
+                screen.toggle_default_split_direction();
+            },
+            ScreenInstruction::FocusMostRecentlyActivePane(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .focus_most_recently_active_pane(client_id)
+                );
+                screen.unblock_input()?;
+                screen.report_tab_state()?;
+                screen.report_pane_state()?;
+                screen.render()?;
+            },
             ScreenInstruction::ToggleFloatingPanes(client_id, default_shell) => {
                 active_tab_and_connected_client_id!(screen, client_id, |tab: &mut Tab, client_id: ClientId| tab
                     .toggle_floating_panes(Some(client_id), default_shell), ?);
                 screen.unblock_input()?;
-                screen.report_tab_state()?; // update tabs so that the ui indication will be send to the plugins
+                screen.report_tab_state()?; // update tabs so that the ui indication will be send to the plugins
+                screen.report_pane_state()?;
+
+                screen.render()?;
+            },
+            ScreenInstruction::HorizontalSplit(
+                pid,
+                initial_pane_title,
+                hold_for_command,
+                client_id,
+            ) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.horizontal_split(pid, initial_pane_title, client_id),
+                    ?
+                );
+                if let Some(hold_for_command) = hold_for_command {
+                    let is_first_run = true;
+                    active_tab_and_connected_client_id!(
+                        screen,
+                        client_id,
+                        |tab: &mut Tab, _client_id: ClientId| tab.hold_pane(
+                            pid,
+                            None,
+                            is_first_run,
+                            hold_for_command
+                        )
+                    );
+                }
+                screen.unblock_input()?;
+                screen.report_tab_state()?;
                 screen.report_pane_state()?;
-
                 screen.render()?;
             },
-            ScreenInstruction::HorizontalSplit(
+            ScreenInstruction::VerticalSplit(
                 pid,
                 initial_pane_title,
                 hold_for_command,
@@ -1695,7 +3408,7 @@ pub(crate) fn screen_thread_main(
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab.horizontal_split(pid, initial_pane_title, client_id),
+                    |tab: &mut Tab, client_id: ClientId| tab.vertical_split(pid, initial_pane_title, client_id),
                     ?
                 );
                 if let Some(hold_for_command) = hold_for_command {
@@ -1716,16 +3429,24 @@ pub(crate) fn screen_thread_main(
                 screen.report_pane_state()?;
                 screen.render()?;
             },
-            ScreenInstruction::VerticalSplit(
+            ScreenInstruction::NewPaneAtFraction(
                 pid,
                 initial_pane_title,
                 hold_for_command,
+                direction,
+                fraction,
                 client_id,
             ) => {
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab.vertical_split(pid, initial_pane_title, client_id),
+                    |tab: &mut Tab, client_id: ClientId| tab.new_pane_at_fraction(
+                        pid,
+                        initial_pane_title,
+                        direction,
+                        fraction,
+                        client_id
+                    ),
                     ?
                 );
                 if let Some(hold_for_command) = hold_for_command {
@@ -1746,6 +3467,28 @@ pub(crate) fn screen_thread_main(
                 screen.report_pane_state()?;
                 screen.render()?;
             },
+            ScreenInstruction::WriteCharacter(bytes, _client_id) if screen.prompt_is_visible() => {
+                // a visible prompt takes the next key as its answer: y or <ENTER> to confirm,
+                // anything else to deny
+                let confirmed = matches!(bytes.as_slice(), [b'y'] | [b'Y'] | [b'\r']);
+                screen.answer_prompt(confirmed)?;
+                screen.render()?;
+            },
+            ScreenInstruction::WriteCharacter(bytes, _client_id)
+                if bytes == [27] && screen.mode_help_is_visible() =>
+            {
+                // <ESC> dismisses the mode help rather than being written to the terminal
+                screen.hide_mode_help();
+                screen.render()?;
+            },
+            ScreenInstruction::WriteCharacter(bytes, client_id)
+                if screen.sync_session_is_active =>
+            {
+                if screen.write_to_all_terminals_in_session(bytes, client_id)? {
+                    screen.report_tab_state()?;
+                    screen.report_pane_state()?;
+                }
+            },
             ScreenInstruction::WriteCharacter(bytes, client_id) => {
                 let mut state_changed = false;
                 active_tab_and_connected_client_id!(
@@ -1768,11 +3511,58 @@ pub(crate) fn screen_thread_main(
                     screen.report_pane_state()?;
                 }
             },
+            ScreenInstruction::WriteToPanesMatching(command_filter, bytes, client_id) => {
+                let mut state_changed = false;
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| {
+                        let write_result =
+                            tab.write_to_panes_matching(&command_filter, bytes, client_id);
+                        if let Ok(true) = write_result {
+                            state_changed = true;
+                        }
+                        write_result
+                    },
+                    ?
+                );
+                if state_changed {
+                    screen.report_tab_state()?;
+                    screen.report_pane_state()?;
+                }
+            },
             ScreenInstruction::Resize(client_id, strategy) => {
+                for _ in 0..screen.resize_steps(client_id) {
+                    active_tab_and_connected_client_id!(
+                        screen,
+                        client_id,
+                        |tab: &mut Tab, client_id: ClientId| tab.resize(client_id, strategy),
+                        ?
+                    );
+                }
+                screen.unblock_input()?;
+                screen.render()?;
+                screen.report_tab_state()?;
+                screen.report_pane_state()?;
+            },
+            ScreenInstruction::SetPaneSize(cols, rows, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.set_pane_size(client_id, cols, rows),
+                    ?
+                );
+                screen.unblock_input()?;
+                screen.render()?;
+                screen.report_tab_state()?;
+                screen.report_pane_state()?;
+            },
+            ScreenInstruction::ResizePaneToPercent(width, height, client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab.resize(client_id, strategy),
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .set_pane_size_in_percent(client_id, width, height),
                     ?
                 );
                 screen.unblock_input()?;
@@ -1780,6 +3570,18 @@ pub(crate) fn screen_thread_main(
                 screen.report_tab_state()?;
                 screen.report_pane_state()?;
             },
+            ScreenInstruction::SetPaneSizeConstraints(size_constraints, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .set_pane_size_constraints(client_id, size_constraints),
+                    ?
+                );
+                screen.unblock_input()?;
+                screen.render()?;
+                screen.report_pane_state()?;
+            },
             ScreenInstruction::SwitchFocus(client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
@@ -1809,6 +3611,27 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
                 screen.report_pane_state()?;
             },
+            ScreenInstruction::FocusNextFloatingPane(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.focus_next_floating_pane(client_id)
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+                screen.report_pane_state()?;
+            },
+            ScreenInstruction::FocusPreviousFloatingPane(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .focus_previous_floating_pane(client_id)
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+                screen.report_pane_state()?;
+            },
             ScreenInstruction::MoveFocusLeft(client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
@@ -1820,6 +3643,39 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
                 screen.report_pane_state()?;
             },
+            ScreenInstruction::FocusPaneWithId(pane_id, should_switch_tab, client_id) => {
+                screen.focus_pane_with_id(pane_id, should_switch_tab, client_id)?;
+                screen.unblock_input()?;
+                screen.render()?;
+            },
+            ScreenInstruction::FocusPaneAtIndex(index, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .focus_pane_at_index(index, client_id), ?
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+                screen.report_pane_state()?;
+            },
+            ScreenInstruction::CycleFocusByCommand(command, client_id) => {
+                screen.cycle_focus_by_command(&command, client_id)?;
+                screen.unblock_input()?;
+                screen.render()?;
+            },
+            ScreenInstruction::FocusPaneByName(name, all_tabs, client_id) => {
+                screen.focus_pane_by_name(&name, all_tabs, client_id)?;
+                screen.unblock_input()?;
+                screen.render()?;
+            },
+            ScreenInstruction::SetClientLayout(layout_path, client_id) => {
+                screen.set_client_layout(layout_path, client_id).non_fatal();
+                screen.unblock_input()?;
+                screen.render()?;
+                screen.report_tab_state()?;
+                screen.report_pane_state()?;
+            },
             ScreenInstruction::MoveFocusLeftOrPreviousTab(client_id) => {
                 screen.move_focus_left_or_previous_tab(client_id)?;
                 screen.unblock_input()?;
@@ -1877,20 +3733,84 @@ pub(crate) fn screen_thread_main(
                 screen.render()?;
                 screen.unblock_input()?;
             },
-            ScreenInstruction::DumpScreen(file, client_id, full) => {
+            ScreenInstruction::ClearScrollback(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .clear_active_terminal_scrollback(client_id),
+                    ?
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::SetScrollbackLines(lines, client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab.dump_active_terminal_screen(
-                        Some(file.to_string()),
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .set_active_terminal_scrollback_lines(lines, client_id),
+                    ?
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::DumpScreen(file, client_id, full, preserve_ansi, header) => {
+                if file == "-" {
+                    screen.dump_screen_to_client(client_id, full, preserve_ansi, header)?;
+                } else {
+                    active_tab_and_connected_client_id!(
+                        screen,
                         client_id,
-                        full
-                    ),
+                        |tab: &mut Tab, client_id: ClientId| tab.dump_active_terminal_screen(
+                            Some(file.to_string()),
+                            client_id,
+                            full,
+                            preserve_ansi,
+                            header
+                        ),
+                        ?
+                    );
+                    screen.render()?;
+                    screen.unblock_input()?;
+                }
+            },
+            ScreenInstruction::DumpScreenSvg(file, client_id, full) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .dump_active_terminal_screen_svg(Some(file.to_string()), client_id, full),
+                    ?
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::DumpScreenJsonl(file, client_id, full) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .dump_active_terminal_screen_jsonl(Some(file.to_string()), client_id, full),
+                    ?
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::DumpScreenHtml(file, client_id, full) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .dump_active_terminal_screen_html(Some(file.to_string()), client_id, full),
                     ?
                 );
                 screen.render()?;
                 screen.unblock_input()?;
             },
+            ScreenInstruction::DumpLayout(file, client_id) => {
+                screen.dump_layout(file, client_id)?;
+            },
             ScreenInstruction::EditScrollback(client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
@@ -1910,6 +3830,16 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
                 screen.render()?;
             },
+            ScreenInstruction::ScrollUpBy(lines, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .scroll_active_terminal_up_by(lines, client_id)
+                );
+                screen.unblock_input()?;
+                screen.render()?;
+            },
             ScreenInstruction::MovePane(client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
@@ -1921,6 +3851,29 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
                 screen.report_pane_state()?;
             },
+            ScreenInstruction::MoveFloatingPaneTo(x, y, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.move_floating_pane_to(client_id, x, y),
+                    ?
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+                screen.report_pane_state()?;
+            },
+            ScreenInstruction::ResizeFloatingPaneTo(cols, rows, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .resize_floating_pane_to(client_id, cols, rows),
+                    ?
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+                screen.report_pane_state()?;
+            },
             ScreenInstruction::MovePaneBackwards(client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
@@ -1932,6 +3885,17 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
                 screen.report_pane_state()?;
             },
+            ScreenInstruction::RotatePanes(backwards, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, _client_id: ClientId| tab.rotate_panes(backwards)
+                );
+                screen.report_tab_state()?;
+                screen.render()?;
+                screen.unblock_input()?;
+                screen.report_pane_state()?;
+            },
             ScreenInstruction::MovePaneDown(client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
@@ -1995,6 +3959,16 @@ pub(crate) fn screen_thread_main(
                 screen.render()?;
                 screen.unblock_input()?;
             },
+            ScreenInstruction::ScrollDownBy(lines, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .scroll_active_terminal_down_by(lines, client_id), ?
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+            },
             ScreenInstruction::ScrollDownAt(point, client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
@@ -2025,6 +3999,16 @@ pub(crate) fn screen_thread_main(
                 screen.render()?;
                 screen.unblock_input()?;
             },
+            ScreenInstruction::ScrollToLine(line, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .scroll_active_terminal_to_line(line, client_id), ?
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+            },
             ScreenInstruction::PageScrollUp(client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
@@ -2045,6 +4029,36 @@ pub(crate) fn screen_thread_main(
                 screen.render()?;
                 screen.unblock_input()?;
             },
+            ScreenInstruction::MoveToPreviousPrompt(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .scroll_active_terminal_to_previous_prompt(client_id)
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::MoveToNextPrompt(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .scroll_active_terminal_to_next_prompt(client_id), ?
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::TogglePaneLineWrap(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .toggle_active_pane_line_wrap(client_id)
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+            },
             ScreenInstruction::HalfPageScrollUp(client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
@@ -2076,16 +4090,48 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
             },
             ScreenInstruction::CloseFocusedPane(client_id) => {
-                active_tab_and_connected_client_id!(
-                    screen,
-                    client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab.close_focused_pane(client_id), ?
-                );
+                let connected_client_id = screen.client_id_or_first(client_id).unwrap_or(client_id);
+                let focused_pane_is_overlay = screen
+                    .get_active_tab(connected_client_id)
+                    .ok()
+                    .and_then(|tab| tab.get_active_pane_id(connected_client_id))
+                    .map(|pane_id| screen.is_overlay_tab_pane(pane_id))
+                    .unwrap_or(false);
+                if focused_pane_is_overlay {
+                    // the pane belongs to another tab, so we only hide it
+                    screen.hide_overlay_tab()?;
+                } else {
+                    active_tab_and_connected_client_id!(
+                        screen,
+                        client_id,
+                        |tab: &mut Tab, client_id: ClientId| tab.close_focused_pane(client_id), ?
+                    );
+                }
                 screen.report_tab_state()?;
                 screen.render()?;
                 screen.unblock_input()?;
                 screen.report_pane_state()?;
             },
+            ScreenInstruction::CloseFocusedPaneSmart(client_id) => {
+                let connected_client_id = screen.client_id_or_first(client_id).unwrap_or(client_id);
+                let has_running_process = screen
+                    .get_active_tab(connected_client_id)
+                    .map(|tab| tab.active_pane_has_running_process(connected_client_id))
+                    .unwrap_or(false);
+                if has_running_process {
+                    screen
+                        .get_active_overlays_mut()
+                        .push(generate_close_pane_prompt(client_id));
+                    screen.render()?;
+                    screen.unblock_input()?;
+                } else {
+                    screen
+                        .bus
+                        .senders
+                        .send_to_screen(ScreenInstruction::CloseFocusedPane(client_id))
+                        .context("failed to close focused pane")?;
+                }
+            },
             ScreenInstruction::SetSelectable(id, selectable, tab_index) => {
                 screen.get_indexed_tab_mut(tab_index).map_or_else(
                     || {
@@ -2102,6 +4148,13 @@ pub(crate) fn screen_thread_main(
                 screen.report_pane_state()?;
             },
             ScreenInstruction::ClosePane(id, client_id) => {
+                let client_id = if screen.is_overlay_tab_pane(id) {
+                    // place the pane back in its own tab first and close it there
+                    screen.hide_overlay_tab()?;
+                    None
+                } else {
+                    client_id
+                };
                 match client_id {
                     Some(client_id) => {
                         active_tab!(screen, client_id, |tab: &mut Tab| tab.close_pane(
@@ -2154,6 +4207,15 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
                 screen.report_pane_state()?;
             },
+            ScreenInstruction::RestartWatchedCommand(id) => {
+                for tab in screen.tabs.values_mut() {
+                    if tab.get_all_pane_ids().contains(&id) {
+                        tab.restart_watched_command(id)?;
+                        break;
+                    }
+                }
+                screen.render()?;
+            },
             ScreenInstruction::UpdatePaneName(c, client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
@@ -2164,6 +4226,16 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
                 screen.report_pane_state()?;
             },
+            ScreenInstruction::ClearPaneName(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.clear_active_pane_name(client_id), ?
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+                screen.report_pane_state()?;
+            },
             ScreenInstruction::UndoRenamePane(client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
@@ -2185,6 +4257,11 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
                 screen.report_pane_state()?;
             },
+            ScreenInstruction::TogglePaneFullscreen(pane_id, client_id) => {
+                screen.toggle_pane_fullscreen(pane_id, client_id)?;
+                screen.render()?;
+                screen.unblock_input()?;
+            },
             ScreenInstruction::TogglePaneFrames => {
                 screen.draw_pane_frames = !screen.draw_pane_frames;
                 for tab in screen.tabs.values_mut() {
@@ -2194,6 +4271,30 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
                 screen.report_pane_state()?;
             },
+            ScreenInstruction::ToggleFocusedPaneFrame(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.toggle_active_pane_frame(client_id),
+                    ?
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::ToggleRuler => {
+                screen.ruler_is_visible = !screen.ruler_is_visible;
+                for tab in screen.tabs.values_mut() {
+                    tab.set_ruler_visibility(screen.ruler_is_visible);
+                }
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::ToggleZenMode => {
+                screen.toggle_zen_mode()?;
+                screen.render()?;
+                screen.unblock_input()?;
+                screen.report_pane_state()?;
+            },
             ScreenInstruction::SwitchTabNext(client_id) => {
                 screen.switch_tab_next(None, client_id)?;
                 screen.unblock_input()?;
@@ -2204,6 +4305,45 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
                 screen.render()?;
             },
+            ScreenInstruction::MoveTabLeft(client_id) => {
+                screen.move_active_tab_left(client_id)?;
+                screen.render()?;
+            },
+            ScreenInstruction::MoveTabRight(client_id) => {
+                screen.move_active_tab_right(client_id)?;
+                screen.render()?;
+            },
+            ScreenInstruction::MoveTabToIndex(tab_position, client_id) => {
+                screen.move_active_tab_to_position(tab_position, client_id)?;
+                screen.render()?;
+            },
+            ScreenInstruction::BreakPane(client_id) => {
+                screen.break_pane(client_id)?;
+                screen.unblock_input()?;
+                screen.render()?;
+            },
+            ScreenInstruction::BreakPaneLeft(client_id) => {
+                screen.break_pane_to_adjacent_tab(client_id, true)?;
+                screen.unblock_input()?;
+                screen.render()?;
+            },
+            ScreenInstruction::BreakPaneRight(client_id) => {
+                screen.break_pane_to_adjacent_tab(client_id, false)?;
+                screen.unblock_input()?;
+                screen.render()?;
+            },
+            ScreenInstruction::MovePaneToTab(tab_position, client_id) => {
+                let result = screen.move_pane_to_tab(tab_position as usize, client_id);
+                screen.report_missing_tab(result, client_id)?;
+                screen.unblock_input()?;
+                screen.render()?;
+            },
+            ScreenInstruction::ToggleOverlayTab(tab_position, client_id) => {
+                let result = screen.toggle_overlay_tab(tab_position as usize, client_id);
+                screen.report_missing_tab(result, client_id)?;
+                screen.unblock_input()?;
+                screen.render()?;
+            },
             ScreenInstruction::CloseTab(client_id) => {
                 screen.close_tab(client_id)?;
                 screen.unblock_input()?;
@@ -2216,22 +4356,29 @@ pub(crate) fn screen_thread_main(
                 floating_panes_layout,
                 tab_name,
                 swap_layouts,
+                empty,
                 client_id,
             ) => {
-                let tab_index = screen.get_new_tab_index();
-                pending_tab_ids.insert(tab_index);
-                screen.new_tab(tab_index, swap_layouts, tab_name.clone(), client_id)?;
-                screen
-                    .bus
-                    .senders
-                    .send_to_plugin(PluginInstruction::NewTab(
-                        cwd,
-                        default_shell,
-                        layout,
-                        floating_panes_layout,
-                        tab_index,
-                        client_id,
-                    ))?;
+                if empty {
+                    screen.new_empty_tab(tab_name, client_id)?;
+                    screen.unblock_input()?;
+                    screen.render()?;
+                } else {
+                    let tab_index = screen.get_new_tab_index();
+                    pending_tab_ids.insert(tab_index);
+                    screen.new_tab(tab_index, swap_layouts, tab_name.clone(), client_id)?;
+                    screen
+                        .bus
+                        .senders
+                        .send_to_plugin(PluginInstruction::NewTab(
+                            cwd,
+                            default_shell,
+                            layout,
+                            floating_panes_layout,
+                            tab_index,
+                            client_id,
+                        ))?;
+                }
             },
             ScreenInstruction::ApplyLayout(
                 layout,
@@ -2289,6 +4436,11 @@ pub(crate) fn screen_thread_main(
                     },
                 }
             },
+            ScreenInstruction::GoToTabOfPane(pane_id, client_id) => {
+                screen.go_to_tab_of_pane(pane_id, client_id)?;
+                screen.unblock_input()?;
+                screen.render()?;
+            },
             ScreenInstruction::GoToTabName(
                 tab_name,
                 swap_layouts,
@@ -2333,6 +4485,11 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
                 screen.render()?;
             },
+            ScreenInstruction::ClearTabName(client_id) => {
+                screen.clear_active_tab_name(client_id)?;
+                screen.unblock_input()?;
+                screen.render()?;
+            },
             ScreenInstruction::UndoRenameTab(client_id) => {
                 screen.undo_active_rename_tab(client_id)?;
                 screen.unblock_input()?;
@@ -2356,10 +4513,64 @@ pub(crate) fn screen_thread_main(
                 screen.update_terminal_color_registers(color_registers);
             },
             ScreenInstruction::ChangeMode(mode_info, client_id) => {
+                // an explicit mode switch supersedes any pending timed one or resize loop
+                screen.timed_modes.remove(&client_id);
+                screen.resize_loops.remove(&client_id);
                 screen.change_mode(mode_info, client_id)?;
                 screen.render()?;
                 screen.unblock_input()?;
             },
+            ScreenInstruction::ChangeModeTimed(mode_info, timeout_ms, client_id) => {
+                // like SwitchMode, a timed switch from the cli applies to all connected clients
+                let client_ids: Vec<ClientId> =
+                    if screen.active_tab_indices.contains_key(&client_id) {
+                        vec![client_id]
+                    } else {
+                        screen.active_tab_indices.keys().copied().collect()
+                    };
+                for client_id in client_ids {
+                    screen.change_mode_timed(mode_info.clone(), timeout_ms, client_id)?;
+                }
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::EnterResizeLoop(mode_info, client_id) => {
+                // like SwitchMode, a resize loop entered from the cli applies to all connected
+                // clients
+                let client_ids: Vec<ClientId> =
+                    if screen.active_tab_indices.contains_key(&client_id) {
+                        vec![client_id]
+                    } else {
+                        screen.active_tab_indices.keys().copied().collect()
+                    };
+                for client_id in client_ids {
+                    screen.enter_resize_loop(mode_info.clone(), client_id)?;
+                }
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::ExitResizeLoop(client_id) => {
+                screen.exit_resize_loop(client_id)?;
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::ResetModeTimer(client_id) => {
+                screen.reset_mode_timer(client_id);
+            },
+            ScreenInstruction::SetModeReportSocket(socket_path) => {
+                screen.mode_report_socket = Some(socket_path);
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::ModeTimeoutElapsed(client_id) => {
+                screen.mode_timeout_elapsed(client_id)?;
+            },
+            ScreenInstruction::DetachWhenIdle(timeout_secs, client_id) => {
+                screen.detach_when_idle(timeout_secs, client_id)?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::CheckPendingDetach => {
+                screen.check_pending_detach()?;
+            },
             ScreenInstruction::ChangeModeForAllClients(mode_info) => {
                 screen.change_mode_for_all_clients(mode_info)?;
                 screen.render()?;
@@ -2375,6 +4586,41 @@ pub(crate) fn screen_thread_main(
                 screen.render()?;
                 screen.unblock_input()?;
             },
+            ScreenInstruction::TogglePinTab(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, _client_id: ClientId| tab.set_pinned(!tab.is_pinned())
+                );
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::PinTab(position) => {
+                screen.pin_tab_at_position(position);
+            },
+            ScreenInstruction::ToggleActiveSyncSession => {
+                screen.toggle_sync_session()?;
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::DuplicatePane(direction, should_float, default_shell, client_id) => {
+                if let Err(e) =
+                    screen.duplicate_pane(direction, should_float, default_shell, client_id)
+                {
+                    Err::<(), _>(e).non_fatal();
+                    screen.unblock_input()?;
+                }
+            },
+            ScreenInstruction::RestartPaneCommand(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .restart_active_pane_command(client_id),
+                    ?
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+            },
             ScreenInstruction::LeftClick(point, client_id) => {
                 active_tab!(screen, client_id, |tab: &mut Tab| tab
                     .handle_left_click(&point, client_id), ?);
@@ -2383,6 +4629,18 @@ pub(crate) fn screen_thread_main(
                 screen.render()?;
                 screen.unblock_input()?;
             },
+            ScreenInstruction::LeftDoubleClick(point, client_id) => {
+                active_tab!(screen, client_id, |tab: &mut Tab| tab
+                    .handle_left_double_click(&point, client_id), ?);
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::LeftTripleClick(point, client_id) => {
+                active_tab!(screen, client_id, |tab: &mut Tab| tab
+                    .handle_left_triple_click(&point, client_id), ?);
+                screen.render()?;
+                screen.unblock_input()?;
+            },
             ScreenInstruction::RightClick(point, client_id) => {
                 active_tab!(screen, client_id, |tab: &mut Tab| tab
                     .handle_right_click(&point, client_id), ?);
@@ -2435,6 +4693,85 @@ pub(crate) fn screen_thread_main(
                     .copy_selection(client_id), ?);
                 screen.render()?;
             },
+            ScreenInstruction::CopyNormalized(client_id) => {
+                active_tab!(screen, client_id, |tab: &mut Tab| tab
+                    .copy_selection_normalized(client_id), ?);
+                screen.render()?;
+            },
+            ScreenInstruction::SelectAll(viewport_only, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.select_all(viewport_only, client_id)
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::CopyUrlUnderCursor(position, client_id) => {
+                active_tab!(screen, client_id, |tab: &mut Tab| tab
+                    .copy_url_at(&position, client_id), ?);
+                screen.render()?;
+            },
+            ScreenInstruction::OpenUrlUnderCursor(position, client_id) => {
+                // a url that cannot be opened should not take the session down with it
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .open_url_at(&position, client_id)
+                        .non_fatal()
+                );
+            },
+            ScreenInstruction::CopyLastCommandOutput(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .copy_last_command_output(client_id), ?
+                );
+                screen.render()?;
+            },
+            ScreenInstruction::CopyThrough(command, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .copy_selection_through(&command, client_id),
+                    ?
+                );
+            },
+            ScreenInstruction::CopyFilteredText(filtered_text, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .copy_filtered_text(&filtered_text, client_id),
+                    ?
+                );
+                screen.render()?;
+            },
+            ScreenInstruction::SearchToQuickfix(needle, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .search_to_quickfix(needle, client_id),
+                    ?
+                );
+                screen.render()?;
+            },
+            ScreenInstruction::SearchJumpToMatch(match_index, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .search_jump_to_match(match_index, client_id)
+                );
+                screen.render()?;
+            },
+            ScreenInstruction::QuerySearchMatchCount(client_id) => {
+                screen.report_search_match_count(client_id)?;
+            },
             ScreenInstruction::Exit => {
                 break;
             },
@@ -2443,6 +4780,17 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
                 screen.render()?;
             },
+            ScreenInstruction::RestoreTabFocus(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.restore_focus(client_id),
+                    ?
+                );
+                screen.unblock_input()?;
+                screen.report_pane_state()?;
+                screen.render()?;
+            },
             ScreenInstruction::AddClient(client_id) => {
                 screen.add_client(client_id)?;
                 screen.report_tab_state()?;
@@ -2464,20 +4812,28 @@ pub(crate) fn screen_thread_main(
                 screen.render()?;
                 screen.unblock_input()?;
             },
+            ScreenInstruction::ResetFloatingPaneGeometry(pane_name) => {
+                screen
+                    .floating_pane_geometries
+                    .borrow_mut()
+                    .remove(&pane_name);
+            },
+            ScreenInstruction::SetRepeatCount(count, client_id) => {
+                screen.set_repeat_count(count, client_id);
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::ShowModeHelp(mode, keybinds, _client_id) => {
+                screen.show_mode_help(ModeHelp::new(mode, keybinds));
+                screen.render()?;
+                screen.unblock_input()?;
+            },
             ScreenInstruction::ConfirmPrompt(_client_id) => {
-                let overlay = screen.get_active_overlays_mut().pop();
-                let instruction = overlay.and_then(|o| o.prompt_confirm());
-                if let Some(instruction) = instruction {
-                    screen
-                        .bus
-                        .senders
-                        .send_to_server(*instruction)
-                        .context("failed to confirm prompt")?;
-                }
+                screen.answer_prompt(true)?;
+                screen.render()?;
                 screen.unblock_input()?;
             },
             ScreenInstruction::DenyPrompt(_client_id) => {
-                screen.get_active_overlays_mut().pop();
+                screen.answer_prompt(false)?;
                 screen.render()?;
                 screen.unblock_input()?;
             },
@@ -2534,6 +4890,24 @@ pub(crate) fn screen_thread_main(
                 screen.render()?;
                 screen.unblock_input()?;
             },
+            ScreenInstruction::SearchToggleRegex(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.toggle_search_regex(client_id)
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::SearchToggleVisibleOnly(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.toggle_search_visible_only(client_id)
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+            },
             ScreenInstruction::AddRedPaneFrameColorOverride(pane_ids, error_text) => {
                 let all_tabs = screen.get_tabs_mut();
                 for pane_id in pane_ids {
@@ -2582,6 +4956,56 @@ pub(crate) fn screen_thread_main(
                 screen.report_pane_state()?;
                 screen.unblock_input()?;
             },
+            ScreenInstruction::GoToSwapLayout(index, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .go_to_swap_layout(index, Some(client_id)),
+                    ?
+                );
+                screen.render()?;
+                screen.report_tab_state()?;
+                screen.report_pane_state()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::ApplySwapLayoutByName(name, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .apply_swap_layout_by_name(&name, Some(client_id)),
+                    ?
+                );
+                screen.render()?;
+                screen.report_tab_state()?;
+                screen.report_pane_state()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::RenameSession(session_name) => {
+                screen.rename_session(session_name)?;
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::QueryClipboardInfo(client_id) => {
+                // if nothing was copied yet, we still let the client know where copies will go
+                let clipboard_info =
+                    screen
+                        .clipboard_info
+                        .borrow()
+                        .clone()
+                        .unwrap_or_else(|| ClipboardInfo {
+                            destination: screen.copy_options.copy_destination(),
+                            bytes: 0,
+                            lines: 0,
+                        });
+                let clipboard_info = serde_json::to_string(&clipboard_info)
+                    .context("failed to serialize clipboard info")?;
+                screen
+                    .bus
+                    .senders
+                    .send_to_server(ServerInstruction::Log(vec![clipboard_info], client_id))?;
+            },
             ScreenInstruction::QueryTabNames(client_id) => {
                 let tab_names = screen
                     .get_tabs_mut()
@@ -2593,6 +5017,15 @@ pub(crate) fn screen_thread_main(
                     .senders
                     .send_to_server(ServerInstruction::Log(tab_names, client_id))?;
             },
+            ScreenInstruction::QueryPaneList(format, client_id) => {
+                // the pty thread knows the child processes of the panes, so it fills in their cwd
+                // before printing the list
+                let pane_list = screen.pane_list();
+                screen
+                    .bus
+                    .senders
+                    .send_to_pty(PtyInstruction::ListPanes(pane_list, format, client_id))?;
+            },
             ScreenInstruction::NewTiledPluginPane(run_plugin_location, pane_title, client_id) => {
                 let tab_index = screen.active_tab_indices.values().next().unwrap_or(&1);
                 let size = Size::default();
@@ -2600,6 +5033,7 @@ pub(crate) fn screen_thread_main(
                 let run_plugin = RunPlugin {
                     _allow_exec_host_cmd: false,
                     location: run_plugin_location,
+                    configuration: Default::default(),
                 };
                 screen.bus.senders.send_to_plugin(PluginInstruction::Load(
                     should_float,
@@ -2623,6 +5057,7 @@ pub(crate) fn screen_thread_main(
                 let run_plugin = RunPlugin {
                     _allow_exec_host_cmd: false,
                     location: run_plugin_location,
+                    configuration: Default::default(),
                 };
                 screen.bus.senders.send_to_plugin(PluginInstruction::Load(
                     should_float,
@@ -0,0 +1,227 @@
+//! A snapshot of the tabs and panes of a running session, from which we build a [`Layout`] that
+//! recreates it.
+use crate::panes::PaneId;
+use std::path::PathBuf;
+use zellij_utils::{
+    input::layout::{
+        FloatingPaneLayout, Layout, PercentOrFixed, Run, SplitDirection, SplitSize, TiledPaneLayout,
+    },
+    pane_size::{Dimension, PaneGeom},
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct SessionLayoutMetadata {
+    pub tabs: Vec<TabLayoutMetadata>,
+    pub focused_tab_index: Option<usize>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct TabLayoutMetadata {
+    pub name: Option<String>,
+    pub is_pinned: bool,
+    pub tiled_panes: Vec<PaneLayoutMetadata>,
+    pub floating_panes: Vec<PaneLayoutMetadata>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PaneLayoutMetadata {
+    pub id: PaneId,
+    // relative to the tab's viewport
+    pub geom: PaneGeom,
+    pub run: Option<Run>,
+    pub is_borderless: bool,
+    pub is_focused: bool,
+    pub is_pinned: bool,
+}
+
+impl SessionLayoutMetadata {
+    /// Fill in the current working directory of terminal panes that don't run a command in a
+    /// specific one (eg. shells)
+    pub fn update_terminal_cwds(&mut self, get_cwd: impl Fn(u32) -> Option<PathBuf>) {
+        let all_panes = self.tabs.iter_mut().flat_map(|tab| {
+            tab.tiled_panes
+                .iter_mut()
+                .chain(tab.floating_panes.iter_mut())
+        });
+        for pane in all_panes {
+            let terminal_id = match pane.id {
+                PaneId::Terminal(terminal_id) => terminal_id,
+                PaneId::Plugin(..) => continue,
+            };
+            match &mut pane.run {
+                Some(Run::Command(run_command)) if run_command.cwd.is_none() => {
+                    run_command.cwd = get_cwd(terminal_id);
+                },
+                Some(Run::Cwd(..)) | None => {
+                    if let Some(cwd) = get_cwd(terminal_id) {
+                        pane.run = Some(Run::Cwd(cwd));
+                    }
+                },
+                _ => {},
+            }
+        }
+    }
+}
+
+impl From<SessionLayoutMetadata> for Layout {
+    fn from(session_layout_metadata: SessionLayoutMetadata) -> Self {
+        let pinned_tab_indices = session_layout_metadata
+            .tabs
+            .iter()
+            .enumerate()
+            .filter(|(_, tab)| tab.is_pinned)
+            .map(|(index, _)| index)
+            .collect();
+        let tabs = session_layout_metadata
+            .tabs
+            .into_iter()
+            .map(|tab| {
+                let tiled_panes = match tiled_pane_layout(&tab.tiled_panes) {
+                    tiled_pane_layout if tiled_pane_layout.children.is_empty() => TiledPaneLayout {
+                        children: vec![tiled_pane_layout],
+                        ..Default::default()
+                    },
+                    tiled_pane_layout => tiled_pane_layout,
+                };
+                let floating_panes = tab
+                    .floating_panes
+                    .iter()
+                    .map(floating_pane_layout)
+                    .collect();
+                (tab.name, tiled_panes, floating_panes)
+            })
+            .collect();
+        Layout {
+            tabs,
+            focused_tab_index: session_layout_metadata.focused_tab_index,
+            pinned_tab_indices,
+            ..Default::default()
+        }
+    }
+}
+
+// Recreate the splits that produced these pane geometries by recursively cutting them into
+// columns or rows wherever no pane crosses the cut
+fn tiled_pane_layout(panes: &[PaneLayoutMetadata]) -> TiledPaneLayout {
+    if panes.len() == 1 {
+        return TiledPaneLayout {
+            run: panes[0].run.clone(),
+            borderless: panes[0].is_borderless,
+            focus: if panes[0].is_focused {
+                Some(true)
+            } else {
+                None
+            },
+            ..Default::default()
+        };
+    }
+    for split_direction in [SplitDirection::Vertical, SplitDirection::Horizontal] {
+        let groups = split_panes(panes, split_direction);
+        if groups.len() < 2 {
+            continue;
+        }
+        let extent = |group: &[&PaneLayoutMetadata]| {
+            let start = group.iter().map(|p| span(p, split_direction).0).min();
+            let end = group.iter().map(|p| span(p, split_direction).1).max();
+            end.unwrap_or(0).saturating_sub(start.unwrap_or(0))
+        };
+        let is_fixed = |group: &[&PaneLayoutMetadata]| {
+            group.len() == 1 && dimension(group[0], split_direction).is_fixed()
+        };
+        let flexible_space: usize = groups
+            .iter()
+            .filter(|group| !is_fixed(group))
+            .map(|group| extent(group))
+            .sum();
+        let last_flexible_group = groups.iter().rposition(|group| !is_fixed(group));
+        let children = groups
+            .iter()
+            .enumerate()
+            .map(|(index, group)| {
+                let group_panes: Vec<PaneLayoutMetadata> =
+                    group.iter().map(|p| (*p).clone()).collect();
+                let mut child = tiled_pane_layout(&group_panes);
+                child.split_size = if is_fixed(group) {
+                    Some(SplitSize::Fixed(extent(group)))
+                } else if Some(index) == last_flexible_group || flexible_space == 0 {
+                    // the last flexible pane takes up whatever space is left, so that rounding
+                    // doesn't leave gaps
+                    None
+                } else {
+                    let percent = extent(group) as f64 / flexible_space as f64 * 100.0;
+                    Some(SplitSize::Percent((percent.round() as usize).max(1)))
+                };
+                child
+            })
+            .collect();
+        return TiledPaneLayout {
+            children_split_direction: split_direction,
+            children,
+            ..Default::default()
+        };
+    }
+    // these panes cannot be cut into rows or columns (eg. they overlap), so we do our best and
+    // place them one under the other
+    TiledPaneLayout {
+        children: panes
+            .iter()
+            .map(|pane| tiled_pane_layout(std::slice::from_ref(pane)))
+            .collect(),
+        ..Default::default()
+    }
+}
+
+fn split_panes(
+    panes: &[PaneLayoutMetadata],
+    split_direction: SplitDirection,
+) -> Vec<Vec<&PaneLayoutMetadata>> {
+    let mut sorted_panes: Vec<&PaneLayoutMetadata> = panes.iter().collect();
+    sorted_panes.sort_by_key(|pane| span(pane, split_direction));
+    let mut groups = vec![];
+    let mut current_group: Vec<&PaneLayoutMetadata> = vec![];
+    let mut current_group_end = 0;
+    for pane in sorted_panes {
+        let (start, end) = span(pane, split_direction);
+        if !current_group.is_empty() && start >= current_group_end {
+            groups.push(std::mem::take(&mut current_group));
+        }
+        current_group_end = current_group_end.max(end);
+        current_group.push(pane);
+    }
+    if !current_group.is_empty() {
+        groups.push(current_group);
+    }
+    groups
+}
+
+// the start and end of the pane along the axis on which the split places its children
+fn span(pane: &PaneLayoutMetadata, split_direction: SplitDirection) -> (usize, usize) {
+    match split_direction {
+        SplitDirection::Vertical => (pane.geom.x, pane.geom.x + pane.geom.cols.as_usize()),
+        SplitDirection::Horizontal => (pane.geom.y, pane.geom.y + pane.geom.rows.as_usize()),
+    }
+}
+
+fn dimension(pane: &PaneLayoutMetadata, split_direction: SplitDirection) -> Dimension {
+    match split_direction {
+        SplitDirection::Vertical => pane.geom.cols,
+        SplitDirection::Horizontal => pane.geom.rows,
+    }
+}
+
+fn floating_pane_layout(pane: &PaneLayoutMetadata) -> FloatingPaneLayout {
+    FloatingPaneLayout {
+        x: Some(PercentOrFixed::Fixed(pane.geom.x)),
+        y: Some(PercentOrFixed::Fixed(pane.geom.y)),
+        width: Some(PercentOrFixed::Fixed(pane.geom.cols.as_usize())),
+        height: Some(PercentOrFixed::Fixed(pane.geom.rows.as_usize())),
+        run: pane.run.clone(),
+        focus: if pane.is_focused { Some(true) } else { None },
+        pinned: if pane.is_pinned { Some(true) } else { None },
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/session_layout_metadata_tests.rs"]
+mod session_layout_metadata_tests;
@@ -0,0 +1,86 @@
+use std::io::prelude::*;
+use std::os::unix::process::CommandExt;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use zellij_utils::anyhow::{anyhow, Context, Result};
+use zellij_utils::nix::{
+    sys::signal::{killpg, Signal},
+    unistd::Pid,
+};
+
+/// How long a filter command may run before it is killed and nothing is copied
+pub const COPY_FILTER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Pipes `input` through `command` (run by the shell, so it can be a pipeline such as
+/// `sort | uniq`) and returns what it printed, failing if it exits with a non-zero status or does
+/// not finish within `timeout`
+pub fn filter_through(command: &str, input: &str, timeout: Duration) -> Result<String> {
+    let err_context = || format!("failed to filter text through '{}'", command);
+    let deadline = Instant::now() + timeout;
+    // the filter runs in its own process group so that the whole pipeline can be killed, not
+    // only the shell (whose children would otherwise keep its output open)
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .process_group(0)
+        .spawn()
+        .with_context(err_context)?;
+    let process_group = Pid::from_raw(child.id() as i32);
+
+    // we write to and read from the filter on separate threads so that a filter writing its
+    // output before it read all of its input does not block on us (and we on it)
+    let mut stdin = child.stdin.take().context("could not get stdin")?;
+    let input = input.to_owned();
+    thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let mut stdout = child.stdout.take().context("could not get stdout")?;
+    let (stdout_sender, stdout_receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut output = vec![];
+        let _ = stdout_sender.send(stdout.read_to_end(&mut output).map(|_| output));
+    });
+    let mut stderr = child.stderr.take().context("could not get stderr")?;
+    let (stderr_sender, stderr_receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut output = String::new();
+        let _ = stderr_sender.send(stderr.read_to_string(&mut output).map(|_| output));
+    });
+
+    let exit_status = loop {
+        if let Some(exit_status) = child.try_wait().with_context(err_context)? {
+            break exit_status;
+        }
+        if Instant::now() >= deadline {
+            let _ = killpg(process_group, Signal::SIGKILL);
+            let _ = child.wait();
+            return Err(anyhow!("filter timed out after {:?}", timeout)).with_context(err_context);
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    // the shell exited, but something it started in the background may still hold its output
+    // open, so the reads are bound by the same deadline and whatever is left over is killed
+    let time_left = || deadline.saturating_duration_since(Instant::now());
+    if !exit_status.success() {
+        let stderr = stderr_receiver
+            .recv_timeout(time_left())
+            .ok()
+            .and_then(|stderr| stderr.ok())
+            .unwrap_or_default();
+        let _ = killpg(process_group, Signal::SIGKILL);
+        return Err(anyhow!("filter {}: {}", exit_status, stderr.trim())).with_context(err_context);
+    }
+    let output = match stdout_receiver.recv_timeout(time_left()) {
+        Ok(output) => output.with_context(err_context)?,
+        Err(_) => {
+            let _ = killpg(process_group, Signal::SIGKILL);
+            return Err(anyhow!("filter output not closed after {:?}", timeout))
+                .with_context(err_context);
+        },
+    };
+    Ok(String::from_utf8_lossy(&output).into_owned())
+}
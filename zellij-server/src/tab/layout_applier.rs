@@ -359,6 +359,10 @@ impl<'a> LayoutApplier<'a> {
                 )?;
                 self.floating_panes
                     .add_pane(PaneId::Plugin(pid), Box::new(new_pane));
+                if floating_pane_layout.pinned.unwrap_or(false) {
+                    self.floating_panes
+                        .set_pane_pinned(PaneId::Plugin(pid), true);
+                }
                 if floating_pane_layout.focus.unwrap_or(false) {
                     focused_floating_pane = Some(PaneId::Plugin(pid));
                 }
@@ -400,6 +404,10 @@ impl<'a> LayoutApplier<'a> {
                 )?;
                 self.floating_panes
                     .add_pane(PaneId::Terminal(*pid), Box::new(new_pane));
+                if floating_pane_layout.pinned.unwrap_or(false) {
+                    self.floating_panes
+                        .set_pane_pinned(PaneId::Terminal(*pid), true);
+                }
                 if floating_pane_layout.focus.unwrap_or(false) {
                     focused_floating_pane = Some(PaneId::Terminal(*pid));
                 }
@@ -3,13 +3,17 @@
 
 mod clipboard;
 mod copy_command;
+pub(crate) mod copy_filter;
 mod layout_applier;
 mod swap_layouts;
+mod url_opener;
 
 use copy_command::CopyCommand;
 use std::env::temp_dir;
+use std::path::PathBuf;
+use url_opener::open_url;
 use uuid::Uuid;
-use zellij_utils::data::{Direction, PaneInfo, ResizeStrategy};
+use zellij_utils::data::{Direction, FloatingPanePlacement, PaneInfo, ResizeStrategy};
 use zellij_utils::errors::prelude::*;
 use zellij_utils::input::command::RunCommand;
 use zellij_utils::position::{Column, Line};
@@ -18,7 +22,10 @@ use zellij_utils::{position::Position, serde};
 use crate::background_jobs::BackgroundJob;
 use crate::pty_writer::PtyWriteInstruction;
 use crate::screen::CopyOptions;
-use crate::ui::{loading_indication::LoadingIndication, pane_boundaries_frame::FrameParams};
+use crate::ui::{
+    loading_indication::LoadingIndication, pane_boundaries_frame::FrameParams,
+    ruler::ruler_character_chunks,
+};
 use layout_applier::LayoutApplier;
 use swap_layouts::SwapLayouts;
 
@@ -27,13 +34,15 @@ use crate::{
     os_input_output::ServerOsApi,
     output::{CharacterChunk, Output, SixelImageChunk},
     panes::sixel::SixelImageStore,
-    panes::{FloatingPanes, TiledPanes},
-    panes::{LinkHandler, PaneId, PluginPane, TerminalPane},
+    panes::{pane_geom_is_inside_viewport, FloatingPanes, TiledPanes},
+    panes::{LinkHandler, PaneId, PluginPane, TerminalCharacter, TerminalPane},
     plugins::PluginInstruction,
     pty::{ClientOrTabIndex, PtyInstruction, VteBytes},
+    session_layout_metadata::{PaneLayoutMetadata, TabLayoutMetadata},
     thread_bus::ThreadSenders,
     ClientId, ServerInstruction,
 };
+use chrono::Local;
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -43,16 +52,17 @@ use std::{
     str,
 };
 use zellij_utils::{
-    data::{Event, InputMode, ModeInfo, Palette, PaletteColor, Style},
+    data::{ClipboardInfo, Event, InputMode, ModeInfo, Palette, PaletteColor, Style},
     input::{
         command::TerminalAction,
         layout::{
-            FloatingPaneLayout, Run, RunPlugin, RunPluginLocation, SwapFloatingLayout,
-            SwapTiledLayout, TiledPaneLayout,
+            FloatingPaneLayout, Run, RunPlugin, RunPluginLocation, SplitDirection,
+            SwapFloatingLayout, SwapTiledLayout, TiledPaneLayout,
         },
         parse_keys,
     },
-    pane_size::{Offset, PaneGeom, Size, SizeInPixels, Viewport},
+    pane_size::{Dimension, Offset, PaneGeom, Size, SizeConstraints, SizeInPixels, Viewport},
+    regex,
 };
 
 #[macro_export]
@@ -61,8 +71,8 @@ macro_rules! resize_pty {
         match $pane.pid() {
             PaneId::Terminal(ref pid) => $os_input.set_terminal_size_using_terminal_id(
                 *pid,
-                $pane.get_content_columns() as u16,
-                $pane.get_content_rows() as u16,
+                $pane.get_pty_size().0 as u16,
+                $pane.get_pty_size().1 as u16,
                 None,
                 None,
             ),
@@ -83,10 +93,9 @@ macro_rules! resize_pty {
             let character_cell_size = $character_cell_size.borrow();
             match *character_cell_size {
                 Some(size_in_pixels) => {
-                    let width_in_pixels =
-                        (size_in_pixels.width * $pane.get_content_columns()) as u16;
-                    let height_in_pixels =
-                        (size_in_pixels.height * $pane.get_content_rows()) as u16;
+                    let (pty_columns, pty_rows) = $pane.get_pty_size();
+                    let width_in_pixels = (size_in_pixels.width * pty_columns) as u16;
+                    let height_in_pixels = (size_in_pixels.height * pty_rows) as u16;
                     (Some(width_in_pixels), Some(height_in_pixels))
                 },
                 None => (None, None),
@@ -95,8 +104,8 @@ macro_rules! resize_pty {
         match $pane.pid() {
             PaneId::Terminal(ref pid) => $os_input.set_terminal_size_using_terminal_id(
                 *pid,
-                $pane.get_content_columns() as u16,
-                $pane.get_content_rows() as u16,
+                $pane.get_pty_size().0 as u16,
+                $pane.get_pty_size().1 as u16,
                 width_in_pixels,
                 height_in_pixels,
             ),
@@ -119,6 +128,7 @@ pub const MIN_TERMINAL_HEIGHT: usize = 5;
 pub const MIN_TERMINAL_WIDTH: usize = 5;
 
 const MAX_PENDING_VTE_EVENTS: usize = 7000;
+const EMPTY_TAB_HINT: &str = "This tab is empty - open a new pane to get started";
 
 type HoldForCommand = Option<RunCommand>;
 
@@ -126,6 +136,7 @@ enum BufferedTabInstruction {
     SetPaneSelectable(PaneId, bool),
     HandlePtyBytes(u32, VteBytes),
     HoldPane(PaneId, Option<i32>, bool, RunCommand), // Option<i32> is the exit status, bool is is_first_run
+    FocusPaneAtIndex(usize, ClientId),
 }
 
 pub(crate) struct Tab {
@@ -144,25 +155,33 @@ pub(crate) struct Tab {
     os_api: Box<dyn ServerOsApi>,
     pub senders: ThreadSenders,
     synchronize_is_active: bool,
+    pinned: bool, // pinned tabs are not closed by CloseTab
     should_clear_display_before_rendering: bool,
     mode_info: Rc<RefCell<HashMap<ClientId, ModeInfo>>>,
     default_mode_info: ModeInfo,
     pub style: Style,
     connected_clients: Rc<RefCell<HashSet<ClientId>>>,
     draw_pane_frames: bool,
+    ruler_is_visible: bool,
     auto_layout: bool,
     pending_vte_events: HashMap<u32, Vec<VteBytes>>,
     pub selecting_with_mouse: bool, // this is only pub for the tests TODO: remove this once we combine write_text_to_clipboard with render
     link_handler: Rc<RefCell<LinkHandler>>,
     clipboard_provider: ClipboardProvider,
+    clipboard_info: Rc<RefCell<Option<ClipboardInfo>>>,
+    floating_pane_geometries: Rc<RefCell<HashMap<String, PaneGeom>>>,
     // TODO: used only to focus the pane when the layout is loaded
     // it seems that optimization is possible using `active_panes`
     focus_pane_id: Option<PaneId>,
+    last_focused_pane_id: Option<PaneId>, // focused by the last client to leave the tab
     copy_on_select: bool,
     last_mouse_hold_position: Option<Position>,
     terminal_emulator_colors: Rc<RefCell<Palette>>,
     terminal_emulator_color_codes: Rc<RefCell<HashMap<usize, String>>>,
-    pids_waiting_resize: HashSet<u32>, // u32 is the terminal_id
+    pending_command_restarts: HashSet<PaneId>,
+    awaiting_first_pane: bool,
+    pids_waiting_resize: HashSet<u32>,     // u32 is the terminal_id
+    last_output_at: HashMap<u32, Instant>, // u32 is the terminal_id
     cursor_positions_and_shape: HashMap<ClientId, (usize, usize, String)>, // (x_position,
     // y_position,
     // cursor_shape_csi)
@@ -170,9 +189,27 @@ pub(crate) struct Tab {
     pending_instructions: Vec<BufferedTabInstruction>, // instructions that came while the tab was
     // pending and need to be re-applied
     swap_layouts: SwapLayouts,
+    peeked_pane: Option<(PaneId, PaneGeom)>, // a tiled pane temporarily floated for a closer look,
+    // along with its original tiled geometry
+    lent_pane: Option<(PaneId, PaneGeom)>, // a tiled pane shown as an overlay on another tab,
+    // along with its original tiled geometry
+    selected_all_pane: Option<PaneId>, // a pane whose contents were selected with SelectAll, the
+    // selection is cleared once it gets input or loses focus
+    hidden_chrome: Option<HiddenChrome>, // set while in zen mode
+    quickfix_sources: HashMap<PathBuf, PaneId>, // the file a quickfix pane lists its search
+    // results from, and the pane they were searched in
     debug: bool,
 }
 
+// the panes outside of the viewport (eg. the tab bar and status bar) while they are hidden in zen
+// mode, along with the layout of the tab from before they were hidden so that it can be restored
+struct HiddenChrome {
+    panes: Vec<(PaneId, Box<dyn Pane>)>,
+    tiled_pane_geoms: HashMap<PaneId, PaneGeom>,
+    viewport: Viewport,
+    display_area: Size,
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 #[serde(crate = "self::serde")]
 pub(crate) struct TabData {
@@ -193,6 +230,11 @@ pub trait Pane {
     fn get_content_y(&self) -> usize;
     fn get_content_columns(&self) -> usize;
     fn get_content_rows(&self) -> usize;
+    /// The (columns, rows) the pty of this pane is sized to, which is the size of its content
+    /// unless the pane is bound to a fixed size
+    fn get_pty_size(&self) -> (usize, usize) {
+        (self.get_content_columns(), self.get_content_rows())
+    }
     fn reset_size_and_position_override(&mut self);
     fn set_geom(&mut self, position_and_size: PaneGeom);
     fn set_geom_override(&mut self, pane_geom: PaneGeom);
@@ -205,6 +247,8 @@ pub trait Pane {
     fn position_and_size(&self) -> PaneGeom;
     fn current_geom(&self) -> PaneGeom;
     fn geom_override(&self) -> Option<PaneGeom>;
+    fn size_constraints(&self) -> SizeConstraints;
+    fn set_size_constraints(&mut self, size_constraints: SizeConstraints);
     fn should_render(&self) -> bool;
     fn set_should_render(&mut self, should_render: bool);
     fn set_should_render_boundaries(&mut self, _should_render: bool) {}
@@ -227,6 +271,7 @@ pub trait Pane {
     ) -> Option<String>;
     fn render_terminal_title(&mut self, _input_mode: InputMode) -> String;
     fn update_name(&mut self, name: &str);
+    fn clear_name(&mut self);
     fn pid(&self) -> PaneId;
     fn reduce_height(&mut self, percent: f64);
     fn increase_height(&mut self, percent: f64);
@@ -237,13 +282,43 @@ pub trait Pane {
     fn pull_left(&mut self, count: usize);
     fn pull_up(&mut self, count: usize);
     fn clear_screen(&mut self);
-    fn dump_screen(&mut self, _client_id: ClientId, _full: bool) -> String {
+    fn clear_scrollback(&mut self);
+    fn set_scrollback_lines(&mut self, _lines: usize) {}
+    fn dump_screen(&mut self, _client_id: ClientId, _full: bool, _preserve_ansi: bool) -> String {
+        "".to_owned()
+    }
+    fn dump_screen_svg(&mut self, _client_id: ClientId, _full: bool) -> String {
+        "".to_owned()
+    }
+    fn dump_screen_jsonl(&mut self, _client_id: ClientId, _full: bool) -> String {
+        "".to_owned()
+    }
+    fn dump_screen_html(&mut self, _client_id: ClientId, _full: bool) -> String {
         "".to_owned()
     }
     fn scroll_up(&mut self, count: usize, client_id: ClientId);
     fn scroll_down(&mut self, count: usize, client_id: ClientId);
     fn clear_scroll(&mut self);
     fn is_scrolled(&self) -> bool;
+    /// returns false if the pane has no prompt to scroll to
+    fn scroll_to_previous_prompt(&mut self) -> bool {
+        false
+    }
+    /// returns false if the pane has no prompt to scroll to
+    fn scroll_to_next_prompt(&mut self) -> bool {
+        false
+    }
+    /// returns false if the pane has no such line to scroll to
+    fn scroll_to_line(&mut self, _line_number: usize) -> bool {
+        false
+    }
+    fn lines_matching(&self, _needle: &str) -> Vec<(usize, String)> {
+        vec![]
+    }
+    fn line_at(&self, _position: &Position) -> Option<String> {
+        None
+    }
+    fn toggle_line_wrap(&mut self) {}
     fn active_at(&self) -> Instant;
     fn set_active_at(&mut self, instant: Instant);
     fn set_frame(&mut self, frame: bool);
@@ -261,9 +336,22 @@ pub trait Pane {
     fn update_selection(&mut self, _position: &Position, _client_id: ClientId) {}
     fn end_selection(&mut self, _end: &Position, _client_id: ClientId) {}
     fn reset_selection(&mut self) {}
+    fn select_all(&mut self, _viewport_only: bool) {}
+    fn select_word_at(&mut self, _position: &Position) {}
+    fn select_line_at(&mut self, _position: &Position) {}
     fn get_selected_text(&self) -> Option<String> {
         None
     }
+    fn get_selected_text_normalized(&self) -> Option<String> {
+        self.get_selected_text()
+            .map(|text| text.replace("\r\n", "\n"))
+    }
+    fn url_at(&self, _position: &Position) -> Option<String> {
+        None
+    }
+    fn last_command_output(&self) -> Option<String> {
+        None
+    }
 
     fn right_boundary_x_coords(&self) -> usize {
         self.x() + self.cols()
@@ -428,9 +516,23 @@ pub trait Pane {
     fn toggle_search_wrap(&mut self) {
         // No-op by default (only terminal-panes currently have search capability)
     }
+    fn toggle_search_regex(&mut self) {
+        // No-op by default (only terminal-panes currently have search capability)
+    }
+    fn toggle_search_visible_only(&mut self) {
+        // No-op by default (only terminal-panes currently have search capability)
+    }
     fn clear_search(&mut self) {
         // No-op by default (only terminal-panes currently have search capability)
     }
+    /// None if there is no active search
+    fn search_match_count(&self) -> Option<usize> {
+        // None by default (only terminal-panes currently have search capability)
+        None
+    }
+    fn search_jump_to_match(&mut self, _match_index: usize) {
+        // No-op by default (only terminal-panes currently have search capability)
+    }
     fn is_alternate_mode_active(&self) -> bool {
         // False by default (only terminal-panes support alternate mode)
         false
@@ -438,6 +540,10 @@ pub trait Pane {
     fn hold(&mut self, _exit_status: Option<i32>, _is_first_run: bool, _run_command: RunCommand) {
         // No-op by default, only terminal panes support holding
     }
+    fn rerun(&mut self) -> Option<RunCommand> {
+        // if the pane is held, releases it and returns the command to rerun in it
+        None
+    }
     fn add_red_pane_frame_color_override(&mut self, _error_text: Option<String>);
     fn clear_pane_frame_color_override(&mut self);
     fn frame_color_override(&self) -> Option<PaletteColor>;
@@ -506,6 +612,8 @@ impl Tab {
         session_is_mirrored: bool,
         client_id: ClientId,
         copy_options: CopyOptions,
+        clipboard_info: Rc<RefCell<Option<ClipboardInfo>>>,
+        floating_pane_geometries: Rc<RefCell<HashMap<String, PaneGeom>>>,
         terminal_emulator_colors: Rc<RefCell<Palette>>,
         terminal_emulator_color_codes: Rc<RefCell<HashMap<usize, String>>>,
         swap_layouts: (Vec<SwapTiledLayout>, Vec<SwapFloatingLayout>),
@@ -573,6 +681,7 @@ impl Tab {
             character_cell_size,
             sixel_image_store,
             synchronize_is_active: false,
+            pinned: false,
             os_api,
             senders,
             should_clear_display_before_rendering: false,
@@ -580,22 +689,34 @@ impl Tab {
             mode_info,
             default_mode_info,
             draw_pane_frames,
+            ruler_is_visible: false,
             auto_layout,
             pending_vte_events: HashMap::new(),
             connected_clients,
             selecting_with_mouse: false,
             link_handler: Rc::new(RefCell::new(LinkHandler::new())),
             clipboard_provider,
+            clipboard_info,
+            floating_pane_geometries,
             focus_pane_id: None,
+            last_focused_pane_id: None,
             copy_on_select: copy_options.copy_on_select,
             last_mouse_hold_position: None,
             terminal_emulator_colors,
             terminal_emulator_color_codes,
             pids_waiting_resize: HashSet::new(),
+            pending_command_restarts: HashSet::new(),
+            awaiting_first_pane: false,
+            last_output_at: HashMap::new(),
             cursor_positions_and_shape: HashMap::new(),
             is_pending: true, // will be switched to false once the layout is applied
             pending_instructions: vec![],
             swap_layouts,
+            peeked_pane: None,
+            lent_pane: None,
+            selected_all_pane: None,
+            quickfix_sources: HashMap::new(),
+            hidden_chrome: None,
             debug,
         }
     }
@@ -671,33 +792,38 @@ impl Tab {
             .swap_layouts
             .swap_floating_panes(&self.floating_panes, search_backwards)
         {
-            LayoutApplier::new(
-                &self.viewport,
-                &self.senders,
-                &self.sixel_image_store,
-                &self.link_handler,
-                &self.terminal_emulator_colors,
-                &self.terminal_emulator_color_codes,
-                &self.character_cell_size,
-                &self.connected_clients,
-                &self.style,
-                &self.display_area,
-                &mut self.tiled_panes,
-                &mut self.floating_panes,
-                self.draw_pane_frames,
-                &mut self.focus_pane_id,
-                &self.os_api,
-                self.debug,
-            )
-            .apply_floating_panes_layout_to_existing_panes(
-                &layout_candidate,
-                refocus_pane,
-                client_id,
-            )?;
+            self.apply_floating_swap_layout(layout_candidate, refocus_pane, client_id)?;
         }
         self.set_force_render();
         Ok(())
     }
+    fn apply_floating_swap_layout(
+        &mut self,
+        layout: Vec<FloatingPaneLayout>,
+        refocus_pane: bool,
+        client_id: Option<ClientId>,
+    ) -> Result<()> {
+        LayoutApplier::new(
+            &self.viewport,
+            &self.senders,
+            &self.sixel_image_store,
+            &self.link_handler,
+            &self.terminal_emulator_colors,
+            &self.terminal_emulator_color_codes,
+            &self.character_cell_size,
+            &self.connected_clients,
+            &self.style,
+            &self.display_area,
+            &mut self.tiled_panes,
+            &mut self.floating_panes,
+            self.draw_pane_frames,
+            &mut self.focus_pane_id,
+            &self.os_api,
+            self.debug,
+        )
+        .apply_floating_panes_layout_to_existing_panes(&layout, refocus_pane, client_id)?;
+        Ok(())
+    }
     fn relayout_tiled_panes(
         &mut self,
         client_id: Option<ClientId>,
@@ -725,29 +851,7 @@ impl Tab {
                 }
             })
         {
-            LayoutApplier::new(
-                &self.viewport,
-                &self.senders,
-                &self.sixel_image_store,
-                &self.link_handler,
-                &self.terminal_emulator_colors,
-                &self.terminal_emulator_color_codes,
-                &self.character_cell_size,
-                &self.connected_clients,
-                &self.style,
-                &self.display_area,
-                &mut self.tiled_panes,
-                &mut self.floating_panes,
-                self.draw_pane_frames,
-                &mut self.focus_pane_id,
-                &self.os_api,
-                self.debug,
-            )
-            .apply_tiled_panes_layout_to_existing_panes(
-                &layout_candidate,
-                refocus_pane,
-                client_id,
-            )?;
+            self.apply_tiled_swap_layout(&layout_candidate, refocus_pane, client_id)?;
         }
         self.tiled_panes.reapply_pane_frames();
         let display_area = *self.display_area.borrow();
@@ -756,6 +860,33 @@ impl Tab {
         self.should_clear_display_before_rendering = true;
         Ok(())
     }
+    fn apply_tiled_swap_layout(
+        &mut self,
+        layout: &TiledPaneLayout,
+        refocus_pane: bool,
+        client_id: Option<ClientId>,
+    ) -> Result<()> {
+        LayoutApplier::new(
+            &self.viewport,
+            &self.senders,
+            &self.sixel_image_store,
+            &self.link_handler,
+            &self.terminal_emulator_colors,
+            &self.terminal_emulator_color_codes,
+            &self.character_cell_size,
+            &self.connected_clients,
+            &self.style,
+            &self.display_area,
+            &mut self.tiled_panes,
+            &mut self.floating_panes,
+            self.draw_pane_frames,
+            &mut self.focus_pane_id,
+            &self.os_api,
+            self.debug,
+        )
+        .apply_tiled_panes_layout_to_existing_panes(layout, refocus_pane, client_id)?;
+        Ok(())
+    }
     pub fn previous_swap_layout(&mut self, client_id: Option<ClientId>) -> Result<()> {
         // warning, here we cache resizes rather than sending them to the pty, we do that in
         // apply_cached_resizes below - beware when bailing on this function early!
@@ -786,6 +917,114 @@ impl Tab {
         self.os_api.apply_cached_resizes();
         Ok(())
     }
+    /// Applies the swap layout at this index (in the order they are defined, the base layout of
+    /// the tab being the first), doing nothing if there is none or if it doesn't fit the panes
+    pub fn go_to_swap_layout(&mut self, index: usize, client_id: Option<ClientId>) -> Result<()> {
+        let err_context = || format!("failed to go to swap layout {index}");
+        // warning, here we cache resizes rather than sending them to the pty, we do that in
+        // apply_cached_resizes below - beware when bailing on this function early!
+        self.os_api.cache_resizes();
+        if self.floating_panes.panes_are_visible() {
+            if let Some(layout) = self
+                .swap_layouts
+                .floating_layout_at(index, &self.floating_panes)
+            {
+                self.apply_floating_swap_layout(layout, true, client_id)
+                    .with_context(err_context)?;
+                self.set_force_render();
+            }
+        } else if let Some(layout) = self.swap_layouts.tiled_layout_at(index, &self.tiled_panes) {
+            if self.tiled_panes.fullscreen_is_active() {
+                self.tiled_panes.unset_fullscreen();
+            }
+            self.apply_tiled_swap_layout(&layout, true, client_id)
+                .with_context(err_context)?;
+            self.tiled_panes.reapply_pane_frames();
+            let display_area = *self.display_area.borrow();
+            self.tiled_panes.resize(display_area);
+            self.should_clear_display_before_rendering = true;
+        }
+        self.os_api.apply_cached_resizes();
+        Ok(())
+    }
+    pub fn apply_swap_layout_by_name(
+        &mut self,
+        name: &str,
+        client_id: Option<ClientId>,
+    ) -> Result<()> {
+        let index = if self.floating_panes.panes_are_visible() {
+            self.swap_layouts.floating_layout_index(name)
+        } else {
+            self.swap_layouts.tiled_layout_index(name)
+        };
+        match index {
+            Some(index) => self
+                .go_to_swap_layout(index, client_id)
+                .with_context(|| format!("failed to apply swap layout {name}")),
+            None => Ok(()),
+        }
+    }
+    /// Rearranges the existing panes of this tab according to the given layout, the same way a
+    /// swap layout would. Panes are not created or closed to match the layout.
+    pub fn apply_client_layout(
+        &mut self,
+        tiled_panes_layout: TiledPaneLayout,
+        floating_panes_layout: Vec<FloatingPaneLayout>,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to apply layout for client {client_id}");
+        // warning, here we cache resizes rather than sending them to the pty, we do that in
+        // apply_cached_resizes below - beware when bailing on this function early!
+        self.os_api.cache_resizes();
+        if self.tiled_panes.fullscreen_is_active() {
+            self.tiled_panes.unset_fullscreen();
+        }
+        let mut layout_applier = LayoutApplier::new(
+            &self.viewport,
+            &self.senders,
+            &self.sixel_image_store,
+            &self.link_handler,
+            &self.terminal_emulator_colors,
+            &self.terminal_emulator_color_codes,
+            &self.character_cell_size,
+            &self.connected_clients,
+            &self.style,
+            &self.display_area,
+            &mut self.tiled_panes,
+            &mut self.floating_panes,
+            self.draw_pane_frames,
+            &mut self.focus_pane_id,
+            &self.os_api,
+            self.debug,
+        );
+        layout_applier
+            .apply_tiled_panes_layout_to_existing_panes(&tiled_panes_layout, true, Some(client_id))
+            .with_context(err_context)?;
+        if !floating_panes_layout.is_empty() {
+            layout_applier
+                .apply_floating_panes_layout_to_existing_panes(
+                    &floating_panes_layout,
+                    true,
+                    Some(client_id),
+                )
+                .with_context(err_context)?;
+        }
+        // the panes no longer follow any of the tab's swap layouts
+        self.swap_layouts.set_is_tiled_damaged();
+        self.tiled_panes.reapply_pane_frames();
+        let display_area = *self.display_area.borrow();
+        self.tiled_panes.resize(display_area);
+        self.should_clear_display_before_rendering = true;
+        self.set_force_render();
+        self.os_api.apply_cached_resizes();
+        Ok(())
+    }
+    pub fn has_other_connected_clients(&self, client_id: ClientId) -> bool {
+        self.connected_clients
+            .borrow()
+            .iter()
+            .any(|connected_client_id| *connected_client_id != client_id)
+    }
     pub fn apply_buffered_instructions(&mut self) -> Result<()> {
         let buffered_instructions: Vec<BufferedTabInstruction> =
             self.pending_instructions.drain(..).collect();
@@ -805,6 +1044,9 @@ impl Tab {
                 ) => {
                     self.hold_pane(terminal_id, exit_status, is_first_run, run_command);
                 },
+                BufferedTabInstruction::FocusPaneAtIndex(index, client_id) => {
+                    self.focus_pane_at_index(index, client_id)?;
+                },
             }
         }
         Ok(())
@@ -842,21 +1084,38 @@ impl Tab {
             );
         } else {
             let mut pane_ids: Vec<PaneId> = self.tiled_panes.pane_ids().copied().collect();
-            if pane_ids.is_empty() {
+            if pane_ids.is_empty() && !self.is_empty() {
                 // no panes here, bye bye
                 return Ok(());
             }
-            let focus_pane_id = if let Some(id) = self.focus_pane_id {
-                id
-            } else {
-                pane_ids.sort(); // TODO: make this predictable
-                pane_ids.retain(|p| !self.tiled_panes.panes_to_hide_contains(*p));
-                *(pane_ids.get(0).with_context(|| {
-                    format!("failed to acquire id of focused pane while adding client {client_id}",)
-                })?)
-            };
-            self.tiled_panes
-                .focus_pane_if_client_not_focused(focus_pane_id, client_id);
+            // an empty tab has no panes to focus, but the client should still be able to see it
+            if !pane_ids.is_empty() {
+                let last_focused_tiled_pane_id = self
+                    .last_focused_pane_id
+                    .filter(|pane_id| self.tiled_panes.panes_contain(pane_id));
+                let focus_pane_id = if let Some(id) = self.focus_pane_id {
+                    id
+                } else if let Some(id) = last_focused_tiled_pane_id {
+                    id
+                } else {
+                    pane_ids.sort(); // TODO: make this predictable
+                    pane_ids.retain(|p| !self.tiled_panes.panes_to_hide_contains(*p));
+                    *(pane_ids.get(0).with_context(|| {
+                        format!(
+                            "failed to acquire id of focused pane while adding client {client_id}",
+                        )
+                    })?)
+                };
+                self.tiled_panes
+                    .focus_pane_if_client_not_focused(focus_pane_id, client_id);
+            }
+            if let Some(last_focused_floating_pane_id) = self
+                .last_focused_pane_id
+                .filter(|pane_id| self.floating_panes.panes_contain(pane_id))
+            {
+                self.floating_panes
+                    .focus_pane_if_client_not_focused(last_focused_floating_pane_id, client_id);
+            }
             self.connected_clients.borrow_mut().insert(client_id);
             self.mode_info.borrow_mut().insert(
                 client_id,
@@ -885,6 +1144,7 @@ impl Tab {
         Ok(())
     }
     pub fn remove_client(&mut self, client_id: ClientId) {
+        self.remember_focused_pane(client_id);
         self.focus_pane_id = None;
         self.connected_clients.borrow_mut().remove(&client_id);
         self.set_force_render();
@@ -903,6 +1163,7 @@ impl Tab {
         client_ids_to_mode_infos
     }
     pub fn drain_single_client(&mut self, client_id: ClientId) -> (ClientId, ModeInfo) {
+        self.remember_focused_pane(client_id);
         let client_mode_info = self
             .mode_info
             .borrow_mut()
@@ -911,12 +1172,36 @@ impl Tab {
         self.connected_clients.borrow_mut().remove(&client_id);
         (client_id, client_mode_info)
     }
+    fn remember_focused_pane(&mut self, client_id: ClientId) {
+        if let Some(pane_id) = self.get_active_pane_id(client_id) {
+            self.last_focused_pane_id = Some(pane_id);
+        }
+    }
+    /// Focuses the pane that was focused when a client last left this tab, if it is still there
+    pub fn restore_focus(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to restore focus of tab for client {client_id}");
+        if let Some(pane_id) = self.last_focused_pane_id {
+            if self.floating_panes.panes_contain(&pane_id) {
+                self.focus_pane_with_id(pane_id, true, client_id)
+                    .with_context(err_context)?;
+            } else if self.tiled_panes.panes_contain(&pane_id) {
+                self.hide_floating_panes();
+                self.focus_pane_with_id(pane_id, false, client_id)
+                    .with_context(err_context)?;
+            }
+        }
+        Ok(())
+    }
     pub fn has_no_connected_clients(&self) -> bool {
         self.connected_clients.borrow().is_empty()
     }
     pub fn toggle_pane_embed_or_floating(&mut self, client_id: ClientId) -> Result<()> {
         let err_context =
             || format!("failed to toggle embedded/floating pane for client {client_id}");
+        if self.peeked_pane.is_some() {
+            // a peeked pane is already tiled, it is only being displayed as floating
+            return self.restore_peeked_pane().with_context(err_context);
+        }
         if self.tiled_panes.fullscreen_is_active() {
             self.tiled_panes.unset_fullscreen();
         }
@@ -951,6 +1236,365 @@ impl Tab {
         }
         Ok(())
     }
+    /// Embeds all floating panes, appending them to the tiled panes from the bottom of the
+    /// floating stack up as long as there is room for them
+    pub fn embed_all_floating_panes(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to embed all floating panes for client {client_id}");
+        if self.peeked_pane.is_some() {
+            self.restore_peeked_pane().with_context(err_context)?;
+        }
+        if self.tiled_panes.fullscreen_is_active() {
+            self.tiled_panes.unset_fullscreen();
+        }
+        let focused_pane_id = self.get_active_pane_id(client_id);
+        for pane_id in self.floating_panes.pane_ids_from_bottom_to_top() {
+            if !self.tiled_panes.has_room_for_new_pane() {
+                break;
+            }
+            if let Some(pane_to_embed) = self.close_pane(pane_id, true, Some(client_id)) {
+                self.hide_floating_panes();
+                self.add_tiled_pane(pane_to_embed, pane_id, Some(client_id))
+                    .with_context(err_context)?;
+            }
+        }
+        if let Some(focused_pane_id) = focused_pane_id {
+            self.focus_pane_with_id(focused_pane_id, false, client_id)
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+    /// Floats all tiled panes but the first one in tiling order (from the top left going right
+    /// and then down), which is kept so that the tab still has a tiled pane
+    pub fn float_all_tiled_panes(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to float all tiled panes for client {client_id}");
+        if self.peeked_pane.is_some() {
+            self.restore_peeked_pane().with_context(err_context)?;
+        }
+        if self.tiled_panes.fullscreen_is_active() {
+            self.tiled_panes.unset_fullscreen();
+        }
+        let focused_pane_id = self.get_active_pane_id(client_id);
+        let viewport = *self.viewport.borrow();
+        let mut panes: Vec<(PaneId, PaneGeom)> = self
+            .tiled_panes
+            .get_panes()
+            .filter(|(_, pane)| {
+                pane.selectable() && pane_geom_is_inside_viewport(&viewport, &pane.current_geom())
+            })
+            .map(|(pane_id, pane)| (*pane_id, pane.current_geom()))
+            .collect();
+        panes.sort_by_key(|(_, geom)| (geom.y, geom.x));
+        for (pane_id, _) in panes.into_iter().skip(1) {
+            if let Some(pane_to_float) = self.close_pane(pane_id, true, Some(client_id)) {
+                self.show_floating_panes();
+                self.add_floating_pane(pane_to_float, pane_id, Some(client_id))
+                    .with_context(err_context)?;
+            }
+        }
+        match focused_pane_id {
+            Some(focused_pane_id) if self.floating_panes.panes_contain(&focused_pane_id) => {
+                self.focus_pane_with_id(focused_pane_id, true, client_id)
+                    .with_context(err_context)?;
+            },
+            Some(focused_pane_id) => {
+                // the pane that stayed tiled was focused
+                self.hide_floating_panes();
+                self.focus_pane_with_id(focused_pane_id, false, client_id)
+                    .with_context(err_context)?;
+            },
+            None => {},
+        }
+        Ok(())
+    }
+    /// Remove the focused pane of this client from the tab (without closing it) so that it can
+    /// be placed in another tab, returning it along with whether it was floating
+    pub fn extract_focused_pane(
+        &mut self,
+        client_id: ClientId,
+    ) -> Option<(PaneId, Box<dyn Pane>, bool)> {
+        let pane_id = self.get_active_pane_id(client_id)?;
+        let is_floating = self.floating_panes.panes_contain(&pane_id);
+        self.close_pane(pane_id, true, Some(client_id))
+            .map(|pane| (pane_id, pane, is_floating))
+    }
+    pub fn add_pane_from_another_tab(
+        &mut self,
+        pane_id: PaneId,
+        pane: Box<dyn Pane>,
+        should_float: bool,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to add pane {pane_id:?} from another tab");
+        if should_float {
+            self.show_floating_panes();
+            self.add_floating_pane(pane, pane_id, Some(client_id))
+                .with_context(err_context)
+        } else {
+            self.hide_floating_panes();
+            self.add_tiled_pane(pane, pane_id, Some(client_id))
+                .with_context(err_context)
+        }
+    }
+    pub fn has_room_for_new_tiled_pane(&mut self) -> bool {
+        self.tiled_panes.has_room_for_new_pane()
+    }
+    /// Whether the client's focused pane is the last selectable tiled pane in this tab
+    pub fn focused_pane_is_last_tiled_pane(&self, client_id: ClientId) -> bool {
+        match self.get_active_pane_id(client_id) {
+            Some(pane_id) => {
+                !self.floating_panes.panes_contain(&pane_id)
+                    && self.get_selectable_tiled_panes().count() <= 1
+            },
+            None => false,
+        }
+    }
+    pub fn has_floating_panes(&self) -> bool {
+        self.floating_panes.has_panes()
+    }
+    pub fn is_floating_pane(&self, pane_id: PaneId) -> bool {
+        self.floating_panes.panes_contain(&pane_id)
+    }
+    pub fn snap_floating_pane(
+        &mut self,
+        pane_id: PaneId,
+        placement: &FloatingPanePlacement,
+    ) -> Result<()> {
+        if self.floating_panes.panes_contain(&pane_id) {
+            // the user asked for an explicit position, don't have the swap layouts override it
+            self.swap_layouts.set_is_floating_damaged();
+            self.floating_panes
+                .snap_pane(pane_id, placement, &mut self.os_api)
+                .with_context(|| format!("failed to snap floating pane {pane_id:?}"))?;
+        }
+        Ok(())
+    }
+    pub fn restore_floating_pane_geom(&mut self, pane_id: PaneId, geom: PaneGeom) -> Result<()> {
+        if self.floating_panes.panes_contain(&pane_id) {
+            self.swap_layouts.set_is_floating_damaged();
+            self.floating_panes
+                .restore_pane_geom(pane_id, geom, &mut self.os_api)
+                .with_context(|| {
+                    format!("failed to restore geometry of floating pane {pane_id:?}")
+                })?;
+        }
+        Ok(())
+    }
+    pub fn remove_pane_frame(&mut self, pane_id: PaneId) -> Result<()> {
+        self.set_pane_borderless(pane_id, true)
+            .with_context(|| format!("failed to remove frame of pane {pane_id:?}"))
+    }
+    pub fn toggle_active_pane_frame(&mut self, client_id: ClientId) -> Result<()> {
+        let active_pane = self
+            .get_active_pane(client_id)
+            .map(|pane| (pane.pid(), pane.borderless()));
+        if let Some((pane_id, borderless)) = active_pane {
+            self.set_pane_borderless(pane_id, !borderless)
+                .with_context(|| format!("failed to toggle frame of pane {pane_id:?}"))?;
+        }
+        Ok(())
+    }
+    fn set_pane_borderless(&mut self, pane_id: PaneId, borderless: bool) -> Result<()> {
+        // borderless panes keep their frame off when frames are toggled for the whole session
+        if let Some(pane) = self.floating_panes.get_pane_mut(pane_id) {
+            pane.set_borderless(borderless);
+            self.floating_panes.set_pane_frames(&mut self.os_api)?;
+        } else if let Some(pane) = self.tiled_panes.get_pane_mut(pane_id) {
+            pane.set_borderless(borderless);
+            self.tiled_panes.reapply_pane_frames();
+        }
+        self.set_force_render();
+        Ok(())
+    }
+    pub fn peek_pane_floating(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to peek pane for client {client_id}");
+        if self.peeked_pane.is_some() {
+            return self.restore_peeked_pane().with_context(err_context);
+        }
+        if self.floating_panes.panes_are_visible() {
+            // we only peek at tiled panes
+            return Ok(());
+        }
+        if self.tiled_panes.fullscreen_is_active() {
+            self.tiled_panes.unset_fullscreen();
+        }
+        let focused_pane_id = match self.tiled_panes.focused_pane_id(client_id) {
+            Some(focused_pane_id) => focused_pane_id,
+            None => return Ok(()),
+        };
+        if self.get_selectable_tiled_panes().count() <= 1 {
+            // there's nothing to take a closer look at, the pane already takes up the whole tab
+            return Ok(());
+        }
+        // we extract the pane rather than closing it so that the other panes do not take over its
+        // space, this way we can place it back exactly where it was
+        if let Some(mut pane) = self.tiled_panes.extract_pane(focused_pane_id) {
+            let original_geom = pane.position_and_size();
+            self.tiled_panes.move_clients_out_of_pane(focused_pane_id);
+            pane.set_active_at(Instant::now());
+            pane.set_geom(self.peeked_pane_geom());
+            pane.set_content_offset(Offset::frame(1)); // floating panes always have a frame
+            resize_pty!(pane, self.os_api, self.senders, self.character_cell_size)
+                .with_context(err_context)?;
+            self.floating_panes.add_pane(focused_pane_id, pane);
+            self.show_floating_panes();
+            self.floating_panes.focus_pane(focused_pane_id, client_id);
+            self.peeked_pane = Some((focused_pane_id, original_geom));
+            self.set_force_render();
+        }
+        Ok(())
+    }
+    fn restore_peeked_pane(&mut self) -> Result<()> {
+        let err_context = || format!("failed to restore peeked pane");
+        let (pane_id, original_geom) = match self.peeked_pane.take() {
+            Some(peeked_pane) => peeked_pane,
+            None => return Ok(()),
+        };
+        let clients_focused_on_pane: Vec<ClientId> = self
+            .connected_clients
+            .borrow()
+            .iter()
+            .copied()
+            .filter(|c_id| self.floating_panes.active_pane_id(*c_id) == Some(pane_id))
+            .collect();
+        if let Some(mut pane) = self.floating_panes.remove_pane(pane_id) {
+            self.floating_panes.move_clients_out_of_pane(pane_id);
+            if !self.floating_panes.has_panes() || !clients_focused_on_pane.is_empty() {
+                self.hide_floating_panes();
+            }
+            pane.set_geom(original_geom);
+            resize_pty!(pane, self.os_api, self.senders, self.character_cell_size)
+                .with_context(err_context)?;
+            self.tiled_panes.add_pane_with_existing_geom(pane_id, pane);
+            for client_id in clients_focused_on_pane {
+                self.tiled_panes.focus_pane(pane_id, client_id);
+            }
+            self.tiled_panes.set_force_render();
+            self.floating_panes.set_force_render();
+            self.set_force_render();
+        }
+        Ok(())
+    }
+    fn restore_peeked_pane_if_unfocused(&mut self) -> Result<()> {
+        if let Some((pane_id, _)) = self.peeked_pane {
+            let peeked_pane_is_focused = self.floating_panes.panes_are_visible()
+                && self
+                    .connected_clients
+                    .borrow()
+                    .iter()
+                    .any(|c_id| self.floating_panes.active_pane_id(*c_id) == Some(pane_id));
+            if !peeked_pane_is_focused {
+                self.restore_peeked_pane()?;
+            }
+        }
+        Ok(())
+    }
+    fn peeked_pane_geom(&self) -> PaneGeom {
+        // the peeked pane covers most of the viewport, leaving a small margin around it so that it
+        // is clear this is a temporary overlay
+        let viewport = *self.viewport.borrow();
+        let margin_x = viewport.cols / 20;
+        let margin_y = viewport.rows / 20;
+        let mut geom = PaneGeom {
+            x: viewport.x + margin_x,
+            y: viewport.y + margin_y,
+            cols: Dimension::fixed(viewport.cols.saturating_sub(margin_x * 2)),
+            rows: Dimension::fixed(viewport.rows.saturating_sub(margin_y * 2)),
+            is_stacked: false,
+        };
+        geom.cols
+            .set_inner(viewport.cols.saturating_sub(margin_x * 2));
+        geom.rows
+            .set_inner(viewport.rows.saturating_sub(margin_y * 2));
+        geom
+    }
+    /// Takes the first (top-left) selectable tiled pane out of this tab so that it can be shown
+    /// as an overlay on another tab. Its slot is kept empty until it is returned with
+    /// [`Tab::return_lent_pane`].
+    pub fn lend_first_pane(&mut self) -> Result<Option<(PaneId, Box<dyn Pane>)>> {
+        if self.lent_pane.is_some() {
+            return Ok(None);
+        }
+        self.restore_peeked_pane()
+            .context("failed to lend first pane")?;
+        if self.tiled_panes.fullscreen_is_active() {
+            self.tiled_panes.unset_fullscreen();
+        }
+        let first_pane_id = self
+            .get_selectable_tiled_panes()
+            .min_by_key(|(_, pane)| (pane.y(), pane.x()))
+            .map(|(pane_id, _)| *pane_id);
+        let pane_id = match first_pane_id {
+            Some(pane_id) => pane_id,
+            None => return Ok(None),
+        };
+        // we extract the pane rather than closing it so that the other panes do not take over its
+        // space, this way we can place it back exactly where it was
+        Ok(self.tiled_panes.extract_pane(pane_id).map(|pane| {
+            self.lent_pane = Some((pane_id, pane.position_and_size()));
+            self.tiled_panes.move_clients_out_of_pane(pane_id);
+            self.set_force_render();
+            (pane_id, pane)
+        }))
+    }
+    pub fn return_lent_pane(&mut self, mut pane: Box<dyn Pane>) -> Result<()> {
+        let err_context = || format!("failed to return lent pane");
+        let (pane_id, original_geom) = match self.lent_pane.take() {
+            Some(lent_pane) => lent_pane,
+            None => return Ok(()),
+        };
+        pane.set_geom(original_geom);
+        self.tiled_panes.add_pane_with_existing_geom(pane_id, pane);
+        // the pane had a floating frame, this brings its content offset back in line with the
+        // other tiled panes before resizing it
+        self.tiled_panes.reapply_pane_frames();
+        if let Some(pane) = self.tiled_panes.get_pane_mut(pane_id) {
+            resize_pty!(pane, self.os_api, self.senders, self.character_cell_size)
+                .with_context(err_context)?;
+        }
+        let connected_clients: Vec<ClientId> =
+            self.connected_clients.borrow().iter().copied().collect();
+        for client_id in connected_clients {
+            if self.tiled_panes.focused_pane_id(client_id).is_none() {
+                self.tiled_panes.focus_pane(pane_id, client_id);
+            }
+        }
+        self.tiled_panes.set_force_render();
+        self.set_force_render();
+        Ok(())
+    }
+    pub fn lent_pane_id(&self) -> Option<PaneId> {
+        self.lent_pane.map(|(pane_id, _)| pane_id)
+    }
+    /// Shows a pane lent by another tab floating on top of this one, see
+    /// [`Tab::lend_first_pane`].
+    pub fn show_overlay_pane(
+        &mut self,
+        pane_id: PaneId,
+        mut pane: Box<dyn Pane>,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to show overlay pane {pane_id:?}");
+        pane.set_active_at(Instant::now());
+        pane.set_geom(self.peeked_pane_geom());
+        pane.set_content_offset(Offset::frame(1)); // floating panes always have a frame
+        resize_pty!(pane, self.os_api, self.senders, self.character_cell_size)
+            .with_context(err_context)?;
+        self.floating_panes.add_pane(pane_id, pane);
+        self.show_floating_panes();
+        self.floating_panes.focus_pane(pane_id, client_id);
+        self.set_force_render();
+        Ok(())
+    }
+    pub fn hide_overlay_pane(&mut self, pane_id: PaneId) -> Option<Box<dyn Pane>> {
+        let pane = self.floating_panes.remove_pane(pane_id)?;
+        self.floating_panes.move_clients_out_of_pane(pane_id);
+        if !self.floating_panes.has_panes() {
+            self.hide_floating_panes();
+        }
+        self.floating_panes.set_force_render();
+        self.set_force_render();
+        Some(pane)
+    }
     pub fn toggle_floating_panes(
         &mut self,
         client_id: Option<ClientId>,
@@ -986,6 +1630,7 @@ impl Tab {
                         Some(should_float),
                         name,
                         client_id_or_tab_index,
+                        None,
                     );
                     self.senders
                         .send_to_pty(instruction)
@@ -1006,6 +1651,12 @@ impl Tab {
         client_id: Option<ClientId>,
     ) -> Result<()> {
         let err_context = || format!("failed to create new pane with id {pid:?}");
+        // an empty tab is only kept open until it has a tiled pane, so its first pane can't float
+        let should_float = if self.is_empty() {
+            Some(false)
+        } else {
+            should_float
+        };
         match should_float {
             Some(true) => self.show_floating_panes(),
             Some(false) => self.hide_floating_panes(),
@@ -1241,6 +1892,72 @@ impl Tab {
         }
         Ok(())
     }
+    pub fn new_pane_at_fraction(
+        &mut self,
+        pid: PaneId,
+        initial_pane_title: Option<String>,
+        direction: Direction,
+        fraction: f64,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context =
+            || format!("failed to split tab at {fraction} for pane {pid:?} (client {client_id})");
+        if self.floating_panes.panes_are_visible() {
+            return Ok(());
+        }
+        self.close_down_to_max_terminals()
+            .with_context(err_context)?;
+        if self.tiled_panes.fullscreen_is_active() {
+            self.toggle_active_pane_fullscreen(client_id);
+        }
+        if let PaneId::Terminal(term_pid) = pid {
+            let next_terminal_position = self.get_next_terminal_position();
+            let new_terminal = TerminalPane::new(
+                term_pid,
+                PaneGeom::default(), // the initial size will be set later
+                self.style,
+                next_terminal_position,
+                String::new(),
+                self.link_handler.clone(),
+                self.character_cell_size.clone(),
+                self.sixel_image_store.clone(),
+                self.terminal_emulator_colors.clone(),
+                self.terminal_emulator_color_codes.clone(),
+                initial_pane_title,
+                None,
+                self.debug,
+            );
+            let fraction = if fraction.is_finite() {
+                fraction.clamp(0.0, 1.0)
+            } else {
+                0.5
+            };
+            if self.tiled_panes.insert_pane_at_fraction(
+                pid,
+                Box::new(new_terminal),
+                direction,
+                fraction,
+            ) {
+                self.should_clear_display_before_rendering = true;
+                self.tiled_panes.focus_pane(pid, client_id);
+                self.swap_layouts.set_is_tiled_damaged();
+            } else {
+                log::error!("No room to split tab at {}", fraction);
+                if let Some(active_pane_id) = self.tiled_panes.get_active_pane_id(client_id) {
+                    self.senders
+                        .send_to_background_jobs(BackgroundJob::DisplayPaneError(
+                            vec![active_pane_id],
+                            "CAN'T SPLIT!".into(),
+                        ))
+                        .with_context(err_context)?;
+                }
+                self.senders
+                    .send_to_pty(PtyInstruction::ClosePane(pid))
+                    .with_context(err_context)?;
+            }
+        }
+        Ok(())
+    }
 
     pub fn get_active_pane(&self, client_id: ClientId) -> Option<&dyn Pane> {
         self.get_active_pane_id(client_id).and_then(|ap| {
@@ -1314,6 +2031,7 @@ impl Tab {
             return Ok(());
         }
         let err_context = || format!("failed to handle pty bytes from fd {pid}");
+        self.last_output_at.insert(pid, Instant::now());
         if let Some(terminal_output) = self
             .tiled_panes
             .get_pane_mut(PaneId::Terminal(pid))
@@ -1392,7 +2110,18 @@ impl Tab {
                 )
                 .with_context(err_context)?;
             }
+            let content_rows = terminal_output.get_content_rows();
             terminal_output.handle_pty_bytes(bytes);
+            if terminal_output.get_content_rows() != content_rows {
+                // eg. a line of output was pinned above the rest of it
+                resize_pty!(
+                    terminal_output,
+                    self.os_api,
+                    self.senders,
+                    self.character_cell_size
+                )
+                .with_context(err_context)?;
+            }
             let messages_to_pty = terminal_output.drain_messages_to_pty();
             let clipboard_update = terminal_output.drain_clipboard_update();
             for message in messages_to_pty {
@@ -1404,22 +2133,60 @@ impl Tab {
                     .with_context(err_context)?;
             }
         }
-        Ok(())
+        Ok(())
+    }
+
+    pub fn write_to_terminals_on_current_tab(
+        &mut self,
+        input_bytes: Vec<u8>,
+        client_id: ClientId,
+    ) -> Result<bool> {
+        // returns true if a UI update should be triggered (eg. when closing a command pane with
+        // ctrl-c)
+        let mut should_trigger_ui_change = false;
+        // panes excluded from sync are skipped here rather than in write_to_pane_id, so that this
+        // also holds when the whole session is synced
+        let pane_ids: Vec<PaneId> = self
+            .tiled_panes
+            .get_panes()
+            .chain(self.floating_panes.get_panes())
+            .filter(|(_, pane)| !pane.exclude_from_sync())
+            .map(|(pane_id, _)| *pane_id)
+            .collect();
+        for pane_id in pane_ids {
+            let ui_change_triggered = self
+                .write_to_pane_id(input_bytes.clone(), pane_id, Some(client_id))
+                .context("failed to write to terminals on current tab")?;
+            if ui_change_triggered {
+                should_trigger_ui_change = true;
+            }
+        }
+        Ok(should_trigger_ui_change)
     }
 
-    pub fn write_to_terminals_on_current_tab(
+    pub fn write_to_panes_matching(
         &mut self,
+        command_filter: &str,
         input_bytes: Vec<u8>,
         client_id: ClientId,
     ) -> Result<bool> {
         // returns true if a UI update should be triggered (eg. when closing a command pane with
         // ctrl-c)
+        let err_context = || format!("failed to write to panes matching '{command_filter}'");
         let mut should_trigger_ui_change = false;
-        let pane_ids = self.get_static_and_floating_pane_ids();
-        for pane_id in pane_ids {
+        let matching_pane_ids: Vec<PaneId> = self
+            .tiled_panes
+            .get_panes()
+            .chain(self.floating_panes.get_panes())
+            .filter(|(pane_id, pane)| {
+                matches!(pane_id, PaneId::Terminal(_)) && pane_matches_command(pane, command_filter)
+            })
+            .map(|(pane_id, _pane)| *pane_id)
+            .collect();
+        for pane_id in matching_pane_ids {
             let ui_change_triggered = self
                 .write_to_pane_id(input_bytes.clone(), pane_id, Some(client_id))
-                .context("failed to write to terminals on current tab")?;
+                .with_context(err_context)?;
             if ui_change_triggered {
                 should_trigger_ui_change = true;
             }
@@ -1441,6 +2208,7 @@ impl Tab {
         };
 
         self.clear_search(client_id); // this is an inexpensive operation if empty, if we need more such cleanups we should consider moving this and the rest to some sort of cleanup method
+        self.clear_select_all();
         let pane_id = if self.floating_panes.panes_are_visible() {
             self.floating_panes
                 .get_active_pane_id(client_id)
@@ -1593,12 +2361,35 @@ impl Tab {
         }
         self.tiled_panes.toggle_active_pane_fullscreen(client_id);
     }
+    /// Focuses the pane with this id and toggles it fullscreen, taking the place of any other
+    /// pane that is fullscreen at the moment
+    pub fn toggle_pane_fullscreen(&mut self, pane_id: PaneId, client_id: ClientId) -> Result<()> {
+        let pane_is_fullscreen = self.is_fullscreen_active()
+            && self.tiled_panes.get_active_pane_id(client_id) == Some(pane_id);
+        if self.is_fullscreen_active() && !pane_is_fullscreen {
+            self.tiled_panes.unset_fullscreen();
+        }
+        self.focus_pane_with_id(pane_id, false, client_id)
+            .with_context(|| format!("failed to toggle fullscreen for pane {pane_id:?}"))?;
+        self.toggle_active_pane_fullscreen(client_id);
+        Ok(())
+    }
     pub fn is_fullscreen_active(&self) -> bool {
         self.tiled_panes.fullscreen_is_active()
     }
     pub fn are_floating_panes_visible(&self) -> bool {
         self.floating_panes.panes_are_visible()
     }
+    pub fn toggle_active_pane_pinned(&mut self, client_id: ClientId) {
+        if !self.floating_panes.panes_are_visible() {
+            // the focused pane is tiled
+            return;
+        }
+        if let Some(active_pane_id) = self.floating_panes.active_pane_id(client_id) {
+            self.floating_panes.toggle_pane_pinned(active_pane_id);
+            self.set_force_render();
+        }
+    }
     pub fn focus_pane_left_fullscreen(&mut self, client_id: ClientId) {
         if !self.is_fullscreen_active() {
             return;
@@ -1642,6 +2433,10 @@ impl Tab {
     pub fn set_force_render(&mut self) {
         self.tiled_panes.set_force_render();
         self.floating_panes.set_force_render();
+        if self.is_empty() {
+            // there are no panes to draw over whatever was previously on screen
+            self.should_clear_display_before_rendering = true;
+        }
     }
     pub fn is_sync_panes_active(&self) -> bool {
         self.synchronize_is_active
@@ -1649,6 +2444,12 @@ impl Tab {
     pub fn toggle_sync_panes_is_active(&mut self) {
         self.synchronize_is_active = !self.synchronize_is_active;
     }
+    pub fn is_pinned(&self) -> bool {
+        self.pinned
+    }
+    pub fn set_pinned(&mut self, pinned: bool) {
+        self.pinned = pinned;
+    }
     pub fn mark_active_pane_for_rerender(&mut self, client_id: ClientId) {
         if let Some(active_pane) = self.get_active_pane_mut(client_id) {
             active_pane.set_should_render(true);
@@ -1675,9 +2476,20 @@ impl Tab {
 
         let connected_clients: HashSet<ClientId> =
             { self.connected_clients.borrow().iter().copied().collect() };
-        if connected_clients.is_empty() || !self.tiled_panes.has_active_panes() {
+        if connected_clients.is_empty() {
+            return Ok(());
+        }
+        if self.is_empty() {
+            return self
+                .render_empty_tab_hint(output, &connected_clients)
+                .with_context(err_context);
+        }
+        if !self.tiled_panes.has_active_panes() {
             return Ok(());
         }
+        self.restore_peeked_pane_if_unfocused()
+            .with_context(err_context)?;
+        self.clear_select_all_if_unfocused();
         self.update_active_panes_in_pty_thread()
             .with_context(err_context)?;
 
@@ -1696,6 +2508,10 @@ impl Tab {
                 .render(output)
                 .with_context(err_context)?;
         }
+        if self.ruler_is_visible {
+            self.render_ruler(output, &connected_clients)
+                .with_context(err_context)?;
+        }
 
         self.render_cursor(output);
         if output.has_rendered_assets() {
@@ -1705,6 +2521,42 @@ impl Tab {
         Ok(())
     }
 
+    fn render_empty_tab_hint(
+        &mut self,
+        output: &mut Output,
+        connected_clients: &HashSet<ClientId>,
+    ) -> Result<()> {
+        let viewport = *self.viewport.borrow();
+        let hint: Vec<TerminalCharacter> = EMPTY_TAB_HINT
+            .chars()
+            .take(viewport.cols)
+            .map(TerminalCharacter::new)
+            .collect();
+        let x = viewport.x + viewport.cols.saturating_sub(hint.len()) / 2;
+        let y = viewport.y + viewport.rows / 2;
+        output.add_clients(connected_clients, self.link_handler.clone(), None);
+        output.add_character_chunks_to_multiple_clients(
+            vec![CharacterChunk::new(hint, x, y)],
+            connected_clients.iter().copied(),
+            None,
+        )?;
+        self.hide_cursor_and_clear_display_as_needed(output);
+        Ok(())
+    }
+    fn render_ruler(
+        &self,
+        output: &mut Output,
+        connected_clients: &HashSet<ClientId>,
+    ) -> Result<()> {
+        let display_area = *self.display_area.borrow();
+        // the ruler is drawn above all floating panes
+        let z_index = self.floating_panes.stack().map(|stack| stack.layers.len());
+        output.add_character_chunks_to_multiple_clients(
+            ruler_character_chunks(display_area, self.style.colors.orange),
+            connected_clients.iter().copied(),
+            z_index,
+        )
+    }
     fn hide_cursor_and_clear_display_as_needed(&mut self, output: &mut Output) {
         let hide_cursor = "\u{1b}[?25l";
         let connected_clients: Vec<ClientId> =
@@ -1807,6 +2659,10 @@ impl Tab {
         // apply_cached_resizes below - beware when bailing on this function early!
         self.os_api.cache_resizes();
         let err_context = || format!("failed to resize whole tab (index {})", self.index);
+        // restore the peeked pane first so that the tiled panes are resized as a whole
+        self.restore_peeked_pane()
+            .with_context(err_context)
+            .non_fatal();
         self.floating_panes.resize(new_screen_size);
         // we need to do this explicitly because floating_panes.resize does not do this
         self.floating_panes
@@ -1866,6 +2722,95 @@ impl Tab {
         }
         Ok(())
     }
+    pub fn set_pane_size(
+        &mut self,
+        client_id: ClientId,
+        cols: Option<usize>,
+        rows: Option<usize>,
+    ) -> Result<()> {
+        let err_context = || format!("failed to set pane size for client {client_id}");
+        if self.floating_panes.panes_are_visible() {
+            self.swap_layouts.set_is_floating_damaged();
+            let successfully_resized = self
+                .floating_panes
+                .set_active_pane_size(client_id, &mut self.os_api, cols, rows)
+                .with_context(err_context)?;
+            if successfully_resized {
+                self.set_force_render(); // so that the panes under the floating pane don't keep its remains in case of a decrease
+            }
+        } else if !self.tiled_panes.fullscreen_is_active() {
+            self.swap_layouts.set_is_tiled_damaged();
+            self.tiled_panes
+                .set_active_pane_size(client_id, cols, rows)
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+    /// Sets bounds for the size of the focused pane, which resizing and laying out tiled panes
+    /// keep it within. Floating panes store the bounds but are not limited by them.
+    pub fn set_pane_size_constraints(
+        &mut self,
+        client_id: ClientId,
+        size_constraints: SizeConstraints,
+    ) -> Result<()> {
+        let err_context = || format!("failed to set pane size constraints for client {client_id}");
+        if self.floating_panes.panes_are_visible() {
+            if let Some(pane) = self.floating_panes.get_active_pane_mut(client_id) {
+                pane.set_size_constraints(size_constraints);
+            }
+        } else if let Some(active_pane_id) = self.tiled_panes.get_active_pane_id(client_id) {
+            self.swap_layouts.set_is_tiled_damaged();
+            self.tiled_panes
+                .set_pane_size_constraints(active_pane_id, size_constraints)
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+    pub fn set_pane_size_in_percent(
+        &mut self,
+        client_id: ClientId,
+        width: Option<u8>,
+        height: Option<u8>,
+    ) -> Result<()> {
+        let err_context = || format!("failed to set pane size in percent for client {client_id}");
+        if self.floating_panes.panes_are_visible() {
+            // floating panes can be placed anywhere, so their size is simply a percentage of the
+            // viewport
+            let viewport = *self.viewport.borrow();
+            let to_cells = |percent: u8, available_size: usize| {
+                let percent = percent.clamp(1, 100) as usize;
+                ((available_size * percent) as f64 / 100.0).round().max(1.0) as usize
+            };
+            let cols = width.map(|width| to_cells(width, viewport.cols));
+            let rows = height.map(|height| to_cells(height, viewport.rows));
+            self.set_pane_size(client_id, cols, rows)
+                .with_context(err_context)?;
+        } else if !self.tiled_panes.fullscreen_is_active() {
+            self.swap_layouts.set_is_tiled_damaged();
+            self.tiled_panes
+                .set_active_pane_size_in_percent(client_id, width, height)
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+    pub fn balance_panes_axis(&mut self, vertical: bool) -> Result<()> {
+        if self.tiled_panes.fullscreen_is_active() {
+            return Ok(());
+        }
+        self.swap_layouts.set_is_tiled_damaged();
+        self.tiled_panes
+            .balance_panes_axis(vertical)
+            .with_context(|| format!("failed to balance panes (vertical: {vertical})"))
+    }
+    pub fn balance_panes(&mut self) -> Result<()> {
+        if self.tiled_panes.fullscreen_is_active() {
+            return Ok(());
+        }
+        self.swap_layouts.set_is_tiled_damaged();
+        self.tiled_panes
+            .balance_panes()
+            .with_context(|| "failed to balance panes")
+    }
     fn set_pane_active_at(&mut self, pane_id: PaneId) {
         if let Some(pane) = self.tiled_panes.get_pane_mut(pane_id) {
             pane.set_active_at(Instant::now());
@@ -1893,6 +2838,18 @@ impl Tab {
         }
         self.tiled_panes.focus_previous_pane(client_id);
     }
+    pub fn focus_next_floating_pane(&mut self, client_id: ClientId) {
+        if self.floating_panes.has_panes() {
+            self.show_floating_panes();
+            self.floating_panes.focus_next_pane(client_id);
+        }
+    }
+    pub fn focus_previous_floating_pane(&mut self, client_id: ClientId) {
+        if self.floating_panes.has_panes() {
+            self.show_floating_panes();
+            self.floating_panes.focus_previous_pane(client_id);
+        }
+    }
     pub fn focus_pane_on_edge(&mut self, direction: Direction, client_id: ClientId) {
         if self.floating_panes.panes_are_visible() {
             self.floating_panes.focus_pane_on_edge(direction, client_id);
@@ -2006,6 +2963,13 @@ impl Tab {
                 .move_active_pane(search_backwards, client_id);
         }
     }
+    pub fn rotate_panes(&mut self, backwards: bool) {
+        // floating panes keep their place, even when they are visible
+        if self.tiled_panes.fullscreen_is_active() {
+            return;
+        }
+        self.tiled_panes.rotate_panes(backwards);
+    }
     pub fn move_active_pane_backwards(&mut self, client_id: ClientId) {
         if !self.has_selectable_panes() {
             return;
@@ -2037,6 +3001,35 @@ impl Tab {
             self.tiled_panes.move_active_pane_down(client_id);
         }
     }
+    /// Moves the focused floating pane so that its top left corner is at these coordinates, as far
+    /// as it stays inside the viewport. Does nothing if the focused pane is tiled.
+    pub fn move_floating_pane_to(&mut self, client_id: ClientId, x: usize, y: usize) -> Result<()> {
+        if self.floating_panes.panes_are_visible() {
+            self.floating_panes
+                .move_active_pane_to(client_id, x, y)
+                .with_context(|| format!("failed to move floating pane to ({x}, {y})"))?;
+            self.swap_layouts.set_is_floating_damaged();
+            self.set_force_render(); // we force render here to make sure the panes under the floating pane render and don't leave "garbage" behind
+        }
+        Ok(())
+    }
+    pub fn resize_floating_pane_to(
+        &mut self,
+        client_id: ClientId,
+        cols: usize,
+        rows: usize,
+    ) -> Result<()> {
+        if self.floating_panes.panes_are_visible() {
+            // setting the size keeps the pane inside the viewport
+            self.set_pane_size(
+                client_id,
+                Some(cols.max(MIN_TERMINAL_WIDTH)),
+                Some(rows.max(MIN_TERMINAL_HEIGHT)),
+            )
+            .with_context(|| format!("failed to resize floating pane to {cols}x{rows}"))?;
+        }
+        Ok(())
+    }
     pub fn move_active_pane_up(&mut self, client_id: ClientId) {
         if self.floating_panes.panes_are_visible() {
             self.floating_panes.move_active_pane_up(client_id);
@@ -2097,6 +3090,34 @@ impl Tab {
     pub fn get_tiled_pane_ids(&self) -> Vec<PaneId> {
         self.get_tiled_panes().map(|(&pid, _)| pid).collect()
     }
+    /// The ids of the panes started with a command containing `command`, in pane id order
+    pub fn pane_ids_with_command(&self, command: &str) -> Vec<PaneId> {
+        let mut pane_ids: Vec<PaneId> = self
+            .tiled_panes
+            .get_panes()
+            .chain(self.floating_panes.get_panes())
+            .filter(|(_, pane)| match pane.invoked_with() {
+                Some(Run::Command(run_command)) => run_command.to_string().contains(command),
+                _ => false,
+            })
+            .map(|(pane_id, _)| *pane_id)
+            .collect();
+        pane_ids.sort();
+        pane_ids
+    }
+    /// The ids of the panes whose title (their name, if they were named) is exactly `name`, in
+    /// pane id order
+    pub fn pane_ids_with_name(&self, name: &str) -> Vec<PaneId> {
+        let mut pane_ids: Vec<PaneId> = self
+            .tiled_panes
+            .get_panes()
+            .chain(self.floating_panes.get_panes())
+            .filter(|(_, pane)| pane.current_title() == name)
+            .map(|(pane_id, _)| *pane_id)
+            .collect();
+        pane_ids.sort();
+        pane_ids
+    }
     pub fn get_all_pane_ids(&self) -> Vec<PaneId> {
         // this is here just as a naming thing to make things more explicit
         self.get_static_and_floating_pane_ids()
@@ -2144,6 +3165,18 @@ impl Tab {
         //
         // TODO: separate the "close_pane" logic and the "move_pane_somewhere_else" logic, they're
         // overloaded here and that's not great
+        if self
+            .peeked_pane
+            .map(|(p_id, _)| p_id == id)
+            .unwrap_or(false)
+        {
+            // place the pane back in its tiled slot so that its space is properly filled
+            if let Err(e) = self.restore_peeked_pane() {
+                Err::<(), _>(e)
+                    .with_context(|| format!("failed to close pane {:?}", id))
+                    .non_fatal();
+            }
+        }
         if !ignore_suppressed_panes && self.suppressed_panes.contains_key(&id) {
             return match self.replace_pane_with_suppressed_pane(id) {
                 Ok(pane) => pane,
@@ -2157,6 +3190,12 @@ impl Tab {
         }
         if self.floating_panes.panes_contain(&id) {
             let closed_pane = self.floating_panes.remove_pane(id);
+            if let Some(closed_pane) = closed_pane.as_ref() {
+                // so that a floating pane opened later with the same name reappears in place
+                self.floating_pane_geometries
+                    .borrow_mut()
+                    .insert(closed_pane.current_title(), closed_pane.position_and_size());
+            }
             self.floating_panes.move_clients_out_of_pane(id);
             if !self.floating_panes.has_panes() {
                 self.hide_floating_panes();
@@ -2213,6 +3252,75 @@ impl Tab {
             self.tiled_panes
                 .hold_pane(id, exit_status, is_first_run, run_command);
         }
+        if self.pending_command_restarts.remove(&id) {
+            // the command was killed because its watched paths changed
+            self.restart_watched_command(id)
+                .with_context(|| format!("failed to restart command in pane {id:?}"))
+                .non_fatal();
+        }
+    }
+    pub fn restart_watched_command(&mut self, id: PaneId) -> Result<()> {
+        let err_context = || format!("failed to restart command in pane {id:?}");
+        let pane = match self
+            .tiled_panes
+            .get_pane_mut(id)
+            .or_else(|| self.floating_panes.get_pane_mut(id))
+            .or_else(|| {
+                self.suppressed_panes
+                    .values_mut()
+                    .find(|s_p| s_p.pid() == id)
+            }) {
+            Some(pane) => pane,
+            None => return Ok(()),
+        };
+        match (id, pane.rerun()) {
+            (PaneId::Terminal(terminal_id), Some(run_command)) => {
+                self.pids_waiting_resize.insert(terminal_id);
+                self.senders
+                    .send_to_pty(PtyInstruction::ReRunCommandInPane(id, run_command))
+                    .with_context(err_context)?;
+            },
+            _ => {
+                // still running, it will be rerun once it exits and is held
+                self.pending_command_restarts.insert(id);
+                self.senders
+                    .send_to_pty(PtyInstruction::KillCommandInPane(id))
+                    .with_context(err_context)?;
+            },
+        }
+        self.senders
+            .send_to_background_jobs(BackgroundJob::DisplayPaneError(
+                vec![id],
+                "RELOADING".into(),
+            ))
+            .with_context(err_context)?;
+        Ok(())
+    }
+    pub fn restart_active_pane_command(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context =
+            || format!("failed to restart command of active pane for client {client_id}");
+        let pane = match self.get_active_pane_or_floating_pane_mut(client_id) {
+            Some(pane) => pane,
+            None => return Ok(()),
+        };
+        // only held panes have a command to rerun, live processes and other panes are left alone
+        if let (PaneId::Terminal(terminal_id), Some(run_command)) = (pane.pid(), pane.rerun()) {
+            self.pids_waiting_resize.insert(terminal_id);
+            self.senders
+                .send_to_pty(PtyInstruction::ReRunCommandInPane(
+                    PaneId::Terminal(terminal_id),
+                    run_command,
+                ))
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+    /// The command the focused pane was opened with, if it is a command pane
+    pub fn get_active_pane_run_command(&self, client_id: ClientId) -> Option<RunCommand> {
+        match self.get_active_pane(client_id)?.invoked_with() {
+            Some(Run::Command(run_command)) => Some(run_command.clone()),
+            _ => None,
+        }
     }
     pub fn replace_pane_with_suppressed_pane(
         &mut self,
@@ -2284,29 +3392,192 @@ impl Tab {
         }
         Ok(())
     }
+    /// Whether closing the focused pane would kill a running process: either the pane's own
+    /// command has not exited yet, or the shell in the pane has a process in the foreground
+    pub fn active_pane_has_running_process(&self, client_id: ClientId) -> bool {
+        self.get_active_pane(client_id)
+            .map(|pane| self.pane_has_running_process(pane.as_ref()))
+            .unwrap_or(false)
+    }
+    /// How many panes in this tab have a running process, as in
+    /// [`Tab::active_pane_has_running_process`]
+    pub fn panes_with_running_process_count(&self) -> usize {
+        self.tiled_panes
+            .get_panes()
+            .chain(self.floating_panes.get_panes())
+            .map(|(_, pane)| pane)
+            .chain(self.suppressed_panes.values())
+            .filter(|pane| self.pane_has_running_process(pane.as_ref()))
+            .count()
+    }
+    fn pane_has_running_process(&self, pane: &dyn Pane) -> bool {
+        if pane.is_held() {
+            return false;
+        }
+        match (pane.pid(), pane.invoked_with()) {
+            (PaneId::Terminal(_), Some(Run::Command(_))) => true,
+            (PaneId::Terminal(terminal_id), _) => self.os_api.has_foreground_process(terminal_id),
+            (PaneId::Plugin(_), _) => false,
+        }
+    }
     pub fn clear_active_terminal_screen(&mut self, client_id: ClientId) -> Result<()> {
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
             active_pane.clear_screen();
         }
         Ok(())
     }
+    pub fn clear_active_terminal_scrollback(&mut self, client_id: ClientId) -> Result<()> {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.clear_scrollback();
+        }
+        Ok(())
+    }
+    pub fn set_active_terminal_scrollback_lines(
+        &mut self,
+        lines: usize,
+        client_id: ClientId,
+    ) -> Result<()> {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.set_scrollback_lines(lines);
+        }
+        Ok(())
+    }
     pub fn dump_active_terminal_screen(
         &mut self,
         file: Option<String>,
         client_id: ClientId,
         full: bool,
+        preserve_ansi: bool,
+        header: bool,
     ) -> Result<()> {
         let err_context =
             || format!("failed to dump active terminal screen for client {client_id}");
 
+        if let Some(dump) = self.active_terminal_screen_dump(client_id, full, preserve_ansi, header)
+        {
+            self.os_api
+                .write_to_file(dump, file)
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+    pub fn active_terminal_screen_dump(
+        &mut self,
+        client_id: ClientId,
+        full: bool,
+        preserve_ansi: bool,
+        header: bool,
+    ) -> Option<String> {
+        let header = if header {
+            self.screen_dump_header(client_id)
+        } else {
+            None
+        };
+        self.get_active_pane_or_floating_pane_mut(client_id)
+            .map(|active_pane| active_pane.dump_screen(client_id, full, preserve_ansi))
+            .map(|dump| match header {
+                Some(header) => format!("{}\n{}", header, dump),
+                None => dump,
+            })
+    }
+    fn screen_dump_header(&self, client_id: ClientId) -> Option<String> {
+        // makes archived dumps self-describing: when, and which session, tab and pane they are of
+        let active_pane = self.get_active_pane(client_id)?;
+        let pane_id = match active_pane.pid() {
+            PaneId::Terminal(id) => format!("terminal_{}", id),
+            PaneId::Plugin(id) => format!("plugin_{}", id),
+        };
+        let session_name = self
+            .mode_info
+            .borrow()
+            .get(&client_id)
+            .unwrap_or(&self.default_mode_info)
+            .session_name
+            .clone()
+            .unwrap_or_default();
+        Some(format!(
+            "# {} session={:?} tab={:?} pane={} name={:?}",
+            Local::now().format("%Y-%m-%dT%H:%M:%S%:z"),
+            session_name,
+            self.name,
+            pane_id,
+            active_pane.current_title(),
+        ))
+    }
+    pub fn dump_active_terminal_screen_svg(
+        &mut self,
+        file: Option<String>,
+        client_id: ClientId,
+        full: bool,
+    ) -> Result<()> {
+        let err_context =
+            || format!("failed to dump active terminal screen as svg for client {client_id}");
+
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            let dump = active_pane.dump_screen_svg(client_id, full);
+            self.os_api
+                .write_to_file(dump, file)
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+    pub fn dump_active_terminal_screen_jsonl(
+        &mut self,
+        file: Option<String>,
+        client_id: ClientId,
+        full: bool,
+    ) -> Result<()> {
+        let err_context =
+            || format!("failed to dump active terminal screen as jsonl for client {client_id}");
+
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
-            let dump = active_pane.dump_screen(client_id, full);
+            let dump = active_pane.dump_screen_jsonl(client_id, full);
             self.os_api
                 .write_to_file(dump, file)
                 .with_context(err_context)?;
         }
         Ok(())
     }
+    pub fn dump_active_terminal_screen_html(
+        &mut self,
+        file: Option<String>,
+        client_id: ClientId,
+        full: bool,
+    ) -> Result<()> {
+        let err_context =
+            || format!("failed to dump active terminal screen as html for client {client_id}");
+
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            let dump = active_pane.dump_screen_html(client_id, full);
+            self.os_api
+                .write_to_file(dump, file)
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+    pub fn layout_metadata(&self, client_id: ClientId) -> TabLayoutMetadata {
+        let active_pane_id = self.get_active_pane_id(client_id);
+        let viewport = *self.viewport.borrow();
+        let pane_metadata = |(pane_id, pane): (&PaneId, &Box<dyn Pane>)| {
+            let mut geom = pane.position_and_size();
+            geom.x = geom.x.saturating_sub(viewport.x);
+            geom.y = geom.y.saturating_sub(viewport.y);
+            PaneLayoutMetadata {
+                id: *pane_id,
+                geom,
+                run: pane.invoked_with().clone(),
+                is_borderless: pane.borderless(),
+                is_focused: active_pane_id == Some(*pane_id),
+                is_pinned: self.floating_panes.pane_is_pinned(*pane_id),
+            }
+        };
+        TabLayoutMetadata {
+            name: Some(self.name.clone()),
+            is_pinned: self.pinned,
+            tiled_panes: self.tiled_panes.get_panes().map(pane_metadata).collect(),
+            floating_panes: self.floating_panes.get_panes().map(pane_metadata).collect(),
+        }
+    }
     pub fn edit_scrollback(&mut self, client_id: ClientId) -> Result<()> {
         let err_context = || format!("failed to edit scrollback for client {client_id}");
 
@@ -2316,30 +3587,126 @@ impl Tab {
             Some(String::from(file.to_string_lossy())),
             client_id,
             true,
+            false,
+            false,
         )
         .with_context(err_context)?;
         let line_number = self
             .get_active_pane(client_id)
             .and_then(|a_t| a_t.get_line_number());
         self.senders
-            .send_to_pty(PtyInstruction::OpenInPlaceEditor(
-                file,
-                line_number,
-                client_id,
+            .send_to_pty(PtyInstruction::OpenInPlaceEditor(
+                file,
+                line_number,
+                client_id,
+            ))
+            .with_context(err_context)
+    }
+    pub fn search_to_quickfix(&mut self, needle: Vec<u8>, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to search to quickfix for client {client_id}");
+
+        let needle = String::from_utf8_lossy(&needle).to_string();
+        let (source_pane_id, matches) = match self.get_active_pane_or_floating_pane_mut(client_id) {
+            Some(active_pane) => (active_pane.pid(), active_pane.lines_matching(&needle)),
+            None => return Ok(()),
+        };
+        if matches.is_empty() {
+            log::info!("No matches for {:?} to list in a quickfix pane", needle);
+            return Ok(());
+        }
+        let quickfix_list: Vec<String> = matches
+            .iter()
+            .map(|(line_number, line)| format!("{}: {}", line_number, line))
+            .collect();
+        let mut file = temp_dir();
+        file.push(format!("{}.quickfix", Uuid::new_v4()));
+        self.os_api
+            .write_to_file(
+                quickfix_list.join("\n"),
+                Some(String::from(file.to_string_lossy())),
+            )
+            .with_context(err_context)?;
+        self.quickfix_sources.insert(file.clone(), source_pane_id);
+        // one match per line (-S) so that the line number of the match can be read back from
+        // wherever it is clicked
+        let pager = RunCommand {
+            command: PathBuf::from("less"),
+            args: vec!["-S".to_owned(), file.to_string_lossy().to_string()],
+            ..Default::default()
+        };
+        self.senders
+            .send_to_pty(PtyInstruction::SpawnTerminal(
+                Some(TerminalAction::RunCommand(pager)),
+                Some(false),
+                Some(format!("Search: {}", needle)),
+                ClientOrTabIndex::ClientId(client_id),
+                None,
             ))
             .with_context(err_context)
     }
+    fn jump_to_quickfix_match(&mut self, position: &Position) -> Result<()> {
+        let err_context = || format!("failed to jump to quickfix match at {position:?}");
+
+        let (invoked_with, line) = match self
+            .get_pane_at(position, false)
+            .with_context(err_context)?
+        {
+            Some(pane) => (
+                pane.invoked_with().clone(),
+                pane.line_at(&pane.relative_position(position)),
+            ),
+            None => return Ok(()),
+        };
+        let source_pane_id = match invoked_with {
+            Some(Run::Command(run_command)) => run_command
+                .args
+                .iter()
+                .find_map(|arg| self.quickfix_sources.get(&PathBuf::from(arg)))
+                .copied(),
+            _ => None,
+        };
+        let line_number = line.and_then(|line| {
+            line.split(':')
+                .next()
+                .and_then(|line_number| line_number.trim().parse::<usize>().ok())
+        });
+        let (source_pane_id, line_number) = match (source_pane_id, line_number) {
+            (Some(source_pane_id), Some(line_number)) => (source_pane_id, line_number),
+            _ => return Ok(()),
+        };
+        if let Some(source_pane) = self
+            .tiled_panes
+            .get_pane_mut(source_pane_id)
+            .or_else(|| self.floating_panes.get_pane_mut(source_pane_id))
+        {
+            source_pane.scroll_to_line(line_number);
+        }
+        Ok(())
+    }
     pub fn scroll_active_terminal_up(&mut self, client_id: ClientId) {
+        self.scroll_active_terminal_up_by(1, client_id);
+    }
+
+    pub fn scroll_active_terminal_up_by(&mut self, lines: usize, client_id: ClientId) {
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
-            active_pane.scroll_up(1, client_id);
+            active_pane.scroll_up(lines, client_id);
         }
     }
 
     pub fn scroll_active_terminal_down(&mut self, client_id: ClientId) -> Result<()> {
-        let err_context = || format!("failed to scroll down active pane for client {client_id}");
+        self.scroll_active_terminal_down_by(1, client_id)
+    }
+
+    pub fn scroll_active_terminal_down_by(
+        &mut self,
+        lines: usize,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context =
+            || format!("failed to scroll down {lines} lines in active pane for client {client_id}");
 
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
-            active_pane.scroll_down(1, client_id);
+            active_pane.scroll_down(lines, client_id);
             if !active_pane.is_scrolled() {
                 if let PaneId::Terminal(raw_fd) = active_pane.pid() {
                     self.process_pending_vte_events(raw_fd)
@@ -2375,6 +3742,39 @@ impl Tab {
         Ok(())
     }
 
+    /// Scrolls to the previous shell prompt, or up a page if the pane has no prompt marks
+    pub fn scroll_active_terminal_to_previous_prompt(&mut self, client_id: ClientId) {
+        let found_prompt = self
+            .get_active_pane_or_floating_pane_mut(client_id)
+            .map(|active_pane| active_pane.scroll_to_previous_prompt())
+            .unwrap_or(false);
+        if !found_prompt {
+            self.scroll_active_terminal_up_page(client_id);
+        }
+    }
+
+    /// Scrolls to the next shell prompt, or down a page if the pane has no prompt marks
+    pub fn scroll_active_terminal_to_next_prompt(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context =
+            || format!("failed to scroll to next prompt in active pane for client {client_id}");
+
+        let mut found_prompt = false;
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            found_prompt = active_pane.scroll_to_next_prompt();
+            if found_prompt && !active_pane.is_scrolled() {
+                if let PaneId::Terminal(raw_fd) = active_pane.pid() {
+                    self.process_pending_vte_events(raw_fd)
+                        .with_context(err_context)?;
+                }
+            }
+        }
+        if !found_prompt {
+            self.scroll_active_terminal_down_page(client_id)
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+
     pub fn scroll_active_terminal_up_half_page(&mut self, client_id: ClientId) {
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
             // prevent overflow when row == 0
@@ -2426,6 +3826,23 @@ impl Tab {
         Ok(())
     }
 
+    pub fn scroll_active_terminal_to_line(
+        &mut self,
+        line: usize,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let found_line = match self.get_active_pane_or_floating_pane_mut(client_id) {
+            // panes count their lines from 1
+            Some(active_pane) => active_pane.scroll_to_line(line.saturating_add(1)),
+            None => return Ok(()),
+        };
+        if !found_line {
+            // the line is past the end of the buffer
+            self.scroll_active_terminal_to_bottom(client_id)?;
+        }
+        Ok(())
+    }
+
     pub fn clear_active_terminal_scroll(&mut self, client_id: ClientId) -> Result<()> {
         // TODO: is this a thing?
         let err_context =
@@ -2575,6 +3992,8 @@ impl Tab {
 
         self.focus_pane_at(position, client_id)
             .with_context(err_context)?;
+        self.jump_to_quickfix_match(position)
+            .with_context(err_context)?;
 
         let search_selectable = false;
         if self.floating_panes.panes_are_visible()
@@ -2607,6 +4026,74 @@ impl Tab {
         Ok(())
     }
 
+    pub fn handle_left_double_click(
+        &mut self,
+        position: &Position,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let select_word = true;
+        self.handle_left_multi_click(position, select_word, client_id)
+    }
+
+    pub fn handle_left_triple_click(
+        &mut self,
+        position: &Position,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let select_word = false;
+        self.handle_left_multi_click(position, select_word, client_id)
+    }
+
+    fn handle_left_multi_click(
+        &mut self,
+        position: &Position,
+        select_word: bool,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || {
+            format!(
+                "failed to handle mouse multi click at position {position:?} for client {client_id}"
+            )
+        };
+
+        self.focus_pane_at(position, client_id)
+            .with_context(err_context)?;
+
+        let copy_on_select = self.copy_on_select;
+        if let Some(pane) = self
+            .get_pane_at(position, false)
+            .with_context(err_context)?
+        {
+            let relative_position = pane.relative_position(position);
+            if let Some(mouse_event) = pane.mouse_left_click(&relative_position, false) {
+                // the application in the pane handles the mouse itself, so it gets a plain click
+                if !pane.position_is_on_frame(position) {
+                    self.write_to_active_terminal(mouse_event.into_bytes(), client_id)
+                        .with_context(err_context)?;
+                }
+                return Ok(());
+            }
+            if select_word {
+                pane.select_word_at(&relative_position);
+            } else {
+                pane.select_line_at(&relative_position);
+            }
+            let selected_text = if copy_on_select {
+                pane.get_selected_text()
+            } else {
+                None
+            };
+            // unlike a drag, the selection is complete right away so the release that follows
+            // does not change it
+            self.selecting_with_mouse = false;
+            if let Some(selected_text) = selected_text {
+                self.write_selection_to_clipboard(&selected_text)
+                    .with_context(err_context)?;
+            }
+        };
+        Ok(())
+    }
+
     pub fn handle_right_click(&mut self, position: &Position, client_id: ClientId) -> Result<()> {
         let err_context = || {
             format!(
@@ -2968,10 +4455,52 @@ impl Tab {
         Ok(false) // we shouldn't even get here, but might as well not needlessly render if we do
     }
 
+    pub fn select_all(&mut self, viewport_only: bool, client_id: ClientId) {
+        self.clear_select_all();
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.select_all(viewport_only);
+            self.selected_all_pane = Some(active_pane.pid());
+        }
+    }
+    fn clear_select_all(&mut self) {
+        if let Some(pane_id) = self.selected_all_pane.take() {
+            if let Some(pane) = self
+                .tiled_panes
+                .get_pane_mut(pane_id)
+                .or_else(|| self.floating_panes.get_pane_mut(pane_id))
+            {
+                pane.reset_selection();
+                pane.set_should_render(true);
+            }
+        }
+    }
+    fn clear_select_all_if_unfocused(&mut self) {
+        if let Some(pane_id) = self.selected_all_pane {
+            let selected_pane_is_focused = self
+                .connected_clients
+                .borrow()
+                .iter()
+                .any(|c_id| self.get_active_pane_id(*c_id) == Some(pane_id));
+            if !selected_pane_is_focused {
+                self.clear_select_all();
+            }
+        }
+    }
     pub fn copy_selection(&self, client_id: ClientId) -> Result<()> {
         let selected_text = self
             .get_active_pane(client_id)
             .and_then(|p| p.get_selected_text());
+        self.copy_selected_text(selected_text, client_id)
+    }
+
+    pub fn copy_selection_normalized(&self, client_id: ClientId) -> Result<()> {
+        let selected_text = self
+            .get_active_pane(client_id)
+            .and_then(|p| p.get_selected_text_normalized());
+        self.copy_selected_text(selected_text, client_id)
+    }
+
+    fn copy_selected_text(&self, selected_text: Option<String>, client_id: ClientId) -> Result<()> {
         if let Some(selected_text) = selected_text {
             self.write_selection_to_clipboard(&selected_text)
                 .with_context(|| {
@@ -2991,6 +4520,72 @@ impl Tab {
         Ok(())
     }
 
+    pub fn copy_selection_through(&self, command: &str, client_id: ClientId) -> Result<()> {
+        let active_pane = self.get_active_pane(client_id);
+        let selected_text = active_pane.and_then(|p| p.get_selected_text());
+        if let (Some(active_pane), Some(selected_text)) = (active_pane, selected_text) {
+            // the filter can take a while, so it runs as a background job that sends the filtered
+            // text back to be copied (see copy_filtered_text)
+            self.senders
+                .send_to_background_jobs(BackgroundJob::RunCopyFilter(
+                    command.to_owned(),
+                    selected_text,
+                    active_pane.pid(),
+                    client_id,
+                ))
+                .with_context(|| {
+                    format!("failed to copy selection through filter for client {client_id}")
+                })?;
+        }
+        Ok(())
+    }
+
+    pub fn copy_filtered_text(&self, filtered_text: &str, client_id: ClientId) -> Result<()> {
+        self.write_selection_to_clipboard(filtered_text)
+            .with_context(|| format!("failed to copy filtered selection for client {client_id}"))
+    }
+
+    pub fn copy_url_at(&self, position: &Position, client_id: ClientId) -> Result<()> {
+        let url = self
+            .get_active_pane(client_id)
+            .and_then(|p| p.url_at(position));
+        if let Some(url) = url {
+            self.write_selection_to_clipboard(&url).with_context(|| {
+                format!("failed to copy url to clipboard for client {client_id}")
+            })?;
+        }
+        Ok(())
+    }
+
+    pub fn open_url_at(&self, position: &Position, client_id: ClientId) -> Result<()> {
+        let url = self
+            .get_active_pane(client_id)
+            .and_then(|p| p.url_at(position));
+        if let Some(url) = url {
+            open_url(&url).with_context(|| format!("failed to open url for client {client_id}"))?;
+        }
+        Ok(())
+    }
+
+    pub fn toggle_active_pane_line_wrap(&mut self, client_id: ClientId) {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.toggle_line_wrap();
+        }
+    }
+
+    pub fn copy_last_command_output(&self, client_id: ClientId) -> Result<()> {
+        let last_command_output = self
+            .get_active_pane(client_id)
+            .and_then(|p| p.last_command_output());
+        if let Some(last_command_output) = last_command_output {
+            self.write_selection_to_clipboard(&last_command_output)
+                .with_context(|| {
+                    format!("failed to copy last command output for client {client_id}")
+                })?;
+        }
+        Ok(())
+    }
+
     fn write_selection_to_clipboard(&self, selection: &str) -> Result<()> {
         let err_context = || format!("failed to write selection to clipboard: '{}'", selection);
 
@@ -2999,7 +4594,7 @@ impl Tab {
             { self.connected_clients.borrow().iter().copied().collect() };
         output.add_clients(&connected_clients, self.link_handler.clone(), None);
         let client_ids = connected_clients.iter().copied();
-        let clipboard_event =
+        let clipboard_events =
             match self
                 .clipboard_provider
                 .set_content(selection, &mut output, client_ids)
@@ -3010,23 +4605,31 @@ impl Tab {
                         self.senders
                             .send_to_server(ServerInstruction::Render(Some(serialized_output)))
                     })
-                    .and_then(|_| {
-                        Ok(Event::CopyToClipboard(
-                            self.clipboard_provider.as_copy_destination(),
-                        ))
+                    .map(|_| {
+                        let clipboard_info = ClipboardInfo {
+                            destination: self.clipboard_provider.as_copy_destination(),
+                            bytes: selection.len(),
+                            lines: selection.lines().count(),
+                        };
+                        *self.clipboard_info.borrow_mut() = Some(clipboard_info.clone());
+                        vec![
+                            Event::CopyToClipboard(clipboard_info.destination),
+                            Event::ClipboardUpdate(clipboard_info),
+                        ]
                     })
                     .with_context(err_context)?,
                 Err(err) => {
                     Err::<(), _>(err).with_context(err_context).non_fatal();
-                    Event::SystemClipboardFailure
+                    vec![Event::SystemClipboardFailure]
                 },
             };
         self.senders
-            .send_to_plugin(PluginInstruction::Update(vec![(
-                None,
-                None,
-                clipboard_event,
-            )]))
+            .send_to_plugin(PluginInstruction::Update(
+                clipboard_events
+                    .into_iter()
+                    .map(|clipboard_event| (None, None, clipboard_event))
+                    .collect(),
+            ))
             .context("failed to notify plugins about new clipboard event")
             .non_fatal();
 
@@ -3073,6 +4676,24 @@ impl Tab {
         Ok(())
     }
 
+    pub fn clear_active_pane_name(&mut self, client_id: ClientId) -> Result<()> {
+        if let Some(active_terminal_id) = self.get_active_terminal_id(client_id) {
+            let active_terminal = if self.are_floating_panes_visible() {
+                self.floating_panes
+                    .get_pane_mut(PaneId::Terminal(active_terminal_id))
+            } else {
+                self.tiled_panes
+                    .get_pane_mut(PaneId::Terminal(active_terminal_id))
+            }
+            .with_context(|| {
+                format!("failed to clear name of active pane for client {client_id}")
+            })?;
+
+            active_terminal.clear_name();
+        }
+        Ok(())
+    }
+
     pub fn undo_active_rename_pane(&mut self, client_id: ClientId) -> Result<()> {
         if let Some(active_terminal_id) = self.get_active_terminal_id(client_id) {
             let active_terminal = if self.are_floating_panes_visible() {
@@ -3112,6 +4733,105 @@ impl Tab {
         self.should_clear_display_before_rendering = true;
         self.set_force_render();
     }
+    /// Hide the panes outside of the viewport (eg. the tab bar and status bar), letting the rest
+    /// of the tiled panes take up the whole display area until `show_chrome` is called
+    pub fn hide_chrome(&mut self) -> Result<()> {
+        if self.hidden_chrome.is_some() {
+            return Ok(());
+        }
+        let err_context = || format!("failed to hide chrome of tab {}", self.index);
+        self.restore_peeked_pane()
+            .with_context(err_context)
+            .non_fatal();
+        self.tiled_panes.unset_fullscreen();
+        let viewport = *self.viewport.borrow();
+        let display_area = *self.display_area.borrow();
+        let tiled_pane_geoms: HashMap<PaneId, PaneGeom> = self
+            .tiled_panes
+            .get_panes()
+            .map(|(pane_id, pane)| (*pane_id, pane.current_geom()))
+            .collect();
+        let chrome_pane_ids: Vec<PaneId> = tiled_pane_geoms
+            .iter()
+            .filter(|(_, geom)| !pane_geom_is_inside_viewport(&viewport, geom))
+            .map(|(pane_id, _)| *pane_id)
+            .collect();
+        let panes = chrome_pane_ids
+            .into_iter()
+            .filter_map(|pane_id| {
+                self.tiled_panes
+                    .extract_pane(pane_id)
+                    .map(|pane| (pane_id, pane))
+            })
+            .collect();
+        *self.viewport.borrow_mut() = display_area.into();
+        // the swap layouts include the chrome, so we should not switch to them until it is back
+        self.swap_layouts.set_is_tiled_damaged();
+        self.tiled_panes.relayout(SplitDirection::Horizontal);
+        self.tiled_panes.relayout(SplitDirection::Vertical);
+        self.hidden_chrome = Some(HiddenChrome {
+            panes,
+            tiled_pane_geoms,
+            viewport,
+            display_area,
+        });
+        self.should_clear_display_before_rendering = true;
+        self.set_force_render();
+        Ok(())
+    }
+    /// Bring back the panes hidden by `hide_chrome`, restoring the previous layout of the tab if
+    /// its panes were not changed in the meantime
+    pub fn show_chrome(&mut self) -> Result<()> {
+        let hidden_chrome = match self.hidden_chrome.take() {
+            Some(hidden_chrome) => hidden_chrome,
+            None => return Ok(()),
+        };
+        let err_context = || format!("failed to show chrome of tab {}", self.index);
+        self.restore_peeked_pane()
+            .with_context(err_context)
+            .non_fatal();
+        self.tiled_panes.unset_fullscreen();
+        let display_area = *self.display_area.borrow();
+        {
+            // the display area might have been resized while the chrome was hidden
+            let mut viewport = self.viewport.borrow_mut();
+            *viewport = hidden_chrome.viewport;
+            viewport.cols = (viewport.cols as isize + display_area.cols as isize
+                - hidden_chrome.display_area.cols as isize) as usize;
+            viewport.rows = (viewport.rows as isize + display_area.rows as isize
+                - hidden_chrome.display_area.rows as isize) as usize;
+        }
+        for (pane_id, pane) in hidden_chrome.panes {
+            self.tiled_panes.add_pane_with_existing_geom(pane_id, pane);
+        }
+        let tiled_panes_are_unchanged = display_area == hidden_chrome.display_area
+            && self.tiled_panes.pane_ids().count() == hidden_chrome.tiled_pane_geoms.len()
+            && self
+                .tiled_panes
+                .pane_ids()
+                .all(|pane_id| hidden_chrome.tiled_pane_geoms.contains_key(pane_id));
+        if tiled_panes_are_unchanged {
+            for (pane_id, geom) in hidden_chrome.tiled_pane_geoms {
+                if let Some(pane) = self.tiled_panes.get_pane_mut(pane_id) {
+                    pane.set_geom(geom);
+                }
+            }
+            self.tiled_panes.reapply_pane_frames();
+        } else {
+            self.tiled_panes.relayout(SplitDirection::Horizontal);
+            self.tiled_panes.relayout(SplitDirection::Vertical);
+        }
+        self.should_clear_display_before_rendering = true;
+        self.set_force_render();
+        Ok(())
+    }
+    pub fn chrome_is_hidden(&self) -> bool {
+        self.hidden_chrome.is_some()
+    }
+    pub fn set_ruler_visibility(&mut self, ruler_is_visible: bool) {
+        self.ruler_is_visible = ruler_is_visible;
+        self.set_force_render();
+    }
     pub fn panes_to_hide_count(&self) -> usize {
         self.tiled_panes.panes_to_hide_count()
     }
@@ -3162,16 +4882,50 @@ impl Tab {
         }
     }
 
+    pub fn toggle_search_regex(&mut self, client_id: ClientId) {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.toggle_search_regex();
+        }
+    }
+
+    pub fn toggle_search_visible_only(&mut self, client_id: ClientId) {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.toggle_search_visible_only();
+        }
+    }
+
     pub fn clear_search(&mut self, client_id: ClientId) {
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
             active_pane.clear_search();
         }
     }
 
+    pub fn search_jump_to_match(&mut self, match_index: usize, client_id: ClientId) {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.search_jump_to_match(match_index);
+        }
+    }
+
+    pub fn search_match_count(&mut self, client_id: ClientId) -> Option<usize> {
+        self.get_active_pane_or_floating_pane_mut(client_id)
+            .and_then(|active_pane| active_pane.search_match_count())
+    }
+
     pub fn is_pending(&self) -> bool {
         self.is_pending
     }
 
+    /// Mark this tab as created without any panes, so that it is kept open (showing a hint)
+    /// rather than closed until its first pane is added.
+    pub fn set_awaiting_first_pane(&mut self) {
+        self.awaiting_first_pane = true;
+    }
+
+    /// A tab that finished loading without any panes in it (eg. one created with `--empty`)
+    pub fn is_empty(&self) -> bool {
+        self.awaiting_first_pane && !self.is_pending
+    }
+
     pub fn add_red_pane_frame_color_override(
         &mut self,
         pane_id: PaneId,
@@ -3276,6 +5030,36 @@ impl Tab {
             })
     }
 
+    pub fn focus_most_recently_active_pane(&mut self, client_id: ClientId) {
+        let most_recently_active_pane_id = self
+            .last_output_at
+            .iter()
+            .map(|(pid, last_output_at)| (PaneId::Terminal(*pid), last_output_at))
+            .filter(|(pane_id, _)| {
+                self.tiled_panes.panes_contain(pane_id)
+                    || self.floating_panes.panes_contain(pane_id)
+            })
+            .max_by_key(|(_, last_output_at)| *last_output_at)
+            .map(|(pane_id, _)| pane_id);
+        match most_recently_active_pane_id {
+            Some(pane_id) if self.tiled_panes.panes_contain(&pane_id) => {
+                if self.tiled_panes.fullscreen_is_active()
+                    && self.tiled_panes.focused_pane_id(client_id) != Some(pane_id)
+                {
+                    self.tiled_panes.unset_fullscreen();
+                }
+                if self.floating_panes.panes_are_visible() {
+                    self.hide_floating_panes();
+                }
+                self.tiled_panes.focus_pane(pane_id, client_id);
+            },
+            Some(pane_id) => {
+                self.show_floating_panes();
+                self.floating_panes.focus_pane(pane_id, client_id);
+            },
+            None => {}, // no pane has produced any output yet
+        }
+    }
     pub fn focus_pane_with_id(
         &mut self,
         pane_id: PaneId,
@@ -3305,6 +5089,39 @@ impl Tab {
                 None => Ok(()),
             })
     }
+    /// Focuses the `index`th (1-based) tiled pane in tiling order, ie. from the top left going
+    /// right and then down, or the last one if there are fewer panes. UI panes outside of the
+    /// viewport (eg. the tab bar) are not counted.
+    pub fn focus_pane_at_index(&mut self, index: usize, client_id: ClientId) -> Result<()> {
+        if self.is_pending {
+            // the panes of the layout are not there yet
+            self.pending_instructions
+                .push(BufferedTabInstruction::FocusPaneAtIndex(index, client_id));
+            return Ok(());
+        }
+        let viewport = *self.viewport.borrow();
+        let mut panes: Vec<(PaneId, PaneGeom)> = self
+            .tiled_panes
+            .get_panes()
+            .filter(|(_, pane)| {
+                pane.selectable() && pane_geom_is_inside_viewport(&viewport, &pane.current_geom())
+            })
+            .map(|(pane_id, pane)| (*pane_id, pane.current_geom()))
+            .collect();
+        panes.sort_by_key(|(_, geom)| (geom.y, geom.x));
+        let pane_id = panes
+            .get(index.saturating_sub(1))
+            .or_else(|| panes.last())
+            .map(|(pane_id, _)| *pane_id);
+        if let Some(pane_id) = pane_id {
+            if self.floating_panes.panes_are_visible() {
+                self.hide_floating_panes();
+            }
+            self.focus_pane_with_id(pane_id, false, client_id)
+                .with_context(|| format!("failed to focus pane at index {index}"))?;
+        }
+        Ok(())
+    }
     pub fn suppress_pane(&mut self, pane_id: PaneId, client_id: ClientId) {
         if let Some(pane) = self.close_pane(pane_id, true, Some(client_id)) {
             self.suppressed_panes.insert(pane_id, pane);
@@ -3371,6 +5188,7 @@ impl Tab {
                 self.tiled_panes.insert_pane(pane_id, pane);
             }
             self.should_clear_display_before_rendering = true;
+            self.awaiting_first_pane = false;
             if let Some(client_id) = client_id {
                 self.tiled_panes.focus_pane(pane_id, client_id);
             }
@@ -3386,6 +5204,33 @@ impl Tab {
     }
 }
 
+/// Whether the command a pane is running matches the given filter - a glob if it includes `*` or
+/// `?`, otherwise a substring. We check both the command the pane was opened with and its title,
+/// since the latter usually reflects the program currently running in a shell.
+fn pane_matches_command(pane: &Box<dyn Pane>, command_filter: &str) -> bool {
+    let invoked_with = match pane.invoked_with() {
+        Some(Run::Command(run_command)) => Some(run_command.to_string()),
+        _ => None,
+    };
+    let is_glob = command_filter.contains('*') || command_filter.contains('?');
+    let filter_regex = if is_glob {
+        let pattern = regex::escape(command_filter)
+            .replace("\\*", ".*")
+            .replace("\\?", ".");
+        regex::Regex::new(&format!("^{}$", pattern)).ok()
+    } else {
+        None
+    };
+    let command_matches = |command: &str| match &filter_regex {
+        Some(filter_regex) => filter_regex.is_match(command),
+        None => command.contains(command_filter),
+    };
+    invoked_with
+        .map(|invoked_with| command_matches(&invoked_with))
+        .unwrap_or(false)
+        || command_matches(&pane.current_title())
+}
+
 pub fn pane_info_for_pane(pane_id: &PaneId, pane: &Box<dyn Pane>) -> PaneInfo {
     let mut pane_info = PaneInfo::default();
     pane_info.pane_x = pane.x();
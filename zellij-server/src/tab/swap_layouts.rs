@@ -268,6 +268,63 @@ impl SwapLayouts {
         }
         None
     }
+    /// The floating swap layout at this index if it fits the floating panes, which then becomes
+    /// the current one
+    pub fn floating_layout_at(
+        &mut self,
+        index: usize,
+        floating_panes: &FloatingPanes,
+    ) -> Option<Vec<FloatingPaneLayout>> {
+        let layout = self
+            .swap_floating_layouts
+            .get(index)?
+            .0
+            .iter()
+            .find(|(constraint, _layout)| {
+                self.state_fits_floating_panes_constraint(constraint, floating_panes)
+            })
+            .map(|(_constraint, layout)| layout.clone())?;
+        self.current_floating_layout_position = index;
+        self.is_floating_damaged = false;
+        Some(layout)
+    }
+    /// The tiled swap layout at this index if it fits the tiled panes, which then becomes the
+    /// current one
+    pub fn tiled_layout_at(
+        &mut self,
+        index: usize,
+        tiled_panes: &TiledPanes,
+    ) -> Option<TiledPaneLayout> {
+        let display_area = PaneGeom::from(&*self.display_area.borrow());
+        let pane_count = tiled_panes.visible_panes_count();
+        let layout = self
+            .swap_tiled_layouts
+            .get(index)?
+            .0
+            .iter()
+            .find(|(constraint, layout)| {
+                self.state_fits_tiled_panes_constraint(constraint, tiled_panes)
+                    && layout
+                        .position_panes_in_space(&display_area, Some(pane_count))
+                        .is_ok()
+            })
+            .map(|(_constraint, layout)| layout.clone())?;
+        self.current_tiled_layout_position = index;
+        self.is_tiled_damaged = false;
+        Some(layout)
+    }
+    /// The index of the floating swap layout declared with this name, if there is one
+    pub fn floating_layout_index(&self, name: &str) -> Option<usize> {
+        self.swap_floating_layouts
+            .iter()
+            .position(|(_layouts, layout_name)| layout_name.as_deref() == Some(name))
+    }
+    /// The index of the tiled swap layout declared with this name, if there is one
+    pub fn tiled_layout_index(&self, name: &str) -> Option<usize> {
+        self.swap_tiled_layouts
+            .iter()
+            .position(|(_layouts, layout_name)| layout_name.as_deref() == Some(name))
+    }
     pub fn best_effort_tiled_layout(
         &mut self,
         tiled_panes: &TiledPanes,
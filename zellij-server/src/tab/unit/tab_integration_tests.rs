@@ -241,6 +241,8 @@ fn create_new_tab(size: Size, default_mode: ModeInfo) -> Tab {
         session_is_mirrored,
         client_id,
         copy_options,
+        Rc::new(RefCell::new(None)),
+        Rc::new(RefCell::new(HashMap::new())),
         terminal_emulator_colors,
         terminal_emulator_color_codes,
         (vec![], vec![]),
@@ -313,6 +315,8 @@ fn create_new_tab_with_swap_layouts(
         session_is_mirrored,
         client_id,
         copy_options,
+        Rc::new(RefCell::new(None)),
+        Rc::new(RefCell::new(HashMap::new())),
         terminal_emulator_colors,
         terminal_emulator_color_codes,
         swap_layouts,
@@ -387,6 +391,8 @@ fn create_new_tab_with_os_api(
         session_is_mirrored,
         client_id,
         copy_options,
+        Rc::new(RefCell::new(None)),
+        Rc::new(RefCell::new(HashMap::new())),
         terminal_emulator_colors,
         terminal_emulator_color_codes,
         (vec![], vec![]), // swap layouts
@@ -447,6 +453,8 @@ fn create_new_tab_with_layout(size: Size, default_mode: ModeInfo, layout: &str)
         session_is_mirrored,
         client_id,
         copy_options,
+        Rc::new(RefCell::new(None)),
+        Rc::new(RefCell::new(HashMap::new())),
         terminal_emulator_colors,
         terminal_emulator_color_codes,
         (vec![], vec![]), // swap layouts
@@ -521,6 +529,8 @@ fn create_new_tab_with_mock_pty_writer(
         session_is_mirrored,
         client_id,
         copy_options,
+        Rc::new(RefCell::new(None)),
+        Rc::new(RefCell::new(HashMap::new())),
         terminal_emulator_colors,
         terminal_emulator_color_codes,
         (vec![], vec![]), // swap layouts
@@ -586,6 +596,8 @@ fn create_new_tab_with_sixel_support(
         session_is_mirrored,
         client_id,
         copy_options,
+        Rc::new(RefCell::new(None)),
+        Rc::new(RefCell::new(HashMap::new())),
         terminal_emulator_colors,
         terminal_emulator_color_codes,
         (vec![], vec![]), // swap layouts
@@ -719,7 +731,7 @@ fn dump_screen() {
     tab.handle_pty_bytes(2, Vec::from("scratch".as_bytes()))
         .unwrap();
     let file = "/tmp/log.sh";
-    tab.dump_active_terminal_screen(Some(file.to_string()), client_id, false)
+    tab.dump_active_terminal_screen(Some(file.to_string()), client_id, false, false, false)
         .unwrap();
     assert_eq!(
         map.lock().unwrap().get(file).unwrap(),
@@ -728,6 +740,76 @@ fn dump_screen() {
     );
 }
 
+#[test]
+fn dump_screen_with_header() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mode_info = ModeInfo {
+        session_name: Some("audited".into()),
+        ..Default::default()
+    };
+    let mut tab = create_new_tab(size, mode_info);
+    let map = Arc::new(Mutex::new(HashMap::new()));
+    tab.os_api = Box::new(FakeInputOutput {
+        file_dumps: map.clone(),
+        ..Default::default()
+    });
+    let new_pane_id = PaneId::Terminal(2);
+    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+        .unwrap();
+    tab.handle_pty_bytes(2, Vec::from("scratch".as_bytes()))
+        .unwrap();
+    let file = "/tmp/log-header.sh";
+    tab.dump_active_terminal_screen(Some(file.to_string()), client_id, false, false, true)
+        .unwrap();
+    let dump = map.lock().unwrap().get(file).unwrap().clone();
+    let (header, contents) = dump.split_once('\n').expect("dump has a header line");
+    assert!(
+        header.starts_with("# "),
+        "header is a comment: {:?}",
+        header
+    );
+    assert!(
+        header.contains("session=\"audited\" tab=\"\" pane=terminal_2 name="),
+        "header names the session, tab and pane: {:?}",
+        header
+    );
+    assert_eq!(contents, "scratch", "screen was dumped after the header");
+}
+
+#[test]
+fn dump_screen_with_ansi() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    let map = Arc::new(Mutex::new(HashMap::new()));
+    tab.os_api = Box::new(FakeInputOutput {
+        file_dumps: map.clone(),
+        ..Default::default()
+    });
+    let new_pane_id = PaneId::Terminal(2);
+    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+        .unwrap();
+    tab.handle_pty_bytes(2, Vec::from("plain \u{1b}[31mred".as_bytes()))
+        .unwrap();
+    let file = "/tmp/log-ansi.sh";
+    tab.dump_active_terminal_screen(Some(file.to_string()), client_id, false, true, false)
+        .unwrap();
+    let dump = map.lock().unwrap().get(file).unwrap().clone();
+    assert!(
+        dump.contains("plain \u{1b}[31mred"),
+        "colors were kept as ANSI escapes: {:?}",
+        dump
+    );
+    assert!(dump.ends_with("\u{1b}[m"), "styles were reset at the end");
+}
+
 #[test]
 fn clear_screen() {
     let size = Size {
@@ -748,7 +830,7 @@ fn clear_screen() {
         .unwrap();
     let file = "/tmp/log-clear-screen.sh";
     tab.clear_active_terminal_screen(client_id).unwrap();
-    tab.dump_active_terminal_screen(Some(file.to_string()), client_id, false)
+    tab.dump_active_terminal_screen(Some(file.to_string()), client_id, false, false, false)
         .unwrap();
     assert_eq!(
         map.lock().unwrap().get(file).unwrap(),
@@ -757,6 +839,51 @@ fn clear_screen() {
     );
 }
 
+#[test]
+fn clear_scrollback() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    let map = Arc::new(Mutex::new(HashMap::new()));
+    tab.os_api = Box::new(FakeInputOutput {
+        file_dumps: map.clone(),
+        ..Default::default()
+    });
+    let new_pane_id = PaneId::Terminal(2);
+    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+        .unwrap();
+    let lines: String = (0..40).map(|i| format!("line {}\n\r", i)).collect();
+    tab.handle_pty_bytes(2, Vec::from(lines.as_bytes()))
+        .unwrap();
+    tab.scroll_active_terminal_up(client_id);
+    let viewport_file = "/tmp/log-clear-scrollback-viewport.sh";
+    tab.clear_active_terminal_scrollback(client_id).unwrap();
+    tab.dump_active_terminal_screen(
+        Some(viewport_file.to_string()),
+        client_id,
+        false,
+        false,
+        false,
+    )
+    .unwrap();
+    let full_file = "/tmp/log-clear-scrollback-full.sh";
+    tab.dump_active_terminal_screen(Some(full_file.to_string()), client_id, true, false, false)
+        .unwrap();
+    let dumps = map.lock().unwrap();
+    assert!(
+        dumps.get(viewport_file).unwrap().contains("line 39"),
+        "scrolled back to the bottom"
+    );
+    assert_eq!(
+        dumps.get(full_file).unwrap(),
+        dumps.get(viewport_file).unwrap(),
+        "only the visible lines were kept"
+    );
+}
+
 #[test]
 fn new_floating_pane() {
     let size = Size {
@@ -3094,6 +3221,74 @@ fn can_swap_tiled_layout_at_runtime() {
     assert_snapshot!(snapshot);
 }
 
+#[test]
+fn can_go_to_swap_layout_by_index() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let swap_layouts = r#"
+        layout {
+            swap_tiled_layout name="vertical" {
+                tab max_panes=2 split_direction="vertical" {
+                    pane
+                    pane
+                }
+            }
+            swap_tiled_layout name="horizontal" {
+                tab max_panes=2 {
+                    pane
+                    pane
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(swap_layouts, "file_name.kdl".into(), None, None).unwrap();
+    let swap_tiled_layouts = layout.swap_tiled_layouts.clone();
+    let swap_floating_layouts = layout.swap_floating_layouts.clone();
+    let mut tab = create_new_tab_with_swap_layouts(
+        size,
+        ModeInfo::default(),
+        (swap_tiled_layouts, swap_floating_layouts),
+        None,
+        true,
+    );
+    tab.new_pane(PaneId::Terminal(2), None, None, None, Some(client_id))
+        .unwrap();
+    let second_pane_is_below_first = |tab: &Tab| {
+        let geom = |pid| tab.tiled_panes.get_pane(PaneId::Terminal(pid)).unwrap().y();
+        geom(2) > geom(1)
+    };
+    tab.go_to_swap_layout(2, Some(client_id)).unwrap();
+    assert_eq!(
+        tab.swap_layout_info().0,
+        Some("horizontal".to_owned()),
+        "went to the third swap layout"
+    );
+    assert!(
+        second_pane_is_below_first(&tab),
+        "panes laid out horizontally"
+    );
+    tab.go_to_swap_layout(1, Some(client_id)).unwrap();
+    assert_eq!(
+        tab.swap_layout_info().0,
+        Some("vertical".to_owned()),
+        "went to the second swap layout"
+    );
+    assert!(
+        !second_pane_is_below_first(&tab),
+        "panes laid out vertically"
+    );
+    tab.go_to_swap_layout(0, Some(client_id)).unwrap();
+    tab.go_to_swap_layout(3, Some(client_id)).unwrap();
+    assert_eq!(
+        tab.swap_layout_info().0,
+        Some("vertical".to_owned()),
+        "layouts that don't fit and ones that don't exist are ignored"
+    );
+}
+
 #[test]
 fn can_swap_floating_layout_at_runtime() {
     let size = Size {
@@ -6876,3 +7071,61 @@ fn can_define_expanded_pane_in_stack() {
     );
     assert_snapshot!(snapshot);
 }
+
+#[test]
+fn can_apply_swap_layout_by_name() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let swap_layouts = r#"
+        layout {
+            swap_tiled_layout name="vertical" {
+                tab max_panes=2 split_direction="vertical" {
+                    pane
+                    pane
+                }
+            }
+            swap_tiled_layout name="horizontal" {
+                tab max_panes=2 {
+                    pane
+                    pane
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(swap_layouts, "file_name.kdl".into(), None, None).unwrap();
+    let swap_tiled_layouts = layout.swap_tiled_layouts.clone();
+    let swap_floating_layouts = layout.swap_floating_layouts.clone();
+    let mut tab = create_new_tab_with_swap_layouts(
+        size,
+        ModeInfo::default(),
+        (swap_tiled_layouts, swap_floating_layouts),
+        None,
+        true,
+    );
+    tab.new_pane(PaneId::Terminal(2), None, None, None, Some(client_id))
+        .unwrap();
+    tab.apply_swap_layout_by_name("horizontal", Some(client_id))
+        .unwrap();
+    assert_eq!(
+        tab.swap_layout_info().0,
+        Some("horizontal".to_owned()),
+        "applied the swap layout with this name"
+    );
+    tab.apply_swap_layout_by_name("vertical", Some(client_id))
+        .unwrap();
+    assert_eq!(
+        tab.swap_layout_info().0,
+        Some("vertical".to_owned()),
+        "applied the other swap layout with this name"
+    );
+    tab.apply_swap_layout_by_name("diagonal", Some(client_id))
+        .unwrap();
+    assert_eq!(
+        tab.swap_layout_info().0,
+        Some("vertical".to_owned()),
+        "names of no swap layout are ignored"
+    );
+}
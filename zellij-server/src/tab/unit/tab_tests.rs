@@ -1,5 +1,7 @@
-use super::Tab;
+use super::copy_filter::filter_through;
+use super::{Tab, MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH};
 use crate::panes::sixel::SixelImageStore;
+use crate::pty_writer::PtyWriteInstruction;
 use crate::screen::CopyOptions;
 use crate::{
     os_input_output::{AsyncReader, Pid, ServerOsApi},
@@ -8,11 +10,14 @@ use crate::{
     ClientId,
 };
 use std::path::PathBuf;
-use zellij_utils::data::{Direction, Resize, ResizeStrategy};
+use std::time::{Duration, Instant};
+use zellij_utils::data::{
+    AspectRatio, Direction, FloatingPanePlacement, FloatingSnap, Resize, ResizeStrategy,
+};
 use zellij_utils::errors::prelude::*;
 use zellij_utils::input::layout::{SplitDirection, SplitSize, TiledPaneLayout};
 use zellij_utils::ipc::IpcReceiverWithContext;
-use zellij_utils::pane_size::{Size, SizeInPixels};
+use zellij_utils::pane_size::{PaneGeom, Size, SizeConstraints, SizeInPixels};
 
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
@@ -20,6 +25,7 @@ use std::os::unix::io::RawFd;
 use std::rc::Rc;
 
 use zellij_utils::{
+    channels::{self, ChannelWithContext, SenderWithContext},
     data::{ModeInfo, Palette, Style},
     input::command::{RunCommand, TerminalAction},
     interprocess::local_socket::LocalSocketStream,
@@ -183,6 +189,8 @@ fn create_new_tab(size: Size) -> Tab {
         session_is_mirrored,
         client_id,
         copy_options,
+        Rc::new(RefCell::new(None)),
+        Rc::new(RefCell::new(HashMap::new())),
         terminal_emulator_colors,
         terminal_emulator_color_codes,
         (vec![], vec![]), // swap layouts
@@ -240,6 +248,8 @@ fn create_new_tab_with_layout(size: Size, layout: TiledPaneLayout) -> Tab {
         session_is_mirrored,
         client_id,
         copy_options,
+        Rc::new(RefCell::new(None)),
+        Rc::new(RefCell::new(HashMap::new())),
         terminal_emulator_colors,
         terminal_emulator_color_codes,
         (vec![], vec![]), // swap layouts
@@ -303,6 +313,8 @@ fn create_new_tab_with_cell_size(
         session_is_mirrored,
         client_id,
         copy_options,
+        Rc::new(RefCell::new(None)),
+        Rc::new(RefCell::new(HashMap::new())),
         terminal_emulator_colors,
         terminal_emulator_color_codes,
         (vec![], vec![]), // swap layouts
@@ -858,6 +870,423 @@ pub fn toggle_focused_pane_fullscreen() {
     // function and we already test that in the e2e tests
 }
 
+#[test]
+pub fn peek_pane_floating_and_restore_to_original_position() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let new_pane_id = PaneId::Terminal(2);
+    tab.new_pane(new_pane_id, None, None, None, Some(1))
+        .unwrap();
+    let original_geom = tab
+        .tiled_panes
+        .panes
+        .get(&new_pane_id)
+        .unwrap()
+        .position_and_size();
+    tab.peek_pane_floating(1).unwrap();
+    assert!(
+        tab.floating_panes.panes_contain(&new_pane_id),
+        "Peeked pane is floating"
+    );
+    assert!(
+        !tab.tiled_panes.panes_contain(&new_pane_id),
+        "Peeked pane was removed from the tiled panes"
+    );
+    assert!(
+        tab.floating_panes.get_pane(new_pane_id).unwrap().cols() > original_geom.cols.as_usize(),
+        "Peeked pane is enlarged"
+    );
+    tab.peek_pane_floating(1).unwrap();
+    assert!(
+        !tab.floating_panes.panes_contain(&new_pane_id),
+        "Pane is no longer floating"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&new_pane_id)
+            .unwrap()
+            .position_and_size(),
+        original_geom,
+        "Pane was restored to its original position"
+    );
+    assert_eq!(
+        tab.get_active_pane_id(1),
+        Some(new_pane_id),
+        "Restored pane is focused"
+    );
+}
+
+#[test]
+pub fn snap_floating_pane_to_top_right_corner() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    let new_pane_id = PaneId::Terminal(2);
+    tab.new_pane(new_pane_id, None, Some(true), None, Some(client_id))
+        .unwrap();
+    tab.snap_floating_pane(
+        new_pane_id,
+        &FloatingPanePlacement {
+            snap: Some(FloatingSnap::TopRight),
+            width: Some(30),
+            height: None,
+            aspect: None,
+        },
+    )
+    .unwrap();
+    let snapped_pane = tab.floating_panes.get_pane(new_pane_id).unwrap();
+    assert_eq!(
+        snapped_pane.x(),
+        91,
+        "Pane is docked against the right edge"
+    );
+    assert_eq!(snapped_pane.y(), 0, "Pane is docked against the top edge");
+    assert_eq!(snapped_pane.cols(), 30, "Pane has the requested width");
+    assert_eq!(
+        snapped_pane.rows(),
+        10,
+        "Pane has the default corner height"
+    );
+}
+
+#[test]
+pub fn pinned_floating_pane_stays_on_top_of_focused_floating_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    let pinned_pane_id = PaneId::Terminal(2);
+    tab.new_pane(pinned_pane_id, None, Some(true), None, Some(client_id))
+        .unwrap();
+    tab.toggle_active_pane_pinned(client_id);
+    let other_pane_id = PaneId::Terminal(3);
+    tab.new_pane(other_pane_id, None, Some(true), None, Some(client_id))
+        .unwrap();
+    let top_layer = |tab: &Tab| tab.floating_panes.stack().unwrap().layers.last().copied();
+    let pane_geom = |tab: &Tab, pane_id| {
+        tab.floating_panes
+            .get_pane(pane_id)
+            .map(|pane| pane.position_and_size())
+    };
+    assert_eq!(
+        tab.get_active_pane_id(client_id),
+        Some(other_pane_id),
+        "new pane is focused"
+    );
+    assert_eq!(
+        top_layer(&tab),
+        pane_geom(&tab, pinned_pane_id),
+        "pinned pane stays on top"
+    );
+    tab.toggle_active_pane_pinned(client_id);
+    assert_eq!(
+        top_layer(&tab),
+        pane_geom(&tab, pinned_pane_id),
+        "toggling the focused unpinned pane pins it, still below the first pinned pane"
+    );
+    tab.focus_pane_with_id(pinned_pane_id, false, client_id)
+        .unwrap();
+    tab.toggle_active_pane_pinned(client_id);
+    assert_eq!(
+        top_layer(&tab),
+        pane_geom(&tab, other_pane_id),
+        "unpinned pane drops below the pinned one"
+    );
+}
+
+#[test]
+pub fn move_floating_pane_to_coordinates_within_the_viewport() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    let new_pane_id = PaneId::Terminal(2);
+    tab.new_pane(new_pane_id, None, Some(true), None, Some(client_id))
+        .unwrap();
+    let pane_geom = |tab: &Tab| {
+        tab.floating_panes
+            .get_pane(new_pane_id)
+            .map(|pane| pane.position_and_size())
+            .unwrap()
+    };
+    tab.move_floating_pane_to(client_id, 10, 5).unwrap();
+    assert_eq!(pane_geom(&tab).x, 10, "pane moved to the requested column");
+    assert_eq!(pane_geom(&tab).y, 5, "pane moved to the requested line");
+    tab.move_floating_pane_to(client_id, 200, 200).unwrap();
+    let geom = pane_geom(&tab);
+    assert_eq!(
+        geom.x + geom.cols.as_usize(),
+        size.cols,
+        "pane clamped to the right edge of the viewport"
+    );
+    assert_eq!(
+        geom.y + geom.rows.as_usize(),
+        size.rows,
+        "pane clamped to the bottom edge of the viewport"
+    );
+}
+
+#[test]
+pub fn resize_floating_pane_to_dimensions_within_bounds() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    let new_pane_id = PaneId::Terminal(2);
+    tab.new_pane(new_pane_id, None, Some(true), None, Some(client_id))
+        .unwrap();
+    let pane_geom = |tab: &Tab| {
+        tab.floating_panes
+            .get_pane(new_pane_id)
+            .map(|pane| pane.position_and_size())
+            .unwrap()
+    };
+    tab.resize_floating_pane_to(client_id, 40, 10).unwrap();
+    assert_eq!(
+        pane_geom(&tab).cols.as_usize(),
+        40,
+        "pane resized to 40 columns"
+    );
+    assert_eq!(
+        pane_geom(&tab).rows.as_usize(),
+        10,
+        "pane resized to 10 rows"
+    );
+    tab.resize_floating_pane_to(client_id, 1, 1).unwrap();
+    assert_eq!(
+        pane_geom(&tab).cols.as_usize(),
+        MIN_TERMINAL_WIDTH,
+        "pane no narrower than the minimum width"
+    );
+    assert_eq!(
+        pane_geom(&tab).rows.as_usize(),
+        MIN_TERMINAL_HEIGHT,
+        "pane no shorter than the minimum height"
+    );
+    tab.resize_floating_pane_to(client_id, 500, 500).unwrap();
+    assert_eq!(
+        pane_geom(&tab).cols.as_usize(),
+        size.cols,
+        "pane no wider than the viewport"
+    );
+    assert_eq!(
+        pane_geom(&tab).rows.as_usize(),
+        size.rows,
+        "pane no taller than the viewport"
+    );
+}
+
+#[test]
+pub fn cycle_focus_among_floating_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    for pid in 2..=4 {
+        tab.new_pane(
+            PaneId::Terminal(pid),
+            None,
+            Some(true),
+            None,
+            Some(client_id),
+        )
+        .unwrap();
+    }
+    let mut focused_panes = vec![];
+    for _ in 0..3 {
+        tab.focus_next_floating_pane(client_id);
+        focused_panes.push(tab.get_active_pane_id(client_id).unwrap());
+    }
+    assert_eq!(
+        focused_panes,
+        vec![
+            PaneId::Terminal(2),
+            PaneId::Terminal(3),
+            PaneId::Terminal(4)
+        ],
+        "next floating pane cycles from the bottom of the stack"
+    );
+    focused_panes.clear();
+    for _ in 0..3 {
+        tab.focus_previous_floating_pane(client_id);
+        focused_panes.push(tab.get_active_pane_id(client_id).unwrap());
+    }
+    assert_eq!(
+        focused_panes,
+        vec![
+            PaneId::Terminal(3),
+            PaneId::Terminal(2),
+            PaneId::Terminal(4)
+        ],
+        "previous floating pane cycles back the other way"
+    );
+}
+
+#[test]
+pub fn float_all_tiled_panes_and_embed_them_back() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    tab.new_pane(
+        PaneId::Terminal(2),
+        None,
+        Some(false),
+        None,
+        Some(client_id),
+    )
+    .unwrap();
+    tab.new_pane(
+        PaneId::Terminal(3),
+        None,
+        Some(false),
+        None,
+        Some(client_id),
+    )
+    .unwrap();
+    tab.float_all_tiled_panes(client_id).unwrap();
+    assert_eq!(
+        tab.tiled_panes.pane_ids().copied().collect::<Vec<_>>(),
+        vec![PaneId::Terminal(1)],
+        "first pane stays tiled"
+    );
+    assert_eq!(
+        tab.floating_panes.pane_ids().count(),
+        2,
+        "other panes float"
+    );
+    assert!(tab.are_floating_panes_visible(), "floating panes shown");
+    assert_eq!(
+        tab.get_active_pane_id(client_id),
+        Some(PaneId::Terminal(3)),
+        "focused pane still focused"
+    );
+    tab.embed_all_floating_panes(client_id).unwrap();
+    assert_eq!(tab.tiled_panes.pane_ids().count(), 3, "all panes tiled");
+    assert!(!tab.floating_panes.has_panes(), "no floating panes left");
+    assert!(!tab.are_floating_panes_visible(), "floating panes hidden");
+    assert_eq!(
+        tab.get_active_pane_id(client_id),
+        Some(PaneId::Terminal(3)),
+        "focused pane still focused"
+    );
+}
+
+#[test]
+pub fn pane_without_frame_stays_without_frame_when_frames_are_toggled() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    let new_pane_id = PaneId::Terminal(2);
+    tab.new_pane(new_pane_id, None, Some(false), None, Some(client_id))
+        .unwrap();
+    tab.remove_pane_frame(new_pane_id).unwrap();
+    tab.set_pane_frames(false);
+    tab.set_pane_frames(true);
+    let frameless_pane = tab.tiled_panes.get_pane(new_pane_id).unwrap();
+    assert_eq!(
+        frameless_pane.get_content_columns(),
+        frameless_pane.cols(),
+        "content of the frameless pane takes up all of its columns"
+    );
+    let framed_pane = tab.tiled_panes.get_pane(PaneId::Terminal(1)).unwrap();
+    assert_eq!(
+        framed_pane.get_content_columns(),
+        framed_pane.cols() - 2,
+        "other panes get their frame back"
+    );
+}
+
+#[test]
+pub fn toggle_frame_of_focused_pane_only() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    let new_pane_id = PaneId::Terminal(2);
+    tab.new_pane(new_pane_id, None, Some(false), None, Some(client_id))
+        .unwrap();
+    tab.set_pane_frames(true);
+    tab.toggle_active_pane_frame(client_id).unwrap();
+    let focused_pane = tab.tiled_panes.get_pane(new_pane_id).unwrap();
+    assert!(focused_pane.borderless(), "focused pane lost its frame");
+    assert_eq!(
+        focused_pane.get_content_columns(),
+        focused_pane.cols(),
+        "content of the frameless pane takes up all of its columns"
+    );
+    let other_pane = tab.tiled_panes.get_pane(PaneId::Terminal(1)).unwrap();
+    assert!(!other_pane.borderless(), "other pane keeps its frame");
+    tab.toggle_active_pane_frame(client_id).unwrap();
+    let focused_pane = tab.tiled_panes.get_pane(new_pane_id).unwrap();
+    assert_eq!(
+        focused_pane.get_content_columns(),
+        focused_pane.cols() - 2,
+        "focused pane gets its frame back"
+    );
+}
+
+#[test]
+pub fn snap_floating_pane_with_aspect_ratio() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    let new_pane_id = PaneId::Terminal(2);
+    tab.new_pane(new_pane_id, None, Some(true), None, Some(client_id))
+        .unwrap();
+    tab.snap_floating_pane(
+        new_pane_id,
+        &FloatingPanePlacement {
+            snap: Some(FloatingSnap::TopRight),
+            width: Some(60),
+            height: None,
+            aspect: Some(AspectRatio { cols: 2, rows: 1 }),
+        },
+    )
+    .unwrap();
+    let snapped_pane = tab.floating_panes.get_pane(new_pane_id).unwrap();
+    assert_eq!(
+        snapped_pane.rows(),
+        20,
+        "Pane height is clamped to the screen"
+    );
+    assert_eq!(
+        snapped_pane.cols(),
+        40,
+        "Pane width is shrunk to keep the aspect ratio"
+    );
+    assert_eq!(
+        snapped_pane.x(),
+        81,
+        "Pane is docked against the right edge"
+    );
+}
+
 #[test]
 fn switch_to_next_pane_fullscreen() {
     let size = Size {
@@ -2839,6 +3268,63 @@ pub fn close_pane_with_multiple_panes_to_the_right_away_from_screen_edges() {
     );
 }
 
+#[test]
+pub fn focus_most_recently_active_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    for i in 2..4 {
+        let new_pane_id = PaneId::Terminal(i);
+        tab.new_pane(new_pane_id, None, None, None, Some(1))
+            .unwrap();
+    }
+    tab.handle_pty_bytes(2, "I am some build output".as_bytes().to_vec())
+        .unwrap();
+    assert_eq!(
+        tab.get_active_pane_id(1),
+        Some(PaneId::Terminal(3)),
+        "last opened pane is focused"
+    );
+    tab.focus_most_recently_active_pane(1);
+    assert_eq!(
+        tab.get_active_pane_id(1),
+        Some(PaneId::Terminal(2)),
+        "pane that most recently produced output is focused"
+    );
+}
+
+#[test]
+pub fn scroll_active_pane_to_scrollback_line() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let mut output = String::new();
+    for line in 0..50 {
+        output.push_str(&format!("line {}\n\r", line));
+    }
+    tab.handle_pty_bytes(1, output.as_bytes().to_vec()).unwrap();
+    let top_line = |tab: &mut Tab| {
+        let active_pane = tab.get_active_pane_or_floating_pane_mut(1).unwrap();
+        let dump = active_pane.dump_screen(1, false, false);
+        dump.lines().next().unwrap().to_owned()
+    };
+    tab.scroll_active_terminal_to_line(10, 1).unwrap();
+    assert_eq!(top_line(&mut tab), "line 10", "scrolled to the given line");
+    tab.scroll_active_terminal_to_line(0, 1).unwrap();
+    assert_eq!(top_line(&mut tab), "line 0", "scrolled to the oldest line");
+    tab.scroll_active_terminal_to_line(1000, 1).unwrap();
+    assert!(
+        !tab.get_active_pane_or_floating_pane_mut(1)
+            .unwrap()
+            .is_scrolled(),
+        "lines past the end of the buffer scroll to the bottom"
+    );
+}
+
 #[test]
 pub fn move_focus_down() {
     let size = Size {
@@ -3060,6 +3546,50 @@ pub fn move_active_pane_down() {
     );
 }
 
+#[test]
+pub fn rotate_panes_forward_and_backward() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), None, 1).unwrap();
+    tab.vertical_split(PaneId::Terminal(3), None, 1).unwrap();
+    let x_of = |tab: &Tab, id: u32| tab.tiled_panes.get_pane(PaneId::Terminal(id)).unwrap().x();
+    let (x_1, x_2, x_3) = (x_of(&tab, 1), x_of(&tab, 2), x_of(&tab, 3));
+    assert!(x_1 < x_2 && x_2 < x_3, "panes are laid out left to right");
+
+    tab.rotate_panes(false);
+    assert_eq!(
+        (x_of(&tab, 1), x_of(&tab, 2), x_of(&tab, 3)),
+        (x_2, x_3, x_1),
+        "every pane moved into the slot of the next one, the last one wrapping around"
+    );
+
+    tab.rotate_panes(true);
+    assert_eq!(
+        (x_of(&tab, 1), x_of(&tab, 2), x_of(&tab, 3)),
+        (x_1, x_2, x_3),
+        "rotating backwards undid the rotation"
+    );
+}
+
+#[test]
+pub fn rotate_panes_with_a_single_pane_does_nothing() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let geom = tab.get_active_pane(1).unwrap().position_and_size();
+    tab.rotate_panes(false);
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().position_and_size(),
+        geom,
+        "the only pane stayed in place"
+    );
+}
+
 #[test]
 pub fn move_active_pane_down_to_the_most_recently_used_position() {
     let size = Size {
@@ -3087,189 +3617,615 @@ pub fn move_active_pane_down_to_the_most_recently_used_position() {
         91,
         "Active pane x position"
     );
-    assert_eq!(
-        tab.get_active_pane(1).unwrap().pid(),
-        PaneId::Terminal(1),
-        "Active pane PaneId"
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().pid(),
+        PaneId::Terminal(1),
+        "Active pane PaneId"
+    );
+}
+
+#[test]
+pub fn move_active_pane_up() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let new_pane_id = PaneId::Terminal(2);
+
+    tab.horizontal_split(new_pane_id, None, 1).unwrap();
+    tab.move_active_pane_up(1);
+
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().y(),
+        0,
+        "Active pane is the top one"
+    );
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().pid(),
+        PaneId::Terminal(2),
+        "Active pane is the top one"
+    );
+}
+
+#[test]
+pub fn move_active_pane_up_to_the_most_recently_used_position() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let new_pane_id_1 = PaneId::Terminal(2);
+    let new_pane_id_2 = PaneId::Terminal(3);
+    let new_pane_id_3 = PaneId::Terminal(4);
+
+    tab.horizontal_split(new_pane_id_1, None, 1).unwrap();
+    tab.move_focus_up(1).unwrap();
+    tab.vertical_split(new_pane_id_2, None, 1).unwrap();
+    tab.vertical_split(new_pane_id_3, None, 1).unwrap();
+    tab.move_focus_down(1).unwrap();
+    tab.move_active_pane_up(1);
+
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().y(),
+        0,
+        "Active pane y position"
+    );
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().x(),
+        91,
+        "Active pane x position"
+    );
+
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().pid(),
+        PaneId::Terminal(2),
+        "Active pane PaneId"
+    );
+}
+
+#[test]
+pub fn move_active_pane_left() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let new_pane_id = PaneId::Terminal(2);
+
+    tab.vertical_split(new_pane_id, None, 1).unwrap();
+    tab.move_active_pane_left(1);
+
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().x(),
+        0,
+        "Active pane is the left one"
+    );
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().pid(),
+        PaneId::Terminal(2),
+        "Active pane is the left one"
+    );
+}
+
+#[test]
+pub fn move_active_pane_left_to_the_most_recently_used_position() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let new_pane_id_1 = PaneId::Terminal(2);
+    let new_pane_id_2 = PaneId::Terminal(3);
+    let new_pane_id_3 = PaneId::Terminal(4);
+
+    tab.vertical_split(new_pane_id_1, None, 1).unwrap();
+    tab.move_focus_left(1).unwrap();
+    tab.horizontal_split(new_pane_id_2, None, 1).unwrap();
+    tab.horizontal_split(new_pane_id_3, None, 1).unwrap();
+    tab.move_focus_right(1).unwrap();
+    tab.move_active_pane_left(1);
+
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().y(),
+        15,
+        "Active pane y position"
+    );
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().x(),
+        0,
+        "Active pane x position"
+    );
+
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().pid(),
+        PaneId::Terminal(2),
+        "Active pane PaneId"
+    );
+}
+
+#[test]
+pub fn move_active_pane_right() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let new_pane_id = PaneId::Terminal(2);
+
+    tab.vertical_split(new_pane_id, None, 1).unwrap();
+    tab.move_focus_left(1).unwrap();
+    tab.move_active_pane_right(1);
+
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().x(),
+        61,
+        "Active pane is the right one"
+    );
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().pid(),
+        PaneId::Terminal(1),
+        "Active pane is the right one"
+    );
+}
+
+#[test]
+pub fn move_active_pane_right_to_the_most_recently_used_position() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let new_pane_id_1 = PaneId::Terminal(2);
+    let new_pane_id_2 = PaneId::Terminal(3);
+    let new_pane_id_3 = PaneId::Terminal(4);
+
+    tab.vertical_split(new_pane_id_1, None, 1).unwrap();
+    tab.horizontal_split(new_pane_id_2, None, 1).unwrap();
+    tab.horizontal_split(new_pane_id_3, None, 1).unwrap();
+    tab.move_focus_left(1).unwrap();
+    tab.move_active_pane_right(1);
+
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().y(),
+        15,
+        "Active pane y position"
+    );
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().x(),
+        61,
+        "Active pane x position"
+    );
+    assert_eq!(
+        tab.get_active_pane(1).unwrap().pid(),
+        PaneId::Terminal(1),
+        "Active pane Paneid"
+    );
+}
+
+#[test]
+pub fn balance_panes_horizontally() {
+    // ┌─────┬───────┐                  ┌──────┬──────┐
+    // │     │███████│                  │      │██████│
+    // │     │███████│ ==balance=hor==> │      │██████│
+    // │     │███████│                  │      │██████│
+    // └─────┴───────┘                  └──────┴──────┘
+    // █ == focused pane
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let new_pane_id = PaneId::Terminal(2);
+    tab.vertical_split(new_pane_id, None, 1).unwrap();
+    tab_resize_left(&mut tab, 1);
+    tab_resize_left(&mut tab, 1);
+    let first_pane_cols = |tab: &Tab| {
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize()
+    };
+    let second_pane_cols = |tab: &Tab| {
+        tab.tiled_panes
+            .panes
+            .get(&new_pane_id)
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize()
+    };
+    assert!(
+        second_pane_cols(&tab) > first_pane_cols(&tab) + 1,
+        "panes are unbalanced before balancing"
+    );
+    tab.balance_panes_axis(false).unwrap();
+    assert!(
+        (first_pane_cols(&tab) as isize - second_pane_cols(&tab) as isize).abs() <= 1,
+        "pane widths are balanced"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&new_pane_id)
+            .unwrap()
+            .position_and_size()
+            .rows
+            .as_usize(),
+        20,
+        "pane heights are untouched"
+    );
+}
+
+#[test]
+pub fn apply_client_layout_to_existing_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), None, client_id)
+        .unwrap();
+    let mut layout = TiledPaneLayout::default();
+    layout.children_split_direction = SplitDirection::Horizontal;
+    layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    tab.apply_client_layout(layout, vec![], client_id).unwrap();
+    let pane_geom = |tab: &Tab, id: u32| {
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(id))
+            .unwrap()
+            .position_and_size()
+    };
+    assert_eq!(
+        tab.tiled_panes.panes.len(),
+        2,
+        "no panes were added or closed"
+    );
+    assert_eq!(pane_geom(&tab, 1).cols.as_usize(), 121);
+    assert_eq!(pane_geom(&tab, 2).cols.as_usize(), 121);
+    assert_ne!(
+        pane_geom(&tab, 1).y,
+        pane_geom(&tab, 2).y,
+        "panes are now on top of each other"
+    );
+}
+
+#[test]
+pub fn tiled_pane_size_constraints_are_honored() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    let new_pane_id = PaneId::Terminal(2);
+    tab.vertical_split(new_pane_id, None, client_id).unwrap();
+    let pane_cols = |tab: &Tab, pane_id: PaneId| {
+        tab.tiled_panes
+            .panes
+            .get(&pane_id)
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize()
+    };
+    tab.set_pane_size_constraints(
+        client_id,
+        SizeConstraints {
+            max_cols: Some(30),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert!(
+        pane_cols(&tab, new_pane_id) <= 30,
+        "pane shrinks to its maximum width, got: {}",
+        pane_cols(&tab, new_pane_id)
+    );
+    assert_eq!(
+        pane_cols(&tab, PaneId::Terminal(1)) + pane_cols(&tab, new_pane_id),
+        121,
+        "other pane takes up the rest of the space"
+    );
+    tab_resize_left(&mut tab, client_id);
+    assert!(
+        pane_cols(&tab, new_pane_id) <= 30,
+        "pane does not grow beyond its maximum width when resized"
+    );
+    tab.set_pane_size_constraints(
+        client_id,
+        SizeConstraints {
+            min_cols: Some(100),
+            max_cols: Some(30),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert!(
+        pane_cols(&tab, new_pane_id) >= 99,
+        "minimum width wins over a conflicting maximum width, got: {}",
+        pane_cols(&tab, new_pane_id)
+    );
+}
+
+#[test]
+pub fn set_tiled_pane_size_in_percent() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    let new_pane_id = PaneId::Terminal(2);
+    tab.vertical_split(new_pane_id, None, client_id).unwrap();
+    let pane_cols = |tab: &Tab, pane_id: PaneId| {
+        tab.tiled_panes
+            .panes
+            .get(&pane_id)
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize()
+    };
+    tab.set_pane_size_in_percent(client_id, Some(70), None)
+        .unwrap();
+    assert!(
+        (pane_cols(&tab, new_pane_id) as isize - 85).abs() <= 1,
+        "focused pane takes 70% of the width, got: {}",
+        pane_cols(&tab, new_pane_id)
+    );
+    tab.set_pane_size_in_percent(client_id, Some(100), None)
+        .unwrap();
+    assert!(
+        pane_cols(&tab, new_pane_id) > 85,
+        "focused pane grows as far as possible"
+    );
+    assert!(
+        pane_cols(&tab, PaneId::Terminal(1)) >= MIN_TERMINAL_WIDTH,
+        "other pane keeps its minimum width"
     );
 }
 
 #[test]
-pub fn move_active_pane_up() {
+pub fn balance_all_tiled_panes() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
+    let client_id = 1;
     let mut tab = create_new_tab(size);
-    let new_pane_id = PaneId::Terminal(2);
-
-    tab.horizontal_split(new_pane_id, None, 1).unwrap();
-    tab.move_active_pane_up(1);
-
+    tab.vertical_split(PaneId::Terminal(2), None, client_id)
+        .unwrap();
+    tab_resize_left(&mut tab, client_id);
+    tab_resize_left(&mut tab, client_id);
+    tab.horizontal_split(PaneId::Terminal(3), None, client_id)
+        .unwrap();
+    tab_resize_up(&mut tab, client_id);
+    tab_resize_up(&mut tab, client_id);
+    let pane_geom = |tab: &Tab, id: u32| {
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(id))
+            .unwrap()
+            .position_and_size()
+    };
+    assert!(
+        pane_geom(&tab, 2).cols.as_usize() > pane_geom(&tab, 1).cols.as_usize() + 1,
+        "pane widths are unbalanced before balancing"
+    );
+    assert!(
+        (pane_geom(&tab, 2).rows.as_usize() as isize - pane_geom(&tab, 3).rows.as_usize() as isize)
+            .abs()
+            > 1,
+        "pane heights are unbalanced before balancing"
+    );
+    tab.balance_panes().unwrap();
+    assert!(
+        (pane_geom(&tab, 1).cols.as_usize() as isize - pane_geom(&tab, 2).cols.as_usize() as isize)
+            .abs()
+            <= 1,
+        "pane widths are balanced"
+    );
+    assert!(
+        (pane_geom(&tab, 2).rows.as_usize() as isize - pane_geom(&tab, 3).rows.as_usize() as isize)
+            .abs()
+            <= 1,
+        "pane heights are balanced"
+    );
     assert_eq!(
-        tab.get_active_pane(1).unwrap().y(),
-        0,
-        "Active pane is the top one"
+        pane_geom(&tab, 2).cols.as_usize(),
+        pane_geom(&tab, 3).cols.as_usize(),
+        "panes split on top of each other keep a shared width"
     );
     assert_eq!(
-        tab.get_active_pane(1).unwrap().pid(),
-        PaneId::Terminal(2),
-        "Active pane is the top one"
+        pane_geom(&tab, 1).rows.as_usize(),
+        20,
+        "pane that takes the full height keeps it"
     );
 }
 
 #[test]
-pub fn move_active_pane_up_to_the_most_recently_used_position() {
+pub fn set_tiled_pane_size_in_cells() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
+    let client_id = 1;
     let mut tab = create_new_tab(size);
-    let new_pane_id_1 = PaneId::Terminal(2);
-    let new_pane_id_2 = PaneId::Terminal(3);
-    let new_pane_id_3 = PaneId::Terminal(4);
-
-    tab.horizontal_split(new_pane_id_1, None, 1).unwrap();
-    tab.move_focus_up(1).unwrap();
-    tab.vertical_split(new_pane_id_2, None, 1).unwrap();
-    tab.vertical_split(new_pane_id_3, None, 1).unwrap();
-    tab.move_focus_down(1).unwrap();
-    tab.move_active_pane_up(1);
-
-    assert_eq!(
-        tab.get_active_pane(1).unwrap().y(),
-        0,
-        "Active pane y position"
-    );
-    assert_eq!(
-        tab.get_active_pane(1).unwrap().x(),
-        91,
-        "Active pane x position"
+    let new_pane_id = PaneId::Terminal(2);
+    tab.vertical_split(new_pane_id, None, client_id).unwrap();
+    tab.set_pane_size(client_id, Some(80), None).unwrap();
+    let new_pane_geom = tab
+        .tiled_panes
+        .panes
+        .get(&new_pane_id)
+        .unwrap()
+        .position_and_size();
+    assert!(
+        (new_pane_geom.cols.as_usize() as isize - 80).abs() <= 1,
+        "focused pane width is set"
+    );
+    assert_eq!(
+        new_pane_geom.rows.as_usize(),
+        20,
+        "pane height is untouched"
     );
-
+    let first_pane_geom = tab
+        .tiled_panes
+        .panes
+        .get(&PaneId::Terminal(1))
+        .unwrap()
+        .position_and_size();
     assert_eq!(
-        tab.get_active_pane(1).unwrap().pid(),
-        PaneId::Terminal(2),
-        "Active pane PaneId"
+        first_pane_geom.cols.as_usize() + new_pane_geom.cols.as_usize(),
+        121,
+        "neighbor pane takes up the rest of the space"
     );
 }
 
 #[test]
-pub fn move_active_pane_left() {
+pub fn set_floating_pane_size_in_cells_is_clamped_to_the_viewport() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
+    let client_id = 1;
     let mut tab = create_new_tab(size);
     let new_pane_id = PaneId::Terminal(2);
-
-    tab.vertical_split(new_pane_id, None, 1).unwrap();
-    tab.move_active_pane_left(1);
-
-    assert_eq!(
-        tab.get_active_pane(1).unwrap().x(),
-        0,
-        "Active pane is the left one"
-    );
+    tab.new_pane(new_pane_id, None, Some(true), None, Some(client_id))
+        .unwrap();
+    tab.set_pane_size(client_id, Some(200), Some(0)).unwrap();
+    let floating_pane = tab.floating_panes.get_pane(new_pane_id).unwrap();
+    assert_eq!(floating_pane.x(), 0, "pane is moved back into the viewport");
     assert_eq!(
-        tab.get_active_pane(1).unwrap().pid(),
-        PaneId::Terminal(2),
-        "Active pane is the left one"
+        floating_pane.cols(),
+        121,
+        "width is clamped to the viewport"
     );
+    assert_eq!(floating_pane.rows(), 1, "height is at least one cell");
 }
 
 #[test]
-pub fn move_active_pane_left_to_the_most_recently_used_position() {
+pub fn new_pane_at_fraction_splits_the_whole_tab() {
+    // ┌─────┬─────┐                  ┌────┬──┬───┐
+    // │     │     │                  │    │  │███│
+    // │     │     │ ==right=at=75%=> │    │  │███│
+    // │     │     │                  │    │  │███│
+    // └─────┴─────┘                  └────┴──┴───┘
+    // █ == new (focused) pane
     let size = Size {
-        cols: 121,
+        cols: 120,
         rows: 20,
     };
     let mut tab = create_new_tab(size);
-    let new_pane_id_1 = PaneId::Terminal(2);
-    let new_pane_id_2 = PaneId::Terminal(3);
-    let new_pane_id_3 = PaneId::Terminal(4);
-
-    tab.vertical_split(new_pane_id_1, None, 1).unwrap();
-    tab.move_focus_left(1).unwrap();
-    tab.horizontal_split(new_pane_id_2, None, 1).unwrap();
-    tab.horizontal_split(new_pane_id_3, None, 1).unwrap();
-    tab.move_focus_right(1).unwrap();
-    tab.move_active_pane_left(1);
-
+    tab.vertical_split(PaneId::Terminal(2), None, 1).unwrap();
+    let new_pane_id = PaneId::Terminal(3);
+    tab.new_pane_at_fraction(new_pane_id, None, Direction::Right, 0.75, 1)
+        .unwrap();
+    let geom = |tab: &Tab, pane_id: PaneId| {
+        tab.tiled_panes
+            .panes
+            .get(&pane_id)
+            .unwrap()
+            .position_and_size()
+    };
     assert_eq!(
-        tab.get_active_pane(1).unwrap().y(),
-        15,
-        "Active pane y position"
+        geom(&tab, new_pane_id).x,
+        90,
+        "new pane starts at 75% of the tab"
     );
     assert_eq!(
-        tab.get_active_pane(1).unwrap().x(),
-        0,
-        "Active pane x position"
+        geom(&tab, new_pane_id).cols.as_usize(),
+        30,
+        "new pane width"
     );
-
     assert_eq!(
-        tab.get_active_pane(1).unwrap().pid(),
-        PaneId::Terminal(2),
-        "Active pane PaneId"
+        geom(&tab, new_pane_id).rows.as_usize(),
+        20,
+        "new pane height"
+    );
+    assert_eq!(
+        geom(&tab, PaneId::Terminal(1)).cols.as_usize()
+            + geom(&tab, PaneId::Terminal(2)).cols.as_usize(),
+        90,
+        "existing panes are squeezed into the rest of the tab"
+    );
+    assert_eq!(
+        tab.get_active_pane_id(1),
+        Some(new_pane_id),
+        "new pane is focused"
     );
 }
 
 #[test]
-pub fn move_active_pane_right() {
+pub fn new_pane_at_fraction_is_clamped_to_minimum_size() {
     let size = Size {
-        cols: 121,
+        cols: 120,
         rows: 20,
     };
     let mut tab = create_new_tab(size);
     let new_pane_id = PaneId::Terminal(2);
+    tab.new_pane_at_fraction(new_pane_id, None, Direction::Down, 0.99, 1)
+        .unwrap();
+    let new_pane_geom = tab
+        .tiled_panes
+        .panes
+        .get(&new_pane_id)
+        .unwrap()
+        .position_and_size();
+    assert_eq!(
+        new_pane_geom.rows.as_usize(),
+        MIN_TERMINAL_HEIGHT,
+        "new pane is clamped to the minimum height"
+    );
+    assert_eq!(new_pane_geom.y + new_pane_geom.rows.as_usize(), 20);
+}
 
-    tab.vertical_split(new_pane_id, None, 1).unwrap();
-    tab.move_focus_left(1).unwrap();
-    tab.move_active_pane_right(1);
-
-    assert_eq!(
-        tab.get_active_pane(1).unwrap().x(),
-        61,
-        "Active pane is the right one"
-    );
-    assert_eq!(
-        tab.get_active_pane(1).unwrap().pid(),
-        PaneId::Terminal(1),
-        "Active pane is the right one"
+#[test]
+pub fn restart_watched_command_reruns_held_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let pane_id = PaneId::Terminal(1);
+    let is_first_run = false;
+    tab.hold_pane(pane_id, Some(1), is_first_run, RunCommand::default());
+    assert!(
+        tab.tiled_panes.panes.get(&pane_id).unwrap().is_held(),
+        "pane is held after its command exited"
+    );
+    tab.restart_watched_command(pane_id).unwrap();
+    assert!(
+        !tab.tiled_panes.panes.get(&pane_id).unwrap().is_held(),
+        "pane is released to rerun its command"
     );
 }
 
 #[test]
-pub fn move_active_pane_right_to_the_most_recently_used_position() {
+pub fn restart_watched_command_reruns_running_pane_once_it_exits() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
     let mut tab = create_new_tab(size);
-    let new_pane_id_1 = PaneId::Terminal(2);
-    let new_pane_id_2 = PaneId::Terminal(3);
-    let new_pane_id_3 = PaneId::Terminal(4);
-
-    tab.vertical_split(new_pane_id_1, None, 1).unwrap();
-    tab.horizontal_split(new_pane_id_2, None, 1).unwrap();
-    tab.horizontal_split(new_pane_id_3, None, 1).unwrap();
-    tab.move_focus_left(1).unwrap();
-    tab.move_active_pane_right(1);
-
-    assert_eq!(
-        tab.get_active_pane(1).unwrap().y(),
-        15,
-        "Active pane y position"
-    );
-    assert_eq!(
-        tab.get_active_pane(1).unwrap().x(),
-        61,
-        "Active pane x position"
-    );
-    assert_eq!(
-        tab.get_active_pane(1).unwrap().pid(),
-        PaneId::Terminal(1),
-        "Active pane Paneid"
+    let pane_id = PaneId::Terminal(1);
+    tab.restart_watched_command(pane_id).unwrap();
+    let is_first_run = false;
+    tab.hold_pane(pane_id, Some(1), is_first_run, RunCommand::default());
+    assert!(
+        !tab.tiled_panes.panes.get(&pane_id).unwrap().is_held(),
+        "pane is rerun instead of held once its killed command exits"
+    );
+    tab.hold_pane(pane_id, Some(1), is_first_run, RunCommand::default());
+    assert!(
+        tab.tiled_panes.panes.get(&pane_id).unwrap().is_held(),
+        "pane is held as usual the next time its command exits"
     );
 }
 
@@ -14408,3 +15364,226 @@ fn correctly_resize_frameless_panes_on_pane_close() {
     let content_size = (pane.get_content_columns(), pane.get_content_rows());
     assert_eq!(content_size, (cols, rows));
 }
+
+#[test]
+fn hiding_chrome_lets_panes_take_up_the_whole_display_and_showing_it_restores_them() {
+    let size = Size { cols: 50, rows: 20 };
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Horizontal;
+    let mut tab_bar = TiledPaneLayout::default();
+    tab_bar.split_size = Some(SplitSize::Fixed(1));
+    tab_bar.borderless = true;
+    let mut panes = TiledPaneLayout::default();
+    panes.children_split_direction = SplitDirection::Vertical;
+    panes.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut status_bar = TiledPaneLayout::default();
+    status_bar.split_size = Some(SplitSize::Fixed(2));
+    status_bar.borderless = true;
+    initial_layout.children = vec![tab_bar, panes, status_bar];
+    let mut tab = create_new_tab_with_layout(size, initial_layout);
+    let geoms = |tab: &Tab| -> HashMap<PaneId, PaneGeom> {
+        tab.tiled_panes
+            .panes
+            .iter()
+            .map(|(pane_id, pane)| (*pane_id, pane.position_and_size()))
+            .collect()
+    };
+    let geoms_before_hiding_chrome = geoms(&tab);
+
+    tab.hide_chrome().unwrap();
+    assert!(tab.chrome_is_hidden(), "chrome is hidden");
+    let mut pane_ids: Vec<PaneId> = tab.tiled_panes.panes.keys().copied().collect();
+    pane_ids.sort();
+    assert_eq!(
+        pane_ids,
+        vec![PaneId::Terminal(1), PaneId::Terminal(2)],
+        "only the panes inside the viewport are left"
+    );
+    for pane in tab.tiled_panes.panes.values() {
+        let geom = pane.position_and_size();
+        assert_eq!(geom.y, 0, "pane starts at the top of the display");
+        assert_eq!(geom.rows.as_usize(), 20, "pane spans the whole display");
+    }
+
+    tab.show_chrome().unwrap();
+    assert!(!tab.chrome_is_hidden(), "chrome is shown");
+    assert_eq!(
+        geoms(&tab),
+        geoms_before_hiding_chrome,
+        "layout restored exactly"
+    );
+}
+
+#[test]
+fn text_is_filtered_through_shell_command() {
+    let filtered_text = filter_through(
+        "sort | uniq",
+        "banana\napple\nbanana\n",
+        Duration::from_secs(5),
+    )
+    .unwrap();
+    assert_eq!(filtered_text, "apple\nbanana\n");
+}
+
+#[test]
+fn failing_filter_command_is_an_error() {
+    let filtered_text = filter_through("cat; exit 3", "text", Duration::from_secs(5));
+    assert!(filtered_text.is_err(), "non-zero exit status is an error");
+}
+
+#[test]
+fn filter_command_that_does_not_finish_in_time_is_an_error() {
+    let started_at = Instant::now();
+    let filtered_text = filter_through("sleep 10", "text", Duration::from_millis(100));
+    assert!(filtered_text.is_err(), "timing out is an error");
+    assert!(
+        started_at.elapsed() < Duration::from_secs(5),
+        "filter was killed when timing out"
+    );
+}
+
+#[test]
+fn filter_command_that_leaves_its_output_open_is_an_error() {
+    let started_at = Instant::now();
+    let filtered_text = filter_through("sleep 10 & echo text", "", Duration::from_millis(300));
+    assert!(
+        filtered_text.is_err(),
+        "output not closed in time is an error"
+    );
+    assert!(
+        started_at.elapsed() < Duration::from_secs(5),
+        "did not wait for the background process"
+    );
+}
+
+#[test]
+fn pipeline_filter_command_that_does_not_finish_in_time_is_killed() {
+    let started_at = Instant::now();
+    let filtered_text = filter_through("cat | sleep 10", "text", Duration::from_millis(100));
+    assert!(filtered_text.is_err(), "timing out is an error");
+    assert!(
+        started_at.elapsed() < Duration::from_secs(5),
+        "the whole pipeline was killed"
+    );
+}
+
+#[test]
+fn focus_pane_at_index_in_tiling_order() {
+    let size = Size { cols: 50, rows: 20 };
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Horizontal;
+    let mut top = TiledPaneLayout::default();
+    top.children_split_direction = SplitDirection::Vertical;
+    top.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    initial_layout.children = vec![top, TiledPaneLayout::default()];
+    let mut tab = create_new_tab_with_layout(size, initial_layout);
+    let client_id = 1;
+
+    tab.focus_pane_at_index(2, client_id).unwrap();
+    assert_eq!(
+        tab.get_active_pane_id(client_id),
+        Some(PaneId::Terminal(1)),
+        "second pane is the top right one"
+    );
+    tab.focus_pane_at_index(3, client_id).unwrap();
+    assert_eq!(
+        tab.get_active_pane_id(client_id),
+        Some(PaneId::Terminal(2)),
+        "third pane is the bottom one"
+    );
+    tab.focus_pane_at_index(1, client_id).unwrap();
+    assert_eq!(
+        tab.get_active_pane_id(client_id),
+        Some(PaneId::Terminal(0)),
+        "first pane is the top left one"
+    );
+    tab.focus_pane_at_index(10, client_id).unwrap();
+    assert_eq!(
+        tab.get_active_pane_id(client_id),
+        Some(PaneId::Terminal(2)),
+        "index out of range focuses the last pane"
+    );
+}
+
+#[test]
+fn write_to_panes_matching_command() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let client_id = 1;
+    tab.vertical_split(PaneId::Terminal(2), None, client_id)
+        .unwrap();
+    tab.vertical_split(PaneId::Terminal(3), None, client_id)
+        .unwrap();
+    for (terminal_id, title) in [(1, "htop"), (2, "vim notes.txt"), (3, "top")] {
+        tab.tiled_panes
+            .get_pane_mut(PaneId::Terminal(terminal_id))
+            .unwrap()
+            .set_title(title.to_owned());
+    }
+    let (to_pty_writer, pty_writer_receiver): ChannelWithContext<PtyWriteInstruction> =
+        channels::unbounded();
+    tab.senders
+        .replace_to_pty_writer(SenderWithContext::new(to_pty_writer));
+    let written_terminal_ids = |tab: &mut Tab, command_filter: &str| {
+        tab.write_to_panes_matching(command_filter, b"q".to_vec(), client_id)
+            .unwrap();
+        let mut terminal_ids: Vec<u32> = pty_writer_receiver
+            .try_iter()
+            .filter_map(|(instruction, _)| match instruction {
+                PtyWriteInstruction::Write(_, terminal_id) => Some(terminal_id),
+                _ => None,
+            })
+            .collect();
+        terminal_ids.sort();
+        terminal_ids
+    };
+    assert_eq!(
+        written_terminal_ids(&mut tab, "top"),
+        vec![1, 3],
+        "a filter without wildcards matches a part of the command"
+    );
+    assert_eq!(
+        written_terminal_ids(&mut tab, "t?p"),
+        vec![3],
+        "a glob matches the whole command"
+    );
+    assert_eq!(
+        written_terminal_ids(&mut tab, "vim *"),
+        vec![2],
+        "a glob matches the command with its arguments"
+    );
+    assert!(
+        written_terminal_ids(&mut tab, "emacs").is_empty(),
+        "nothing is written without a matching pane"
+    );
+}
+
+#[test]
+fn clear_active_pane_name() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    let client_id = 1;
+    tab.tiled_panes
+        .get_pane_mut(PaneId::Terminal(1))
+        .unwrap()
+        .set_title("bash".to_owned());
+    tab.update_active_pane_name("logs".as_bytes().to_vec(), client_id)
+        .unwrap();
+    assert_eq!(
+        tab.get_active_pane(client_id).unwrap().current_title(),
+        "logs",
+        "pane is named"
+    );
+    tab.clear_active_pane_name(client_id).unwrap();
+    assert_eq!(
+        tab.get_active_pane(client_id).unwrap().current_title(),
+        "bash",
+        "pane name is cleared, showing its title again"
+    );
+}
@@ -0,0 +1,29 @@
+use std::process::{Command, Stdio};
+use std::thread;
+
+use zellij_utils::anyhow::{Context, Result};
+
+#[cfg(target_os = "macos")]
+const URL_OPENER: &str = "open";
+#[cfg(not(target_os = "macos"))]
+const URL_OPENER: &str = "xdg-open";
+
+/// Opens `url` with the platform's default application for it, without waiting for it to be
+/// opened. Bare `www.` urls are opened as https.
+pub fn open_url(url: &str) -> Result<()> {
+    let url = if url.starts_with("www.") {
+        format!("https://{}", url)
+    } else {
+        url.to_owned()
+    };
+    let mut child = Command::new(URL_OPENER)
+        .arg(&url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("failed to open '{}' with {}", url, URL_OPENER))?;
+    // reap the opener once it exits so that it does not linger as a zombie
+    thread::spawn(move || child.wait());
+    Ok(())
+}
@@ -5,7 +5,9 @@ use crate::{
 };
 use async_std::{future::timeout as async_timeout, task};
 use std::{
-    os::unix::io::RawFd,
+    io::Write,
+    os::unix::{io::RawFd, net::UnixStream},
+    path::{Path, PathBuf},
     time::{Duration, Instant},
 };
 use zellij_utils::{
@@ -40,8 +42,13 @@ pub(crate) struct TerminalBytes {
     minimum_render_send_time: Option<Duration>,
     buffering_pause: Duration,
     last_render: Instant,
+    output_socket: Option<UnixStream>,
 }
 
+// a consumer of the output socket that does not read for this long is considered disconnected,
+// so that it cannot stall the pane
+const OUTPUT_SOCKET_WRITE_TIMEOUT: Duration = Duration::from_millis(100);
+
 impl TerminalBytes {
     pub fn new(
         pid: RawFd,
@@ -61,8 +68,15 @@ impl TerminalBytes {
             minimum_render_send_time: None,
             buffering_pause: Duration::from_millis(30),
             last_render: Instant::now(),
+            output_socket: None,
         }
     }
+    /// Also stream the bytes read from the pty to the unix socket listening at this path
+    pub fn with_output_socket(mut self, output_socket: Option<PathBuf>) -> Self {
+        self.output_socket =
+            output_socket.and_then(|path| connect_to_output_socket(&path, self.terminal_id));
+        self
+    }
     pub async fn listen(&mut self) -> Result<()> {
         // This function reads bytes from the pty and then sends them as
         // ScreenInstruction::PtyBytes to screen to be parsed there
@@ -98,6 +112,7 @@ impl TerminalBytes {
                     if self.debug {
                         let _ = debug_to_file(bytes, self.pid);
                     }
+                    self.write_to_output_socket(bytes);
                     self.async_send_to_screen(ScreenInstruction::PtyBytes(
                         self.terminal_id,
                         bytes.to_vec(),
@@ -139,6 +154,19 @@ impl TerminalBytes {
 
         Ok(())
     }
+    fn write_to_output_socket(&mut self, bytes: &[u8]) {
+        if let Some(output_socket) = self.output_socket.as_mut() {
+            if let Err(e) = output_socket.write_all(bytes) {
+                // the consumer went away, the pane itself carries on without it
+                log::warn!(
+                    "Stopped streaming output of terminal {} to its output socket: {}",
+                    self.terminal_id,
+                    e
+                );
+                self.output_socket = None;
+            }
+        }
+    }
     async fn async_send_to_screen(
         &self,
         screen_instruction: ScreenInstruction,
@@ -192,3 +220,22 @@ impl TerminalBytes {
         }
     }
 }
+
+fn connect_to_output_socket(path: &Path, terminal_id: u32) -> Option<UnixStream> {
+    let output_socket = UnixStream::connect(path).and_then(|output_socket| {
+        output_socket.set_write_timeout(Some(OUTPUT_SOCKET_WRITE_TIMEOUT))?;
+        Ok(output_socket)
+    });
+    match output_socket {
+        Ok(output_socket) => Some(output_socket),
+        Err(e) => {
+            log::warn!(
+                "Failed to connect terminal {} to output socket {}: {}",
+                terminal_id,
+                path.display(),
+                e
+            );
+            None
+        },
+    }
+}
@@ -5,10 +5,13 @@
 //! prompt's:
 //!
 //! notification's:
+//!
+//! mode help:
 
+pub mod mode_help;
 pub mod prompt;
 
-use crate::ServerInstruction;
+use prompt::PromptInstruction;
 use zellij_utils::errors::prelude::*;
 use zellij_utils::pane_size::Size;
 
@@ -26,6 +29,7 @@ pub trait Overlayable {
 #[derive(Clone, Debug)]
 pub enum OverlayType {
     Prompt(prompt::Prompt),
+    ModeHelp(mode_help::ModeHelp),
 }
 
 impl Overlayable for OverlayType {
@@ -34,6 +38,9 @@ impl Overlayable for OverlayType {
             OverlayType::Prompt(prompt) => prompt
                 .generate_overlay(size)
                 .context("failed to generate VTE output from overlay type"),
+            OverlayType::ModeHelp(mode_help) => mode_help
+                .generate_overlay(size)
+                .context("failed to generate VTE output from overlay type"),
         }
     }
 }
@@ -62,14 +69,16 @@ impl Overlayable for OverlayWindow {
 }
 
 impl Overlay {
-    pub fn prompt_confirm(self) -> Option<Box<ServerInstruction>> {
+    pub fn prompt_confirm(self) -> Option<Box<PromptInstruction>> {
         match self.overlay_type {
             OverlayType::Prompt(p) => p.confirm(),
+            OverlayType::ModeHelp(..) => None,
         }
     }
-    pub fn prompt_deny(self) -> Option<Box<ServerInstruction>> {
+    pub fn prompt_deny(self) -> Option<Box<PromptInstruction>> {
         match self.overlay_type {
             OverlayType::Prompt(p) => p.deny(),
+            OverlayType::ModeHelp(..) => None,
         }
     }
 }
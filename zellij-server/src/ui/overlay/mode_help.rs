@@ -0,0 +1,103 @@
+use zellij_utils::data::{InputMode, Key};
+use zellij_utils::input::actions::Action;
+use zellij_utils::pane_size::Size;
+
+use super::Overlayable;
+use zellij_utils::errors::prelude::*;
+
+use std::fmt::Write;
+
+/// A list of the keybindings of an input mode, drawn in a box in the middle of the screen
+#[derive(Clone, Debug)]
+pub struct ModeHelp {
+    pub mode: InputMode,
+    keybinds: Vec<(Key, Vec<Action>)>,
+}
+
+impl ModeHelp {
+    pub fn new(mode: InputMode, keybinds: Vec<(Key, Vec<Action>)>) -> Self {
+        Self { mode, keybinds }
+    }
+    fn title(&self) -> String {
+        format!(" {:?} mode keybindings, <ESC> to dismiss ", self.mode)
+    }
+    fn lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .keybinds
+            .iter()
+            .map(|(key, actions)| {
+                let actions: Vec<String> = actions
+                    .iter()
+                    .map(|action| format!("{:?}", action))
+                    .collect();
+                format!(" {} => {} ", key, actions.join("; "))
+            })
+            .collect();
+        // keybinds are stored in a map, so we sort them to keep their order stable
+        lines.sort();
+        if lines.is_empty() {
+            lines.push(String::from(" No keybindings "));
+        }
+        lines
+    }
+}
+
+impl Overlayable for ModeHelp {
+    fn generate_overlay(&self, size: Size) -> Result<String> {
+        let err_context = || {
+            format!(
+                "failed to generate VTE output for {:?} mode help",
+                self.mode
+            )
+        };
+
+        let mut output = String::new();
+        if size.rows < 3 || size.cols < 3 {
+            // no room for anything but the borders
+            return Ok(output);
+        }
+        let title = self.title();
+        let mut lines = self.lines();
+        let max_line_count = size.rows - 2;
+        if lines.len() > max_line_count {
+            let hidden_line_count = lines.len() - max_line_count + 1;
+            lines.truncate(max_line_count - 1);
+            lines.push(format!(" ...and {} more ", hidden_line_count));
+        }
+        let width = lines
+            .iter()
+            .chain(std::iter::once(&title))
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0)
+            .min(size.cols - 2);
+        let x = (size.cols - (width + 2)) / 2;
+        let y = (size.rows - (lines.len() + 2)) / 2;
+
+        let top_border = format!("┌{:─<width$}┐", fit(&title, width), width = width);
+        let bottom_border = format!("└{}┘", "─".repeat(width));
+        let rows = std::iter::once(top_border)
+            .chain(
+                lines
+                    .iter()
+                    .map(|line| format!("│{:<width$}│", fit(line, width), width = width)),
+            )
+            .chain(std::iter::once(bottom_border));
+        for (index, row) in rows.enumerate() {
+            write!(
+                &mut output,
+                "\u{1b}[{};{}H\u{1b}[m\u{1b}[48;5;238m{}",
+                y + index + 1,
+                x + 1,
+                row,
+            )
+            .with_context(err_context)?;
+        }
+        output.push_str("\u{1b}[m");
+        Ok(output)
+    }
+}
+
+fn fit(line: &str, width: usize) -> String {
+    line.chars().take(width).collect()
+}
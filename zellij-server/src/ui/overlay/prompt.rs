@@ -1,23 +1,30 @@
 use zellij_utils::pane_size::Size;
 
 use super::{Overlay, OverlayType, Overlayable};
-use crate::{ClientId, ServerInstruction};
+use crate::{screen::ScreenInstruction, ClientId, ServerInstruction};
 use zellij_utils::errors::prelude::*;
 
 use std::fmt::Write;
 
+/// What to do once a prompt is answered
+#[derive(Clone, Debug)]
+pub enum PromptInstruction {
+    Server(ServerInstruction),
+    Screen(ScreenInstruction),
+}
+
 #[derive(Clone, Debug)]
 pub struct Prompt {
     pub message: String,
-    on_confirm: Option<Box<ServerInstruction>>,
-    on_deny: Option<Box<ServerInstruction>>,
+    on_confirm: Option<Box<PromptInstruction>>,
+    on_deny: Option<Box<PromptInstruction>>,
 }
 
 impl Prompt {
     pub fn new(
         message: String,
-        on_confirm: Option<Box<ServerInstruction>>,
-        on_deny: Option<Box<ServerInstruction>>,
+        on_confirm: Option<Box<PromptInstruction>>,
+        on_deny: Option<Box<PromptInstruction>>,
     ) -> Self {
         Self {
             message,
@@ -25,10 +32,10 @@ impl Prompt {
             on_deny,
         }
     }
-    pub fn confirm(self) -> Option<Box<ServerInstruction>> {
+    pub fn confirm(self) -> Option<Box<PromptInstruction>> {
         self.on_confirm
     }
-    pub fn deny(self) -> Option<Box<ServerInstruction>> {
+    pub fn deny(self) -> Option<Box<PromptInstruction>> {
         self.on_deny
     }
 }
@@ -56,7 +63,22 @@ impl Overlayable for Prompt {
 pub fn _generate_quit_prompt(client_id: ClientId) -> Overlay {
     let prompt = Prompt::new(
         (" Do you want to quit zellij? [Y]es / [N]o").to_string(),
-        Some(Box::new(ServerInstruction::ClientExit(client_id))),
+        Some(Box::new(PromptInstruction::Server(
+            ServerInstruction::ClientExit(client_id),
+        ))),
+        None,
+    );
+    Overlay {
+        overlay_type: OverlayType::Prompt(prompt),
+    }
+}
+
+pub fn generate_close_pane_prompt(client_id: ClientId) -> Overlay {
+    let prompt = Prompt::new(
+        (" A process is running in this pane, close it anyway? [Y]es / [N]o").to_string(),
+        Some(Box::new(PromptInstruction::Screen(
+            ScreenInstruction::CloseFocusedPane(client_id),
+        ))),
         None,
     );
     Overlay {
@@ -0,0 +1,61 @@
+use crate::output::CharacterChunk;
+use crate::panes::terminal_character::{TerminalCharacter, RESET_STYLES};
+use zellij_utils::data::PaletteColor;
+use zellij_utils::pane_size::Size;
+
+const HORIZONTAL: char = '─';
+const VERTICAL: char = '│';
+const CORNER: char = '┼';
+const HORIZONTAL_TICK: char = '┬';
+const VERTICAL_TICK: char = '├';
+
+/// Guides along the top and left edges of the display area, with a labeled tick at every 10% of
+/// its width and height
+pub fn ruler_character_chunks(display_area: Size, color: PaletteColor) -> Vec<CharacterChunk> {
+    let styled = |character: char| TerminalCharacter {
+        character,
+        width: 1,
+        styles: RESET_STYLES.foreground(Some(color.into())),
+    };
+    let mut character_chunks = vec![];
+    if display_area.rows == 0 || display_area.cols == 0 {
+        return character_chunks;
+    }
+
+    let mut top_ruler: Vec<char> = vec![HORIZONTAL; display_area.cols];
+    top_ruler[0] = CORNER;
+    for (x, label) in ticks(display_area.cols) {
+        top_ruler[x] = HORIZONTAL_TICK;
+        for (offset, character) in label.chars().enumerate() {
+            if let Some(ruler_character) = top_ruler.get_mut(x + 1 + offset) {
+                *ruler_character = character;
+            }
+        }
+    }
+    character_chunks.push(CharacterChunk::new(
+        top_ruler.into_iter().map(styled).collect(),
+        0,
+        0,
+    ));
+
+    let ticks: Vec<(usize, String)> = ticks(display_area.rows);
+    for y in 1..display_area.rows {
+        let line = match ticks.iter().find(|(tick_y, _)| *tick_y == y) {
+            Some((_, label)) => std::iter::once(VERTICAL_TICK)
+                .chain(label.chars())
+                .take(display_area.cols)
+                .map(styled)
+                .collect(),
+            None => vec![styled(VERTICAL)],
+        };
+        character_chunks.push(CharacterChunk::new(line, 0, y));
+    }
+    character_chunks
+}
+
+fn ticks(length: usize) -> Vec<(usize, String)> {
+    (1..10)
+        .map(|tenth| (length * tenth / 10, format!("{}%", tenth * 10)))
+        .filter(|(position, _)| *position > 0)
+        .collect()
+}
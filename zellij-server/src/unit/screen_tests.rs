@@ -5,34 +5,42 @@ use crate::{
     os_input_output::{AsyncReader, Pid, ServerOsApi},
     route::route_action,
     thread_bus::Bus,
-    ClientId, ServerInstruction, SessionMetaData, ThreadSenders,
+    ClientId, ServerInstruction, SessionMetaData, SessionState, ThreadSenders,
 };
 use insta::assert_snapshot;
 use std::path::PathBuf;
 use zellij_utils::cli::CliAction;
+use zellij_utils::consts::MAX_SESSION_VARS;
 use zellij_utils::data::{Event, Resize};
 use zellij_utils::errors::{prelude::*, ErrorContext};
 use zellij_utils::input::actions::Action;
-use zellij_utils::input::command::{RunCommand, TerminalAction};
+use zellij_utils::input::command::{PaneRunAction, RunCommand, TerminalAction};
 use zellij_utils::input::layout::{
     Layout, Run, RunPlugin, RunPluginLocation, SplitDirection, TiledPaneLayout,
 };
-use zellij_utils::input::options::Options;
+use zellij_utils::input::options::{Options, TimedModeKeypress};
 use zellij_utils::ipc::IpcReceiverWithContext;
 use zellij_utils::pane_size::{Size, SizeInPixels};
+use zellij_utils::position::Position;
 
-use crate::background_jobs::BackgroundJob;
+use crate::background_jobs::{background_jobs_main, BackgroundJob};
 use crate::pty_writer::PtyWriteInstruction;
 use std::env::set_var;
 use std::os::unix::io::RawFd;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, RwLock};
 
-use crate::{plugins::PluginInstruction, pty::PtyInstruction};
+use crate::{
+    plugins::PluginInstruction,
+    pty::{get_default_shell, ClientOrTabIndex, PtyInstruction},
+};
 use zellij_utils::ipc::PixelDimensions;
 
 use zellij_utils::{
     channels::{self, ChannelWithContext, Receiver},
-    data::{Direction, InputMode, ModeInfo, Palette, PluginCapabilities},
+    data::{
+        Direction, FloatingPanePlacement, FloatingSnap, InputMode, ModeInfo, Palette,
+        PaneListFormat, PluginCapabilities,
+    },
     interprocess::local_socket::LocalSocketStream,
     ipc::{ClientAttributes, ClientToServerMsg, ServerToClientMsg},
 };
@@ -119,6 +127,7 @@ fn send_cli_action_to_server(
             client_attributes.clone(),
             default_shell.clone(),
             default_layout.clone(),
+            None,
         )
         .unwrap();
     }
@@ -233,6 +242,8 @@ fn create_new_screen(size: Size) -> Screen {
     let auto_layout = true;
     let session_is_mirrored = true;
     let copy_options = CopyOptions::default();
+    let timed_mode_keypress = TimedModeKeypress::default();
+    let resize_loop_step = 1;
 
     let debug = false;
     let screen = Screen::new(
@@ -244,6 +255,8 @@ fn create_new_screen(size: Size) -> Screen {
         auto_layout,
         session_is_mirrored,
         copy_options,
+        timed_mode_keypress,
+        resize_loop_step,
         debug,
     );
     screen
@@ -321,6 +334,7 @@ impl MockScreen {
             vec![], // floating_panes_layout
             tab_name,
             (vec![], vec![]), // swap layouts
+            false,            // empty
             self.main_client_id,
         ));
         let _ = self.to_screen.send(ScreenInstruction::ApplyLayout(
@@ -352,6 +366,7 @@ impl MockScreen {
             vec![], // floating_panes_layout
             tab_name,
             (vec![], vec![]), // swap layouts
+            false,            // empty
             self.main_client_id,
         ));
         let _ = self.to_screen.send(ScreenInstruction::ApplyLayout(
@@ -538,26 +553,39 @@ fn open_new_tab() {
 }
 
 #[test]
-pub fn switch_to_prev_tab() {
+pub fn toggle_default_split_direction() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
     let mut screen = create_new_screen(size);
 
-    new_tab(&mut screen, 1, 1);
-    new_tab(&mut screen, 2, 2);
-    screen.switch_tab_prev(None, 1).expect("TEST");
-
     assert_eq!(
-        screen.get_active_tab(1).unwrap().position,
-        0,
-        "Active tab switched to previous tab"
+        screen.default_split_direction, None,
+        "new panes go to the biggest available space by default"
+    );
+    screen.toggle_default_split_direction();
+    assert_eq!(
+        screen.default_split_direction,
+        Some(SplitDirection::Vertical),
+        "first toggle splits new panes vertically"
+    );
+    screen.toggle_default_split_direction();
+    assert_eq!(
+        screen.default_split_direction,
+        Some(SplitDirection::Horizontal),
+        "second toggle flips to horizontal"
+    );
+    screen.toggle_default_split_direction();
+    assert_eq!(
+        screen.default_split_direction,
+        Some(SplitDirection::Vertical),
+        "third toggle flips back to vertical"
     );
 }
 
 #[test]
-pub fn switch_to_next_tab() {
+pub fn switch_to_prev_tab() {
     let size = Size {
         cols: 121,
         rows: 20,
@@ -567,255 +595,992 @@ pub fn switch_to_next_tab() {
     new_tab(&mut screen, 1, 1);
     new_tab(&mut screen, 2, 2);
     screen.switch_tab_prev(None, 1).expect("TEST");
-    screen.switch_tab_next(None, 1).expect("TEST");
 
     assert_eq!(
         screen.get_active_tab(1).unwrap().position,
-        1,
-        "Active tab switched to next tab"
+        0,
+        "Active tab switched to previous tab"
     );
 }
 
 #[test]
-pub fn switch_to_tab_name() {
+pub fn move_active_tab_right() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
     let mut screen = create_new_screen(size);
 
-    new_tab(&mut screen, 1, 1);
-    new_tab(&mut screen, 2, 2);
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    new_tab(&mut screen, 3, 2);
+    screen.switch_tab_prev(None, 1).expect("TEST");
+    screen.move_active_tab_right(1).expect("TEST");
 
+    let tab_positions: Vec<usize> = screen.tabs.values().map(|tab| tab.position).collect();
     assert_eq!(
-        screen
-            .switch_active_tab_name("Tab #1".to_string(), 1)
-            .expect("TEST"),
-        false,
-        "Active tab switched to tab by name"
-    );
-    assert_eq!(
-        screen
-            .switch_active_tab_name("Tab #2".to_string(), 1)
-            .expect("TEST"),
-        true,
-        "Active tab switched to tab by name"
+        tab_positions,
+        vec![0, 2, 1],
+        "Active tab swapped with the one to its right"
     );
     assert_eq!(
-        screen
-            .switch_active_tab_name("Tab #3".to_string(), 1)
-            .expect("TEST"),
-        true,
-        "Active tab switched to tab by name"
+        screen.get_active_tab(1).unwrap().index,
+        1,
+        "Moved tab stays focused"
     );
 }
 
 #[test]
-pub fn close_tab() {
+pub fn move_active_tab_left_wraps_around() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
     let mut screen = create_new_screen(size);
 
-    new_tab(&mut screen, 1, 1);
-    new_tab(&mut screen, 2, 2);
-    screen.close_tab(1).expect("TEST");
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    new_tab(&mut screen, 3, 2);
+    screen.go_to_tab(1, 1).expect("TEST");
+    screen.move_active_tab_left(1).expect("TEST");
 
-    assert_eq!(screen.tabs.len(), 1, "Only one tab left");
+    let tab_positions: Vec<usize> = screen.tabs.values().map(|tab| tab.position).collect();
+    assert_eq!(tab_positions, vec![2, 0, 1], "First tab moved to the end");
     assert_eq!(
-        screen.get_active_tab(1).unwrap().position,
+        screen.get_active_tab(1).unwrap().index,
         0,
-        "Active tab switched to previous tab"
+        "Moved tab stays focused"
     );
 }
 
 #[test]
-pub fn close_the_middle_tab() {
+pub fn move_active_tab_right_wraps_around() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
     let mut screen = create_new_screen(size);
 
-    new_tab(&mut screen, 1, 1);
-    new_tab(&mut screen, 2, 2);
-    new_tab(&mut screen, 3, 3);
-    screen.switch_tab_prev(None, 1).expect("TEST");
-    screen.close_tab(1).expect("TEST");
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    new_tab(&mut screen, 3, 2);
+    screen.move_active_tab_right(1).expect("TEST");
 
-    assert_eq!(screen.tabs.len(), 2, "Two tabs left");
+    let tab_positions: Vec<usize> = screen.tabs.values().map(|tab| tab.position).collect();
+    assert_eq!(tab_positions, vec![1, 2, 0], "Last tab moved to the start");
     assert_eq!(
-        screen.get_active_tab(1).unwrap().position,
-        1,
-        "Active tab switched to previous tab"
+        screen.get_active_tab(1).unwrap().index,
+        2,
+        "Moved tab stays focused"
     );
 }
 
 #[test]
-fn move_focus_left_at_left_screen_edge_changes_tab() {
+pub fn move_active_tab_to_index() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
     let mut screen = create_new_screen(size);
 
-    new_tab(&mut screen, 1, 1);
-    new_tab(&mut screen, 2, 2);
-    new_tab(&mut screen, 3, 3);
-    screen.switch_tab_prev(None, 1).expect("TEST");
-    screen.move_focus_left_or_previous_tab(1).expect("TEST");
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    new_tab(&mut screen, 3, 2);
+    new_tab(&mut screen, 4, 3);
+    screen.move_active_tab_to_position(2, 1).expect("TEST");
 
+    let tab_positions: Vec<usize> = screen.tabs.values().map(|tab| tab.position).collect();
     assert_eq!(
-        screen.get_active_tab(1).unwrap().position,
-        0,
-        "Active tab switched to previous"
+        tab_positions,
+        vec![0, 2, 3, 1],
+        "Last tab moved to the second position"
+    );
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().index,
+        3,
+        "Moved tab stays focused"
     );
 }
 
 #[test]
-fn move_focus_right_at_right_screen_edge_changes_tab() {
+pub fn move_active_tab_to_out_of_range_index_clamps_to_the_ends() {
     let size = Size {
         cols: 121,
         rows: 20,
     };
     let mut screen = create_new_screen(size);
 
-    new_tab(&mut screen, 1, 1);
-    new_tab(&mut screen, 2, 2);
-    new_tab(&mut screen, 3, 3);
-    screen.switch_tab_prev(None, 1).expect("TEST");
-    screen.move_focus_right_or_next_tab(1).expect("TEST");
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    new_tab(&mut screen, 3, 2);
+    screen.move_active_tab_to_position(0, 1).expect("TEST");
 
-    assert_eq!(
-        screen.get_active_tab(1).unwrap().position,
-        2,
-        "Active tab switched to next"
-    );
+    let tab_positions: Vec<usize> = screen.tabs.values().map(|tab| tab.position).collect();
+    assert_eq!(tab_positions, vec![1, 2, 0], "Last tab moved to the start");
+
+    screen.move_active_tab_to_position(10, 1).expect("TEST");
+
+    let tab_positions: Vec<usize> = screen.tabs.values().map(|tab| tab.position).collect();
+    assert_eq!(tab_positions, vec![0, 1, 2], "Tab moved back to the end");
 }
 
 #[test]
-pub fn toggle_to_previous_tab_simple() {
-    let position_and_size = Size {
+pub fn empty_tab_is_kept_open_until_its_first_pane_is_added() {
+    let size = Size {
         cols: 121,
         rows: 20,
     };
-    let mut screen = create_new_screen(position_and_size);
+    let mut screen = create_new_screen(size);
+    let client_id = 1;
 
-    new_tab(&mut screen, 1, 1);
-    new_tab(&mut screen, 2, 2);
-    screen.go_to_tab(1, 1).expect("TEST");
-    screen.go_to_tab(2, 1).expect("TEST");
+    new_tab(&mut screen, 1, 0);
+    screen.new_empty_tab(None, client_id).expect("TEST");
+    screen.render().expect("TEST");
 
-    screen.toggle_tab(1).expect("TEST");
+    assert_eq!(screen.tabs.len(), 2, "Empty tab was not closed");
     assert_eq!(
-        screen.get_active_tab(1).unwrap().position,
-        0,
-        "Active tab toggler to previous tab"
+        screen.get_active_tab(client_id).unwrap().index,
+        1,
+        "Switched to the empty tab"
+    );
+    assert!(
+        screen.get_active_tab(client_id).unwrap().is_empty(),
+        "New tab is empty"
     );
 
-    screen.toggle_tab(1).expect("TEST");
+    screen
+        .get_active_tab_mut(client_id)
+        .unwrap()
+        .new_pane(PaneId::Terminal(2), None, None, None, Some(client_id))
+        .expect("TEST");
+    screen.render().expect("TEST");
+
+    let active_tab = screen.get_active_tab(client_id).unwrap();
+    assert!(!active_tab.is_empty(), "Tab is no longer empty");
     assert_eq!(
-        screen.get_active_tab(1).unwrap().position,
-        1,
-        "Active tab toggler to previous tab"
+        active_tab.get_all_pane_ids(),
+        vec![PaneId::Terminal(2)],
+        "First pane added to the empty tab"
     );
+    assert_eq!(screen.tabs.len(), 2, "Tab still open after adding a pane");
 }
 
 #[test]
-pub fn toggle_to_previous_tab_create_tabs_only() {
-    let position_and_size = Size {
+pub fn break_pane_to_new_tab() {
+    let size = Size {
         cols: 121,
         rows: 20,
     };
-    let mut screen = create_new_screen(position_and_size);
+    let mut screen = create_new_screen(size);
+    let client_id = 1;
 
     new_tab(&mut screen, 1, 0);
-    new_tab(&mut screen, 2, 1);
-    new_tab(&mut screen, 3, 2);
-
-    assert_eq!(
-        screen.tab_history.get(&1).unwrap(),
-        &[0, 1],
-        "Tab history is invalid"
-    );
+    screen
+        .get_active_tab_mut(client_id)
+        .unwrap()
+        .new_pane(PaneId::Terminal(2), None, None, None, Some(client_id))
+        .expect("TEST");
+    screen.break_pane(client_id).expect("TEST");
+    screen.render().expect("TEST");
 
-    screen.toggle_tab(1).expect("TEST");
+    assert_eq!(screen.tabs.len(), 2, "Pane broken out to a new tab");
     assert_eq!(
-        screen.get_active_tab(1).unwrap().position,
-        1,
-        "Active tab toggler to previous tab"
+        screen.get_indexed_tab_mut(0).unwrap().get_all_pane_ids(),
+        vec![PaneId::Terminal(1)],
+        "Original tab kept its other pane"
     );
+    let active_tab = screen.get_active_tab(client_id).unwrap();
+    assert_eq!(active_tab.position, 1, "Switched to the new tab");
     assert_eq!(
-        screen.tab_history.get(&1).unwrap(),
-        &[0, 2],
-        "Tab history is invalid"
+        active_tab.get_all_pane_ids(),
+        vec![PaneId::Terminal(2)],
+        "Broken out pane is in the new tab"
     );
+    assert!(!active_tab.is_empty(), "New tab is not empty");
+}
 
-    screen.toggle_tab(1).expect("TEST");
+#[test]
+pub fn break_pane_with_single_pane_does_nothing() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    let client_id = 1;
+
+    new_tab(&mut screen, 1, 0);
+    screen.break_pane(client_id).expect("TEST");
+    screen.render().expect("TEST");
+
+    assert_eq!(screen.tabs.len(), 1, "No new tab created");
     assert_eq!(
-        screen.get_active_tab(1).unwrap().position,
-        2,
-        "Active tab toggler to previous tab"
+        screen.get_active_tab(client_id).unwrap().get_all_pane_ids(),
+        vec![PaneId::Terminal(1)],
+        "Pane stayed in its tab"
     );
+}
+
+#[test]
+pub fn break_pane_right_to_existing_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    let client_id = 1;
+
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    screen.switch_tab_prev(None, client_id).expect("TEST");
+    screen
+        .get_active_tab_mut(client_id)
+        .unwrap()
+        .new_pane(PaneId::Terminal(3), None, None, None, Some(client_id))
+        .expect("TEST");
+    screen
+        .break_pane_to_adjacent_tab(client_id, false)
+        .expect("TEST");
+    screen.render().expect("TEST");
+
+    assert_eq!(screen.tabs.len(), 2, "No new tab created");
+    let active_tab = screen.get_active_tab(client_id).unwrap();
+    assert_eq!(active_tab.position, 1, "Switched to the tab on the right");
+    let mut pane_ids = active_tab.get_all_pane_ids();
+    pane_ids.sort();
     assert_eq!(
-        screen.tab_history.get(&1).unwrap(),
-        &[0, 1],
-        "Tab history is invalid"
+        pane_ids,
+        vec![PaneId::Terminal(2), PaneId::Terminal(3)],
+        "Pane moved to the tab on the right"
     );
+}
 
-    screen.toggle_tab(1).expect("TEST");
+#[test]
+pub fn break_last_pane_left_to_existing_tab_closes_its_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    let client_id = 1;
+
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    screen
+        .break_pane_to_adjacent_tab(client_id, true)
+        .expect("TEST");
+    screen.render().expect("TEST");
+
+    assert_eq!(screen.tabs.len(), 1, "Emptied tab was closed");
+    let mut pane_ids = screen.get_active_tab(client_id).unwrap().get_all_pane_ids();
+    pane_ids.sort();
     assert_eq!(
-        screen.get_active_tab(1).unwrap().position,
-        1,
-        "Active tab toggler to previous tab"
+        pane_ids,
+        vec![PaneId::Terminal(1), PaneId::Terminal(2)],
+        "Pane moved to the tab on the left"
     );
 }
 
 #[test]
-pub fn toggle_to_previous_tab_delete() {
-    let position_and_size = Size {
+pub fn move_pane_to_tab_by_index() {
+    let size = Size {
         cols: 121,
         rows: 20,
     };
-    let mut screen = create_new_screen(position_and_size);
+    let mut screen = create_new_screen(size);
+    let client_id = 1;
 
     new_tab(&mut screen, 1, 0);
     new_tab(&mut screen, 2, 1);
     new_tab(&mut screen, 3, 2);
-    new_tab(&mut screen, 4, 3);
+    screen
+        .get_active_tab_mut(client_id)
+        .unwrap()
+        .new_pane(PaneId::Terminal(4), None, None, None, Some(client_id))
+        .expect("TEST");
+    screen.move_pane_to_tab(1, client_id).expect("TEST");
+    screen.render().expect("TEST");
 
+    assert_eq!(screen.tabs.len(), 3, "No tab created or closed");
+    let active_tab = screen.get_active_tab(client_id).unwrap();
+    assert_eq!(active_tab.position, 0, "Switched to the first tab");
+    let mut pane_ids = active_tab.get_all_pane_ids();
+    pane_ids.sort();
     assert_eq!(
-        screen.tab_history.get(&1).unwrap(),
-        &[0, 1, 2],
-        "Tab history is invalid"
+        pane_ids,
+        vec![PaneId::Terminal(1), PaneId::Terminal(4)],
+        "Pane moved to the first tab"
     );
-    assert_eq!(
-        screen.get_active_tab(1).unwrap().position,
-        3,
-        "Active tab toggler to previous tab"
+    assert!(
+        screen.move_pane_to_tab(4, client_id).is_err(),
+        "Moving to a tab that does not exist fails"
     );
+}
 
-    screen.toggle_tab(1).expect("TEST");
-    assert_eq!(
-        screen.tab_history.get(&1).unwrap(),
-        &[0, 1, 3],
-        "Tab history is invalid"
+#[test]
+pub fn toggle_overlay_tab_shows_and_returns_first_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    let client_id = 1;
+
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    screen.toggle_overlay_tab(1, client_id).expect("TEST");
+    screen.render().expect("TEST");
+
+    let active_tab = screen.get_active_tab(client_id).unwrap();
+    assert_eq!(active_tab.position, 1, "Did not switch tabs");
+    assert!(
+        active_tab.is_floating_pane(PaneId::Terminal(1)),
+        "First pane of the first tab floats over the current tab"
     );
-    assert_eq!(
-        screen.get_active_tab(1).unwrap().position,
-        2,
-        "Active tab toggler to previous tab"
+    assert!(
+        screen.tabs.get(&0).unwrap().get_all_pane_ids().is_empty(),
+        "Pane lent by the first tab"
     );
 
-    screen.toggle_tab(1).expect("TEST");
+    screen.toggle_overlay_tab(1, client_id).expect("TEST");
+    screen.render().expect("TEST");
     assert_eq!(
-        screen.tab_history.get(&1).unwrap(),
-        &[0, 1, 2],
-        "Tab history is invalid"
+        screen.get_active_tab(client_id).unwrap().get_all_pane_ids(),
+        vec![PaneId::Terminal(2)],
+        "Overlay hidden on repeat"
     );
     assert_eq!(
-        screen.get_active_tab(1).unwrap().position,
+        screen.tabs.get(&0).unwrap().get_all_pane_ids(),
+        vec![PaneId::Terminal(1)],
+        "Pane returned to the first tab"
+    );
+}
+
+#[test]
+pub fn focus_pane_by_name() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    let client_id = 1;
+
+    new_tab(&mut screen, 1, 0);
+    screen
+        .get_active_tab_mut(client_id)
+        .unwrap()
+        .update_active_pane_name("logs".as_bytes().to_vec(), client_id)
+        .expect("TEST");
+    new_tab(&mut screen, 2, 1);
+    screen
+        .get_active_tab_mut(client_id)
+        .unwrap()
+        .new_pane(PaneId::Terminal(3), None, None, None, Some(client_id))
+        .expect("TEST");
+    let focused_pane = |screen: &Screen| {
+        let active_tab = screen.get_active_tab(client_id).unwrap();
+        (
+            active_tab.position,
+            active_tab.get_active_pane_id(client_id),
+        )
+    };
+
+    screen
+        .focus_pane_by_name("logs", false, client_id)
+        .expect("TEST");
+    assert_eq!(
+        focused_pane(&screen),
+        (1, Some(PaneId::Terminal(3))),
+        "Pane in another tab not focused without searching all tabs"
+    );
+    screen
+        .focus_pane_by_name("log", true, client_id)
+        .expect("TEST");
+    assert_eq!(
+        focused_pane(&screen),
+        (1, Some(PaneId::Terminal(3))),
+        "Name is matched exactly"
+    );
+    screen
+        .focus_pane_by_name("logs", true, client_id)
+        .expect("TEST");
+    assert_eq!(
+        focused_pane(&screen),
+        (0, Some(PaneId::Terminal(1))),
+        "Switched to the tab of the named pane"
+    );
+}
+
+#[test]
+pub fn toggle_fullscreen_of_pane_by_id() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    let client_id = 1;
+
+    new_tab(&mut screen, 1, 0);
+    screen
+        .get_active_tab_mut(client_id)
+        .unwrap()
+        .new_pane(PaneId::Terminal(2), None, None, None, Some(client_id))
+        .expect("TEST");
+    new_tab(&mut screen, 3, 1);
+    let fullscreen_pane = |screen: &Screen| {
+        let active_tab = screen.get_active_tab(client_id).unwrap();
+        (
+            active_tab.position,
+            active_tab.get_active_pane_id(client_id),
+            active_tab.is_fullscreen_active(),
+        )
+    };
+
+    screen
+        .toggle_pane_fullscreen(PaneId::Terminal(1), client_id)
+        .expect("TEST");
+    assert_eq!(
+        fullscreen_pane(&screen),
+        (0, Some(PaneId::Terminal(1)), true),
+        "Switched to the tab of the pane, focused it and made it fullscreen"
+    );
+    screen
+        .toggle_pane_fullscreen(PaneId::Terminal(2), client_id)
+        .expect("TEST");
+    assert_eq!(
+        fullscreen_pane(&screen),
+        (0, Some(PaneId::Terminal(2)), true),
+        "Other pane took the place of the fullscreen pane"
+    );
+    screen
+        .toggle_pane_fullscreen(PaneId::Terminal(2), client_id)
+        .expect("TEST");
+    assert_eq!(
+        fullscreen_pane(&screen),
+        (0, Some(PaneId::Terminal(2)), false),
+        "Fullscreen pane was toggled back"
+    );
+    screen
+        .toggle_pane_fullscreen(PaneId::Terminal(42), client_id)
+        .expect("TEST");
+    assert_eq!(
+        fullscreen_pane(&screen),
+        (0, Some(PaneId::Terminal(2)), false),
+        "Unknown pane id does nothing"
+    );
+}
+
+#[test]
+pub fn go_to_tab_of_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 1);
+    new_tab(&mut screen, 2, 2);
+    let found = screen
+        .go_to_tab_of_pane(PaneId::Terminal(1), 1)
+        .expect("TEST");
+
+    assert!(found, "Pane found");
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().position,
+        0,
+        "Active tab switched to the tab containing the pane"
+    );
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().get_active_pane_id(1),
+        Some(PaneId::Terminal(1)),
+        "Pane is focused"
+    );
+}
+
+#[test]
+pub fn go_to_tab_of_unknown_pane_is_a_noop() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 1);
+    new_tab(&mut screen, 2, 2);
+    let found = screen
+        .go_to_tab_of_pane(PaneId::Terminal(42), 1)
+        .expect("TEST");
+
+    assert!(!found, "Pane not found");
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().position,
+        1,
+        "Active tab unchanged"
+    );
+}
+
+#[test]
+pub fn focus_pane_with_id_in_active_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    let client_id = 1;
+
+    new_tab(&mut screen, 1, 0);
+    screen
+        .get_active_tab_mut(client_id)
+        .unwrap()
+        .new_pane(PaneId::Terminal(2), None, None, None, Some(client_id))
+        .expect("TEST");
+    screen
+        .focus_pane_with_id(PaneId::Terminal(1), false, client_id)
+        .expect("TEST");
+
+    assert_eq!(
+        screen
+            .get_active_tab(client_id)
+            .unwrap()
+            .get_active_pane_id(client_id),
+        Some(PaneId::Terminal(1)),
+        "Pane is focused"
+    );
+}
+
+#[test]
+pub fn focus_pane_with_id_in_other_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    let client_id = 1;
+
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    screen
+        .focus_pane_with_id(PaneId::Terminal(1), false, client_id)
+        .expect("TEST");
+    assert_eq!(
+        screen.get_active_tab(client_id).unwrap().position,
+        1,
+        "Did not switch tabs without being asked to"
+    );
+
+    screen
+        .focus_pane_with_id(PaneId::Terminal(1), true, client_id)
+        .expect("TEST");
+    assert_eq!(
+        screen.get_active_tab(client_id).unwrap().position,
+        0,
+        "Switched to the tab of the pane"
+    );
+    assert_eq!(
+        screen
+            .get_active_tab(client_id)
+            .unwrap()
+            .get_active_pane_id(client_id),
+        Some(PaneId::Terminal(1)),
+        "Pane is focused"
+    );
+}
+
+#[test]
+pub fn focus_pane_with_unknown_id_is_a_noop() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    let client_id = 1;
+
+    new_tab(&mut screen, 1, 0);
+    screen
+        .focus_pane_with_id(PaneId::Terminal(42), true, client_id)
+        .expect("TEST");
+
+    assert_eq!(
+        screen
+            .get_active_tab(client_id)
+            .unwrap()
+            .get_active_pane_id(client_id),
+        Some(PaneId::Terminal(1)),
+        "Focus unchanged"
+    );
+}
+
+#[test]
+pub fn cycle_focus_by_command_across_tabs() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    let client_id = 1;
+
+    let commands = ["ssh host-a", "htop", "ssh host-b"];
+    for (tab_index, command) in commands.iter().enumerate() {
+        let mut command = command.split_whitespace().map(String::from);
+        let mut run_command = RunCommand::new(PathBuf::from(command.next().unwrap()));
+        run_command.args = command.collect();
+        let layout = TiledPaneLayout {
+            run: Some(Run::Command(run_command)),
+            ..Default::default()
+        };
+        screen
+            .new_tab(tab_index, (vec![], vec![]), None, client_id)
+            .expect("TEST");
+        screen
+            .apply_layout(
+                layout,
+                vec![], // floating panes layout
+                vec![(tab_index as u32 + 1, None)],
+                vec![], // new floating terminal ids
+                HashMap::new(),
+                tab_index,
+                client_id,
+            )
+            .expect("TEST");
+    }
+    let focused_pane = |screen: &Screen| {
+        let active_tab = screen.get_active_tab(client_id).unwrap();
+        (
+            active_tab.position,
+            active_tab.get_active_pane_id(client_id),
+        )
+    };
+
+    screen
+        .cycle_focus_by_command("ssh", client_id)
+        .expect("TEST");
+    assert_eq!(
+        focused_pane(&screen),
+        (0, Some(PaneId::Terminal(1))),
+        "Wrapped around to the first matching pane"
+    );
+    screen
+        .cycle_focus_by_command("ssh", client_id)
+        .expect("TEST");
+    assert_eq!(
+        focused_pane(&screen),
+        (2, Some(PaneId::Terminal(3))),
+        "Skipped the tab without a matching pane"
+    );
+    screen
+        .cycle_focus_by_command("vim", client_id)
+        .expect("TEST");
+    assert_eq!(
+        focused_pane(&screen),
+        (2, Some(PaneId::Terminal(3))),
+        "Focus unchanged when no pane matches"
+    );
+}
+
+#[test]
+pub fn switch_to_next_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 1);
+    new_tab(&mut screen, 2, 2);
+    screen.switch_tab_prev(None, 1).expect("TEST");
+    screen.switch_tab_next(None, 1).expect("TEST");
+
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().position,
+        1,
+        "Active tab switched to next tab"
+    );
+}
+
+#[test]
+pub fn clear_active_tab_name() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 1);
+    screen
+        .update_active_tab_name("Logs".as_bytes().to_vec(), 1)
+        .expect("TEST");
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().name,
+        "Logs",
+        "Active tab renamed"
+    );
+    screen.clear_active_tab_name(1).expect("TEST");
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().name,
+        "",
+        "Active tab name cleared"
+    );
+}
+
+#[test]
+pub fn switch_to_tab_name() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 1);
+    new_tab(&mut screen, 2, 2);
+
+    assert_eq!(
+        screen
+            .switch_active_tab_name("Tab #1".to_string(), 1)
+            .expect("TEST"),
+        false,
+        "Active tab switched to tab by name"
+    );
+    assert_eq!(
+        screen
+            .switch_active_tab_name("Tab #2".to_string(), 1)
+            .expect("TEST"),
+        true,
+        "Active tab switched to tab by name"
+    );
+    assert_eq!(
+        screen
+            .switch_active_tab_name("Tab #3".to_string(), 1)
+            .expect("TEST"),
+        true,
+        "Active tab switched to tab by name"
+    );
+}
+
+#[test]
+pub fn close_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 1);
+    new_tab(&mut screen, 2, 2);
+    screen.close_tab(1).expect("TEST");
+
+    assert_eq!(screen.tabs.len(), 1, "Only one tab left");
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().position,
+        0,
+        "Active tab switched to previous tab"
+    );
+}
+
+#[test]
+pub fn pinned_tab_is_not_closed() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 1);
+    new_tab(&mut screen, 2, 2);
+    screen.pin_tab_at_position(1);
+    screen.close_tab(1).expect("TEST");
+
+    assert_eq!(screen.tabs.len(), 2, "Pinned tab was not closed");
+    screen.get_active_tab_mut(1).unwrap().set_pinned(false);
+    screen.close_tab(1).expect("TEST");
+
+    assert_eq!(screen.tabs.len(), 1, "Unpinned tab was closed");
+}
+
+#[test]
+pub fn close_the_middle_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 1);
+    new_tab(&mut screen, 2, 2);
+    new_tab(&mut screen, 3, 3);
+    screen.switch_tab_prev(None, 1).expect("TEST");
+    screen.close_tab(1).expect("TEST");
+
+    assert_eq!(screen.tabs.len(), 2, "Two tabs left");
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().position,
+        1,
+        "Active tab switched to previous tab"
+    );
+}
+
+#[test]
+fn move_focus_left_at_left_screen_edge_changes_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 1);
+    new_tab(&mut screen, 2, 2);
+    new_tab(&mut screen, 3, 3);
+    screen.switch_tab_prev(None, 1).expect("TEST");
+    screen.move_focus_left_or_previous_tab(1).expect("TEST");
+
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().position,
+        0,
+        "Active tab switched to previous"
+    );
+}
+
+#[test]
+fn move_focus_right_at_right_screen_edge_changes_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 1);
+    new_tab(&mut screen, 2, 2);
+    new_tab(&mut screen, 3, 3);
+    screen.switch_tab_prev(None, 1).expect("TEST");
+    screen.move_focus_right_or_next_tab(1).expect("TEST");
+
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().position,
+        2,
+        "Active tab switched to next"
+    );
+}
+
+#[test]
+pub fn toggle_to_previous_tab_simple() {
+    let position_and_size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(position_and_size);
+
+    new_tab(&mut screen, 1, 1);
+    new_tab(&mut screen, 2, 2);
+    screen.go_to_tab(1, 1).expect("TEST");
+    screen.go_to_tab(2, 1).expect("TEST");
+
+    screen.toggle_tab(1).expect("TEST");
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().position,
+        0,
+        "Active tab toggler to previous tab"
+    );
+
+    screen.toggle_tab(1).expect("TEST");
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().position,
+        1,
+        "Active tab toggler to previous tab"
+    );
+}
+
+#[test]
+pub fn toggle_to_previous_tab_create_tabs_only() {
+    let position_and_size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(position_and_size);
+
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    new_tab(&mut screen, 3, 2);
+
+    assert_eq!(
+        screen.tab_history.get(&1).unwrap(),
+        &[0, 1],
+        "Tab history is invalid"
+    );
+
+    screen.toggle_tab(1).expect("TEST");
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().position,
+        1,
+        "Active tab toggler to previous tab"
+    );
+    assert_eq!(
+        screen.tab_history.get(&1).unwrap(),
+        &[0, 2],
+        "Tab history is invalid"
+    );
+
+    screen.toggle_tab(1).expect("TEST");
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().position,
+        2,
+        "Active tab toggler to previous tab"
+    );
+    assert_eq!(
+        screen.tab_history.get(&1).unwrap(),
+        &[0, 1],
+        "Tab history is invalid"
+    );
+
+    screen.toggle_tab(1).expect("TEST");
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().position,
+        1,
+        "Active tab toggler to previous tab"
+    );
+}
+
+#[test]
+pub fn toggle_to_previous_tab_delete() {
+    let position_and_size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(position_and_size);
+
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    new_tab(&mut screen, 3, 2);
+    new_tab(&mut screen, 4, 3);
+
+    assert_eq!(
+        screen.tab_history.get(&1).unwrap(),
+        &[0, 1, 2],
+        "Tab history is invalid"
+    );
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().position,
+        3,
+        "Active tab toggler to previous tab"
+    );
+
+    screen.toggle_tab(1).expect("TEST");
+    assert_eq!(
+        screen.tab_history.get(&1).unwrap(),
+        &[0, 1, 3],
+        "Tab history is invalid"
+    );
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().position,
+        2,
+        "Active tab toggler to previous tab"
+    );
+
+    screen.toggle_tab(1).expect("TEST");
+    assert_eq!(
+        screen.tab_history.get(&1).unwrap(),
+        &[0, 1, 2],
+        "Tab history is invalid"
+    );
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().position,
         3,
         "Active tab toggler to previous tab"
     );
@@ -904,6 +1669,60 @@ fn switch_to_tab_with_fullscreen() {
     );
 }
 
+#[test]
+fn switch_back_to_tab_restores_its_focused_pane() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 1);
+    {
+        let active_tab = screen.get_active_tab_mut(1).unwrap();
+        active_tab
+            .new_pane(PaneId::Terminal(2), None, None, None, Some(1))
+            .unwrap();
+        active_tab
+            .new_pane(PaneId::Terminal(3), None, None, None, Some(1))
+            .unwrap();
+        active_tab
+            .focus_pane_with_id(PaneId::Terminal(2), false, 1)
+            .unwrap();
+    }
+    new_tab(&mut screen, 4, 2);
+
+    screen.switch_tab_prev(None, 1).expect("TEST");
+    let active_pane_id = |screen: &Screen| {
+        screen
+            .get_active_tab(1)
+            .unwrap()
+            .get_active_pane_id(1)
+            .unwrap()
+    };
+    assert_eq!(
+        active_pane_id(&screen),
+        PaneId::Terminal(2),
+        "Pane focused before switching away is focused again"
+    );
+
+    screen
+        .get_active_tab_mut(1)
+        .unwrap()
+        .focus_pane_with_id(PaneId::Terminal(3), false, 1)
+        .unwrap();
+    screen
+        .get_active_tab_mut(1)
+        .unwrap()
+        .restore_focus(1)
+        .unwrap();
+    assert_eq!(
+        active_pane_id(&screen),
+        PaneId::Terminal(2),
+        "Restoring the tab focus focuses the pane focused when last switching away"
+    );
+}
+
 #[test]
 fn update_screen_pixel_dimensions() {
     let size = Size {
@@ -1006,15 +1825,233 @@ fn attach_after_first_tab_closed() {
     screen.add_client(1).expect("TEST");
 }
 
-// Following are tests for sending CLI actions
-// these tests are only partially relevant to Screen
-// and are included here for two reasons:
-// 1. The best way to "integration test" these is combining the "screen_thread_main" and
-//    "route_action" functions and mocking everything around them
-// 2. These inadvertently also test many parts of Screen that are not tested elsewhere
-
 #[test]
-pub fn send_cli_write_chars_action_to_screen() {
+fn timed_mode_reverts_to_previous_mode() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 0);
+
+    let mut locked_mode_info = ModeInfo::default();
+    locked_mode_info.mode = InputMode::Locked;
+    screen
+        .change_mode_timed(locked_mode_info.clone(), 0, 1)
+        .expect("TEST");
+    assert_eq!(
+        screen.mode_info.get(&1).unwrap().mode,
+        InputMode::Locked,
+        "switched to timed mode"
+    );
+    screen.mode_timeout_elapsed(1).expect("TEST");
+    assert_eq!(
+        screen.mode_info.get(&1).unwrap().mode,
+        InputMode::Normal,
+        "reverted to previous mode once the timer elapsed"
+    );
+
+    screen.timed_mode_keypress = TimedModeKeypress::Cancel;
+    screen
+        .change_mode_timed(locked_mode_info, 0, 1)
+        .expect("TEST");
+    screen.reset_mode_timer(1);
+    screen.mode_timeout_elapsed(1).expect("TEST");
+    assert_eq!(
+        screen.mode_info.get(&1).unwrap().mode,
+        InputMode::Locked,
+        "keypress cancelled the revert"
+    );
+}
+
+#[test]
+fn resize_loop_returns_to_the_mode_it_was_entered_from() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 0);
+    screen.resize_loop_step = 3;
+
+    let mut locked_mode_info = ModeInfo::default();
+    locked_mode_info.mode = InputMode::Locked;
+    screen.change_mode(locked_mode_info, 1).expect("TEST");
+    let mut resize_mode_info = ModeInfo::default();
+    resize_mode_info.mode = InputMode::Resize;
+    screen
+        .enter_resize_loop(resize_mode_info.clone(), 1)
+        .expect("TEST");
+    screen.enter_resize_loop(resize_mode_info, 1).expect("TEST");
+    assert_eq!(
+        screen.mode_info.get(&1).unwrap().mode,
+        InputMode::Resize,
+        "switched to resize mode"
+    );
+    assert_eq!(
+        screen.resize_steps(1),
+        3,
+        "resizes are a step big in the loop"
+    );
+
+    screen.exit_resize_loop(1).expect("TEST");
+    assert_eq!(
+        screen.mode_info.get(&1).unwrap().mode,
+        InputMode::Locked,
+        "returned to the mode the loop was first entered from"
+    );
+    assert_eq!(
+        screen.resize_steps(1),
+        1,
+        "resizes are back to a single increment"
+    );
+}
+
+#[test]
+fn session_sync_is_reflected_in_mode_info() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+
+    screen.change_mode(ModeInfo::default(), 1).expect("TEST");
+    screen.toggle_sync_session().expect("TEST");
+    assert!(
+        screen.mode_info.get(&1).unwrap().is_sync_session_active,
+        "session sync reflected in mode info"
+    );
+    assert!(
+        !screen.tabs.values().any(|tab| tab.is_sync_panes_active()),
+        "tab sync untouched"
+    );
+
+    let mut locked_mode_info = ModeInfo::default();
+    locked_mode_info.mode = InputMode::Locked;
+    screen.change_mode(locked_mode_info, 1).expect("TEST");
+    assert!(
+        screen.mode_info.get(&1).unwrap().is_sync_session_active,
+        "session sync survives a mode switch"
+    );
+
+    screen.toggle_sync_session().expect("TEST");
+    assert!(
+        !screen.mode_info.get(&1).unwrap().is_sync_session_active,
+        "session sync toggled off"
+    );
+}
+
+#[test]
+fn mode_switches_are_reported_to_mode_report_socket() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 0);
+
+    let socket_dir = tempfile::tempdir().expect("TEST");
+    let socket_path = socket_dir.path().join("mode-report");
+    let listener = std::os::unix::net::UnixListener::bind(&socket_path).expect("TEST");
+    screen.mode_report_socket = Some(socket_path);
+
+    let mut locked_mode_info = ModeInfo::default();
+    locked_mode_info.mode = InputMode::Locked;
+    screen.change_mode(locked_mode_info, 1).expect("TEST");
+    let (mut stream, _) = listener.accept().expect("TEST");
+    let mut reported_mode = String::new();
+    std::io::Read::read_to_string(&mut stream, &mut reported_mode).expect("TEST");
+    assert_eq!(reported_mode, "locked\n", "mode switch was reported");
+
+    drop(listener);
+    let mut normal_mode_info = ModeInfo::default();
+    normal_mode_info.mode = InputMode::Normal;
+    screen.change_mode(normal_mode_info, 1).expect("TEST");
+    assert_eq!(
+        screen.mode_info.get(&1).unwrap().mode,
+        InputMode::Normal,
+        "mode switch succeeded with nothing listening on the socket"
+    );
+}
+
+// Following are tests for sending CLI actions
+// these tests are only partially relevant to Screen
+// and are included here for two reasons:
+// 1. The best way to "integration test" these is combining the "screen_thread_main" and
+//    "route_action" functions and mocking everything around them
+// 2. These inadvertently also test many parts of Screen that are not tested elsewhere
+
+#[test]
+pub fn send_cli_write_chars_action_to_screen() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_writer_receiver = mock_screen.pty_writer_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(None);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_writer_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyWriteInstruction::Exit,
+        pty_writer_receiver
+    );
+    let cli_action = CliAction::WriteChars {
+        chars: "input from the cli".into(),
+    };
+    send_cli_action_to_server(&session_metadata, cli_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
+    assert_snapshot!(format!("{:?}", *received_pty_instructions.lock().unwrap()));
+}
+
+#[test]
+pub fn send_cli_paste_text_action_to_screen() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_writer_receiver = mock_screen.pty_writer_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(None);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_writer_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyWriteInstruction::Exit,
+        pty_writer_receiver
+    );
+    let cli_action = CliAction::PasteText {
+        text: Some("echo one\necho two\n".into()),
+        file: None,
+    };
+    send_cli_action_to_server(&session_metadata, cli_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
+    let written_bytes: Vec<u8> = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            PtyWriteInstruction::Write(bytes, _terminal_id) => Some(bytes.clone()),
+            _ => None,
+        })
+        .flatten()
+        .collect();
+    assert_eq!(
+        String::from_utf8_lossy(&written_bytes),
+        "echo one\necho two\n",
+        "text was written without the bracketed paste markers"
+    );
+}
+
+#[test]
+pub fn send_cli_paste_text_action_to_screen_with_bracketed_paste() {
     let size = Size {
         cols: 121,
         rows: 20,
@@ -1030,13 +2067,33 @@ pub fn send_cli_write_chars_action_to_screen() {
         PtyWriteInstruction::Exit,
         pty_writer_receiver
     );
-    let cli_action = CliAction::WriteChars {
-        chars: "input from the cli".into(),
+    // the pane asks for bracketed paste
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "\u{1b}[?2004h".as_bytes().to_vec(),
+    ));
+    let cli_action = CliAction::PasteText {
+        text: Some("echo one\necho two\n".into()),
+        file: None,
     };
     send_cli_action_to_server(&session_metadata, cli_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
     mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
-    assert_snapshot!(format!("{:?}", *received_pty_instructions.lock().unwrap()));
+    let written_bytes: Vec<u8> = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            PtyWriteInstruction::Write(bytes, _terminal_id) => Some(bytes.clone()),
+            _ => None,
+        })
+        .flatten()
+        .collect();
+    assert_eq!(
+        String::from_utf8_lossy(&written_bytes),
+        "\u{1b}[200~echo one\necho two\n\u{1b}[201~",
+        "text was wrapped in the bracketed paste markers"
+    );
 }
 
 #[test]
@@ -1083,8 +2140,10 @@ pub fn send_cli_resize_action_to_screen() {
         server_receiver
     );
     let resize_cli_action = CliAction::Resize {
-        resize: Resize::Increase,
+        resize: Some(Resize::Increase),
         direction: Some(Direction::Left),
+        width_percent: None,
+        height_percent: None,
     };
     send_cli_action_to_server(&session_metadata, resize_cli_action, client_id);
     mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
@@ -1099,6 +2158,44 @@ pub fn send_cli_resize_action_to_screen() {
     assert_snapshot!(format!("{}", snapshot_count));
 }
 
+#[test]
+pub fn send_cli_detach_action_waiting_for_idle_panes() {
+    let size = Size { cols: 80, rows: 20 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()));
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_instruction = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let detach_action = CliAction::Detach {
+        wait: true,
+        timeout: 600,
+    };
+    send_cli_action_to_server(&session_metadata, detach_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_instruction, screen_thread]);
+    let detached_clients = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|instruction| match instruction {
+            ServerInstruction::DetachSession(client_ids) => Some(client_ids.clone()),
+            _ => None,
+        });
+    // no pane runs anything in the mock os api, so the detach happens right away and detaches
+    // the clients attached to the session rather than the cli client
+    assert_eq!(
+        detached_clients,
+        Some(vec![1]),
+        "attached client detached once idle"
+    );
+}
+
 #[test]
 pub fn send_cli_focus_next_pane_action() {
     let size = Size { cols: 80, rows: 20 };
@@ -1116,7 +2213,7 @@ pub fn send_cli_focus_next_pane_action() {
         ServerInstruction::KillSession,
         server_receiver
     );
-    let focus_next_pane_action = CliAction::FocusNextPane;
+    let focus_next_pane_action = CliAction::FocusNextPane { count: 1 };
     send_cli_action_to_server(&session_metadata, focus_next_pane_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100));
     mock_screen.teardown(vec![server_instruction, screen_thread]);
@@ -1149,7 +2246,7 @@ pub fn send_cli_focus_previous_pane_action() {
         ServerInstruction::KillSession,
         server_receiver
     );
-    let focus_next_pane_action = CliAction::FocusPreviousPane;
+    let focus_next_pane_action = CliAction::FocusPreviousPane { count: 1 };
     send_cli_action_to_server(&session_metadata, focus_next_pane_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100));
     mock_screen.teardown(vec![server_instruction, screen_thread]);
@@ -1157,16 +2254,239 @@ pub fn send_cli_focus_previous_pane_action() {
         received_server_instructions.lock().unwrap().iter(),
         size,
     );
-    let snapshot_count = snapshots.len();
-    for (cursor_coordinates, _snapshot) in snapshots {
-        // here we assert he cursor_coordinates to let us know if we switched the pane focus
-        assert_snapshot!(format!("{:?}", cursor_coordinates));
-    }
-    assert_snapshot!(format!("{}", snapshot_count));
+    let snapshot_count = snapshots.len();
+    for (cursor_coordinates, _snapshot) in snapshots {
+        // here we assert he cursor_coordinates to let us know if we switched the pane focus
+        assert_snapshot!(format!("{:?}", cursor_coordinates));
+    }
+    assert_snapshot!(format!("{}", snapshot_count));
+}
+
+#[test]
+pub fn send_cli_move_focus_pane_action() {
+    let size = Size { cols: 80, rows: 20 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout));
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_instruction = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let move_focus_action = CliAction::MoveFocus {
+        direction: Direction::Right,
+        count: 1,
+    };
+    send_cli_action_to_server(&session_metadata, move_focus_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_instruction, screen_thread]);
+    let snapshots = take_snapshots_and_cursor_coordinates_from_render_events(
+        received_server_instructions.lock().unwrap().iter(),
+        size,
+    );
+    let snapshot_count = snapshots.len();
+    for (cursor_coordinates, _snapshot) in snapshots {
+        // here we assert he cursor_coordinates to let us know if we switched the pane focus
+        assert_snapshot!(format!("{:?}", cursor_coordinates));
+    }
+    assert_snapshot!(format!("{}", snapshot_count));
+}
+
+#[test]
+pub fn send_cli_move_focus_or_tab_pane_action() {
+    let size = Size { cols: 80, rows: 20 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout));
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_instruction = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let move_focus_action = CliAction::MoveFocusOrTab {
+        direction: Direction::Right,
+        stop_at_edge: false,
+    };
+    send_cli_action_to_server(&session_metadata, move_focus_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_instruction, screen_thread]);
+    let snapshots = take_snapshots_and_cursor_coordinates_from_render_events(
+        received_server_instructions.lock().unwrap().iter(),
+        size,
+    );
+    let snapshot_count = snapshots.len();
+    for (cursor_coordinates, _snapshot) in snapshots {
+        // here we assert he cursor_coordinates to let us know if we switched the pane focus
+        assert_snapshot!(format!("{:?}", cursor_coordinates));
+    }
+    assert_snapshot!(format!("{}", snapshot_count));
+}
+
+#[test]
+pub fn cli_move_focus_or_tab_action_can_stop_at_edge() {
+    let get_current_dir = || PathBuf::from(".");
+    let move_focus_action = CliAction::MoveFocusOrTab {
+        direction: Direction::Right,
+        stop_at_edge: false,
+    };
+    let actions =
+        Action::actions_from_cli(move_focus_action, Box::new(get_current_dir), None).unwrap();
+    assert_eq!(actions, vec![Action::MoveFocusOrTab(Direction::Right)]);
+    let move_focus_action = CliAction::MoveFocusOrTab {
+        direction: Direction::Right,
+        stop_at_edge: true,
+    };
+    let actions =
+        Action::actions_from_cli(move_focus_action, Box::new(get_current_dir), None).unwrap();
+    assert_eq!(
+        actions,
+        vec![Action::MoveFocus(Direction::Right)],
+        "focus does not spill over into the next tab"
+    );
+}
+
+#[test]
+pub fn send_cli_move_pane_action() {
+    let size = Size { cols: 80, rows: 20 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout));
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_instruction = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let cli_action = CliAction::MovePane {
+        direction: Some(Direction::Right),
+    };
+    send_cli_action_to_server(&session_metadata, cli_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_instruction, screen_thread]);
+    let snapshots = take_snapshots_and_cursor_coordinates_from_render_events(
+        received_server_instructions.lock().unwrap().iter(),
+        size,
+    );
+    let snapshot_count = snapshots.len();
+    for (_cursor_coordinates, snapshot) in snapshots {
+        assert_snapshot!(format!("{}", snapshot));
+    }
+    assert_snapshot!(format!("{}", snapshot_count));
+}
+
+#[test]
+pub fn send_cli_dump_screen_action() {
+    let size = Size { cols: 80, rows: 20 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout));
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let cli_action = CliAction::DumpScreen {
+        path: PathBuf::from("/tmp/foo"),
+        full: true,
+        format: "text".to_owned(),
+        ansi: false,
+        jsonl: false,
+        header: false,
+    };
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "fill pane up with something".as_bytes().to_vec(),
+    ));
+    send_cli_action_to_server(&session_metadata, cli_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    assert_snapshot!(format!(
+        "{:?}",
+        *mock_screen.os_input.fake_filesystem.lock().unwrap()
+    ));
+}
+
+#[test]
+pub fn send_cli_dump_screen_action_to_stdout_with_ansi() {
+    let size = Size { cols: 80, rows: 20 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()));
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let cli_action = CliAction::DumpScreen {
+        path: PathBuf::from("-"),
+        full: false,
+        format: "text".to_owned(),
+        ansi: true,
+        jsonl: false,
+        header: false,
+    };
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "fill pane up with \u{1b}[31msomething".as_bytes().to_vec(),
+    ));
+    send_cli_action_to_server(&session_metadata, cli_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let dump = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|instruction| match instruction {
+            ServerInstruction::Log(lines, log_client_id) if *log_client_id == client_id => {
+                Some(lines.join("\n"))
+            },
+            _ => None,
+        })
+        .expect("screen was dumped to the cli client");
+    assert!(
+        dump.contains("fill pane up with \u{1b}[31msomething"),
+        "dump kept the colors: {:?}",
+        dump
+    );
+    assert!(
+        mock_screen
+            .os_input
+            .fake_filesystem
+            .lock()
+            .unwrap()
+            .is_empty(),
+        "nothing was written to a file"
+    );
 }
 
 #[test]
-pub fn send_cli_move_focus_pane_action() {
+pub fn send_cli_dump_screen_svg_action() {
     let size = Size { cols: 80, rows: 20 };
     let client_id = 10; // fake client id should not appear in the screen's state
     let mut initial_layout = TiledPaneLayout::default();
@@ -1177,31 +2497,45 @@ pub fn send_cli_move_focus_pane_action() {
     let screen_thread = mock_screen.run(Some(initial_layout));
     let received_server_instructions = Arc::new(Mutex::new(vec![]));
     let server_receiver = mock_screen.server_receiver.take().unwrap();
-    let server_instruction = log_actions_in_thread!(
+    let server_thread = log_actions_in_thread!(
         received_server_instructions,
         ServerInstruction::KillSession,
         server_receiver
     );
-    let move_focus_action = CliAction::MoveFocus {
-        direction: Direction::Right,
+    let cli_action = CliAction::DumpScreen {
+        path: PathBuf::from("/tmp/foo.svg"),
+        full: false,
+        format: "svg".to_owned(),
+        ansi: false,
+        jsonl: false,
+        header: false,
     };
-    send_cli_action_to_server(&session_metadata, move_focus_action, client_id);
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "fill pane up with \u{1b}[1;31msomething"
+            .as_bytes()
+            .to_vec(),
+    ));
+    send_cli_action_to_server(&session_metadata, cli_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100));
-    mock_screen.teardown(vec![server_instruction, screen_thread]);
-    let snapshots = take_snapshots_and_cursor_coordinates_from_render_events(
-        received_server_instructions.lock().unwrap().iter(),
-        size,
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let fake_filesystem = mock_screen.os_input.fake_filesystem.lock().unwrap();
+    let dump = fake_filesystem
+        .get("/tmp/foo.svg")
+        .expect("svg dump was written");
+    assert!(dump.starts_with("<svg"), "dump is an svg image");
+    assert!(
+        dump.contains(">fill pane up with </text>"),
+        "plain text is dumped"
+    );
+    assert!(
+        dump.contains("font-weight=\"bold\" xml:space=\"preserve\">something</text>"),
+        "bold text keeps its attribute"
     );
-    let snapshot_count = snapshots.len();
-    for (cursor_coordinates, _snapshot) in snapshots {
-        // here we assert he cursor_coordinates to let us know if we switched the pane focus
-        assert_snapshot!(format!("{:?}", cursor_coordinates));
-    }
-    assert_snapshot!(format!("{}", snapshot_count));
 }
 
 #[test]
-pub fn send_cli_move_focus_or_tab_pane_action() {
+pub fn send_cli_dump_screen_html_action() {
     let size = Size { cols: 80, rows: 20 };
     let client_id = 10; // fake client id should not appear in the screen's state
     let mut initial_layout = TiledPaneLayout::default();
@@ -1212,65 +2546,97 @@ pub fn send_cli_move_focus_or_tab_pane_action() {
     let screen_thread = mock_screen.run(Some(initial_layout));
     let received_server_instructions = Arc::new(Mutex::new(vec![]));
     let server_receiver = mock_screen.server_receiver.take().unwrap();
-    let server_instruction = log_actions_in_thread!(
+    let server_thread = log_actions_in_thread!(
         received_server_instructions,
         ServerInstruction::KillSession,
         server_receiver
     );
-    let move_focus_action = CliAction::MoveFocusOrTab {
-        direction: Direction::Right,
+    let cli_action = CliAction::DumpScreenHtml {
+        path: PathBuf::from("/tmp/foo.html"),
+        full: false,
     };
-    send_cli_action_to_server(&session_metadata, move_focus_action, client_id);
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "fill <pane> up with \u{1b}[1;31msomething"
+            .as_bytes()
+            .to_vec(),
+    ));
+    send_cli_action_to_server(&session_metadata, cli_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100));
-    mock_screen.teardown(vec![server_instruction, screen_thread]);
-    let snapshots = take_snapshots_and_cursor_coordinates_from_render_events(
-        received_server_instructions.lock().unwrap().iter(),
-        size,
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let fake_filesystem = mock_screen.os_input.fake_filesystem.lock().unwrap();
+    let dump = fake_filesystem
+        .get("/tmp/foo.html")
+        .expect("html dump was written");
+    assert!(dump.starts_with("<!DOCTYPE html>"), "dump is an html page");
+    assert!(
+        !dump.contains("<link") && !dump.contains("<script"),
+        "html page is self-contained"
+    );
+    assert!(
+        dump.contains("fill &lt;pane&gt; up with <span"),
+        "plain text is dumped and escaped"
+    );
+    assert!(
+        dump.contains("font-weight: bold;\">something</span>"),
+        "bold text keeps its style"
     );
-    let snapshot_count = snapshots.len();
-    for (cursor_coordinates, _snapshot) in snapshots {
-        // here we assert he cursor_coordinates to let us know if we switched the pane focus
-        assert_snapshot!(format!("{:?}", cursor_coordinates));
-    }
-    assert_snapshot!(format!("{}", snapshot_count));
 }
 
 #[test]
-pub fn send_cli_move_pane_action() {
+pub fn send_cli_dump_screen_jsonl_action() {
     let size = Size { cols: 80, rows: 20 };
     let client_id = 10; // fake client id should not appear in the screen's state
-    let mut initial_layout = TiledPaneLayout::default();
-    initial_layout.children_split_direction = SplitDirection::Vertical;
-    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
     let mut mock_screen = MockScreen::new(size);
     let session_metadata = mock_screen.clone_session_metadata();
-    let screen_thread = mock_screen.run(Some(initial_layout));
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()));
     let received_server_instructions = Arc::new(Mutex::new(vec![]));
     let server_receiver = mock_screen.server_receiver.take().unwrap();
-    let server_instruction = log_actions_in_thread!(
+    let server_thread = log_actions_in_thread!(
         received_server_instructions,
         ServerInstruction::KillSession,
         server_receiver
     );
-    let cli_action = CliAction::MovePane {
-        direction: Some(Direction::Right),
+    let cli_action = CliAction::DumpScreen {
+        path: PathBuf::from("/tmp/foo.jsonl"),
+        full: false,
+        format: "text".to_owned(),
+        ansi: false,
+        jsonl: true,
+        header: false,
     };
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "plain line\n\rsome \u{1b}[1mbold\u{1b}[m text"
+            .as_bytes()
+            .to_vec(),
+    ));
     send_cli_action_to_server(&session_metadata, cli_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100));
-    mock_screen.teardown(vec![server_instruction, screen_thread]);
-    let snapshots = take_snapshots_and_cursor_coordinates_from_render_events(
-        received_server_instructions.lock().unwrap().iter(),
-        size,
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let fake_filesystem = mock_screen.os_input.fake_filesystem.lock().unwrap();
+    let dump = fake_filesystem
+        .get("/tmp/foo.jsonl")
+        .expect("jsonl dump was written");
+    let mut lines = dump.lines();
+    assert_eq!(
+        lines.next(),
+        Some(r#"{"line_number":1,"text":"plain line"}"#),
+        "unstyled line has no style runs"
     );
-    let snapshot_count = snapshots.len();
-    for (_cursor_coordinates, snapshot) in snapshots {
-        assert_snapshot!(format!("{}", snapshot));
-    }
-    assert_snapshot!(format!("{}", snapshot_count));
+    let styled_line = lines.next().unwrap();
+    assert!(
+        styled_line.starts_with(
+            r#"{"line_number":2,"text":"some bold text","style_runs":[{"start":5,"end":9,"#
+        ),
+        "styled line has a run for its bold text: {}",
+        styled_line
+    );
+    assert!(styled_line.contains(r#""bold":true"#), "run is bold");
 }
 
 #[test]
-pub fn send_cli_dump_screen_action() {
+pub fn send_cli_edit_scrollback_action() {
     let size = Size { cols: 80, rows: 20 };
     let client_id = 10; // fake client id should not appear in the screen's state
     let mut initial_layout = TiledPaneLayout::default();
@@ -1279,37 +2645,104 @@ pub fn send_cli_dump_screen_action() {
     let mut mock_screen = MockScreen::new(size);
     let session_metadata = mock_screen.clone_session_metadata();
     let screen_thread = mock_screen.run(Some(initial_layout));
-    let received_server_instructions = Arc::new(Mutex::new(vec![]));
-    let server_receiver = mock_screen.server_receiver.take().unwrap();
-    let server_thread = log_actions_in_thread!(
-        received_server_instructions,
-        ServerInstruction::KillSession,
-        server_receiver
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
     );
-    let cli_action = CliAction::DumpScreen {
-        path: PathBuf::from("/tmp/foo"),
-        full: true,
-    };
+    let cli_action = CliAction::EditScrollback;
     let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
         0,
         "fill pane up with something".as_bytes().to_vec(),
     ));
     send_cli_action_to_server(&session_metadata, cli_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100));
-    mock_screen.teardown(vec![server_thread, screen_thread]);
-    assert_snapshot!(format!(
-        "{:?}",
-        *mock_screen.os_input.fake_filesystem.lock().unwrap()
-    ));
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    let dumped_file_name = mock_screen
+        .os_input
+        .fake_filesystem
+        .lock()
+        .unwrap()
+        .keys()
+        .next()
+        .unwrap()
+        .clone();
+    let mut found_instruction = false;
+    for instruction in received_pty_instructions.lock().unwrap().iter() {
+        if let PtyInstruction::OpenInPlaceEditor(scrollback_contents_file, terminal_id, client_id) =
+            instruction
+        {
+            assert_eq!(scrollback_contents_file, &PathBuf::from(&dumped_file_name));
+            assert_eq!(terminal_id, &Some(1));
+            assert_eq!(client_id, &1);
+            found_instruction = true;
+        }
+    }
+    assert!(found_instruction);
 }
 
 #[test]
-pub fn send_cli_edit_scrollback_action() {
+pub fn send_cli_duplicate_pane_action() {
+    let size = Size { cols: 80, rows: 20 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut run_command = RunCommand::new(PathBuf::from("htop"));
+    run_command.args = vec!["-d".to_owned(), "10".to_owned()];
+    run_command.hold_on_close = true;
+    let initial_layout = TiledPaneLayout {
+        run: Some(Run::Command(run_command.clone())),
+        ..Default::default()
+    };
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout));
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    let cli_action = CliAction::DuplicatePane {
+        direction: Some(Direction::Right),
+        floating: false,
+    };
+    send_cli_action_to_server(&session_metadata, cli_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    let mut found_instruction = false;
+    for instruction in received_pty_instructions.lock().unwrap().iter() {
+        if let PtyInstruction::SpawnTerminalVertically(
+            Some(TerminalAction::RunCommand(duplicated_command)),
+            _name,
+            client_id,
+        ) = instruction
+        {
+            assert_eq!(
+                duplicated_command, &run_command,
+                "the new pane runs the same command"
+            );
+            assert_eq!(client_id, &1);
+            found_instruction = true;
+        }
+    }
+    assert!(found_instruction);
+}
+
+#[test]
+pub fn send_cli_query_pane_list_action() {
     let size = Size { cols: 80, rows: 20 };
     let client_id = 10; // fake client id should not appear in the screen's state
     let mut initial_layout = TiledPaneLayout::default();
     initial_layout.children_split_direction = SplitDirection::Vertical;
-    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    initial_layout.children = vec![
+        TiledPaneLayout::default(),
+        TiledPaneLayout {
+            run: Some(Run::Command(RunCommand::new(PathBuf::from("htop")))),
+            ..Default::default()
+        },
+    ];
     let mut mock_screen = MockScreen::new(size);
     let session_metadata = mock_screen.clone_session_metadata();
     let screen_thread = mock_screen.run(Some(initial_layout));
@@ -1320,35 +2753,200 @@ pub fn send_cli_edit_scrollback_action() {
         PtyInstruction::Exit,
         pty_receiver
     );
-    let cli_action = CliAction::EditScrollback;
-    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
-        0,
-        "fill pane up with something".as_bytes().to_vec(),
-    ));
-    send_cli_action_to_server(&session_metadata, cli_action, client_id);
+    let cli_action = CliAction::QueryPaneList {
+        format: PaneListFormat::Json,
+    };
+    send_cli_action_to_server(&session_metadata, cli_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    let mut found_instruction = false;
+    for instruction in received_pty_instructions.lock().unwrap().iter() {
+        if let PtyInstruction::ListPanes(pane_list, format, client_id) = instruction {
+            assert_eq!(format, &PaneListFormat::Json);
+            assert_eq!(client_id, &10);
+            let listed: Vec<(u32, usize, bool, Option<String>)> = pane_list
+                .iter()
+                .map(|pane| {
+                    (
+                        pane.id,
+                        pane.tab_index,
+                        pane.is_focused,
+                        pane.command.clone(),
+                    )
+                })
+                .collect();
+            assert_eq!(
+                listed,
+                vec![(1, 1, true, None), (2, 1, false, Some("htop".to_owned()))],
+                "both panes of the first tab listed with the command of the second"
+            );
+            found_instruction = true;
+        }
+    }
+    assert!(found_instruction);
+}
+
+#[test]
+pub fn send_cli_mode_help_action_and_dismiss_it_with_esc() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()));
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_instruction = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    send_cli_action_to_server(
+        &session_metadata,
+        CliAction::ModeHelp {
+            mode: InputMode::Pane,
+        },
+        client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    send_cli_action_to_server(
+        &session_metadata,
+        CliAction::Write { bytes: vec![27] },
+        client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_instruction, screen_thread]);
+    let snapshots = take_snapshots_and_cursor_coordinates_from_render_events(
+        received_server_instructions.lock().unwrap().iter(),
+        size,
+    );
+    let shows_mode_help =
+        |snapshot: &String| snapshot.contains("Pane mode keybindings, <ESC> to dismiss");
+    assert!(
+        snapshots
+            .iter()
+            .any(|(_cursor_coordinates, snapshot)| shows_mode_help(snapshot)),
+        "mode help was shown"
+    );
+    assert!(
+        !snapshots
+            .last()
+            .map(|(_cursor_coordinates, snapshot)| shows_mode_help(snapshot))
+            .unwrap_or(true),
+        "mode help was dismissed"
+    );
+}
+
+#[test]
+pub fn named_floating_pane_reopens_with_its_last_geometry() {
+    let size = Size { cols: 80, rows: 20 };
+    let mut mock_screen = MockScreen::new(size);
+    let client_id = mock_screen.main_client_id;
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()));
+    let received_plugin_instructions = Arc::new(Mutex::new(vec![]));
+    let plugin_receiver = mock_screen.plugin_receiver.take().unwrap();
+    let plugin_thread = log_actions_in_thread!(
+        received_plugin_instructions,
+        PluginInstruction::Exit,
+        plugin_receiver
+    );
+    let placement = FloatingPanePlacement {
+        snap: Some(FloatingSnap::TopLeft),
+        width: Some(20),
+        height: Some(5),
+        aspect: None,
+    };
+    let _ = mock_screen.to_screen.send(ScreenInstruction::NewPane(
+        PaneId::Terminal(2),
+        Some("scratch".to_owned()),
+        Some(true),
+        None,
+        ClientOrTabIndex::ClientId(client_id),
+        Some(placement),
+    ));
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::ClosePane(PaneId::Terminal(2), None));
+    let _ = mock_screen.to_screen.send(ScreenInstruction::NewPane(
+        PaneId::Terminal(3),
+        Some("scratch".to_owned()),
+        Some(true),
+        None,
+        ClientOrTabIndex::ClientId(client_id),
+        None,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![plugin_thread, screen_thread]);
+    let pane_geometry = |pane_id: u32| {
+        received_plugin_instructions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|instruction| match instruction {
+                PluginInstruction::Update(updates) => updates.iter().find_map(|u| match u {
+                    (_, _, Event::PaneUpdate(pane_manifest)) => pane_manifest
+                        .panes
+                        .values()
+                        .flatten()
+                        .find(|pane_info| pane_info.id == pane_id && pane_info.is_floating)
+                        .map(|pane_info| {
+                            (
+                                pane_info.pane_x,
+                                pane_info.pane_y,
+                                pane_info.pane_columns,
+                                pane_info.pane_rows,
+                            )
+                        }),
+                    _ => None,
+                }),
+                _ => None,
+            })
+            .last()
+    };
+    assert_eq!(pane_geometry(2), Some((0, 0, 20, 5)), "pane was snapped");
+    assert_eq!(
+        pane_geometry(3),
+        pane_geometry(2),
+        "pane with the same name reopened in place"
+    );
+}
+
+#[test]
+pub fn send_cli_repeat_action_before_go_to_next_tab() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    mock_screen.new_tab(TiledPaneLayout::default());
+    mock_screen.new_tab(TiledPaneLayout::default());
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()));
+    let received_plugin_instructions = Arc::new(Mutex::new(vec![]));
+    let plugin_receiver = mock_screen.plugin_receiver.take().unwrap();
+    let plugin_thread = log_actions_in_thread!(
+        received_plugin_instructions,
+        PluginInstruction::Exit,
+        plugin_receiver
+    );
+    send_cli_action_to_server(&session_metadata, CliAction::Repeat { count: 2 }, client_id);
+    send_cli_action_to_server(&session_metadata, CliAction::GoToNextTab, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100));
-    mock_screen.teardown(vec![pty_thread, screen_thread]);
-    let dumped_file_name = mock_screen
-        .os_input
-        .fake_filesystem
+    mock_screen.teardown(vec![plugin_thread, screen_thread]);
+    let active_tab_position = received_plugin_instructions
         .lock()
         .unwrap()
-        .keys()
-        .next()
-        .unwrap()
-        .clone();
-    let mut found_instruction = false;
-    for instruction in received_pty_instructions.lock().unwrap().iter() {
-        if let PtyInstruction::OpenInPlaceEditor(scrollback_contents_file, terminal_id, client_id) =
-            instruction
-        {
-            assert_eq!(scrollback_contents_file, &PathBuf::from(&dumped_file_name));
-            assert_eq!(terminal_id, &Some(1));
-            assert_eq!(client_id, &1);
-            found_instruction = true;
-        }
-    }
-    assert!(found_instruction);
+        .iter()
+        .filter_map(|instruction| match instruction {
+            PluginInstruction::Update(updates) => updates.iter().find_map(|u| match u {
+                (_, _, Event::TabUpdate(tab_infos)) => tab_infos
+                    .iter()
+                    .find(|tab_info| tab_info.active)
+                    .map(|tab_info| tab_info.position),
+                _ => None,
+            }),
+            _ => None,
+        })
+        .last();
+    // we start at the third tab, so going to the next tab twice wraps around to the second one
+    assert_eq!(active_tab_position, Some(1), "went to the next tab twice");
 }
 
 #[test]
@@ -1368,7 +2966,7 @@ pub fn send_cli_scroll_up_action() {
         ServerInstruction::KillSession,
         server_receiver
     );
-    let cli_action = CliAction::ScrollUp;
+    let cli_action = CliAction::ScrollUp { count: None };
     let mut pane_contents = String::new();
     for i in 0..20 {
         pane_contents.push_str(&format!("fill pane up with something {}\n\r", i));
@@ -1395,6 +2993,57 @@ pub fn send_cli_scroll_up_action() {
     assert_snapshot!(format!("{}", snapshot_count));
 }
 
+#[test]
+pub fn send_cli_scroll_up_action_with_count() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let last_snapshot_after_cli_actions = |cli_actions: Vec<CliAction>| {
+        let mut mock_screen = MockScreen::new(size);
+        let session_metadata = mock_screen.clone_session_metadata();
+        let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()));
+        let received_server_instructions = Arc::new(Mutex::new(vec![]));
+        let server_receiver = mock_screen.server_receiver.take().unwrap();
+        let server_instruction = log_actions_in_thread!(
+            received_server_instructions,
+            ServerInstruction::KillSession,
+            server_receiver
+        );
+        let mut pane_contents = String::new();
+        for i in 0..20 {
+            pane_contents.push_str(&format!("fill pane up with something {}\n\r", i));
+        }
+        let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+            0,
+            pane_contents.as_bytes().to_vec(),
+        ));
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        for cli_action in cli_actions {
+            send_cli_action_to_server(&session_metadata, cli_action, client_id);
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        mock_screen.teardown(vec![server_instruction, screen_thread]);
+        let snapshots = take_snapshots_and_cursor_coordinates_from_render_events(
+            received_server_instructions.lock().unwrap().iter(),
+            size,
+        );
+        snapshots
+            .last()
+            .map(|(_cursor_coordinates, snapshot)| snapshot.clone())
+    };
+    let scrolled_with_count =
+        last_snapshot_after_cli_actions(vec![CliAction::ScrollUp { count: Some(3) }]);
+    let scrolled_three_times = last_snapshot_after_cli_actions(vec![
+        CliAction::ScrollUp { count: None },
+        CliAction::ScrollUp { count: None },
+        CliAction::ScrollUp { count: None },
+    ]);
+    assert!(scrolled_with_count.is_some());
+    assert_eq!(
+        scrolled_with_count, scrolled_three_times,
+        "scrolling with a count is the same as scrolling that many times"
+    );
+}
+
 #[test]
 pub fn send_cli_scroll_down_action() {
     let size = Size { cols: 80, rows: 10 };
@@ -1412,8 +3061,8 @@ pub fn send_cli_scroll_down_action() {
         ServerInstruction::KillSession,
         server_receiver
     );
-    let scroll_up_cli_action = CliAction::ScrollUp;
-    let scroll_down_cli_action = CliAction::ScrollDown;
+    let scroll_up_cli_action = CliAction::ScrollUp { count: None };
+    let scroll_down_cli_action = CliAction::ScrollDown { count: None };
     let mut pane_contents = String::new();
     for i in 0..20 {
         pane_contents.push_str(&format!("fill pane up with something {}\n\r", i));
@@ -1462,7 +3111,7 @@ pub fn send_cli_scroll_to_bottom_action() {
         ServerInstruction::KillSession,
         server_receiver
     );
-    let scroll_up_cli_action = CliAction::ScrollUp;
+    let scroll_up_cli_action = CliAction::ScrollUp { count: None };
     let scroll_to_bottom_action = CliAction::ScrollToBottom;
     let mut pane_contents = String::new();
     for i in 0..20 {
@@ -1557,7 +3206,7 @@ pub fn send_cli_page_scroll_up_action() {
         ServerInstruction::KillSession,
         server_receiver
     );
-    let page_scroll_up_action = CliAction::PageScrollUp;
+    let page_scroll_up_action = CliAction::PageScrollUp { count: 1 };
     let mut pane_contents = String::new();
     for i in 0..20 {
         pane_contents.push_str(&format!("fill pane up with something {}\n\r", i));
@@ -1598,8 +3247,8 @@ pub fn send_cli_page_scroll_down_action() {
         ServerInstruction::KillSession,
         server_receiver
     );
-    let page_scroll_up_action = CliAction::PageScrollUp;
-    let page_scroll_down_action = CliAction::PageScrollDown;
+    let page_scroll_up_action = CliAction::PageScrollUp { count: 1 };
+    let page_scroll_down_action = CliAction::PageScrollDown { count: 1 };
     let mut pane_contents = String::new();
     for i in 0..20 {
         pane_contents.push_str(&format!("fill pane up with something {}\n\r", i));
@@ -1646,7 +3295,7 @@ pub fn send_cli_half_page_scroll_up_action() {
         ServerInstruction::KillSession,
         server_receiver
     );
-    let half_page_scroll_up_action = CliAction::HalfPageScrollUp;
+    let half_page_scroll_up_action = CliAction::HalfPageScrollUp { count: 1 };
     let mut pane_contents = String::new();
     for i in 0..20 {
         pane_contents.push_str(&format!("fill pane up with something {}\n\r", i));
@@ -1687,8 +3336,8 @@ pub fn send_cli_half_page_scroll_down_action() {
         ServerInstruction::KillSession,
         server_receiver
     );
-    let half_page_scroll_up_action = CliAction::HalfPageScrollUp;
-    let half_page_scroll_down_action = CliAction::HalfPageScrollDown;
+    let half_page_scroll_up_action = CliAction::HalfPageScrollUp { count: 1 };
+    let half_page_scroll_down_action = CliAction::HalfPageScrollDown { count: 1 };
     let mut pane_contents = String::new();
     for i in 0..20 {
         pane_contents.push_str(&format!("fill pane up with something {}\n\r", i));
@@ -1743,7 +3392,7 @@ pub fn send_cli_toggle_full_screen_action() {
         ServerInstruction::KillSession,
         server_receiver
     );
-    let toggle_full_screen_action = CliAction::ToggleFullscreen;
+    let toggle_full_screen_action = CliAction::ToggleFullscreen { pane_id: None };
     send_cli_action_to_server(&session_metadata, toggle_full_screen_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100));
     mock_screen.teardown(vec![server_instruction, screen_thread]);
@@ -1790,6 +3439,44 @@ pub fn send_cli_toggle_pane_frames_action() {
     assert_snapshot!(format!("{}", snapshot_count));
 }
 
+#[test]
+pub fn send_cli_toggle_ruler_action() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout));
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_instruction = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    send_cli_action_to_server(&session_metadata, CliAction::ToggleRuler, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_instruction, screen_thread]);
+    let snapshots = take_snapshots_and_cursor_coordinates_from_render_events(
+        received_server_instructions.lock().unwrap().iter(),
+        size,
+    );
+    let (_cursor_coordinates, last_snapshot) = snapshots.last().unwrap();
+    assert!(
+        last_snapshot.contains("┬50%"),
+        "ruler is drawn with a tick at half the width"
+    );
+    assert!(
+        last_snapshot.contains("├50%"),
+        "ruler is drawn with a tick at half the height"
+    );
+}
+
 #[test]
 pub fn send_cli_toggle_active_tab_sync_action() {
     let size = Size {
@@ -1821,12 +3508,287 @@ pub fn send_cli_toggle_active_tab_sync_action() {
     );
     send_cli_action_to_server(&session_metadata, cli_write_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
-    mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
-    assert_snapshot!(format!("{:?}", *received_pty_instructions.lock().unwrap()));
+    mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
+    assert_snapshot!(format!("{:?}", *received_pty_instructions.lock().unwrap()));
+}
+
+#[test]
+pub fn send_cli_new_pane_action_with_default_parameters() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let screen_thread = mock_screen.run(Some(initial_layout));
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    let cli_new_pane_action = CliAction::NewPane {
+        direction: None,
+        command: vec![],
+        plugin: None,
+        cwd: None,
+        floating: false,
+        snap: None,
+        width: None,
+        height: None,
+        aspect: None,
+        place_at: None,
+        name: None,
+        close_on_exit: false,
+        start_suspended: false,
+        login_shell: false,
+        shell: None,
+        watch: vec![],
+        output_socket: None,
+        pin_matching: None,
+        cwd_from_focus: false,
+        no_frame: false,
+        capture_exit: None,
+        env: vec![],
+        fixed_size: None,
+        reset_geometry: false,
+    };
+    send_cli_action_to_server(&session_metadata, cli_new_pane_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    assert_snapshot!(format!("{:?}", *received_pty_instructions.lock().unwrap()));
+}
+
+#[test]
+pub fn send_cli_new_pane_action_with_split_direction() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let screen_thread = mock_screen.run(Some(initial_layout));
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    let cli_new_pane_action = CliAction::NewPane {
+        direction: Some(Direction::Right),
+        command: vec![],
+        plugin: None,
+        cwd: None,
+        floating: false,
+        snap: None,
+        width: None,
+        height: None,
+        aspect: None,
+        place_at: None,
+        name: None,
+        close_on_exit: false,
+        start_suspended: false,
+        login_shell: false,
+        shell: None,
+        watch: vec![],
+        output_socket: None,
+        pin_matching: None,
+        cwd_from_focus: false,
+        no_frame: false,
+        capture_exit: None,
+        env: vec![],
+        fixed_size: None,
+        reset_geometry: false,
+    };
+    send_cli_action_to_server(&session_metadata, cli_new_pane_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    assert_snapshot!(format!("{:?}", *received_pty_instructions.lock().unwrap()));
+}
+
+#[test]
+pub fn send_cli_new_pane_action_with_command_and_cwd() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let screen_thread = mock_screen.run(Some(initial_layout));
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    let cli_new_pane_action = CliAction::NewPane {
+        direction: Some(Direction::Right),
+        command: vec!["htop".into()],
+        plugin: None,
+        cwd: Some("/some/folder".into()),
+        floating: false,
+        snap: None,
+        width: None,
+        height: None,
+        aspect: None,
+        place_at: None,
+        name: None,
+        close_on_exit: false,
+        start_suspended: false,
+        login_shell: false,
+        shell: None,
+        watch: vec![],
+        output_socket: None,
+        pin_matching: None,
+        cwd_from_focus: false,
+        no_frame: false,
+        capture_exit: None,
+        env: vec![],
+        fixed_size: None,
+        reset_geometry: false,
+    };
+    send_cli_action_to_server(&session_metadata, cli_new_pane_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    assert_snapshot!(format!("{:?}", *received_pty_instructions.lock().unwrap()));
+}
+
+#[test]
+pub fn send_cli_new_pane_action_with_login_shell() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let screen_thread = mock_screen.run(Some(initial_layout));
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    let cli_new_pane_action = CliAction::NewPane {
+        direction: None,
+        command: vec!["tail".into(), "-f".into(), "my log.txt".into()],
+        plugin: None,
+        cwd: None,
+        floating: false,
+        snap: None,
+        width: None,
+        height: None,
+        aspect: None,
+        place_at: None,
+        name: None,
+        close_on_exit: false,
+        start_suspended: false,
+        login_shell: true,
+        shell: None,
+        watch: vec![],
+        output_socket: None,
+        pin_matching: None,
+        cwd_from_focus: false,
+        no_frame: false,
+        capture_exit: None,
+        env: vec![],
+        fixed_size: None,
+        reset_geometry: false,
+    };
+    send_cli_action_to_server(&session_metadata, cli_new_pane_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    // the shell itself comes from the environment, so we only look at its arguments
+    let received_pty_instructions = format!("{:?}", *received_pty_instructions.lock().unwrap());
+    assert!(
+        received_pty_instructions.contains(r#"args: ["-lc", "tail -f 'my log.txt'"]"#),
+        "Command run through a login shell: {}",
+        received_pty_instructions
+    );
+}
+
+#[test]
+pub fn send_cli_new_pane_action_with_env() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()));
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    let cli_new_pane_action = CliAction::NewPane {
+        direction: None,
+        command: vec!["htop".into()],
+        plugin: None,
+        cwd: None,
+        floating: false,
+        snap: None,
+        width: None,
+        height: None,
+        aspect: None,
+        place_at: None,
+        name: None,
+        close_on_exit: false,
+        start_suspended: false,
+        login_shell: false,
+        shell: None,
+        watch: vec![],
+        output_socket: None,
+        pin_matching: None,
+        cwd_from_focus: false,
+        no_frame: false,
+        capture_exit: None,
+        env: vec!["FOO=bar".into(), "BAZ=a=b".into()],
+        fixed_size: None,
+        reset_geometry: false,
+    };
+    send_cli_action_to_server(&session_metadata, cli_new_pane_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    let env = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|instruction| match instruction {
+            PtyInstruction::SpawnTerminal(Some(TerminalAction::RunCommand(run_command)), ..) => {
+                Some(run_command.env.clone())
+            },
+            _ => None,
+        })
+        .unwrap_or_default();
+    assert_eq!(env.get("FOO").map(|v| v.as_str()), Some("bar"));
+    assert_eq!(env.get("BAZ").map(|v| v.as_str()), Some("a=b"));
 }
 
 #[test]
-pub fn send_cli_new_pane_action_with_default_parameters() {
+pub fn send_cli_new_pane_action_with_output_socket() {
     let size = Size {
         cols: 121,
         rows: 20,
@@ -1835,10 +3797,7 @@ pub fn send_cli_new_pane_action_with_default_parameters() {
     let mut mock_screen = MockScreen::new(size);
     let pty_receiver = mock_screen.pty_receiver.take().unwrap();
     let session_metadata = mock_screen.clone_session_metadata();
-    let mut initial_layout = TiledPaneLayout::default();
-    initial_layout.children_split_direction = SplitDirection::Vertical;
-    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
-    let screen_thread = mock_screen.run(Some(initial_layout));
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()));
     let received_pty_instructions = Arc::new(Mutex::new(vec![]));
     let pty_thread = log_actions_in_thread!(
         received_pty_instructions,
@@ -1847,22 +3806,52 @@ pub fn send_cli_new_pane_action_with_default_parameters() {
     );
     let cli_new_pane_action = CliAction::NewPane {
         direction: None,
-        command: vec![],
+        command: vec!["htop".into()],
         plugin: None,
         cwd: None,
         floating: false,
+        snap: None,
+        width: None,
+        height: None,
+        aspect: None,
+        place_at: None,
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        login_shell: false,
+        shell: None,
+        watch: vec![],
+        output_socket: Some("/tmp/htop-output.sock".into()),
+        pin_matching: None,
+        cwd_from_focus: false,
+        no_frame: false,
+        capture_exit: None,
+        env: vec![],
+        fixed_size: None,
+        reset_geometry: false,
     };
     send_cli_action_to_server(&session_metadata, cli_new_pane_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
     mock_screen.teardown(vec![pty_thread, screen_thread]);
-    assert_snapshot!(format!("{:?}", *received_pty_instructions.lock().unwrap()));
+    let output_socket = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|instruction| match instruction {
+            PtyInstruction::SpawnTerminal(Some(TerminalAction::RunCommand(run_command)), ..) => {
+                run_command.output_socket.clone()
+            },
+            _ => None,
+        });
+    assert_eq!(
+        output_socket,
+        Some(PathBuf::from("/tmp/htop-output.sock")),
+        "the command is spawned with its output socket"
+    );
 }
 
 #[test]
-pub fn send_cli_new_pane_action_with_split_direction() {
+pub fn send_cli_new_pane_action_placed_at_percent() {
     let size = Size {
         cols: 121,
         rows: 20,
@@ -1871,10 +3860,7 @@ pub fn send_cli_new_pane_action_with_split_direction() {
     let mut mock_screen = MockScreen::new(size);
     let pty_receiver = mock_screen.pty_receiver.take().unwrap();
     let session_metadata = mock_screen.clone_session_metadata();
-    let mut initial_layout = TiledPaneLayout::default();
-    initial_layout.children_split_direction = SplitDirection::Vertical;
-    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
-    let screen_thread = mock_screen.run(Some(initial_layout));
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()));
     let received_pty_instructions = Arc::new(Mutex::new(vec![]));
     let pty_thread = log_actions_in_thread!(
         received_pty_instructions,
@@ -1887,18 +3873,272 @@ pub fn send_cli_new_pane_action_with_split_direction() {
         plugin: None,
         cwd: None,
         floating: false,
+        snap: None,
+        width: None,
+        height: None,
+        aspect: None,
+        place_at: Some(60),
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        login_shell: false,
+        shell: None,
+        watch: vec![],
+        output_socket: None,
+        pin_matching: None,
+        cwd_from_focus: false,
+        no_frame: false,
+        capture_exit: None,
+        env: vec![],
+        fixed_size: None,
+        reset_geometry: false,
     };
     send_cli_action_to_server(&session_metadata, cli_new_pane_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
     mock_screen.teardown(vec![pty_thread, screen_thread]);
-    assert_snapshot!(format!("{:?}", *received_pty_instructions.lock().unwrap()));
+    let fraction =
+        received_pty_instructions.lock().unwrap().iter().find_map(
+            |instruction| match instruction {
+                PtyInstruction::SpawnTerminalAtFraction(_, _, Direction::Right, fraction, _) => {
+                    Some(*fraction)
+                },
+                _ => None,
+            },
+        );
+    assert_eq!(fraction, Some(0.6), "the tab is split at 60% of its width");
 }
 
 #[test]
-pub fn send_cli_new_pane_action_with_command_and_cwd() {
+pub fn cli_new_pane_action_with_malformed_env_is_an_error() {
+    let cli_new_pane_action = CliAction::NewPane {
+        direction: None,
+        command: vec!["htop".into()],
+        plugin: None,
+        cwd: None,
+        floating: false,
+        snap: None,
+        width: None,
+        height: None,
+        aspect: None,
+        place_at: None,
+        name: None,
+        close_on_exit: false,
+        start_suspended: false,
+        login_shell: false,
+        shell: None,
+        watch: vec![],
+        output_socket: None,
+        pin_matching: None,
+        cwd_from_focus: false,
+        no_frame: false,
+        capture_exit: None,
+        env: vec!["FOO".into()],
+        fixed_size: None,
+        reset_geometry: false,
+    };
+    let get_current_dir = || PathBuf::from(".");
+    let actions = Action::actions_from_cli(cli_new_pane_action, Box::new(get_current_dir), None);
+    assert!(actions.is_err(), "malformed env variable was rejected");
+}
+
+#[test]
+pub fn cli_new_pane_action_runs_command_in_given_shell() {
+    let cli_new_pane_action = CliAction::NewPane {
+        direction: None,
+        command: vec![
+            "ls".into(),
+            "*.txt".into(),
+            "|".into(),
+            "wc".into(),
+            "-l".into(),
+        ],
+        plugin: None,
+        cwd: None,
+        floating: false,
+        snap: None,
+        width: None,
+        height: None,
+        aspect: None,
+        place_at: None,
+        name: None,
+        close_on_exit: false,
+        start_suspended: false,
+        login_shell: false,
+        shell: Some("/bin/sh".into()),
+        watch: vec![],
+        output_socket: None,
+        pin_matching: None,
+        cwd_from_focus: false,
+        no_frame: false,
+        capture_exit: None,
+        env: vec![],
+        fixed_size: None,
+        reset_geometry: false,
+    };
+    let get_current_dir = || PathBuf::from(".");
+    let actions =
+        Action::actions_from_cli(cli_new_pane_action, Box::new(get_current_dir), None).unwrap();
+    let run_command = match actions.first() {
+        Some(Action::NewTiledPane(_, Some(PaneRunAction::Command(run_command_action)), _)) => {
+            RunCommand::from(run_command_action.clone())
+        },
+        _ => panic!("no new pane action: {:?}", actions),
+    };
+    assert_eq!(run_command.command, PathBuf::from("/bin/sh"));
+    assert_eq!(
+        run_command.args,
+        vec!["-c".to_owned(), "ls *.txt | wc -l".to_owned()],
+        "command line is left for the shell to interpret"
+    );
+}
+
+#[test]
+pub fn cli_new_pane_action_with_unknown_shell_is_an_error() {
+    let cli_new_pane_action = CliAction::NewPane {
+        direction: None,
+        command: vec![
+            "ls".into(),
+            "*.txt".into(),
+            "|".into(),
+            "wc".into(),
+            "-l".into(),
+        ],
+        plugin: None,
+        cwd: None,
+        floating: false,
+        snap: None,
+        width: None,
+        height: None,
+        aspect: None,
+        place_at: None,
+        name: None,
+        close_on_exit: false,
+        start_suspended: false,
+        login_shell: false,
+        shell: Some("no-such-shell-anywhere".into()),
+        watch: vec![],
+        output_socket: None,
+        pin_matching: None,
+        cwd_from_focus: false,
+        no_frame: false,
+        capture_exit: None,
+        env: vec![],
+        fixed_size: None,
+        reset_geometry: false,
+    };
+    let get_current_dir = || PathBuf::from(".");
+    let actions = Action::actions_from_cli(cli_new_pane_action, Box::new(get_current_dir), None);
+    assert!(actions.is_err(), "shell that cannot be found was rejected");
+}
+
+#[test]
+pub fn cli_open_url_action_rejects_out_of_range_positions() {
+    let get_current_dir = || PathBuf::from(".");
+    let actions = Action::actions_from_cli(
+        CliAction::OpenUrl {
+            x: u16::MAX as usize + 1,
+            y: 0,
+        },
+        Box::new(get_current_dir),
+        None,
+    );
+    assert!(actions.is_err(), "column past u16::MAX was rejected");
+    let actions = Action::actions_from_cli(
+        CliAction::CopyUrl {
+            x: 0,
+            y: i32::MAX as usize + 1,
+        },
+        Box::new(get_current_dir),
+        None,
+    );
+    assert!(actions.is_err(), "line past i32::MAX was rejected");
+    let actions = Action::actions_from_cli(
+        CliAction::OpenUrl { x: 3, y: 2 },
+        Box::new(get_current_dir),
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        actions,
+        vec![Action::OpenUrlUnderCursor(Position::new(2, 3))],
+        "in range position is kept"
+    );
+}
+
+#[test]
+pub fn cli_write_filtered_action_requires_a_filter() {
+    let get_current_dir = || PathBuf::from(".");
+    let actions = Action::actions_from_cli(
+        CliAction::WriteFiltered {
+            command: None,
+            chars: "q".into(),
+        },
+        Box::new(get_current_dir),
+        None,
+    );
+    assert!(actions.is_err(), "writing without a filter was rejected");
+    let actions = Action::actions_from_cli(
+        CliAction::WriteFiltered {
+            command: Some("htop".into()),
+            chars: "q".into(),
+        },
+        Box::new(get_current_dir),
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        actions,
+        vec![Action::WriteToPanesMatching("htop".into(), vec![b'q'])],
+        "the command filter is kept"
+    );
+}
+
+#[test]
+pub fn cli_new_pane_action_with_cwd_from_focus_overrides_cwd() {
+    let cli_new_pane_action = CliAction::NewPane {
+        direction: None,
+        command: vec!["htop".into()],
+        plugin: None,
+        cwd: Some("/some/other/dir".into()),
+        floating: false,
+        snap: None,
+        width: None,
+        height: None,
+        aspect: None,
+        place_at: None,
+        name: None,
+        close_on_exit: false,
+        start_suspended: false,
+        login_shell: false,
+        shell: None,
+        watch: vec![],
+        output_socket: None,
+        pin_matching: None,
+        cwd_from_focus: true,
+        no_frame: false,
+        capture_exit: None,
+        env: vec![],
+        fixed_size: None,
+        reset_geometry: false,
+    };
+    let get_current_dir = || PathBuf::from("/current/dir");
+    let actions =
+        Action::actions_from_cli(cli_new_pane_action, Box::new(get_current_dir), None).unwrap();
+    match actions.get(0) {
+        Some(Action::NewTiledPane(_, Some(PaneRunAction::Command(run_command_action)), _)) => {
+            assert!(run_command_action.cwd_from_focus, "cwd is taken from focus");
+            assert_eq!(
+                run_command_action.cwd,
+                Some(PathBuf::from("/current/dir")),
+                "falls back to the current dir rather than --cwd"
+            );
+        },
+        _ => panic!("unexpected actions: {:?}", actions),
+    }
+}
+
+#[test]
+pub fn send_cli_new_pane_action_with_no_frame_and_no_command_spawns_the_default_shell() {
     let size = Size {
         cols: 121,
         rows: 20,
@@ -1907,10 +4147,7 @@ pub fn send_cli_new_pane_action_with_command_and_cwd() {
     let mut mock_screen = MockScreen::new(size);
     let pty_receiver = mock_screen.pty_receiver.take().unwrap();
     let session_metadata = mock_screen.clone_session_metadata();
-    let mut initial_layout = TiledPaneLayout::default();
-    initial_layout.children_split_direction = SplitDirection::Vertical;
-    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
-    let screen_thread = mock_screen.run(Some(initial_layout));
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()));
     let received_pty_instructions = Arc::new(Mutex::new(vec![]));
     let pty_thread = log_actions_in_thread!(
         received_pty_instructions,
@@ -1918,19 +4155,111 @@ pub fn send_cli_new_pane_action_with_command_and_cwd() {
         pty_receiver
     );
     let cli_new_pane_action = CliAction::NewPane {
-        direction: Some(Direction::Right),
-        command: vec!["htop".into()],
+        direction: None,
+        command: vec![],
         plugin: None,
-        cwd: Some("/some/folder".into()),
+        cwd: None,
         floating: false,
+        snap: None,
+        width: None,
+        height: None,
+        aspect: None,
+        place_at: None,
         name: None,
         close_on_exit: false,
         start_suspended: false,
+        login_shell: false,
+        shell: None,
+        watch: vec![],
+        output_socket: None,
+        pin_matching: None,
+        cwd_from_focus: false,
+        no_frame: true,
+        capture_exit: None,
+        env: vec![],
+        fixed_size: None,
+        reset_geometry: false,
     };
     send_cli_action_to_server(&session_metadata, cli_new_pane_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
     mock_screen.teardown(vec![pty_thread, screen_thread]);
-    assert_snapshot!(format!("{:?}", *received_pty_instructions.lock().unwrap()));
+    let spawned_command = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|instruction| match instruction {
+            PtyInstruction::SpawnTerminal(Some(TerminalAction::RunCommand(run_command)), ..) => {
+                Some(run_command.clone())
+            },
+            _ => None,
+        })
+        .expect("a terminal was spawned");
+    assert_eq!(
+        spawned_command.command,
+        get_default_shell(),
+        "no command is given, the default shell is spawned"
+    );
+    assert!(
+        spawned_command.args.is_empty(),
+        "the shell gets no arguments"
+    );
+    assert!(spawned_command.no_frame, "the pane has no frame");
+    assert_eq!(spawned_command.cwd, None, "cwd is left to the server");
+}
+
+#[test]
+pub fn send_cli_restart_pane_action() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()));
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    let rerun_count = || {
+        received_pty_instructions
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|instruction| matches!(instruction, PtyInstruction::ReRunCommandInPane(..)))
+            .count()
+    };
+    // the command of the pane is still running, so there is nothing to restart
+    send_cli_action_to_server(&session_metadata, CliAction::RestartPane, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    assert_eq!(rerun_count(), 0, "live pane was left alone");
+    let _ = mock_screen.to_screen.send(ScreenInstruction::HoldPane(
+        PaneId::Terminal(0),
+        Some(1),
+        RunCommand::new(PathBuf::from("tail")),
+        None,
+        None,
+    ));
+    send_cli_action_to_server(&session_metadata, CliAction::RestartPane, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    let rerun_command =
+        received_pty_instructions.lock().unwrap().iter().find_map(
+            |instruction| match instruction {
+                PtyInstruction::ReRunCommandInPane(PaneId::Terminal(0), run_command) => {
+                    Some(run_command.command.clone())
+                },
+                _ => None,
+            },
+        );
+    assert_eq!(
+        rerun_command,
+        Some(PathBuf::from("tail")),
+        "held pane reran its command"
+    );
 }
 
 #[test]
@@ -1957,6 +4286,7 @@ pub fn send_cli_edit_action_with_default_parameters() {
         file: PathBuf::from("/file/to/edit"),
         direction: None,
         line_number: None,
+        column: None,
         floating: false,
         cwd: None,
     };
@@ -1990,6 +4320,7 @@ pub fn send_cli_edit_action_with_line_number() {
         file: PathBuf::from("/file/to/edit"),
         direction: None,
         line_number: Some(100),
+        column: None,
         floating: false,
         cwd: None,
     };
@@ -1999,6 +4330,50 @@ pub fn send_cli_edit_action_with_line_number() {
     assert_snapshot!(format!("{:?}", *received_pty_instructions.lock().unwrap()));
 }
 
+#[test]
+pub fn send_cli_edit_action_with_line_number_and_column() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()));
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    let cli_edit_action = CliAction::Edit {
+        file: PathBuf::from("/file/to/edit"),
+        direction: None,
+        line_number: Some(100),
+        column: Some(10),
+        floating: false,
+        cwd: None,
+    };
+    send_cli_action_to_server(&session_metadata, cli_edit_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    let opened_file_position = received_pty_instructions.lock().unwrap().iter().find_map(
+        |instruction| match instruction {
+            PtyInstruction::SpawnTerminal(
+                Some(TerminalAction::OpenFile(_, line_number, column, _)),
+                ..,
+            ) => Some((*line_number, *column)),
+            _ => None,
+        },
+    );
+    assert_eq!(
+        opened_file_position,
+        Some((Some(100), Some(10))),
+        "file opened at the requested line and column"
+    );
+}
+
 #[test]
 pub fn send_cli_edit_action_with_split_direction() {
     let size = Size {
@@ -2023,6 +4398,7 @@ pub fn send_cli_edit_action_with_split_direction() {
         file: PathBuf::from("/file/to/edit"),
         direction: Some(Direction::Down),
         line_number: None,
+        column: None,
         floating: false,
         cwd: None,
     };
@@ -2162,7 +4538,7 @@ pub fn send_cli_close_pane_action() {
         ServerInstruction::KillSession,
         server_receiver
     );
-    let close_pane_action = CliAction::ClosePane;
+    let close_pane_action = CliAction::ClosePane { smart: false };
     send_cli_action_to_server(&session_metadata, close_pane_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100));
     mock_screen.teardown(vec![server_instruction, screen_thread]);
@@ -2177,6 +4553,85 @@ pub fn send_cli_close_pane_action() {
     assert_snapshot!(format!("{}", snapshot_count));
 }
 
+#[test]
+pub fn send_cli_smart_close_pane_action_closes_idle_pane_without_prompt() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()));
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    let close_pane_action = CliAction::ClosePane { smart: true };
+    send_cli_action_to_server(&session_metadata, close_pane_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    let closed_pane = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|instruction| matches!(instruction, PtyInstruction::ClosePane(PaneId::Terminal(0))));
+    assert!(closed_pane, "idle pane was closed right away");
+}
+
+#[test]
+pub fn smart_close_pane_asks_for_confirmation_while_command_is_running() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut mock_screen = MockScreen::new(size);
+    let client_id = mock_screen.main_client_id;
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let command_layout = TiledPaneLayout {
+        run: Some(Run::Command(RunCommand::new(PathBuf::from("tail")))),
+        ..Default::default()
+    };
+    let screen_thread = mock_screen.run(Some(command_layout));
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    let closed_pane = || {
+        received_pty_instructions
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|instruction| {
+                matches!(instruction, PtyInstruction::ClosePane(PaneId::Terminal(0)))
+            })
+    };
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::CloseFocusedPaneSmart(client_id));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    assert!(!closed_pane(), "pane was not closed before confirming");
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::WriteCharacter(vec![b'n'], client_id));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    assert!(!closed_pane(), "pane was not closed after denying");
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::CloseFocusedPaneSmart(client_id));
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::WriteCharacter(vec![b'y'], client_id));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    assert!(closed_pane(), "pane was closed after confirming");
+}
+
 #[test]
 pub fn send_cli_new_tab_action_default_params() {
     let size = Size { cols: 80, rows: 10 };
@@ -2199,6 +4654,9 @@ pub fn send_cli_new_tab_action_default_params() {
         layout: None,
         layout_dir: None,
         cwd: None,
+        empty: false,
+        focus_pane: None,
+        inherit_vars: false,
     };
     send_cli_action_to_server(&session_metadata, new_tab_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100));
@@ -2214,6 +4672,51 @@ pub fn send_cli_new_tab_action_default_params() {
     assert_snapshot!(format!("{:#?}", new_tab_action));
 }
 
+#[test]
+pub fn send_cli_new_tab_action_inheriting_vars() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout));
+    let received_plugin_instructions = Arc::new(Mutex::new(vec![]));
+    let plugin_receiver = mock_screen.plugin_receiver.take().unwrap();
+    let plugin_thread = log_actions_in_thread!(
+        received_plugin_instructions,
+        PluginInstruction::Exit,
+        plugin_receiver
+    );
+    let new_tab_action = CliAction::NewTab {
+        name: None,
+        layout: None,
+        layout_dir: None,
+        cwd: None,
+        empty: false,
+        focus_pane: None,
+        inherit_vars: true,
+    };
+    send_cli_action_to_server(&session_metadata, new_tab_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![plugin_thread, screen_thread]);
+    let received_plugin_instructions = received_plugin_instructions.lock().unwrap();
+    let shell_starts_in_focused_cwd =
+        received_plugin_instructions
+            .iter()
+            .any(|instruction| match instruction {
+                PluginInstruction::NewTab(_, Some(TerminalAction::RunCommand(run_command)), ..) => {
+                    run_command.cwd_from_focus
+                },
+                _ => false,
+            });
+    assert!(
+        shell_starts_in_focused_cwd,
+        "panes of the new tab start in the cwd of the focused pane"
+    );
+}
+
 #[test]
 pub fn send_cli_new_tab_action_with_name_and_layout() {
     let size = Size { cols: 80, rows: 10 };
@@ -2239,6 +4742,9 @@ pub fn send_cli_new_tab_action_with_name_and_layout() {
         ))),
         layout_dir: None,
         cwd: None,
+        empty: false,
+        focus_pane: None,
+        inherit_vars: false,
     };
     send_cli_action_to_server(&session_metadata, new_tab_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100));
@@ -2449,6 +4955,37 @@ pub fn send_cli_rename_tab() {
     assert_snapshot!(format!("{:#?}", plugin_rename_tab_instruction))
 }
 
+#[test]
+pub fn rename_session_updates_the_session_name_of_clients() {
+    let size = Size { cols: 80, rows: 10 };
+    let mut mock_screen = MockScreen::new(size);
+    let screen_thread = mock_screen.run(None);
+    let received_plugin_instructions = Arc::new(Mutex::new(vec![]));
+    let plugin_receiver = mock_screen.plugin_receiver.take().unwrap();
+    let plugin_thread = log_actions_in_thread!(
+        received_plugin_instructions,
+        PluginInstruction::Exit,
+        plugin_receiver
+    );
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::RenameSession("new-session-name".into()));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![plugin_thread, screen_thread]);
+    let updated_session_name = received_plugin_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|instruction| match instruction {
+            PluginInstruction::Update(updates) => updates.iter().find_map(|u| match u {
+                (_, _, Event::ModeUpdate(mode_info)) => mode_info.session_name.clone(),
+                _ => None,
+            }),
+            _ => None,
+        });
+    assert_eq!(updated_session_name, Some("new-session-name".to_owned()));
+}
+
 #[test]
 pub fn send_cli_undo_rename_tab() {
     let size = Size { cols: 80, rows: 10 };
@@ -2530,6 +5067,234 @@ pub fn send_cli_query_tab_names_action() {
     assert_snapshot!(format!("{:#?}", log_tab_names_instruction));
 }
 
+#[test]
+pub fn send_cli_move_pane_to_tab_past_the_last_tab_action() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    mock_screen.new_tab(TiledPaneLayout::default());
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(TiledPaneLayout::default()));
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    send_cli_action_to_server(
+        &session_metadata,
+        CliAction::MovePaneToTab { index: 5 },
+        client_id,
+    );
+    send_cli_action_to_server(
+        &session_metadata,
+        CliAction::OverlayTab { index: 6 },
+        client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let logged_lines: Vec<String> = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            ServerInstruction::Log(lines, id) if *id == client_id => Some(lines.clone()),
+            _ => None,
+        })
+        .flatten()
+        .collect();
+    assert_eq!(
+        logged_lines,
+        vec![
+            "There is no tab at position 5, there are 2 tabs".to_owned(),
+            "There is no tab at position 6, there are 2 tabs".to_owned(),
+        ],
+        "the cli client is told that the tabs do not exist"
+    );
+}
+
+#[test]
+pub fn send_cli_set_var_action() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(None);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let set_var = CliAction::SetVar {
+        name: "target".to_owned(),
+        value: "release".to_owned(),
+    };
+    send_cli_action_to_server(&session_metadata, set_var, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let set_session_var_instruction =
+        received_server_instructions
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|instruction| match instruction {
+                ServerInstruction::SetSessionVar(name, value, None) => {
+                    name == "target" && value == "release"
+                },
+                _ => false,
+            });
+    assert!(
+        set_session_var_instruction,
+        "session variable set on the server"
+    );
+}
+
+#[test]
+pub fn write_chars_with_session_var_placeholder_is_written_in_order() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_receiver = mock_screen.screen_receiver.take().unwrap();
+    let mut session_state = SessionState::new();
+    session_state
+        .set_var("X".to_owned(), "make".to_owned())
+        .unwrap();
+    let session_state = Arc::new(RwLock::new(session_state));
+    route_action(
+        Action::Sequence(vec![
+            Action::WriteChars("{{X}}".to_owned()),
+            Action::Write(vec![13]),
+        ]),
+        client_id,
+        session_metadata.senders.clone(),
+        PluginCapabilities::default(),
+        ClientAttributes::default(),
+        None,
+        Box::new(Layout::default()),
+        Some(session_state),
+    )
+    .unwrap();
+    let written_bytes: Vec<Vec<u8>> = screen_receiver
+        .try_iter()
+        .filter_map(|(instruction, _)| match instruction {
+            ScreenInstruction::WriteCharacter(bytes, id) if id == client_id => Some(bytes),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        written_bytes,
+        vec![b"make".to_vec(), vec![13]],
+        "placeholder replaced and written before the following action"
+    );
+}
+
+#[test]
+pub fn set_session_var_past_the_limit_is_reported_to_the_client() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let mut session_state = SessionState::new();
+    for i in 0..MAX_SESSION_VARS {
+        session_state
+            .set_var(format!("VAR{}", i), String::new())
+            .unwrap();
+    }
+    let session_state = Arc::new(RwLock::new(session_state));
+    route_action(
+        Action::SetSessionVar("ONE_TOO_MANY".to_owned(), "1".to_owned()),
+        client_id,
+        session_metadata.senders.clone(),
+        PluginCapabilities::default(),
+        ClientAttributes::default(),
+        None,
+        Box::new(Layout::default()),
+        Some(session_state.clone()),
+    )
+    .unwrap();
+    let logged_for_client = server_receiver.try_iter().any(
+        |(instruction, _)| matches!(instruction, ServerInstruction::Log(_, id) if id == client_id),
+    );
+    assert!(logged_for_client, "client told the variable was not set");
+    assert_eq!(session_state.read().unwrap().get_var("ONE_TOO_MANY"), None);
+}
+
+#[test]
+pub fn send_cli_get_var_action() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(None);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let get_var = CliAction::GetVar {
+        name: "build_status".to_owned(),
+    };
+    send_cli_action_to_server(&session_metadata, get_var, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let query_session_var_instruction =
+        received_server_instructions
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|instruction| match instruction {
+                ServerInstruction::QuerySessionVar(name, id) => {
+                    name == "build_status" && *id == client_id
+                },
+                _ => false,
+            });
+    assert!(
+        query_session_var_instruction,
+        "session variable queried from the server"
+    );
+}
+
+#[test]
+pub fn send_cli_clipboard_info_action() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(None);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    send_cli_action_to_server(&session_metadata, CliAction::ClipboardInfo, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let logged_clipboard_info = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|instruction| match instruction {
+            ServerInstruction::Log(lines, _) => Some(lines.clone()),
+            _ => None,
+        });
+    assert_eq!(
+        logged_clipboard_info,
+        Some(vec![
+            r#"{"destination":"System","bytes":0,"lines":0}"#.to_owned()
+        ]),
+        "nothing was copied yet, so only the destination is known"
+    );
+}
+
 #[test]
 pub fn send_cli_launch_or_focus_plugin_action() {
     let size = Size {
@@ -2550,6 +5315,7 @@ pub fn send_cli_launch_or_focus_plugin_action() {
     let cli_action = CliAction::LaunchOrFocusPlugin {
         floating: true,
         url: url::Url::parse("file:/path/to/fake/plugin").unwrap(),
+        config: vec![],
     };
     send_cli_action_to_server(&session_metadata, cli_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
@@ -2568,6 +5334,60 @@ pub fn send_cli_launch_or_focus_plugin_action() {
     assert_snapshot!(format!("{:#?}", plugin_load_instruction));
 }
 
+#[test]
+pub fn send_cli_launch_or_focus_plugin_action_with_different_configuration() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let plugin_receiver = mock_screen.plugin_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let mut initial_layout = TiledPaneLayout::default();
+    let existing_plugin_pane = TiledPaneLayout {
+        run: Some(Run::Plugin(RunPlugin {
+            _allow_exec_host_cmd: false,
+            location: RunPluginLocation::File(PathBuf::from("/path/to/fake/plugin")),
+            configuration: Default::default(),
+        })),
+        ..Default::default()
+    };
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), existing_plugin_pane];
+    let screen_thread = mock_screen.run(Some(initial_layout));
+    let received_plugin_instructions = Arc::new(Mutex::new(vec![]));
+    let plugin_thread = log_actions_in_thread!(
+        received_plugin_instructions,
+        PluginInstruction::Exit,
+        plugin_receiver
+    );
+    let cli_action = CliAction::LaunchOrFocusPlugin {
+        floating: true,
+        url: url::Url::parse("file:/path/to/fake/plugin").unwrap(),
+        config: vec!["key=value".to_owned()],
+    };
+    send_cli_action_to_server(&session_metadata, cli_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![plugin_thread, screen_thread]);
+
+    let loaded_run_plugin = received_plugin_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|instruction| match instruction {
+            PluginInstruction::Load(_, _, run_plugin, ..) => Some(run_plugin.clone()),
+            _ => None,
+        });
+    let mut expected_configuration = std::collections::BTreeMap::new();
+    expected_configuration.insert("key".to_owned(), "value".to_owned());
+    assert_eq!(
+        loaded_run_plugin.map(|run_plugin| run_plugin.configuration),
+        Some(expected_configuration),
+        "Plugin with the same location but a different configuration is loaded anew"
+    );
+}
+
 #[test]
 pub fn send_cli_launch_or_focus_plugin_action_when_plugin_is_already_loaded() {
     let size = Size {
@@ -2583,6 +5403,7 @@ pub fn send_cli_launch_or_focus_plugin_action_when_plugin_is_already_loaded() {
         run: Some(Run::Plugin(RunPlugin {
             _allow_exec_host_cmd: false,
             location: RunPluginLocation::File(PathBuf::from("/path/to/fake/plugin")),
+            configuration: Default::default(),
         })),
         ..Default::default()
     };
@@ -2605,6 +5426,7 @@ pub fn send_cli_launch_or_focus_plugin_action_when_plugin_is_already_loaded() {
     let cli_action = CliAction::LaunchOrFocusPlugin {
         floating: true,
         url: url::Url::parse("file:/path/to/fake/plugin").unwrap(),
+        config: vec![],
     };
     send_cli_action_to_server(&session_metadata, cli_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
@@ -2671,3 +5493,82 @@ pub fn screen_can_suppress_pane() {
     }
     assert_snapshot!(format!("{}", snapshot_count));
 }
+
+fn run_background_jobs() -> (
+    SenderWithContext<BackgroundJob>,
+    Receiver<(ScreenInstruction, ErrorContext)>,
+    std::thread::JoinHandle<()>,
+) {
+    let (to_screen, screen_receiver): ChannelWithContext<ScreenInstruction> = channels::unbounded();
+    let to_screen = SenderWithContext::new(to_screen);
+    let (to_background_jobs, background_jobs_receiver): ChannelWithContext<BackgroundJob> =
+        channels::unbounded();
+    let to_background_jobs = SenderWithContext::new(to_background_jobs);
+    let background_jobs_bus = Bus::new(
+        vec![background_jobs_receiver],
+        Some(&to_screen),
+        None,
+        None,
+        None,
+        None,
+        Some(&to_background_jobs),
+        None,
+    )
+    .should_silently_fail();
+    let background_jobs_thread = std::thread::Builder::new()
+        .name("background_jobs_thread".to_string())
+        .spawn(move || background_jobs_main(background_jobs_bus).expect("TEST"))
+        .unwrap();
+    (to_background_jobs, screen_receiver, background_jobs_thread)
+}
+
+fn next_tour_tab(screen_receiver: &Receiver<(ScreenInstruction, ErrorContext)>) -> Option<u32> {
+    loop {
+        match screen_receiver.recv_timeout(std::time::Duration::from_millis(500)) {
+            Ok((ScreenInstruction::GoToTab(tab_index, Some(1)), _)) => return Some(tab_index),
+            Ok(_) => continue,
+            Err(_) => return None,
+        }
+    }
+}
+
+#[test]
+pub fn tab_tour_cycles_through_tabs_and_stops() {
+    let (to_background_jobs, screen_receiver, background_jobs_thread) = run_background_jobs();
+    let client_id = 1;
+    to_background_jobs
+        .send(BackgroundJob::RunTabTour(client_id, vec![2, 1, 3], 10))
+        .unwrap();
+    let visited_tabs: Vec<u32> = std::iter::from_fn(|| next_tour_tab(&screen_receiver)).collect();
+    assert_eq!(
+        visited_tabs,
+        vec![2, 1, 3],
+        "tour visited the tabs in order and then stopped"
+    );
+    let _ = to_background_jobs.send(BackgroundJob::Exit);
+    background_jobs_thread.join().unwrap();
+}
+
+#[test]
+pub fn tab_tour_stops_when_asked_to() {
+    let (to_background_jobs, screen_receiver, background_jobs_thread) = run_background_jobs();
+    let client_id = 1;
+    to_background_jobs
+        .send(BackgroundJob::RunTabTour(client_id, vec![2, 1, 3], 200))
+        .unwrap();
+    assert_eq!(
+        next_tour_tab(&screen_receiver),
+        Some(2),
+        "tour started at the first tab"
+    );
+    to_background_jobs
+        .send(BackgroundJob::StopTabTour(client_id))
+        .unwrap();
+    assert_eq!(
+        next_tour_tab(&screen_receiver),
+        None,
+        "no tab visited after stopping the tour"
+    );
+    let _ = to_background_jobs.send(BackgroundJob::Exit);
+    background_jobs_thread.join().unwrap();
+}
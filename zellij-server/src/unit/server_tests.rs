@@ -0,0 +1,35 @@
+use super::*;
+
+#[test]
+fn rename_session_socket_moves_the_socket() {
+    let socket_dir = tempfile::tempdir().expect("TEST");
+    let socket_path = socket_dir.path().join("old-name");
+    std::fs::write(&socket_path, "").expect("TEST");
+    let new_socket_path = rename_session_socket(&socket_path, "new-name").expect("TEST");
+    assert_eq!(new_socket_path, socket_dir.path().join("new-name"));
+    assert!(
+        new_socket_path.exists(),
+        "session listed under its new name"
+    );
+    assert!(
+        !socket_path.exists(),
+        "session no longer listed under its old name"
+    );
+}
+
+#[test]
+fn rename_session_socket_rejects_existing_sessions() {
+    let socket_dir = tempfile::tempdir().expect("TEST");
+    let socket_path = socket_dir.path().join("old-name");
+    let other_socket_path = socket_dir.path().join("other-session");
+    std::fs::write(&socket_path, "").expect("TEST");
+    std::fs::write(&other_socket_path, "").expect("TEST");
+    assert_eq!(
+        rename_session_socket(&socket_path, "other-session"),
+        Err("A session named 'other-session' already exists".to_owned())
+    );
+    assert!(
+        socket_path.exists(),
+        "session still listed under its old name"
+    );
+}
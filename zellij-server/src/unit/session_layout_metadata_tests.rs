@@ -0,0 +1,133 @@
+use super::*;
+use std::path::PathBuf;
+
+fn pane(id: u32, x: usize, y: usize, cols: Dimension, rows: Dimension) -> PaneLayoutMetadata {
+    PaneLayoutMetadata {
+        id: PaneId::Terminal(id),
+        geom: PaneGeom {
+            x,
+            y,
+            cols,
+            rows,
+            is_stacked: false,
+        },
+        run: None,
+        is_borderless: false,
+        is_focused: false,
+        is_pinned: false,
+    }
+}
+
+fn flexible(size: usize) -> Dimension {
+    let mut dimension = Dimension::percent(50.0);
+    dimension.set_inner(size);
+    dimension
+}
+
+#[test]
+pub fn tiled_panes_are_split_into_columns_and_rows() {
+    // +-----+-----+
+    // |     |  2  |
+    // |  1  +-----+
+    // |     |  3  |
+    // +-----+-----+
+    // |     4     | <== fixed
+    // +-----------+
+    let session_layout_metadata = SessionLayoutMetadata {
+        tabs: vec![TabLayoutMetadata {
+            name: Some("tab".to_owned()),
+            is_pinned: false,
+            tiled_panes: vec![
+                pane(1, 0, 0, flexible(50), flexible(20)),
+                pane(2, 50, 0, flexible(50), flexible(10)),
+                pane(3, 50, 10, flexible(50), flexible(10)),
+                pane(4, 0, 20, flexible(100), Dimension::fixed(2)),
+            ],
+            floating_panes: vec![],
+        }],
+        focused_tab_index: Some(0),
+    };
+    let layout = Layout::from(session_layout_metadata);
+    let (tab_name, tiled_panes, _floating_panes) = &layout.tabs[0];
+    assert_eq!(tab_name.as_deref(), Some("tab"));
+    assert_eq!(
+        tiled_panes.children_split_direction,
+        SplitDirection::Horizontal
+    );
+    assert_eq!(tiled_panes.children.len(), 2);
+    let top = &tiled_panes.children[0];
+    assert_eq!(top.split_size, None, "last flexible pane has no size");
+    assert_eq!(top.children_split_direction, SplitDirection::Vertical);
+    assert_eq!(top.children[0].split_size, Some(SplitSize::Percent(50)));
+    assert_eq!(top.children[1].children.len(), 2);
+    assert_eq!(
+        tiled_panes.children[1].split_size,
+        Some(SplitSize::Fixed(2))
+    );
+}
+
+#[test]
+pub fn terminal_cwds_are_filled_in() {
+    let mut session_layout_metadata = SessionLayoutMetadata {
+        tabs: vec![TabLayoutMetadata {
+            name: None,
+            is_pinned: false,
+            tiled_panes: vec![pane(1, 0, 0, flexible(100), flexible(20))],
+            floating_panes: vec![pane(2, 10, 10, flexible(20), flexible(5))],
+        }],
+        focused_tab_index: None,
+    };
+    session_layout_metadata.update_terminal_cwds(|id| Some(PathBuf::from(format!("/tmp/{}", id))));
+    let tab = &session_layout_metadata.tabs[0];
+    assert_eq!(
+        tab.tiled_panes[0].run,
+        Some(Run::Cwd(PathBuf::from("/tmp/1")))
+    );
+    assert_eq!(
+        tab.floating_panes[0].run,
+        Some(Run::Cwd(PathBuf::from("/tmp/2")))
+    );
+}
+
+#[test]
+pub fn pinned_floating_panes_are_pinned_in_the_layout() {
+    let mut pinned_pane = pane(3, 20, 5, flexible(20), flexible(5));
+    pinned_pane.is_pinned = true;
+    let session_layout_metadata = SessionLayoutMetadata {
+        tabs: vec![TabLayoutMetadata {
+            name: None,
+            is_pinned: false,
+            tiled_panes: vec![pane(1, 0, 0, flexible(100), flexible(20))],
+            floating_panes: vec![pane(2, 10, 10, flexible(20), flexible(5)), pinned_pane],
+        }],
+        focused_tab_index: None,
+    };
+    let layout = Layout::from(session_layout_metadata);
+    let (_tab_name, _tiled_panes, floating_panes) = &layout.tabs[0];
+    assert_eq!(floating_panes[0].pinned, None);
+    assert_eq!(floating_panes[1].pinned, Some(true));
+    assert!(
+        layout.to_kdl().contains("pane pinned=true"),
+        "pinned state is part of the dumped layout"
+    );
+}
+
+#[test]
+pub fn pinned_tabs_are_pinned_in_the_layout() {
+    let tab = |name: &str, is_pinned: bool| TabLayoutMetadata {
+        name: Some(name.to_owned()),
+        is_pinned,
+        tiled_panes: vec![pane(1, 0, 0, flexible(100), flexible(20))],
+        floating_panes: vec![],
+    };
+    let session_layout_metadata = SessionLayoutMetadata {
+        tabs: vec![tab("first", false), tab("second", true)],
+        focused_tab_index: None,
+    };
+    let layout = Layout::from(session_layout_metadata);
+    assert_eq!(layout.pinned_tab_indices, vec![1]);
+    assert!(
+        layout.to_kdl().contains("tab name=\"second\" pinned=true"),
+        "pinned state is part of the dumped layout"
+    );
+}
@@ -1,4 +1,4 @@
-use crate::data::{Direction, InputMode, Resize};
+use crate::data::{AspectRatio, Direction, FloatingSnap, InputMode, PaneListFormat, Resize};
 use crate::setup::Setup;
 use crate::{
     consts::{ZELLIJ_CONFIG_DIR_ENV, ZELLIJ_CONFIG_FILE_ENV},
@@ -145,6 +145,20 @@ pub enum Sessions {
         /// Start the command suspended, only running after you first presses ENTER
         #[clap(short, long, value_parser, default_value("false"), takes_value(false))]
         start_suspended: bool,
+
+        /// Run the command through a login shell ($SHELL -lc), sourcing the shell profile first
+        /// (eg. for PATH entries set up there)
+        #[clap(long, value_parser, default_value("false"), takes_value(false))]
+        login_shell: bool,
+
+        /// Run the command through this shell (`<shell> -c "<command> <args>"`), so that it can
+        /// use shell features such as pipes and globs
+        #[clap(long, value_parser, conflicts_with("login_shell"))]
+        shell: Option<String>,
+
+        /// Set an environment variable for the command as KEY=VALUE (can be given more than once)
+        #[clap(long, value_parser)]
+        env: Vec<String>,
     },
     /// Edit file with default $EDITOR / $VISUAL
     #[clap(visible_alias = "e")]
@@ -155,6 +169,10 @@ pub enum Sessions {
         #[clap(short, long, value_parser)]
         line_number: Option<usize>,
 
+        /// Open the file in the specified column of the line
+        #[clap(long, value_parser, requires("line_number"))]
+        column: Option<usize>,
+
         /// Direction to open the new pane in
         #[clap(short, long, value_parser, conflicts_with("floating"))]
         direction: Option<Direction>,
@@ -184,27 +202,136 @@ pub enum CliAction {
     Write {
         bytes: Vec<u8>,
     },
-    /// Write characters to the terminal.
+    /// Write characters to the terminal. Placeholders of the form {{NAME}} are replaced with the
+    /// value of the session variable NAME, if it is set.
     WriteChars {
         chars: String,
     },
+    /// Paste text to the terminal as a single bracketed paste, so that a shell does not run each
+    /// of its lines as soon as it is written
+    PasteText {
+        #[clap(required_unless_present("file"))]
+        text: Option<String>,
+
+        /// Paste the contents of this file instead
+        #[clap(short, long, value_parser, conflicts_with("text"))]
+        file: Option<PathBuf>,
+    },
+    /// Write characters to all terminals in the current tab whose running command matches
+    /// [command] (a substring, or a glob if it includes * or ?)
+    WriteMatching {
+        command: String,
+        chars: String,
+    },
+    /// Write characters to all terminals in the current tab matching every given filter, at least
+    /// one filter is required
+    WriteFiltered {
+        /// Only write to terminals whose running command matches this filter (a substring, or a
+        /// glob if it includes * or ?)
+        #[clap(short, long, value_parser)]
+        command: Option<String>,
+        chars: String,
+    },
     /// [increase|decrease] the focused panes area at the [left|down|up|right] border.
     Resize {
-        resize: Resize,
+        #[clap(required_unless_present_any(&["width_percent", "height_percent"]))]
+        resize: Option<Resize>,
         direction: Option<Direction>,
+        /// Set the width of the focused pane to this percentage of the tab instead
+        #[clap(long, value_parser, conflicts_with("resize"))]
+        width_percent: Option<u8>,
+        /// Set the height of the focused pane to this percentage of the tab instead
+        #[clap(long, value_parser, conflicts_with("resize"))]
+        height_percent: Option<u8>,
+    },
+    /// Set the size of the focused pane in cells, as far as the space around it allows
+    SetPaneSize {
+        /// The width of the pane in columns
+        #[clap(short, long, value_parser)]
+        cols: Option<usize>,
+        /// The height of the pane in rows
+        #[clap(short, long, value_parser)]
+        rows: Option<usize>,
+    },
+    /// Keep the size of the focused pane within bounds when tiled panes are resized or laid out,
+    /// bounds that are not specified are cleared
+    SetPaneConstraints {
+        /// The minimum width of the pane in columns
+        #[clap(long, value_parser)]
+        min_cols: Option<usize>,
+        /// The maximum width of the pane in columns
+        #[clap(long, value_parser)]
+        max_cols: Option<usize>,
+        /// The minimum height of the pane in rows
+        #[clap(long, value_parser)]
+        min_rows: Option<usize>,
+        /// The maximum height of the pane in rows
+        #[clap(long, value_parser)]
+        max_rows: Option<usize>,
     },
     /// Change focus to the next pane
-    FocusNextPane,
+    FocusNextPane {
+        /// Number of times to perform this action
+        #[clap(long, value_parser, default_value = "1")]
+        count: usize,
+    },
     /// Change focus to the previous pane
-    FocusPreviousPane,
+    FocusPreviousPane {
+        /// Number of times to perform this action
+        #[clap(long, value_parser, default_value = "1")]
+        count: usize,
+    },
+    /// Change focus to the next floating pane, cycling through the floating panes only
+    FocusNextFloatingPane {
+        /// Number of times to perform this action
+        #[clap(long, value_parser, default_value = "1")]
+        count: usize,
+    },
+    /// Change focus to the previous floating pane, cycling through the floating panes only
+    FocusPreviousFloatingPane {
+        /// Number of times to perform this action
+        #[clap(long, value_parser, default_value = "1")]
+        count: usize,
+    },
     /// Move the focused pane in the specified direction. [right|left|up|down]
     MoveFocus {
         direction: Direction,
+        /// Number of times to perform this action
+        #[clap(long, value_parser, default_value = "1")]
+        count: usize,
     },
     /// Move focus to the pane or tab (if on screen edge) in the specified direction
     /// [right|left|up|down]
     MoveFocusOrTab {
         direction: Direction,
+
+        /// Stay on the focused pane at the edge of the screen rather than switching tabs, like
+        /// move-focus
+        #[clap(short, long, value_parser)]
+        stop_at_edge: bool,
+    },
+    /// Focus the terminal pane with id [id]
+    FocusPane {
+        id: u32,
+
+        /// Also switch to the tab containing the pane, rather than only focusing it if it is in
+        /// the current tab
+        #[clap(short, long, value_parser)]
+        switch_tab: bool,
+    },
+    /// Focus the next pane whose command contains [command], switching tabs if needed (panes
+    /// are cycled through by tab and then by pane id)
+    CycleByCommand {
+        command: String,
+    },
+    /// Focus the first pane named [name]
+    FocusPaneByName {
+        name: String,
+
+        /// Look for the pane in all tabs (in order) and switch to its tab, rather than only in
+        /// the current tab
+        #[clap(short, long, value_parser)]
+        all_tabs: bool,
     },
     /// Change the location of the focused pane in the specified direction or rotate forwrads
     /// [right|left|up|down]
@@ -213,40 +340,174 @@ pub enum CliAction {
     },
     /// Rotate the location of the previous pane backwards
     MovePaneBackwards,
+    /// Move every tiled pane in the current tab into the slot of the next one, keeping the way
+    /// the tab is split
+    RotatePanes {
+        /// Move every pane into the slot of the previous one instead
+        #[clap(short, long, value_parser)]
+        backward: bool,
+    },
+    /// Move the top left corner of the focused floating pane to these coordinates, keeping the
+    /// pane inside the screen
+    MoveFloatingPane {
+        /// The column to move the pane to
+        #[clap(value_parser)]
+        x: usize,
+        /// The line to move the pane to
+        #[clap(value_parser)]
+        y: usize,
+    },
+    /// Resize the focused floating pane to this many columns and rows, keeping it inside the
+    /// screen
+    ResizeFloatingPane {
+        /// The number of columns of the pane
+        #[clap(value_parser)]
+        cols: usize,
+        /// The number of rows of the pane
+        #[clap(value_parser)]
+        rows: usize,
+    },
     /// Clear all buffers for a focused pane
     Clear,
-    /// Dump the focused pane to a file
+    /// Discard the scrollback of the focused pane, keeping what is visible on screen
+    ClearScrollback,
+    /// Change the most lines the focused pane keeps in its scrollback (0 for none), dropping its
+    /// oldest lines right away if it has more
+    SetScrollback {
+        #[clap(value_parser)]
+        lines: usize,
+    },
+    /// Dump the focused pane to a file, or to stdout if the path is -
     DumpScreen {
         path: PathBuf,
 
         /// Dump the pane with full scrollback
         #[clap(short, long, value_parser, default_value("false"), takes_value(false))]
         full: bool,
+
+        /// The format of the dump: plain text, or an svg image with colors and text attributes
+        #[clap(long, value_parser = ["text", "svg"], default_value("text"))]
+        format: String,
+
+        /// Keep the colors and text attributes of a text dump as ANSI escape sequences
+        #[clap(long, value_parser, default_value("false"), takes_value(false))]
+        ansi: bool,
+
+        /// Dump the text as JSON lines, one object per line with its line_number, text and
+        /// (if it is styled) style_runs
+        #[clap(
+            long,
+            value_parser,
+            default_value("false"),
+            takes_value(false),
+            conflicts_with("ansi")
+        )]
+        jsonl: bool,
+
+        /// Start the dump with a line holding the time it was taken at and the session, tab and
+        /// pane it was taken of
+        #[clap(long, value_parser, default_value("false"), takes_value(false))]
+        header: bool,
+    },
+    /// Dump the focused pane to a self-contained HTML file, keeping its colors and text
+    /// attributes
+    DumpScreenHtml {
+        path: PathBuf,
+
+        /// Dump the pane with full scrollback
+        #[clap(short, long, value_parser, default_value("false"), takes_value(false))]
+        full: bool,
+    },
+    /// Dump the layout of the current session as KDL, to the given file or to stdout if no
+    /// path is given
+    DumpLayout {
+        path: Option<PathBuf>,
     },
     /// Open the pane scrollback in your default editor
     EditScrollback,
     /// Scroll up in the focused pane
-    ScrollUp,
+    ScrollUp {
+        /// Number of lines to scroll (one if not given)
+        #[clap(long, value_parser)]
+        count: Option<usize>,
+    },
     /// Scroll down in focus pane.
-    ScrollDown,
+    ScrollDown {
+        /// Number of lines to scroll (one if not given)
+        #[clap(long, value_parser)]
+        count: Option<usize>,
+    },
     /// Scroll down to bottom in focus pane.
     ScrollToBottom,
     /// Scroll up to top in focus pane.
     ScrollToTop,
+    /// Scroll the focused pane so that the given line of its scrollback is at the top
+    ScrollToLine {
+        /// The line to scroll to, 0 being the oldest line of the scrollback
+        #[clap(value_parser)]
+        line: usize,
+    },
     /// Scroll up one page in focus pane.
-    PageScrollUp,
+    PageScrollUp {
+        /// Number of times to perform this action
+        #[clap(long, value_parser, default_value = "1")]
+        count: usize,
+    },
     /// Scroll down one page in focus pane.
-    PageScrollDown,
+    PageScrollDown {
+        /// Number of times to perform this action
+        #[clap(long, value_parser, default_value = "1")]
+        count: usize,
+    },
+    /// Scroll the focused pane to the previous shell prompt (needs shell integration emitting
+    /// OSC 133 marks), or up one page if there are none.
+    MoveToPreviousPrompt {
+        /// Number of times to perform this action
+        #[clap(long, value_parser, default_value = "1")]
+        count: usize,
+    },
+    /// Scroll the focused pane to the next shell prompt (needs shell integration emitting OSC
+    /// 133 marks), or down one page if there are none.
+    MoveToNextPrompt {
+        /// Number of times to perform this action
+        #[clap(long, value_parser, default_value = "1")]
+        count: usize,
+    },
+    /// Toggle between wrapping and truncating lines that are wider than the focused pane
+    TogglePaneLineWrap,
     /// Scroll up half page in focus pane.
-    HalfPageScrollUp,
+    HalfPageScrollUp {
+        /// Number of times to perform this action
+        #[clap(long, value_parser, default_value = "1")]
+        count: usize,
+    },
     /// Scroll down half page in focus pane.
-    HalfPageScrollDown,
+    HalfPageScrollDown {
+        /// Number of times to perform this action
+        #[clap(long, value_parser, default_value = "1")]
+        count: usize,
+    },
     /// Toggle between fullscreen focus pane and normal layout.
-    ToggleFullscreen,
+    ToggleFullscreen {
+        /// Focus the terminal pane with this id (switching to its tab) and toggle it instead
+        #[clap(short, long, value_parser)]
+        pane_id: Option<u32>,
+    },
     /// Toggle frames around panes in the UI
     TogglePaneFrames,
+    /// Toggle the frame of the focused pane only, independently of the frames of other panes
+    ToggleFocusedPaneFrame,
+    /// Toggle row and column guides drawn over the UI, to help with aligning panes
+    ToggleRuler,
+    /// Hide the tab bar, status bar and pane frames together, or bring them back as they were
+    ToggleZen,
     /// Toggle between sending text commands to all panes on the current tab and normal mode.
     ToggleActiveSyncTab,
+    /// Toggle between sending text commands to all panes in all tabs and normal mode,
+    /// independently of the sync state of the current tab.
+    ToggleActiveSyncSession,
+    /// Rerun the command of the focused pane if it exited and is held open
+    RestartPane,
     /// Open a new pane in the specified direction [right|down]
     /// If no direction is specified, will try to use the biggest available space.
     NewPane {
@@ -264,10 +525,55 @@ pub enum CliAction {
         #[clap(long, value_parser)]
         cwd: Option<PathBuf>,
 
+        /// Start the new pane in the working directory of the focused pane, taking precedence
+        /// over --cwd (falls back to the current directory if the focused pane has none)
+        #[clap(
+            long,
+            value_parser,
+            default_value("false"),
+            takes_value(false),
+            conflicts_with("plugin")
+        )]
+        cwd_from_focus: bool,
+
+        /// Draw the new pane without a frame or title, even if pane frames are on (eg. for panes
+        /// that draw their own borders)
+        #[clap(
+            long,
+            value_parser,
+            default_value("false"),
+            takes_value(false),
+            conflicts_with("plugin")
+        )]
+        no_frame: bool,
+
         /// Open the new pane in floating mode
         #[clap(short, long, value_parser, default_value("false"), takes_value(false))]
         floating: bool,
 
+        /// Dock the new floating pane against a screen edge or corner (top, bottom, left, right,
+        /// top-left, top-right, bottom-left, bottom-right or center)
+        #[clap(long, value_parser, requires("floating"))]
+        snap: Option<FloatingSnap>,
+
+        /// The width (in columns) of the new floating pane
+        #[clap(long, value_parser, requires("floating"))]
+        width: Option<usize>,
+
+        /// The height (in rows) of the new floating pane
+        #[clap(long, value_parser, requires("floating"))]
+        height: Option<usize>,
+
+        /// Size the new floating pane to approximate this ratio of columns to rows (eg. 2:1)
+        /// within the available space, ignored for tiled panes
+        #[clap(long, value_parser)]
+        aspect: Option<AspectRatio>,
+
+        /// Split the whole tab (rather than the focused pane) at this fraction of its width or
+        /// height, eg. 60% or 0.6, placing the new pane on the side given by --direction
+        #[clap(long, value_parser = parse_percent, requires("direction"))]
+        place_at: Option<u8>,
+
         /// Name of the new pane
         #[clap(short, long, value_parser)]
         name: Option<String>,
@@ -292,6 +598,76 @@ pub enum CliAction {
             requires("command")
         )]
         start_suspended: bool,
+
+        /// Run the command through a login shell ($SHELL -lc), sourcing the shell profile first
+        /// (eg. for PATH entries set up there)
+        #[clap(
+            long,
+            value_parser,
+            default_value("false"),
+            takes_value(false),
+            requires("command")
+        )]
+        login_shell: bool,
+
+        /// Run the command through this shell (`<shell> -c "<command> <args>"`), so that it can
+        /// use shell features such as pipes and globs
+        #[clap(long, value_parser, requires("command"), conflicts_with("login_shell"))]
+        shell: Option<String>,
+
+        /// Rerun the command whenever this path changes (can be given more than once)
+        #[clap(
+            long,
+            value_parser,
+            requires("command"),
+            conflicts_with("close_on_exit")
+        )]
+        watch: Vec<PathBuf>,
+
+        /// Also stream the output of the command to the unix socket listening at this path
+        #[clap(long, value_parser, requires("command"))]
+        output_socket: Option<PathBuf>,
+
+        /// Keep the lines of output matching this regex pinned at the top of the pane while the
+        /// rest of the output scrolls below them (only the most recent matches are kept)
+        #[clap(long, value_parser, requires("command"))]
+        pin_matching: Option<String>,
+
+        /// Store the exit code of the command in this session variable whenever it exits (read it
+        /// with `zellij action get-var`)
+        #[clap(long, value_parser, requires("command"))]
+        capture_exit: Option<String>,
+
+        /// Set an environment variable for the command as KEY=VALUE (can be given more than once)
+        #[clap(long, value_parser, requires("command"))]
+        env: Vec<String>,
+
+        /// Present a fixed size to the command of the new pane as COLSxROWS (eg. 80x24), whatever
+        /// the size of the pane and however it is resized. The output is cropped where the pane is
+        /// smaller and letterboxed (surrounded by blank space) where it is larger
+        #[clap(long, value_parser = parse_pty_size, conflicts_with("plugin"))]
+        fixed_size: Option<(u16, u16)>,
+
+        /// Do not place the new floating pane where the last one with the same name was closed
+        #[clap(
+            long,
+            value_parser,
+            default_value("false"),
+            takes_value(false),
+            requires_all(&["floating", "name"])
+        )]
+        reset_geometry: bool,
+    },
+    /// Open a new pane running the same command as the focused pane, or a shell in the same
+    /// working directory if it is not a command pane
+    DuplicatePane {
+        /// Direction to open the new pane in
+        #[clap(short, long, value_parser, conflicts_with("floating"))]
+        direction: Option<Direction>,
+
+        /// Open the new pane in floating mode
+        #[clap(short, long, value_parser, default_value("false"), takes_value(false))]
+        floating: bool,
     },
     /// Open the specified file in a new zellij pane with your default EDITOR
     Edit {
@@ -305,6 +681,10 @@ pub enum CliAction {
         #[clap(short, long, value_parser)]
         line_number: Option<usize>,
 
+        /// Open the file in the specified column of the line
+        #[clap(long, value_parser, requires("line_number"))]
+        column: Option<usize>,
+
         /// Open the new pane in floating mode
         #[clap(short, long, value_parser, default_value("false"), takes_value(false))]
         floating: bool,
@@ -317,12 +697,54 @@ pub enum CliAction {
     SwitchMode {
         input_mode: InputMode,
     },
+    /// Switch input mode of all connected clients, reverting to the previous mode after the given
+    /// amount of milliseconds [locked|pane|tab|resize|move|search|session]
+    SwitchModeTimed {
+        input_mode: InputMode,
+        /// How long to stay in the mode before reverting, in milliseconds
+        #[clap(value_parser)]
+        ms: u64,
+    },
+    /// Switch all connected clients to resize mode until they leave it, making each resize there
+    /// `resize_loop_step` increments big
+    ResizeLoop,
+    /// Write the name of every input mode switched to from now on as a line to a unix socket,
+    /// reports are dropped while nothing listens on it
+    SetModeReportSocket {
+        /// Path of the unix socket to write mode names to
+        path: PathBuf,
+    },
+    /// Show the keybindings of an input mode without switching to it, <ESC> dismisses them
+    /// [locked|pane|tab|resize|move|search|session]
+    ModeHelp {
+        mode: InputMode,
+    },
+    /// Perform the next scroll-up, scroll-down, focus-next-pane, focus-previous-pane, move-focus,
+    /// go-to-next-tab or go-to-previous-tab action this many times
+    Repeat {
+        count: u32,
+    },
     /// Embed focused pane if floating or float focused pane if embedded
     TogglePaneEmbedOrFloating,
+    /// Embed all floating panes of the current tab, keeping the focused pane focused
+    EmbedAllFloatingPanes,
+    /// Float all tiled panes of the current tab but the top left one, keeping the focused pane
+    /// focused
+    FloatAllTiledPanes,
+    /// Temporarily float the focused pane enlarged, run again (or focus another pane) to place
+    /// it back in its original position
+    PeekPane,
+    /// Pin the focused floating pane so that it stays on top of the other floating panes, or unpin
+    /// it
+    TogglePanePinned,
     /// Toggle the visibility of all fdirectionloating panes in the current Tab, open one if none exist
     ToggleFloatingPanes,
     /// Close the focused pane.
-    ClosePane,
+    ClosePane {
+        /// Ask for confirmation first if a process is still running in the pane
+        #[clap(long, value_parser)]
+        smart: bool,
+    },
     /// Renames the focused pane
     RenamePane {
         name: String,
@@ -333,8 +755,37 @@ pub enum CliAction {
     GoToNextTab,
     /// Go to the previous tab.
     GoToPreviousTab,
+    /// Focus the pane that was focused when the current tab was last switched away from
+    RestoreTabFocus,
+    /// Move the current tab one position to the left, wrapping around to the end.
+    MoveTabLeft,
+    /// Move the current tab one position to the right, wrapping around to the start.
+    MoveTabRight,
+    /// Move the current tab to index [index], indices past the first or last tab move it to
+    /// the start or the end
+    MoveTabToIndex {
+        index: u32,
+    },
+    /// Move the focused pane out of its tab and into a new tab of its own.
+    BreakPane,
+    /// Move the focused pane into the tab to the left of the current one.
+    BreakPaneLeft,
+    /// Move the focused pane into the tab to the right of the current one.
+    BreakPaneRight,
+    /// Move the focused pane into the tab with index [index], keeping it floating if it was
+    /// floating
+    MovePaneToTab {
+        index: u32,
+    },
+    /// Show the first pane of the tab with index [index] floating on top of the current tab, or
+    /// hide it if it is already shown
+    OverlayTab {
+        index: u32,
+    },
     /// Close the current tab.
     CloseTab,
+    /// Pin or unpin the current tab, pinned tabs are not closed by close-tab.
+    TogglePinTab,
     /// Go to tab with index [index]
     GoToTab {
         index: u32,
@@ -346,6 +797,10 @@ pub enum CliAction {
         #[clap(short, long, value_parser)]
         create: bool,
     },
+    /// Go to the tab containing the terminal pane with id [id] and focus that pane
+    GoToPaneTab {
+        id: u32,
+    },
     /// Renames the focused pane
     RenameTab {
         name: String,
@@ -369,17 +824,206 @@ pub enum CliAction {
         /// Change the working directory of the new tab
         #[clap(short, long, value_parser, requires("layout"))]
         cwd: Option<PathBuf>,
+
+        /// Create the tab without any panes, to be populated later (eg. with `new-pane`)
+        #[clap(short, long, value_parser, conflicts_with("layout"))]
+        empty: bool,
+
+        /// Focus the [FOCUS_PANE]th pane of the new tab in tiling order (left to right, top to
+        /// bottom, starting at 1) rather than the one focused by the layout, or the last pane if
+        /// there are fewer
+        #[clap(long, value_parser, conflicts_with("empty"))]
+        focus_pane: Option<usize>,
+
+        /// Start the panes of the new tab in the working directory of the focused pane of the
+        /// current tab, as a new tab opened with a keybinding does. This is the only state that is
+        /// copied: session variables are shared by all tabs, and tabs have no env of their own.
+        #[clap(long, value_parser, default_value("false"), takes_value(false))]
+        inherit_vars: bool,
+    },
+    /// Rearrange the panes of the current tab according to a layout, as long as no other client
+    /// is viewing the tab (pane positions are shared by all clients of a tab)
+    SetClientLayout {
+        /// The layout to apply, either a path or the name of a layout in the layout folder
+        layout: PathBuf,
     },
     PreviousSwapLayout,
     NextSwapLayout,
+    /// Apply the swap layout at this index of the current tab, 0 being the layout the tab was
+    /// created with
+    GoToSwapLayout {
+        #[clap(value_parser)]
+        index: usize,
+    },
+    /// Apply the swap layout of the current tab with this name, as given to it in the layout file
+    ApplySwapLayout {
+        #[clap(value_parser)]
+        name: String,
+    },
     /// Query all tab names
     QueryTabNames,
+    /// List the panes of all tabs with their id, title, tab index, whether they are floating or
+    /// focused, their cwd and the command they run
+    QueryPaneList {
+        /// The output format, plain (one line per pane) or json
+        #[clap(long, value_parser, default_value("plain"))]
+        format: PaneListFormat,
+    },
+    /// Set a session variable, readable with `get-var` and usable as a {{NAME}} placeholder in
+    /// `write-chars`. Session variables are kept in memory only and do not survive the session
+    /// being restarted (names are up to 64 characters of letters, digits, '_' or '-', values up
+    /// to 4096 bytes, and a session holds at most 256 variables)
+    SetVar {
+        name: String,
+        value: String,
+    },
+    /// Print the value of a session variable (eg. one set by `new-pane --capture-exit`)
+    GetVar {
+        name: String,
+    },
+    /// Rename the current session
+    RenameSession {
+        name: String,
+    },
+    /// Print the destination and size of the last copy to the clipboard as JSON
+    ClipboardInfo,
     StartOrReloadPlugin {
         url: String,
+        /// Configuration to pass to the plugin, can be specified multiple times (eg. --config
+        /// key=value --config other_key=other_value)
+        #[clap(short, long, value_parser)]
+        config: Vec<String>,
     },
     LaunchOrFocusPlugin {
         #[clap(short, long, value_parser)]
         floating: bool,
         url: Url,
+        /// Configuration to pass to the plugin, can be specified multiple times (eg. --config
+        /// key=value --config other_key=other_value)
+        #[clap(short, long, value_parser)]
+        config: Vec<String>,
+    },
+    /// Step through the given tab indices (eg. for a presentation), waiting [delay-ms] between
+    /// each
+    TabTour {
+        #[clap(required(true))]
+        indices: Vec<u32>,
+
+        /// The delay in milliseconds before moving to the next tab
+        #[clap(short, long, value_parser, default_value("3000"))]
+        delay_ms: u64,
     },
+    /// Stop a tab tour that is in progress
+    StopTabTour,
+    /// Even out the widths of the panes in the current tab, leaving their heights untouched
+    BalanceAxis {
+        /// Even out the heights of the panes instead of their widths
+        #[clap(short, long, value_parser, default_value("false"), takes_value(false))]
+        vertical: bool,
+    },
+    /// Even out the sizes of all tiled panes in the current tab, keeping the way they are split
+    BalancePanes,
+    /// Focus the pane in the current tab that most recently produced output
+    FocusActivePane,
+    /// Split new panes opened without a direction to the right, or down if already doing so,
+    /// instead of placing them in the biggest available space
+    ToggleSplitDirection,
+    /// Copy the selection in the focused pane to the clipboard, exactly as it is unless
+    /// normalized
+    Copy {
+        /// Turn CRLF line endings into LF and expand tabs to spaces according to the tab stops of
+        /// the focused pane
+        #[clap(short, long, value_parser)]
+        normalize: bool,
+    },
+    /// Select the whole scrollback of the focused pane, the selection is cleared once the pane
+    /// gets input or loses focus
+    SelectAll {
+        /// Only select the lines currently visible in the pane
+        #[clap(short, long, value_parser)]
+        viewport_only: bool,
+    },
+    /// Copy the URL at the given position of the focused pane to the clipboard, if there is one
+    CopyUrl {
+        /// The column of the URL in the focused pane (0 is the leftmost column)
+        #[clap(short, long, value_parser)]
+        x: usize,
+        /// The line of the URL in the focused pane (0 is the top line of the viewport)
+        #[clap(short, long, value_parser)]
+        y: usize,
+    },
+    /// Open the URL at the given position of the focused pane with xdg-open (or open on macOS),
+    /// if there is one
+    OpenUrl {
+        /// The column of the URL in the focused pane (0 is the leftmost column)
+        #[clap(short, long, value_parser)]
+        x: usize,
+        /// The line of the URL in the focused pane (0 is the top line of the viewport)
+        #[clap(short, long, value_parser)]
+        y: usize,
+    },
+    /// Copy the output of the last command that ran in the focused pane to the clipboard (needs
+    /// shell integration emitting OSC 133 prompt marks)
+    CopyLastCommandOutput,
+    /// Copy the selection in the focused pane after piping it through a shell command, copying
+    /// the command's output instead (eg. `zellij action copy-through 'sort | uniq'`)
+    CopyThrough {
+        /// The shell command to filter the selection through
+        #[clap(value_parser)]
+        command: String,
+    },
+    /// Search the scrollback of the focused pane and list the matching lines in a new pane,
+    /// clicking a line in the list scrolls the focused pane to it
+    SearchQuickfix {
+        /// The text to search for
+        #[clap(value_parser)]
+        query: String,
+    },
+    /// Scroll the focused pane to a match of its active search
+    SearchJumpToMatch {
+        /// The index of the match among all the matches in the scrollback, 0 being the first
+        #[clap(value_parser)]
+        index: usize,
+    },
+    /// Print how many matches the active search of the focused pane has in its scrollback,
+    /// prints nothing if there is no active search
+    QuerySearchMatchCount,
+    /// Detach from the session, when run from outside of a client everyone attached to the
+    /// session is detached
+    Detach {
+        /// Wait for the commands running in all panes to finish before detaching
+        #[clap(long, value_parser, default_value("false"), takes_value(false))]
+        wait: bool,
+        /// How long to wait at most, in seconds, before detaching anyway
+        #[clap(long, value_parser, default_value("600"), requires("wait"))]
+        timeout: u64,
+    },
+}
+
+/// Parses a terminal size given as COLSxROWS (eg. "80x24")
+fn parse_pty_size(s: &str) -> Result<(u16, u16), String> {
+    let err = || format!("'{}' is not a valid size (eg. 80x24)", s);
+    let (cols, rows) = s.trim().split_once('x').ok_or_else(err)?;
+    let cols = cols.trim().parse::<u16>().map_err(|_| err())?;
+    let rows = rows.trim().parse::<u16>().map_err(|_| err())?;
+    if cols == 0 || rows == 0 {
+        return Err(format!("'{}' must have non-zero sides", s));
+    }
+    Ok((cols, rows))
+}
+
+/// Parses a fraction given either as a percentage (eg. "60%") or as a number between 0 and 1
+/// (eg. "0.6") into a whole percent
+fn parse_percent(s: &str) -> Result<u8, String> {
+    let percent = match s.trim().strip_suffix('%') {
+        Some(percent) => percent.trim().parse::<f64>(),
+        None => s.trim().parse::<f64>().map(|fraction| fraction * 100.0),
+    }
+    .map_err(|_| format!("'{}' is not a valid fraction (eg. 60% or 0.6)", s))?
+    .round();
+    if percent >= 1.0 && percent <= 99.0 {
+        Ok(percent as u8)
+    } else {
+        Err(format!("'{}' must be between 1% and 99%", s))
+    }
 }
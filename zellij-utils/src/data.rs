@@ -251,6 +251,74 @@ impl FromStr for Direction {
     }
 }
 
+/// A screen edge or corner a floating pane can be docked against.
+#[derive(Eq, Clone, Copy, Debug, PartialEq, Hash, Deserialize, Serialize)]
+pub enum FloatingSnap {
+    Top,
+    Bottom,
+    Left,
+    Right,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Center,
+}
+
+impl FromStr for FloatingSnap {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Top" | "top" => Ok(FloatingSnap::Top),
+            "Bottom" | "bottom" => Ok(FloatingSnap::Bottom),
+            "Left" | "left" => Ok(FloatingSnap::Left),
+            "Right" | "right" => Ok(FloatingSnap::Right),
+            "TopLeft" | "top-left" => Ok(FloatingSnap::TopLeft),
+            "TopRight" | "top-right" => Ok(FloatingSnap::TopRight),
+            "BottomLeft" | "bottom-left" => Ok(FloatingSnap::BottomLeft),
+            "BottomRight" | "bottom-right" => Ok(FloatingSnap::BottomRight),
+            "Center" | "center" => Ok(FloatingSnap::Center),
+            _ => Err(format!(
+                "Failed to parse FloatingSnap. Unknown FloatingSnap: {}",
+                s
+            )),
+        }
+    }
+}
+
+/// The ratio of columns to rows a floating pane should have, eg. 2:1
+#[derive(Eq, Clone, Copy, Debug, PartialEq, Hash, Deserialize, Serialize)]
+pub struct AspectRatio {
+    pub cols: usize,
+    pub rows: usize,
+}
+
+impl FromStr for AspectRatio {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || format!("'{}' is not a valid aspect ratio (eg. 2:1)", s);
+        let (cols, rows) = s.split_once(':').ok_or_else(err)?;
+        let cols = cols.trim().parse::<usize>().map_err(|_| err())?;
+        let rows = rows.trim().parse::<usize>().map_err(|_| err())?;
+        if cols == 0 || rows == 0 {
+            return Err(format!("'{}' must have non-zero sides", s));
+        }
+        Ok(AspectRatio { cols, rows })
+    }
+}
+
+/// Where to place a new floating pane and how big to make it, rather than letting zellij find
+/// room for it.
+#[derive(Eq, Clone, Copy, Debug, Default, PartialEq, Hash, Deserialize, Serialize)]
+pub struct FloatingPanePlacement {
+    pub snap: Option<FloatingSnap>,
+    pub width: Option<usize>,  // in columns
+    pub height: Option<usize>, // in rows
+    /// Shrink the pane within the space it would otherwise take so that it has this ratio of
+    /// columns to rows
+    pub aspect: Option<AspectRatio>,
+}
+
 /// Resize operation to perform.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize)]
 pub enum Resize {
@@ -470,6 +538,7 @@ pub enum Event {
     Timer(f64),
     CopyToClipboard(CopyDestination),
     SystemClipboardFailure,
+    ClipboardUpdate(ClipboardInfo),
     InputReceived,
     Visible(bool),
     CustomMessage(
@@ -670,6 +739,9 @@ pub struct ModeInfo {
     pub style: Style,
     pub capabilities: PluginCapabilities,
     pub session_name: Option<String>,
+    /// Whether input is sent to all panes in all tabs (see `TabInfo::is_sync_panes_active` for
+    /// the sync state of a single tab)
+    pub is_sync_session_active: bool,
 }
 
 impl ModeInfo {
@@ -778,3 +850,45 @@ pub enum CopyDestination {
     Primary,
     System,
 }
+
+/// A pane as listed by `zellij action query-pane-list`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaneListEntry {
+    pub id: u32,
+    pub is_plugin: bool,
+    pub title: String,
+    pub tab_index: usize, // 1-based, like the index given to go-to-tab
+    pub is_floating: bool,
+    pub is_focused: bool,
+    pub cwd: Option<PathBuf>,
+    pub command: Option<String>, // the command the pane was opened with, if it is a command pane
+}
+
+/// The output format of `zellij action query-pane-list`
+#[derive(Eq, Clone, Copy, Debug, PartialEq, Hash, Deserialize, Serialize)]
+pub enum PaneListFormat {
+    Plain,
+    Json,
+}
+
+impl FromStr for PaneListFormat {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Plain" | "plain" => Ok(PaneListFormat::Plain),
+            "Json" | "json" => Ok(PaneListFormat::Json),
+            _ => Err(format!(
+                "Failed to parse PaneListFormat. Unknown PaneListFormat: {}",
+                s
+            )),
+        }
+    }
+}
+
+/// The destination and size of the text most recently copied to the clipboard
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClipboardInfo {
+    pub destination: CopyDestination,
+    pub bytes: usize,
+    pub lines: usize,
+}
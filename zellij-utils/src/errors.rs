@@ -215,11 +215,22 @@ pub enum ScreenContext {
     Render,
     NewPane,
     OpenInPlaceEditor,
+    RemovePaneFrame,
     ToggleFloatingPanes,
     ShowFloatingPanes,
     HideFloatingPanes,
     TogglePaneEmbedOrFloating,
+    EmbedAllFloatingPanes,
+    FloatAllTiledPanes,
+    PeekPaneFloating,
+    TogglePanePinned,
+    BalancePanesAxis,
+    BalancePanes,
+    FocusMostRecentlyActivePane,
+    ToggleDefaultSplitDirection,
+    WriteToPanesMatching,
     HorizontalSplit,
+    NewPaneAtFraction,
     VerticalSplit,
     WriteCharacter,
     ResizeIncreaseAll,
@@ -238,9 +249,14 @@ pub enum ScreenContext {
     ResizeUp,
     ResizeIncrease,
     ResizeDecrease,
+    SetPaneSize,
+    ResizePaneToPercent,
+    SetPaneSizeConstraints,
     SwitchFocus,
     FocusNextPane,
     FocusPreviousPane,
+    FocusNextFloatingPane,
+    FocusPreviousFloatingPane,
     FocusPaneAt,
     MoveFocusLeft,
     MoveFocusLeftOrPreviousTab,
@@ -248,47 +264,89 @@ pub enum ScreenContext {
     MoveFocusUp,
     MoveFocusRight,
     MoveFocusRightOrNextTab,
+    FocusPaneWithId,
+    CycleFocusByCommand,
+    FocusPaneByName,
+    FocusPaneAtIndex,
+    SetClientLayout,
     MovePane,
     MovePaneBackwards,
+    RotatePanes,
+    MoveFloatingPaneTo,
+    ResizeFloatingPaneTo,
     MovePaneDown,
     MovePaneUp,
     MovePaneRight,
     MovePaneLeft,
     Exit,
     ClearScreen,
+    ClearScrollback,
+    SetScrollbackLines,
     DumpScreen,
+    DumpScreenSvg,
+    DumpScreenJsonl,
+    DumpScreenHtml,
+    DumpLayout,
     EditScrollback,
     ScrollUp,
     ScrollUpAt,
+    ScrollUpBy,
     ScrollDown,
     ScrollDownAt,
+    ScrollDownBy,
     ScrollToBottom,
     ScrollToTop,
+    ScrollToLine,
     PageScrollUp,
+    MoveToPreviousPrompt,
+    MoveToNextPrompt,
+    TogglePaneLineWrap,
     PageScrollDown,
     HalfPageScrollUp,
     HalfPageScrollDown,
     ClearScroll,
     CloseFocusedPane,
+    CloseFocusedPaneSmart,
     ToggleActiveSyncTab,
+    TogglePinTab,
+    PinTab,
+    ToggleActiveSyncSession,
+    RestartPaneCommand,
+    DuplicatePane,
     ToggleActiveTerminalFullscreen,
+    TogglePaneFullscreen,
     TogglePaneFrames,
+    ToggleFocusedPaneFrame,
+    ToggleRuler,
+    ToggleZenMode,
     SetSelectable,
     SetInvisibleBorders,
     SetFixedHeight,
     SetFixedWidth,
     ClosePane,
     HoldPane,
+    RestartWatchedCommand,
     UpdatePaneName,
+    ClearPaneName,
     UndoRenamePane,
     NewTab,
     ApplyLayout,
     SwitchTabNext,
     SwitchTabPrev,
+    MoveTabLeft,
+    MoveTabRight,
+    MoveTabToIndex,
+    BreakPane,
+    BreakPaneLeft,
+    BreakPaneRight,
+    MovePaneToTab,
+    ToggleOverlayTab,
     CloseTab,
     GoToTab,
     GoToTabName,
+    GoToTabOfPane,
     UpdateTabName,
+    ClearTabName,
     UndoRenameTab,
     TerminalResize,
     TerminalPixelDimensions,
@@ -297,7 +355,15 @@ pub enum ScreenContext {
     TerminalColorRegisters,
     ChangeMode,
     ChangeModeForAllClients,
+    ChangeModeTimed,
+    EnterResizeLoop,
+    ExitResizeLoop,
+    ResetModeTimer,
+    ModeTimeoutElapsed,
+    SetModeReportSocket,
     LeftClick,
+    LeftDoubleClick,
+    LeftTripleClick,
     RightClick,
     MiddleClick,
     LeftMouseRelease,
@@ -307,11 +373,27 @@ pub enum ScreenContext {
     MouseHoldRight,
     MouseHoldMiddle,
     Copy,
+    CopyNormalized,
+    SelectAll,
+    CopyUrlUnderCursor,
+    OpenUrlUnderCursor,
+    CopyLastCommandOutput,
+    CopyThrough,
+    CopyFilteredText,
+    SearchToQuickfix,
+    SearchJumpToMatch,
+    QuerySearchMatchCount,
+    DetachWhenIdle,
+    CheckPendingDetach,
     ToggleTab,
+    RestoreTabFocus,
     AddClient,
     RemoveClient,
     AddOverlay,
     RemoveOverlay,
+    ShowModeHelp,
+    SetRepeatCount,
+    ResetFloatingPaneGeometry,
     ConfirmPrompt,
     DenyPrompt,
     UpdateSearch,
@@ -320,11 +402,18 @@ pub enum ScreenContext {
     SearchToggleCaseSensitivity,
     SearchToggleWholeWord,
     SearchToggleWrap,
+    SearchToggleRegex,
+    SearchToggleVisibleOnly,
     AddRedPaneFrameColorOverride,
     ClearPaneFrameColorOverride,
     PreviousSwapLayout,
     NextSwapLayout,
+    GoToSwapLayout,
+    ApplySwapLayoutByName,
     QueryTabNames,
+    QueryPaneList,
+    RenameSession,
+    QueryClipboardInfo,
     NewTiledPluginPane,
     StartOrReloadPluginPane,
     NewFloatingPluginPane,
@@ -344,12 +433,16 @@ pub enum PtyContext {
     OpenInPlaceEditor,
     SpawnTerminalVertically,
     SpawnTerminalHorizontally,
+    SpawnTerminalAtFraction,
     UpdateActivePane,
     GoToTab,
     NewTab,
     ClosePane,
     CloseTab,
     ReRunCommandInPane,
+    KillCommandInPane,
+    DumpLayout,
+    ListPanes,
     Exit,
 }
 
@@ -405,6 +498,9 @@ pub enum ServerContext {
     ConnStatus,
     ActiveClients,
     Log,
+    RenameSession,
+    SetSessionVar,
+    QuerySessionVar,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -418,6 +514,12 @@ pub enum BackgroundJobContext {
     DisplayPaneError,
     AnimatePluginLoading,
     StopPluginLoadingAnimation,
+    RunTabTour,
+    StopTabTour,
+    TabTourEnded,
+    ModeTimeout,
+    PollPendingDetach,
+    RunCopyFilter,
     Exit,
 }
 
@@ -502,6 +604,9 @@ open an issue on GitHub:
 
     #[error("The plugin does not exist")]
     PluginDoesNotExist,
+
+    #[error("There is no tab at position {position}, there are {tab_count} tabs")]
+    NoTabAtPosition { position: usize, tab_count: usize },
 }
 
 #[cfg(not(target_family = "wasm"))]
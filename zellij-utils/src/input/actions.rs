@@ -1,20 +1,23 @@
 //! Definition of the actions that can be bound to keys.
 
-use super::command::RunCommandAction;
+use super::command::{PaneRunAction, RunCommandAction, ShellOptions};
 use super::layout::{
     FloatingPaneLayout, Layout, RunPlugin, RunPluginLocation, SwapFloatingLayout, SwapTiledLayout,
     TiledPaneLayout,
 };
 use crate::cli::CliAction;
+use crate::consts::{MAX_SESSION_VAR_NAME_LEN, MAX_SESSION_VAR_VALUE_LEN};
 use crate::data::InputMode;
-use crate::data::{Direction, Resize};
+use crate::data::{Direction, FloatingPanePlacement, PaneListFormat, Resize};
 use crate::input::config::{Config, ConfigError, KdlError};
 use crate::input::options::OnForceClose;
 use crate::setup::{find_default_config_dir, get_layout_dir};
 use miette::{NamedSource, Report};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use crate::position::Position;
@@ -71,6 +74,11 @@ pub enum SearchOption {
     CaseSensitivity,
     WholeWord,
     Wrap,
+    Regex,
+    /// Only search the visible viewport rather than the whole scrollback. Each scroll of a
+    /// search otherwise re-searches a line of scrollback until it finds a match, which can be slow
+    /// in huge buffers.
+    VisibleOnly,
 }
 
 impl FromStr for SearchOption {
@@ -82,6 +90,8 @@ impl FromStr for SearchOption {
             },
             "WholeWord" | "wholeword" | "Wholeword" => Ok(SearchOption::WholeWord),
             "Wrap" | "wrap" => Ok(SearchOption::Wrap),
+            "Regex" | "regex" | "Regexp" => Ok(SearchOption::Regex),
+            "VisibleOnly" | "visibleonly" | "Visibleonly" => Ok(SearchOption::VisibleOnly),
             _ => Err(format!(
                 "Failed to parse SearchOption. Unknown SearchOption: {}",
                 s
@@ -103,54 +113,168 @@ pub enum Action {
     Write(Vec<u8>),
     /// Write Characters to the terminal.
     WriteChars(String),
+    /// Paste text to the terminal, wrapped in bracketed paste markers if the terminal asked for
+    /// them so that eg. a shell does not run each line of it on its own.
+    PasteText(String),
+    /// Write to the terminals in the current tab whose running command matches the given filter
+    /// (a substring, or a glob if it includes `*` or `?`)
+    WriteToPanesMatching(String, Vec<u8>),
     /// Switch to the specified input mode.
     SwitchToMode(InputMode),
+    /// Switch to the specified input mode, reverting to the previous one after the given amount
+    /// of milliseconds
+    SwitchToModeTimed(InputMode, u64),
+    /// Switch to resize mode until [`Action::ExitResizeLoop`] (or any other mode switch),
+    /// making each resize there `resize_loop_step` increments big
+    EnterResizeLoop,
+    /// Leave a resize loop, returning to the mode it was entered from
+    ExitResizeLoop,
     /// Switch all connected clients to the specified input mode.
     SwitchModeForAllClients(InputMode),
+    /// Write the name of every input mode switched to from now on as a line to the unix socket
+    /// at the given path
+    SetModeReportSocket(PathBuf),
+    /// Show the keybindings of the specified input mode without switching to it
+    ShowModeHelp(InputMode),
     /// Shrink/enlarge focused pane at specified border
     Resize(Resize, Option<Direction>),
+    /// Set the width and/or height of the focused pane in cells, leaving the other one unchanged
+    /// if None
+    SetPaneSize(Option<usize>, Option<usize>),
+    /// Set the bounds the size of the focused pane is kept within when tiled panes are resized or
+    /// laid out (minimum columns, maximum columns, minimum rows, maximum rows), None leaving that
+    /// bound unset
+    SetPaneSizeConstraints(Option<usize>, Option<usize>, Option<usize>, Option<usize>),
+    /// Set the width and/or height of the focused pane to a percentage (1-100) of the tab,
+    /// leaving the other one unchanged if None
+    ResizePaneToPercent {
+        width: Option<u8>,
+        height: Option<u8>,
+    },
     /// Switch focus to next pane in specified direction.
     FocusNextPane,
     FocusPreviousPane,
+    /// Focus the bottom-most floating pane, raising it, so that repeating this cycles through the
+    /// floating panes in z-order
+    FocusNextFloatingPane,
+    /// Focus the floating pane below the focused one, lowering the focused pane to the bottom
+    FocusPreviousFloatingPane,
     /// Move the focus pane in specified direction.
     SwitchFocus,
     MoveFocus(Direction),
     /// Tries to move the focus pane in specified direction.
     /// If there is no pane in the direction, move to previous/next Tab.
     MoveFocusOrTab(Direction),
+    /// Focus the terminal pane with this id, also switching to its tab if the bool is set
+    /// (otherwise only a pane in the current tab is focused)
+    FocusPaneWithId(u32, bool),
+    /// Focus the next pane whose command contains this string, across all tabs (ordered by tab
+    /// and then by pane id), wrapping around to the first one
+    CycleFocusByCommand(String),
+    /// Focus the first pane named exactly this, searching all tabs in order if the bool is set
+    /// (otherwise only the current tab)
+    FocusPaneByName(String, bool),
+    /// Focus the tiled pane at this 1-based index in tiling order (left to right, top to bottom)
+    /// in the current tab, or the last one if there are fewer panes
+    FocusPaneAtIndex(usize),
     MovePane(Option<Direction>),
     MovePaneBackwards,
+    /// Move every tiled pane of the current tab into the slot of the next one (left to right,
+    /// top to bottom), the last one wrapping around to the first slot
+    RotatePanesForward,
+    /// Move every tiled pane of the current tab into the slot of the previous one, the first one
+    /// wrapping around to the last slot
+    RotatePanesBackward,
+    /// Move the top left corner of the focused floating pane to these terminal cell coordinates,
+    /// as far as the pane stays inside the viewport. Does nothing if the focused pane is tiled.
+    MoveFloatingPaneTo {
+        x: usize,
+        y: usize,
+    },
+    /// Resize the focused floating pane to this many columns and rows, no smaller than the minimum
+    /// pane size and no larger than the viewport. Does nothing if the focused pane is tiled.
+    ResizeFloatingPaneTo {
+        cols: usize,
+        rows: usize,
+    },
     /// Clear all buffers of a current screen
     ClearScreen,
-    /// Dumps the screen to a file
-    DumpScreen(String, bool),
+    /// Discard the scrollback of the current screen, keeping what is visible
+    ClearScrollback,
+    /// Change the most lines the focused pane keeps in its scrollback, dropping the oldest lines
+    /// if it has more than that (0 keeps no scrollback)
+    SetScrollbackLines(usize),
+    /// Dumps the screen to a file, or to the cli client if the file is `-`. The first bool
+    /// includes the scrollback, the second keeps colors and text attributes as ANSI escapes, the
+    /// third starts the dump with a line naming the time, session, tab and pane it was taken of.
+    DumpScreen(String, bool, bool, bool),
+    /// Dumps the screen to a file as an SVG image, keeping colors and text attributes
+    DumpScreenSvg(String, bool),
+    /// Dumps the screen to a file as JSON lines, one object per line of text with its
+    /// `line_number`, `text` and `style_runs` (see the server's jsonl dump for the schema)
+    DumpScreenJsonl(String, bool),
+    /// Dumps the screen to a file as a self-contained HTML page, keeping colors and text
+    /// attributes as inline styles
+    DumpScreenHtml(String, bool),
+    /// Dumps the layout of the session as KDL to a file, or to the cli client if there is no file
+    DumpLayout(Option<String>),
     /// Scroll up in focus pane.
     EditScrollback,
     ScrollUp,
     /// Scroll up at point
     ScrollUpAt(Position),
+    /// Scroll up the given amount of lines in focus pane.
+    ScrollUpBy(usize),
     /// Scroll down in focus pane.
     ScrollDown,
     /// Scroll down at point
     ScrollDownAt(Position),
+    /// Scroll down the given amount of lines in focus pane.
+    ScrollDownBy(usize),
     /// Scroll down to bottom in focus pane.
     ScrollToBottom,
     /// Scroll up to top in focus pane.
     ScrollToTop,
+    /// Scroll the focused pane so that the given line of its scrollback (0 being the oldest) is
+    /// at the top of the viewport.
+    ScrollToLine(usize),
     /// Scroll up one page in focus pane.
     PageScrollUp,
     /// Scroll down one page in focus pane.
     PageScrollDown,
+    /// Scroll the focus pane to the previous shell prompt, as marked by shell integration
+    /// (OSC 133), or up one page if it has no prompt marks.
+    MoveToPreviousPrompt,
+    /// Scroll the focus pane to the next shell prompt, as marked by shell integration (OSC 133),
+    /// or down one page if it has no prompt marks.
+    MoveToNextPrompt,
+    /// Toggle between wrapping and truncating lines wider than the focused pane
+    TogglePaneLineWrap,
     /// Scroll up half page in focus pane.
     HalfPageScrollUp,
     /// Scroll down half page in focus pane.
     HalfPageScrollDown,
     /// Toggle between fullscreen focus pane and normal layout.
     ToggleFocusFullscreen,
+    /// Focus the terminal pane with this id (switching to its tab) and toggle it fullscreen, does
+    /// nothing if there is no such pane
+    ToggleFullscreenForPane(u32),
     /// Toggle frames around panes in the UI
     TogglePaneFrames,
+    /// Toggle the frame of the focused pane only, it stays that way when frames are toggled for
+    /// all panes
+    ToggleFocusedPaneFrame,
+    /// Toggle row and column guides drawn over the UI, to help with aligning panes
+    ToggleRuler,
+    /// Hide the tab bar, status bar and pane frames together, or bring them back as they were
+    ToggleZenMode,
     /// Toggle between sending text commands to all panes on the current tab and normal mode.
     ToggleActiveSyncTab,
+    /// Toggle between sending text commands to all panes in all tabs and normal mode,
+    /// independently of the sync state of each tab.
+    ToggleActiveSyncSession,
+    /// Rerun the command of the focused pane if it exited and is held open
+    RestartPaneCommand,
     /// Open a new pane in the specified direction (relative to focus).
     /// If no direction is specified, will try to use the biggest available space.
     NewPane(Option<Direction>, Option<String>), // String is an optional pane name
@@ -158,23 +282,56 @@ pub enum Action {
     EditFile(
         PathBuf,
         Option<usize>,
+        Option<usize>,
         Option<PathBuf>,
         Option<Direction>,
         bool,
-    ), // usize is an optional line number, Option<PathBuf> is an optional cwd, bool is floating true/false
+    ), // the usizes are an optional line number and column, Option<PathBuf> is an optional cwd, bool is floating true/false
     /// Open a new floating pane
-    NewFloatingPane(Option<RunCommandAction>, Option<String>), // String is an optional pane name
+    NewFloatingPane(
+        Option<PaneRunAction>,
+        Option<String>,
+        Option<FloatingPanePlacement>,
+    ), // String is an optional pane name
     /// Open a new tiled (embedded, non-floating) pane
-    NewTiledPane(Option<Direction>, Option<RunCommandAction>, Option<String>), // String is an
+    NewTiledPane(Option<Direction>, Option<PaneRunAction>, Option<String>), // String is an
     // optional pane
     // name
+    /// Open a new tiled pane on the given side of the whole tab, splitting the tab at the given
+    /// percent (1 - 99) of its width/height rather than splitting the focused pane
+    NewPaneAtFraction(Direction, u8, Option<PaneRunAction>, Option<String>), // String is an optional pane name
+    /// Open a new tiled pane in the specified direction (relative to focus) running the same
+    /// command as the focused pane, or a shell in the same working directory if it is not a
+    /// command pane. If no direction is specified, will try to use the biggest available space.
+    DuplicatePane(Option<Direction>),
+    /// Like `DuplicatePane`, but opens the new pane floating
+    DuplicateFloatingPane,
     /// Embed focused pane in tab if floating or float focused pane if embedded
     TogglePaneEmbedOrFloating,
+    /// Embed all floating panes in the tab, appending them to the tiled panes from the bottom of
+    /// the floating stack up
+    EmbedAllFloatingPanes,
+    /// Float all tiled panes in the tab but the first one, which the tab needs to keep
+    FloatAllTiledPanes,
+    /// Temporarily float the focused tiled pane enlarged, restoring it to its tiled position when
+    /// invoked again or when it loses focus
+    PeekPaneFloating,
+    /// Pin the focused floating pane on top of the other floating panes, or unpin it. Does nothing
+    /// if the focused pane is tiled
+    TogglePanePinned,
     /// Toggle the visibility of all floating panes (if any) in the current Tab
     ToggleFloatingPanes,
     /// Close the focus pane.
     CloseFocus,
+    /// Close the focused pane, asking for confirmation first only if a process is running in it.
+    /// A pane counts as running a process if its command has not exited yet, or if the
+    /// foreground process group of its terminal is not the one of the shell leading the session
+    /// (ie. the shell is waiting on a job), otherwise it is closed as if with
+    /// `SkipConfirm(CloseFocus)`.
+    CloseFocusSmart,
     PaneNameInput(Vec<u8>),
+    /// Clear the name of the focused pane, so that following `PaneNameInput`s start from scratch.
+    ClearPaneName,
     UndoRenamePane,
     /// Create a new tab, optionally with a specified tab layout.
     NewTab(
@@ -183,25 +340,65 @@ pub enum Action {
         Option<Vec<SwapTiledLayout>>,
         Option<Vec<SwapFloatingLayout>>,
         Option<String>,
-    ), // the String is the tab name
+        bool,
+        bool,
+    ), // the String is the tab name, the first bool is whether the tab should be created without
+    // any panes, the second whether its panes start in the cwd of the focused pane
+    /// Rearrange the panes of the current tab according to the layout at the given path, only
+    /// while no other client is viewing the tab since pane positions are shared by all clients
+    SetClientLayout(PathBuf),
     /// Do nothing.
     NoOp,
     /// Go to the next tab.
     GoToNextTab,
     /// Go to the previous tab.
     GoToPreviousTab,
+    /// Move the focused tab one position to the left, wrapping around to the end.
+    MoveTabLeft,
+    /// Move the focused tab one position to the right, wrapping around to the start.
+    MoveTabRight,
+    /// Move the focused tab to the given (1-based) position, indices past either end move it to
+    /// that end.
+    MoveTabToIndex(u32),
+    /// Move the focused pane out of its tab and into a new tab of its own.
+    BreakPane,
+    /// Move the focused pane into the tab to the left of the current one.
+    BreakPaneLeft,
+    /// Move the focused pane into the tab to the right of the current one.
+    BreakPaneRight,
+    /// Move the focused pane into the tab at the given (1-based) position, keeping it floating
+    /// if it was floating.
+    MovePaneToTab(u32),
+    /// Show the first pane of the tab at the given (1-based) position floating on top of the
+    /// current tab, without switching to it. Hides it again if it is already shown.
+    ToggleOverlayTab(u32),
     /// Close the current tab.
     CloseTab,
+    /// Pin or unpin the current tab, pinned tabs are not closed by CloseTab.
+    TogglePinTab,
     GoToTab(u32),
     GoToTabName(String, bool),
+    /// Switch to the tab containing the terminal pane with this id and focus it
+    GoToTabOfPane(u32),
     ToggleTab,
+    /// Focus the pane that was focused when the current tab was last switched away from
+    RestoreTabFocus,
     TabNameInput(Vec<u8>),
+    /// Clear the name of the focused tab, so that following `TabNameInput`s start from scratch.
+    ClearTabName,
     UndoRenameTab,
     /// Run specified command in new pane.
     Run(RunCommandAction),
     /// Detach session and exit
     Detach,
+    /// Detach once no pane has a command running anymore, or once the timeout (in seconds) runs
+    /// out
+    DetachWhenIdle(u64),
     LeftClick(Position),
+    /// Select the word at the given position of the focused pane
+    LeftDoubleClick(Position),
+    /// Select the line at the given position of the focused pane
+    LeftTripleClick(Position),
     RightClick(Position),
     MiddleClick(Position),
     LaunchOrFocusPlugin(RunPlugin, bool), // bool => should float
@@ -212,6 +409,25 @@ pub enum Action {
     MouseHoldRight(Position),
     MouseHoldMiddle(Position),
     Copy,
+    /// Copy the selection with CRLF line endings turned into LF and tabs expanded to spaces
+    /// according to the tab stops of the focused pane
+    CopyNormalized,
+    /// Select the whole scrollback of the focused pane, or only its viewport if true
+    SelectAll(bool),
+    /// Copy the URL (either an OSC 8 link or plain text) at the given position of the focused
+    /// pane, relative to its viewport
+    CopyUrlUnderCursor(Position),
+    /// Open the URL (either an OSC 8 link or plain text) at the given position of the focused
+    /// pane, relative to its viewport, with the default application for it. Like the mouse
+    /// actions, it needs a position and so is only sent by the cli (`zellij action open-url`)
+    /// rather than bound to a key in the config.
+    OpenUrlUnderCursor(Position),
+    /// Copy the output of the last command that ran in the focused pane, ie. everything between
+    /// the last two prompts marked by shell integration (OSC 133). Does nothing without the marks.
+    CopyLastCommandOutput,
+    /// Copy the selection after piping it through a shell command (eg. `sort | uniq`), copying
+    /// the command's output instead. Nothing is copied if the command fails or times out.
+    CopyThrough(String),
     /// Confirm a prompt
     Confirm,
     /// Deny a prompt
@@ -224,15 +440,216 @@ pub enum Action {
     Search(SearchDirection),
     /// Toggle case sensitivity of search
     SearchToggleOption(SearchOption),
+    /// Search the focused pane and list the matching lines with their line numbers in a new pane,
+    /// clicking a line there scrolls the searched pane to it
+    SearchToQuickfix(Vec<u8>),
+    /// Scroll the focused pane to the match of its active search at this (0 based) index among
+    /// all the matches in its scrollback. Does nothing if there is no active search.
+    SearchJumpToMatch(usize),
+    /// Query how many matches the active search of the focused pane has in its scrollback
+    QuerySearchMatchCount,
     ToggleMouseMode,
     PreviousSwapLayout,
     NextSwapLayout,
+    /// Apply the swap layout at this index of the current tab, the base layout of the tab being
+    /// the first. Does nothing if there is no such swap layout.
+    GoToSwapLayout(usize),
+    /// Apply the swap layout of the current tab declared with this name (the floating one if the
+    /// floating panes are visible). Does nothing if there is no such swap layout.
+    ApplySwapLayoutByName(String),
     /// Query all tab names
     QueryTabNames,
+    /// Query the panes of all tabs, with their cwd and command
+    QueryPaneList(PaneListFormat),
+    /// Set the value of a session variable, for the lifetime of the session
+    SetSessionVar(String, String),
+    /// Query the value of a session variable
+    QuerySessionVar(String),
+    /// Rename the current session
+    RenameSession(String),
+    /// Query the destination and size of the last copy to the clipboard
+    QueryClipboardInfo,
     /// Open a new tiled (embedded, non-floating) plugin pane
     NewTiledPluginPane(RunPluginLocation, Option<String>), // String is an optional name
     NewFloatingPluginPane(RunPluginLocation, Option<String>), // String is an optional name
     StartOrReloadPlugin(RunPlugin),
+    /// Step through the given tab indices, waiting the given amount of milliseconds between each
+    StartTabTour(Vec<u32>, u64),
+    /// Stop a tab tour that is in progress
+    StopTabTour,
+    /// Even out the sizes of tiled panes along one axis only (true => vertical, false =>
+    /// horizontal)
+    BalancePanesAxis(bool),
+    /// Even out the sizes of all tiled panes in the current tab, keeping the way they are split
+    BalancePanes,
+    /// Focus the pane in the current tab that most recently produced output
+    FocusMostRecentlyActivePane,
+    /// Flip the direction new panes without an explicit direction are split in (instead of
+    /// placing them in the biggest available space)
+    ToggleDefaultSplitDirection,
+    /// Perform several actions in order, eg. from a single keybinding (use [`Action::sequence`]
+    /// to create one)
+    Sequence(Vec<Action>),
+    /// Perform an action the given number of times (use [`Action::repeat`] to create one)
+    Repeat(usize, Box<Action>),
+    /// Perform the next count-aware action this many times, like a numeric prefix in vi. Actions
+    /// that honor the count are scrolling up or down (also by several lines), moving focus
+    /// between panes and going to the next or previous tab. The count is dropped when the input
+    /// mode changes.
+    SetRepeatCount(u32),
+    /// Forget the geometry of the last closed floating pane with this name, so that the next one
+    /// opened with it is placed anew
+    ResetFloatingPaneGeometry(String),
+}
+
+/// Parses a single action from its name followed by its arguments, separated by whitespace (eg.
+/// `MoveFocus Left` or `Resize Increase Up`), as a lighter alternative to a KDL config node.
+///
+/// Only the actions without arguments and `MoveFocus`, `Resize`, `GoToTab` and `SwitchToMode`
+/// are supported.
+impl FromStr for Action {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        let action_name = words
+            .next()
+            .ok_or_else(|| "Failed to parse Action. No action given".to_owned())?;
+        let arguments: Vec<&str> = words.collect();
+        let single_argument = || match arguments.as_slice() {
+            [argument] => Ok(*argument),
+            _ => Err(format!(
+                "Failed to parse Action. {} takes exactly one argument, got: {:?}",
+                action_name, arguments
+            )),
+        };
+        match action_name {
+            "MoveFocus" => single_argument()?
+                .parse()
+                .map(Action::MoveFocus)
+                .map_err(|e| format!("Failed to parse MoveFocus. {}", e)),
+            "SwitchToMode" => InputMode::from_str(single_argument()?)
+                .map(Action::SwitchToMode)
+                .map_err(|e| format!("Failed to parse SwitchToMode. Unknown InputMode: {}", e)),
+            "GoToTab" => match single_argument()?.parse::<u32>() {
+                Ok(tab_index) if tab_index > 0 => Ok(Action::GoToTab(tab_index)),
+                _ => Err(format!(
+                    "Failed to parse GoToTab. Tab index must be a positive integer, got: {}",
+                    arguments[0]
+                )),
+            },
+            "Resize" => {
+                let mut resize = None;
+                let mut direction = None;
+                for argument in &arguments {
+                    if let Ok(value) = Resize::from_str(argument) {
+                        resize = Some(value);
+                    } else if let Ok(value) = Direction::from_str(argument) {
+                        direction = Some(value);
+                    } else {
+                        return Err(format!(
+                            "Failed to parse Resize. Unknown resize type or direction: {}",
+                            argument
+                        ));
+                    }
+                }
+                match resize {
+                    Some(resize) => Ok(Action::Resize(resize, direction)),
+                    None => Err(
+                        "Failed to parse Resize. Expected a resize type (Increase or Decrease)"
+                            .to_owned(),
+                    ),
+                }
+            },
+            _ => {
+                let action = match action_name {
+                    "Quit" => Action::Quit,
+                    "FocusNextPane" => Action::FocusNextPane,
+                    "FocusPreviousPane" => Action::FocusPreviousPane,
+                    "FocusNextFloatingPane" => Action::FocusNextFloatingPane,
+                    "FocusPreviousFloatingPane" => Action::FocusPreviousFloatingPane,
+                    "SwitchFocus" => Action::SwitchFocus,
+                    "EditScrollback" => Action::EditScrollback,
+                    "ScrollUp" => Action::ScrollUp,
+                    "ScrollDown" => Action::ScrollDown,
+                    "ScrollToBottom" => Action::ScrollToBottom,
+                    "ScrollToTop" => Action::ScrollToTop,
+                    "PageScrollUp" => Action::PageScrollUp,
+                    "PageScrollDown" => Action::PageScrollDown,
+                    "HalfPageScrollUp" => Action::HalfPageScrollUp,
+                    "HalfPageScrollDown" => Action::HalfPageScrollDown,
+                    "MoveToPreviousPrompt" => Action::MoveToPreviousPrompt,
+                    "MoveToNextPrompt" => Action::MoveToNextPrompt,
+                    "TogglePaneLineWrap" => Action::TogglePaneLineWrap,
+                    "ToggleFocusFullscreen" => Action::ToggleFocusFullscreen,
+                    "TogglePaneFrames" => Action::TogglePaneFrames,
+                    "ToggleFocusedPaneFrame" => Action::ToggleFocusedPaneFrame,
+                    "ToggleRuler" => Action::ToggleRuler,
+                    "ToggleZenMode" => Action::ToggleZenMode,
+                    "ToggleActiveSyncTab" => Action::ToggleActiveSyncTab,
+                    "ToggleActiveSyncSession" => Action::ToggleActiveSyncSession,
+                    "RestartPaneCommand" => Action::RestartPaneCommand,
+                    "TogglePaneEmbedOrFloating" => Action::TogglePaneEmbedOrFloating,
+                    "EmbedAllFloatingPanes" => Action::EmbedAllFloatingPanes,
+                    "FloatAllTiledPanes" => Action::FloatAllTiledPanes,
+                    "StopTabTour" => Action::StopTabTour,
+                    "FocusMostRecentlyActivePane" => Action::FocusMostRecentlyActivePane,
+                    "ToggleDefaultSplitDirection" => Action::ToggleDefaultSplitDirection,
+                    "PeekPaneFloating" => Action::PeekPaneFloating,
+                    "TogglePanePinned" => Action::TogglePanePinned,
+                    "BalancePanes" => Action::BalancePanes,
+                    "ToggleFloatingPanes" => Action::ToggleFloatingPanes,
+                    "CloseFocus" => Action::CloseFocus,
+                    "CloseFocusSmart" => Action::CloseFocusSmart,
+                    "ClearPaneName" => Action::ClearPaneName,
+                    "UndoRenamePane" => Action::UndoRenamePane,
+                    "NoOp" => Action::NoOp,
+                    "GoToNextTab" => Action::GoToNextTab,
+                    "GoToPreviousTab" => Action::GoToPreviousTab,
+                    "MoveTabLeft" => Action::MoveTabLeft,
+                    "MoveTabRight" => Action::MoveTabRight,
+                    "BreakPane" => Action::BreakPane,
+                    "BreakPaneLeft" => Action::BreakPaneLeft,
+                    "BreakPaneRight" => Action::BreakPaneRight,
+                    "CloseTab" => Action::CloseTab,
+                    "TogglePinTab" => Action::TogglePinTab,
+                    "ToggleTab" => Action::ToggleTab,
+                    "RestoreTabFocus" => Action::RestoreTabFocus,
+                    "ClearTabName" => Action::ClearTabName,
+                    "UndoRenameTab" => Action::UndoRenameTab,
+                    "Detach" => Action::Detach,
+                    "Copy" => Action::Copy,
+                    "CopyNormalized" => Action::CopyNormalized,
+                    "CopyLastCommandOutput" => Action::CopyLastCommandOutput,
+                    "Confirm" => Action::Confirm,
+                    "Deny" => Action::Deny,
+                    "ToggleMouseMode" => Action::ToggleMouseMode,
+                    "PreviousSwapLayout" => Action::PreviousSwapLayout,
+                    "NextSwapLayout" => Action::NextSwapLayout,
+                    "Clear" => Action::ClearScreen,
+                    "ClearScrollback" => Action::ClearScrollback,
+                    "RotatePanesForward" => Action::RotatePanesForward,
+                    "RotatePanesBackward" => Action::RotatePanesBackward,
+                    "EnterResizeLoop" => Action::EnterResizeLoop,
+                    "ExitResizeLoop" => Action::ExitResizeLoop,
+                    "DuplicateFloatingPane" => Action::DuplicateFloatingPane,
+                    _ => {
+                        return Err(format!(
+                            "Failed to parse Action. Unknown or unsupported action: {}",
+                            action_name
+                        ))
+                    },
+                };
+                if arguments.is_empty() {
+                    Ok(action)
+                } else {
+                    Err(format!(
+                        "Failed to parse Action. {} takes no arguments, got: {:?}",
+                        action_name, arguments
+                    ))
+                }
+            },
+        }
+    }
 }
 
 impl Action {
@@ -240,10 +657,50 @@ impl Action {
     pub fn shallow_eq(&self, other_action: &Action) -> bool {
         match (self, other_action) {
             (Action::NewTab(..), Action::NewTab(..)) => true,
+            (Action::Write(..), Action::Write(..))
+            | (Action::WriteChars(..), Action::WriteChars(..))
+            | (Action::PaneNameInput(..), Action::PaneNameInput(..))
+            | (Action::TabNameInput(..), Action::TabNameInput(..))
+            | (Action::SearchInput(..), Action::SearchInput(..)) => true,
+            (Action::Sequence(actions), Action::Sequence(other_actions)) => {
+                actions.len() == other_actions.len()
+                    && actions
+                        .iter()
+                        .zip(other_actions)
+                        .all(|(action, other_action)| action.shallow_eq(other_action))
+            },
             _ => self == other_action,
         }
     }
 
+    /// Creates an action that performs this one `count` times. Repeating zero times is an
+    /// [`Action::NoOp`] and actions that end the session for the client (eg. [`Action::Quit`])
+    /// are only performed once.
+    pub fn repeat(count: usize, action: Action) -> Action {
+        match (count, action) {
+            (0, _) | (_, Action::NoOp) => Action::NoOp,
+            (_, action @ (Action::Quit | Action::Detach)) | (1, action) => action,
+            (count, action) => Action::Repeat(count, Box::new(action)),
+        }
+    }
+
+    /// Creates a sequence of these actions, flattening any sequences nested in it so that
+    /// performing it never recurses more than once. An empty sequence is an [`Action::NoOp`].
+    pub fn sequence(actions: Vec<Action>) -> Action {
+        let actions: Vec<Action> = actions
+            .into_iter()
+            .flat_map(|action| match action {
+                Action::Sequence(nested_actions) => nested_actions,
+                action => vec![action],
+            })
+            .collect();
+        if actions.is_empty() {
+            Action::NoOp
+        } else {
+            Action::Sequence(actions)
+        }
+    }
+
     pub fn actions_from_cli(
         cli_action: CliAction,
         get_current_dir: Box<dyn Fn() -> PathBuf>,
@@ -252,42 +709,248 @@ impl Action {
         match cli_action {
             CliAction::Write { bytes } => Ok(vec![Action::Write(bytes)]),
             CliAction::WriteChars { chars } => Ok(vec![Action::WriteChars(chars)]),
-            CliAction::Resize { resize, direction } => Ok(vec![Action::Resize(resize, direction)]),
-            CliAction::FocusNextPane => Ok(vec![Action::FocusNextPane]),
-            CliAction::FocusPreviousPane => Ok(vec![Action::FocusPreviousPane]),
-            CliAction::MoveFocus { direction } => Ok(vec![Action::MoveFocus(direction)]),
-            CliAction::MoveFocusOrTab { direction } => Ok(vec![Action::MoveFocusOrTab(direction)]),
+            CliAction::PasteText { text, file } => match (text, file) {
+                (Some(text), _) => Ok(vec![Action::PasteText(text)]),
+                (None, Some(file)) => {
+                    let file = get_current_dir().join(file);
+                    let text = std::fs::read_to_string(&file).map_err(|e| {
+                        format!(
+                            "Failed to read text to paste from {}: {}",
+                            file.display(),
+                            e
+                        )
+                    })?;
+                    Ok(vec![Action::PasteText(text)])
+                },
+                (None, None) => Err("Nothing to paste, expected text or a --file".into()),
+            },
+            CliAction::WriteMatching { command, chars } => Ok(vec![Action::WriteToPanesMatching(
+                command,
+                chars.into_bytes(),
+            )]),
+            CliAction::WriteFiltered { command, chars } => match command {
+                Some(command) => Ok(vec![Action::WriteToPanesMatching(
+                    command,
+                    chars.into_bytes(),
+                )]),
+                None => Err(
+                    "Refusing to write to every pane in the tab, expected a --command filter"
+                        .into(),
+                ),
+            },
+            CliAction::Resize {
+                resize,
+                direction,
+                width_percent,
+                height_percent,
+            } => {
+                if width_percent.is_some() || height_percent.is_some() {
+                    Ok(vec![Action::ResizePaneToPercent {
+                        width: width_percent,
+                        height: height_percent,
+                    }])
+                } else if let Some(resize) = resize {
+                    Ok(vec![Action::Resize(resize, direction)])
+                } else {
+                    Err("Either a resize type or a percentage must be specified".into())
+                }
+            },
+            CliAction::SetPaneSize { cols, rows } => {
+                if cols.is_none() && rows.is_none() {
+                    Err("Either --cols or --rows must be specified".into())
+                } else {
+                    Ok(vec![Action::SetPaneSize(cols, rows)])
+                }
+            },
+            CliAction::SetPaneConstraints {
+                min_cols,
+                max_cols,
+                min_rows,
+                max_rows,
+            } => Ok(vec![Action::SetPaneSizeConstraints(
+                min_cols, max_cols, min_rows, max_rows,
+            )]),
+            CliAction::FocusNextPane { count } => {
+                Ok(vec![Action::repeat(count, Action::FocusNextPane)])
+            },
+            CliAction::FocusPreviousPane { count } => {
+                Ok(vec![Action::repeat(count, Action::FocusPreviousPane)])
+            },
+            CliAction::FocusNextFloatingPane { count } => {
+                Ok(vec![Action::repeat(count, Action::FocusNextFloatingPane)])
+            },
+            CliAction::FocusPreviousFloatingPane { count } => Ok(vec![Action::repeat(
+                count,
+                Action::FocusPreviousFloatingPane,
+            )]),
+            CliAction::MoveFocus { direction, count } => {
+                Ok(vec![Action::repeat(count, Action::MoveFocus(direction))])
+            },
+            CliAction::FocusPane { id, switch_tab } => {
+                Ok(vec![Action::FocusPaneWithId(id, switch_tab)])
+            },
+            CliAction::CycleByCommand { command } => Ok(vec![Action::CycleFocusByCommand(command)]),
+            CliAction::FocusPaneByName { name, all_tabs } => {
+                Ok(vec![Action::FocusPaneByName(name, all_tabs)])
+            },
+            CliAction::MoveFocusOrTab {
+                direction,
+                stop_at_edge,
+            } => {
+                // focus that stays in the tab is just a plain move
+                if stop_at_edge {
+                    Ok(vec![Action::MoveFocus(direction)])
+                } else {
+                    Ok(vec![Action::MoveFocusOrTab(direction)])
+                }
+            },
             CliAction::MovePane { direction } => Ok(vec![Action::MovePane(direction)]),
             CliAction::MovePaneBackwards => Ok(vec![Action::MovePaneBackwards]),
+            CliAction::RotatePanes { backward } => {
+                if backward {
+                    Ok(vec![Action::RotatePanesBackward])
+                } else {
+                    Ok(vec![Action::RotatePanesForward])
+                }
+            },
+            CliAction::MoveFloatingPane { x, y } => Ok(vec![Action::MoveFloatingPaneTo { x, y }]),
+            CliAction::ResizeFloatingPane { cols, rows } => {
+                Ok(vec![Action::ResizeFloatingPaneTo { cols, rows }])
+            },
             CliAction::Clear => Ok(vec![Action::ClearScreen]),
-            CliAction::DumpScreen { path, full } => Ok(vec![Action::DumpScreen(
-                path.as_os_str().to_string_lossy().into(),
+            CliAction::ClearScrollback => Ok(vec![Action::ClearScrollback]),
+            CliAction::SetScrollback { lines } => Ok(vec![Action::SetScrollbackLines(lines)]),
+            CliAction::DumpScreen {
+                path,
                 full,
-            )]),
+                format,
+                ansi,
+                jsonl,
+                header,
+            } => {
+                let path = path.as_os_str().to_string_lossy().into();
+                if header && (format == "svg" || jsonl) {
+                    return Err("--header only applies to text dumps".into());
+                }
+                if format == "svg" {
+                    if ansi || jsonl {
+                        return Err("--ansi and --jsonl only apply to text dumps".into());
+                    }
+                    Ok(vec![Action::DumpScreenSvg(path, full)])
+                } else if jsonl {
+                    Ok(vec![Action::DumpScreenJsonl(path, full)])
+                } else {
+                    Ok(vec![Action::DumpScreen(path, full, ansi, header)])
+                }
+            },
+            CliAction::DumpScreenHtml { path, full } => {
+                let path = path.as_os_str().to_string_lossy().into();
+                Ok(vec![Action::DumpScreenHtml(path, full)])
+            },
+            CliAction::DumpLayout { path } => {
+                let path = path.map(|path| {
+                    get_current_dir()
+                        .join(path)
+                        .as_os_str()
+                        .to_string_lossy()
+                        .into()
+                });
+                Ok(vec![Action::DumpLayout(path)])
+            },
             CliAction::EditScrollback => Ok(vec![Action::EditScrollback]),
-            CliAction::ScrollUp => Ok(vec![Action::ScrollUp]),
-            CliAction::ScrollDown => Ok(vec![Action::ScrollDown]),
+            CliAction::ScrollUp { count } => match count {
+                Some(count) => Ok(vec![Action::ScrollUpBy(count)]),
+                None => Ok(vec![Action::ScrollUp]),
+            },
+            CliAction::ScrollDown { count } => match count {
+                Some(count) => Ok(vec![Action::ScrollDownBy(count)]),
+                None => Ok(vec![Action::ScrollDown]),
+            },
             CliAction::ScrollToBottom => Ok(vec![Action::ScrollToBottom]),
             CliAction::ScrollToTop => Ok(vec![Action::ScrollToTop]),
-            CliAction::PageScrollUp => Ok(vec![Action::PageScrollUp]),
-            CliAction::PageScrollDown => Ok(vec![Action::PageScrollDown]),
-            CliAction::HalfPageScrollUp => Ok(vec![Action::HalfPageScrollUp]),
-            CliAction::HalfPageScrollDown => Ok(vec![Action::HalfPageScrollDown]),
-            CliAction::ToggleFullscreen => Ok(vec![Action::ToggleFocusFullscreen]),
+            CliAction::ScrollToLine { line } => Ok(vec![Action::ScrollToLine(line)]),
+            CliAction::PageScrollUp { count } => {
+                Ok(vec![Action::repeat(count, Action::PageScrollUp)])
+            },
+            CliAction::PageScrollDown { count } => {
+                Ok(vec![Action::repeat(count, Action::PageScrollDown)])
+            },
+            CliAction::MoveToPreviousPrompt { count } => {
+                Ok(vec![Action::repeat(count, Action::MoveToPreviousPrompt)])
+            },
+            CliAction::MoveToNextPrompt { count } => {
+                Ok(vec![Action::repeat(count, Action::MoveToNextPrompt)])
+            },
+            CliAction::TogglePaneLineWrap => Ok(vec![Action::TogglePaneLineWrap]),
+            CliAction::HalfPageScrollUp { count } => {
+                Ok(vec![Action::repeat(count, Action::HalfPageScrollUp)])
+            },
+            CliAction::HalfPageScrollDown { count } => {
+                Ok(vec![Action::repeat(count, Action::HalfPageScrollDown)])
+            },
+            CliAction::ToggleFullscreen { pane_id } => match pane_id {
+                Some(pane_id) => Ok(vec![Action::ToggleFullscreenForPane(pane_id)]),
+                None => Ok(vec![Action::ToggleFocusFullscreen]),
+            },
             CliAction::TogglePaneFrames => Ok(vec![Action::TogglePaneFrames]),
+            CliAction::ToggleFocusedPaneFrame => Ok(vec![Action::ToggleFocusedPaneFrame]),
+            CliAction::ToggleRuler => Ok(vec![Action::ToggleRuler]),
+            CliAction::ToggleZen => Ok(vec![Action::ToggleZenMode]),
             CliAction::ToggleActiveSyncTab => Ok(vec![Action::ToggleActiveSyncTab]),
+            CliAction::ToggleActiveSyncSession => Ok(vec![Action::ToggleActiveSyncSession]),
+            CliAction::RestartPane => Ok(vec![Action::RestartPaneCommand]),
             CliAction::NewPane {
                 direction,
                 command,
                 plugin,
                 cwd,
+                cwd_from_focus,
+                no_frame,
                 floating,
+                snap,
+                width,
+                height,
+                aspect,
+                place_at,
                 name,
                 close_on_exit,
                 start_suspended,
+                login_shell,
+                shell,
+                watch,
+                output_socket,
+                pin_matching,
+                capture_exit,
+                env,
+                fixed_size,
+                reset_geometry,
             } => {
+                let placement =
+                    if snap.is_some() || width.is_some() || height.is_some() || aspect.is_some() {
+                        Some(FloatingPanePlacement {
+                            snap,
+                            width,
+                            height,
+                            aspect,
+                        })
+                    } else {
+                        None
+                    };
+                let mut floating_pane_actions: Vec<Action> = name
+                    .clone()
+                    .filter(|_| reset_geometry)
+                    .map(Action::ResetFloatingPaneGeometry)
+                    .into_iter()
+                    .collect();
                 let current_dir = get_current_dir();
+                let watch_paths: Vec<PathBuf> =
+                    watch.iter().map(|path| current_dir.join(path)).collect();
+                let output_socket = output_socket.map(|path| current_dir.join(path));
+                // the cwd of the focused pane is only known to the server, the current dir is what
+                // it falls back to if there is none
+                let cwd_was_given = cwd.is_some();
                 let cwd = cwd
+                    .filter(|_| !cwd_from_focus)
                     .map(|cwd| current_dir.join(cwd))
                     .or_else(|| Some(current_dir));
                 if let Some(plugin) = plugin {
@@ -315,16 +978,80 @@ impl Action {
                     let (command, args) = (PathBuf::from(command.remove(0)), command);
                     let hold_on_start = start_suspended;
                     let hold_on_close = !close_on_exit;
-                    let run_command_action = RunCommandAction {
+                    let env = parse_key_value_pairs(env, "environment variable")?;
+                    if let Some(capture_exit) = &capture_exit {
+                        validate_session_var(capture_exit, "")?;
+                    }
+                    if let Some(pin_matching) = &pin_matching {
+                        Regex::new(pin_matching).map_err(|e| {
+                            format!("Invalid --pin-matching pattern '{pin_matching}': {e}")
+                        })?;
+                    }
+                    if let Some(shell) = &shell {
+                        validate_shell(shell)?;
+                    }
+                    let mut run_command_action = RunCommandAction {
                         command,
                         args,
                         cwd,
                         direction,
                         hold_on_close,
                         hold_on_start,
+                        watch_paths,
+                        output_socket,
+                        pin_matching,
+                        cwd_from_focus,
+                        no_frame,
+                        capture_exit,
+                        env,
+                        fixed_pty_size: fixed_size,
+                        run_in_shell: shell,
                     };
+                    if login_shell {
+                        let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".into());
+                        run_command_action.wrap_in_login_shell(PathBuf::from(shell));
+                    }
+                    let run_command_action = PaneRunAction::Command(run_command_action);
                     if floating {
-                        Ok(vec![Action::NewFloatingPane(
+                        floating_pane_actions.push(Action::NewFloatingPane(
+                            Some(run_command_action),
+                            name,
+                            placement,
+                        ));
+                        Ok(floating_pane_actions)
+                    } else if let (Some(direction), Some(percent)) = (direction, place_at) {
+                        Ok(vec![Action::NewPaneAtFraction(
+                            direction,
+                            percent,
+                            Some(run_command_action),
+                            name,
+                        )])
+                    } else {
+                        Ok(vec![Action::NewTiledPane(
+                            direction,
+                            Some(run_command_action),
+                            name,
+                        )])
+                    }
+                } else if cwd_from_focus || no_frame || fixed_size.is_some() {
+                    let run_command_action = PaneRunAction::DefaultShell(ShellOptions {
+                        // without a cwd the server starts the shell in that of the focused pane
+                        cwd: cwd.filter(|_| cwd_was_given || cwd_from_focus),
+                        cwd_from_focus,
+                        no_frame,
+                        fixed_pty_size: fixed_size,
+                    });
+                    if floating {
+                        floating_pane_actions.push(Action::NewFloatingPane(
+                            Some(run_command_action),
+                            name,
+                            placement,
+                        ));
+                        Ok(floating_pane_actions)
+                    } else if let (Some(direction), Some(percent)) = (direction, place_at) {
+                        Ok(vec![Action::NewPaneAtFraction(
+                            direction,
+                            percent,
                             Some(run_command_action),
                             name,
                         )])
@@ -337,16 +1064,32 @@ impl Action {
                     }
                 } else {
                     if floating {
-                        Ok(vec![Action::NewFloatingPane(None, name)])
+                        floating_pane_actions.push(Action::NewFloatingPane(None, name, placement));
+                        Ok(floating_pane_actions)
+                    } else if let (Some(direction), Some(percent)) = (direction, place_at) {
+                        Ok(vec![Action::NewPaneAtFraction(
+                            direction, percent, None, name,
+                        )])
                     } else {
                         Ok(vec![Action::NewTiledPane(direction, None, name)])
                     }
                 }
             },
+            CliAction::DuplicatePane {
+                direction,
+                floating,
+            } => {
+                if floating {
+                    Ok(vec![Action::DuplicateFloatingPane])
+                } else {
+                    Ok(vec![Action::DuplicatePane(direction)])
+                }
+            },
             CliAction::Edit {
                 direction,
                 file,
                 line_number,
+                column,
                 floating,
                 cwd,
             } => {
@@ -363,6 +1106,7 @@ impl Action {
                 Ok(vec![Action::EditFile(
                     file,
                     line_number,
+                    column,
                     cwd,
                     direction,
                     floating,
@@ -371,9 +1115,28 @@ impl Action {
             CliAction::SwitchMode { input_mode } => {
                 Ok(vec![Action::SwitchModeForAllClients(input_mode)])
             },
+            CliAction::SwitchModeTimed { input_mode, ms } => {
+                Ok(vec![Action::SwitchToModeTimed(input_mode, ms)])
+            },
+            CliAction::ResizeLoop => Ok(vec![Action::EnterResizeLoop]),
+            CliAction::SetModeReportSocket { path } => {
+                // the socket is connected to by the server, so relative paths are resolved here
+                Ok(vec![Action::SetModeReportSocket(
+                    get_current_dir().join(path),
+                )])
+            },
+            CliAction::ModeHelp { mode } => Ok(vec![Action::ShowModeHelp(mode)]),
+            CliAction::Repeat { count } => Ok(vec![Action::SetRepeatCount(count)]),
             CliAction::TogglePaneEmbedOrFloating => Ok(vec![Action::TogglePaneEmbedOrFloating]),
+            CliAction::EmbedAllFloatingPanes => Ok(vec![Action::EmbedAllFloatingPanes]),
+            CliAction::FloatAllTiledPanes => Ok(vec![Action::FloatAllTiledPanes]),
+            CliAction::PeekPane => Ok(vec![Action::PeekPaneFloating]),
+            CliAction::TogglePanePinned => Ok(vec![Action::TogglePanePinned]),
             CliAction::ToggleFloatingPanes => Ok(vec![Action::ToggleFloatingPanes]),
-            CliAction::ClosePane => Ok(vec![Action::CloseFocus]),
+            CliAction::ClosePane { smart } => match smart {
+                true => Ok(vec![Action::CloseFocusSmart]),
+                false => Ok(vec![Action::CloseFocus]),
+            },
             CliAction::RenamePane { name } => Ok(vec![
                 Action::UndoRenamePane,
                 Action::PaneNameInput(name.as_bytes().to_vec()),
@@ -381,11 +1144,38 @@ impl Action {
             CliAction::UndoRenamePane => Ok(vec![Action::UndoRenamePane]),
             CliAction::GoToNextTab => Ok(vec![Action::GoToNextTab]),
             CliAction::GoToPreviousTab => Ok(vec![Action::GoToPreviousTab]),
+            CliAction::RestoreTabFocus => Ok(vec![Action::RestoreTabFocus]),
+            CliAction::MoveTabLeft => Ok(vec![Action::MoveTabLeft]),
+            CliAction::MoveTabRight => Ok(vec![Action::MoveTabRight]),
+            CliAction::MoveTabToIndex { index } => Ok(vec![Action::MoveTabToIndex(index)]),
+            CliAction::BreakPane => Ok(vec![Action::BreakPane]),
+            CliAction::BreakPaneLeft => Ok(vec![Action::BreakPaneLeft]),
+            CliAction::BreakPaneRight => Ok(vec![Action::BreakPaneRight]),
+            CliAction::MovePaneToTab { index } => {
+                if index == 0 {
+                    // the number of tabs is only known to the server, which tells the client
+                    // about indices past the last tab
+                    return Err(format!(
+                        "Tab index {index} is out of range, tab indices start at 1 like in go-to-tab"
+                    ));
+                }
+                Ok(vec![Action::MovePaneToTab(index)])
+            },
+            CliAction::OverlayTab { index } => {
+                if index == 0 {
+                    return Err(format!(
+                        "Tab index {index} is out of range, tab indices start at 1 like in go-to-tab"
+                    ));
+                }
+                Ok(vec![Action::ToggleOverlayTab(index)])
+            },
             CliAction::CloseTab => Ok(vec![Action::CloseTab]),
+            CliAction::TogglePinTab => Ok(vec![Action::TogglePinTab]),
             CliAction::GoToTab { index } => Ok(vec![Action::GoToTab(index)]),
             CliAction::GoToTabName { name, create } => Ok(vec![Action::GoToTabName(name, create)]),
+            CliAction::GoToPaneTab { id } => Ok(vec![Action::GoToTabOfPane(id)]),
             CliAction::RenameTab { name } => Ok(vec![
-                Action::TabNameInput(vec![0]),
+                Action::ClearTabName,
                 Action::TabNameInput(name.as_bytes().to_vec()),
             ]),
             CliAction::UndoRenameTab => Ok(vec![Action::UndoRenameTab]),
@@ -394,12 +1184,18 @@ impl Action {
                 layout,
                 layout_dir,
                 cwd,
+                empty,
+                focus_pane,
+                inherit_vars,
             } => {
+                if focus_pane == Some(0) {
+                    return Err("Pane index 0 is out of range, pane indices start at 1".to_owned());
+                }
                 let current_dir = get_current_dir();
                 let cwd = cwd
                     .map(|cwd| current_dir.join(cwd))
                     .or_else(|| Some(current_dir));
-                if let Some(layout_path) = layout {
+                let mut actions = if let Some(layout_path) = layout {
                     let layout_dir = layout_dir
                         .or_else(|| config.and_then(|c| c.options.layout_dir))
                         .or_else(|| get_layout_dir(find_default_config_dir()));
@@ -453,6 +1249,8 @@ impl Action {
                             swap_tiled_layouts,
                             swap_floating_layouts,
                             name,
+                            false,
+                            inherit_vars,
                         )])
                     } else {
                         let swap_tiled_layouts = Some(layout.swap_tiled_layouts.clone());
@@ -464,39 +1262,223 @@ impl Action {
                             swap_tiled_layouts,
                             swap_floating_layouts,
                             name,
+                            false,
+                            inherit_vars,
                         )])
                     }
                 } else {
-                    Ok(vec![Action::NewTab(None, vec![], None, None, name)])
+                    Ok(vec![Action::NewTab(
+                        None,
+                        vec![],
+                        None,
+                        None,
+                        name,
+                        empty,
+                        inherit_vars,
+                    )])
+                }?;
+                if let Some(focus_pane) = focus_pane {
+                    // the new tab becomes the current tab, so the focus moves in it
+                    actions.push(Action::FocusPaneAtIndex(focus_pane));
                 }
+                Ok(actions)
+            },
+            CliAction::SetClientLayout { layout } => {
+                // the layout is loaded by the server, so we resolve it to a full path here if
+                // it refers to a file
+                let layout = if layout.extension().is_some() || layout.components().count() > 1 {
+                    get_current_dir().join(layout)
+                } else {
+                    config
+                        .and_then(|c| c.options.layout_dir)
+                        .or_else(|| get_layout_dir(find_default_config_dir()))
+                        .map(|layout_dir| layout_dir.join(&layout).with_extension("kdl"))
+                        .filter(|layout_path| layout_path.exists())
+                        .unwrap_or(layout)
+                };
+                Ok(vec![Action::SetClientLayout(layout)])
             },
             CliAction::PreviousSwapLayout => Ok(vec![Action::PreviousSwapLayout]),
             CliAction::NextSwapLayout => Ok(vec![Action::NextSwapLayout]),
+            CliAction::GoToSwapLayout { index } => Ok(vec![Action::GoToSwapLayout(index)]),
+            CliAction::ApplySwapLayout { name } => Ok(vec![Action::ApplySwapLayoutByName(name)]),
             CliAction::QueryTabNames => Ok(vec![Action::QueryTabNames]),
-            CliAction::StartOrReloadPlugin { url } => {
+            CliAction::QueryPaneList { format } => Ok(vec![Action::QueryPaneList(format)]),
+            CliAction::SetVar { name, value } => {
+                validate_session_var(&name, &value)?;
+                Ok(vec![Action::SetSessionVar(name, value)])
+            },
+            CliAction::GetVar { name } => Ok(vec![Action::QuerySessionVar(name)]),
+            CliAction::ClipboardInfo => Ok(vec![Action::QueryClipboardInfo]),
+            CliAction::RenameSession { name } => {
+                validate_session_name(&name)?;
+                Ok(vec![Action::RenameSession(name)])
+            },
+            CliAction::StartOrReloadPlugin { url, config } => {
                 let current_dir = get_current_dir();
                 let run_plugin_location = RunPluginLocation::parse(&url, Some(current_dir))
                     .map_err(|e| format!("Failed to parse plugin location: {}", e))?;
                 let run_plugin = RunPlugin {
                     location: run_plugin_location,
                     _allow_exec_host_cmd: false,
+                    configuration: parse_key_value_pairs(config, "plugin configuration")?,
                 };
                 Ok(vec![Action::StartOrReloadPlugin(run_plugin)])
             },
-            CliAction::LaunchOrFocusPlugin { url, floating } => {
+            CliAction::LaunchOrFocusPlugin {
+                url,
+                floating,
+                config,
+            } => {
                 let current_dir = get_current_dir();
                 let run_plugin_location = RunPluginLocation::parse(url.as_str(), Some(current_dir))
                     .map_err(|e| format!("Failed to parse plugin location: {}", e))?;
                 let run_plugin = RunPlugin {
                     location: run_plugin_location,
                     _allow_exec_host_cmd: false,
+                    configuration: parse_key_value_pairs(config, "plugin configuration")?,
                 };
                 Ok(vec![Action::LaunchOrFocusPlugin(run_plugin, floating)])
             },
+            CliAction::TabTour { indices, delay_ms } => {
+                Ok(vec![Action::StartTabTour(indices, delay_ms)])
+            },
+            CliAction::StopTabTour => Ok(vec![Action::StopTabTour]),
+            CliAction::BalanceAxis { vertical } => Ok(vec![Action::BalancePanesAxis(vertical)]),
+            CliAction::FocusActivePane => Ok(vec![Action::FocusMostRecentlyActivePane]),
+            CliAction::BalancePanes => Ok(vec![Action::BalancePanes]),
+            CliAction::ToggleSplitDirection => Ok(vec![Action::ToggleDefaultSplitDirection]),
+            CliAction::Copy { normalize } => {
+                if normalize {
+                    Ok(vec![Action::CopyNormalized])
+                } else {
+                    Ok(vec![Action::Copy])
+                }
+            },
+            CliAction::SelectAll { viewport_only } => Ok(vec![Action::SelectAll(viewport_only)]),
+            CliAction::CopyUrl { x, y } => {
+                Ok(vec![Action::CopyUrlUnderCursor(position_from_cli(x, y)?)])
+            },
+            CliAction::OpenUrl { x, y } => {
+                Ok(vec![Action::OpenUrlUnderCursor(position_from_cli(x, y)?)])
+            },
+            CliAction::CopyLastCommandOutput => Ok(vec![Action::CopyLastCommandOutput]),
+            CliAction::CopyThrough { command } => Ok(vec![Action::CopyThrough(command)]),
+            CliAction::SearchQuickfix { query } => {
+                Ok(vec![Action::SearchToQuickfix(query.into_bytes())])
+            },
+            CliAction::SearchJumpToMatch { index } => Ok(vec![Action::SearchJumpToMatch(index)]),
+            CliAction::QuerySearchMatchCount => Ok(vec![Action::QuerySearchMatchCount]),
+            CliAction::Detach { wait, timeout } => {
+                if wait {
+                    Ok(vec![Action::DetachWhenIdle(timeout)])
+                } else {
+                    Ok(vec![Action::Detach])
+                }
+            },
         }
     }
 }
 
+fn validate_session_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("Session name cannot be empty".into());
+    }
+    if name.contains(std::path::is_separator) {
+        return Err(format!(
+            "Session name cannot contain path separators: '{}'",
+            name
+        ));
+    }
+    // the server checks again when renaming, in case the session is created in the meantime
+    if session_exists(name) {
+        return Err(format!("A session named '{}' already exists", name));
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn session_exists(name: &str) -> bool {
+    crate::consts::ZELLIJ_SOCK_DIR.join(name).exists()
+}
+
+// sessions only exist on unix
+#[cfg(not(unix))]
+fn session_exists(_name: &str) -> bool {
+    false
+}
+
+/// Parses `what` (eg. plugin configuration) given as "key=value" pairs
+fn parse_key_value_pairs(
+    key_value_pairs: Vec<String>,
+    what: &str,
+) -> Result<BTreeMap<String, String>, String> {
+    key_value_pairs
+        .iter()
+        .map(|key_value_pair| match key_value_pair.split_once('=') {
+            Some((key, value)) if !key.is_empty() => Ok((key.to_owned(), value.to_owned())),
+            _ => Err(format!(
+                "Invalid {} '{}', expected key=value",
+                what, key_value_pair
+            )),
+        })
+        .collect()
+}
+
+/// Checks that the shell to run a command through is an absolute path or can be found in the
+/// PATH, so that a typo is reported to the user rather than failing to spawn in the server
+fn validate_shell(shell: &str) -> Result<(), String> {
+    let is_resolvable = Path::new(shell).is_absolute()
+        || (!shell.contains('/')
+            && std::env::var_os("PATH")
+                .map(|paths| std::env::split_paths(&paths).any(|path| path.join(shell).is_file()))
+                .unwrap_or(false));
+    if is_resolvable {
+        Ok(())
+    } else {
+        Err(format!(
+            "Cannot find shell '{}', expected an absolute path or a program in the PATH",
+            shell
+        ))
+    }
+}
+
+fn position_from_cli(x: usize, y: usize) -> Result<Position, String> {
+    let column = u16::try_from(x).map_err(|_| {
+        format!(
+            "Column {} is out of range, expected at most {}",
+            x,
+            u16::MAX
+        )
+    })?;
+    let line = i32::try_from(y)
+        .map_err(|_| format!("Line {} is out of range, expected at most {}", y, i32::MAX))?;
+    Ok(Position::new(line, column))
+}
+
+/// Checks a session variable against the limits the server enforces, so that these can be
+/// reported to the user rather than only logged by the server
+fn validate_session_var(name: &str, value: &str) -> Result<(), String> {
+    if name.is_empty()
+        || name.len() > MAX_SESSION_VAR_NAME_LEN
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(format!(
+            "Invalid session variable name '{}', expected up to {} letters, digits, '_' or '-'",
+            name, MAX_SESSION_VAR_NAME_LEN
+        ));
+    }
+    if value.len() > MAX_SESSION_VAR_VALUE_LEN {
+        return Err(format!(
+            "The value of session variable '{}' is longer than {} bytes",
+            name, MAX_SESSION_VAR_VALUE_LEN
+        ));
+    }
+    Ok(())
+}
+
 impl From<OnForceClose> for Action {
     fn from(ofc: OnForceClose) -> Action {
         match ofc {
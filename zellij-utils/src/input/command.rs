@@ -1,19 +1,20 @@
 //! Trigger a command
 use crate::data::Direction;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
 pub enum TerminalAction {
-    OpenFile(PathBuf, Option<usize>, Option<PathBuf>), // path to file (should be absolute), optional line_number and an
-    // optional cwd
+    OpenFile(PathBuf, Option<usize>, Option<usize>, Option<PathBuf>), // path to file (should be absolute), optional line_number, optional
+    // column and an optional cwd
     RunCommand(RunCommand),
 }
 
 impl TerminalAction {
     pub fn change_cwd(&mut self, new_cwd: PathBuf) {
         match self {
-            TerminalAction::OpenFile(_, _, cwd) => {
+            TerminalAction::OpenFile(_, _, _, cwd) => {
                 *cwd = Some(new_cwd);
             },
             TerminalAction::RunCommand(run_command) => {
@@ -35,6 +36,22 @@ pub struct RunCommand {
     pub hold_on_close: bool,
     #[serde(default)]
     pub hold_on_start: bool,
+    #[serde(default)]
+    pub watch_paths: Vec<PathBuf>, // rerun the command when any of these change
+    #[serde(default)]
+    pub output_socket: Option<PathBuf>, // a unix socket to also stream the pane's output to
+    #[serde(default)]
+    pub pin_matching: Option<String>, // a regex, lines of output matching it are pinned atop the pane
+    #[serde(default)]
+    pub cwd_from_focus: bool, // start in the cwd of the focused pane if it has one, cwd otherwise
+    #[serde(default)]
+    pub no_frame: bool, // the pane is borderless even when pane frames are on
+    #[serde(default)]
+    pub capture_exit: Option<String>, // a session variable to store the exit code of the command in
+    #[serde(default)]
+    pub env: BTreeMap<String, String>, // set over the environment inherited by the command
+    #[serde(default)]
+    pub fixed_pty_size: Option<(u16, u16)>, // (columns, rows) the command sees, whatever the pane's size
 }
 
 impl std::fmt::Display for RunCommand {
@@ -68,16 +85,74 @@ pub struct RunCommandAction {
     pub hold_on_close: bool,
     #[serde(default)]
     pub hold_on_start: bool,
+    #[serde(default)]
+    pub watch_paths: Vec<PathBuf>,
+    #[serde(default)]
+    pub output_socket: Option<PathBuf>,
+    #[serde(default)]
+    pub pin_matching: Option<String>,
+    #[serde(default)]
+    pub cwd_from_focus: bool,
+    #[serde(default)]
+    pub no_frame: bool,
+    #[serde(default)]
+    pub capture_exit: Option<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub fixed_pty_size: Option<(u16, u16)>,
+    #[serde(default)]
+    pub run_in_shell: Option<String>, // run as `<shell> -c "<command> <args>"` rather than directly
+}
+
+/// What a new terminal pane opened by an action runs
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum PaneRunAction {
+    Command(RunCommandAction),
+    /// The default shell of the server, started with these options
+    DefaultShell(ShellOptions),
+}
+
+/// Options for a pane running the default shell of the server
+#[derive(Clone, Debug, Deserialize, Default, Serialize, PartialEq, Eq)]
+pub struct ShellOptions {
+    #[serde(default)]
+    pub cwd: Option<PathBuf>,
+    #[serde(default)]
+    pub cwd_from_focus: bool,
+    #[serde(default)]
+    pub no_frame: bool,
+    #[serde(default)]
+    pub fixed_pty_size: Option<(u16, u16)>,
 }
 
 impl From<RunCommandAction> for RunCommand {
     fn from(action: RunCommandAction) -> Self {
+        let (command, args) = match action.run_in_shell {
+            // the words are left unquoted so that the shell interprets pipes, globs and the like
+            Some(shell) => {
+                let command_line = std::iter::once(action.command.to_string_lossy().to_string())
+                    .chain(action.args)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                (PathBuf::from(shell), vec!["-c".to_owned(), command_line])
+            },
+            None => (action.command, action.args),
+        };
         RunCommand {
-            command: action.command,
-            args: action.args,
+            command,
+            args,
             cwd: action.cwd,
             hold_on_close: action.hold_on_close,
             hold_on_start: action.hold_on_start,
+            watch_paths: action.watch_paths,
+            output_socket: action.output_socket,
+            pin_matching: action.pin_matching,
+            cwd_from_focus: action.cwd_from_focus,
+            no_frame: action.no_frame,
+            capture_exit: action.capture_exit,
+            env: action.env,
+            fixed_pty_size: action.fixed_pty_size,
         }
     }
 }
@@ -91,10 +166,43 @@ impl From<RunCommand> for RunCommandAction {
             direction: None,
             hold_on_close: run_command.hold_on_close,
             hold_on_start: run_command.hold_on_start,
+            watch_paths: run_command.watch_paths,
+            output_socket: run_command.output_socket,
+            pin_matching: run_command.pin_matching,
+            cwd_from_focus: run_command.cwd_from_focus,
+            no_frame: run_command.no_frame,
+            capture_exit: run_command.capture_exit,
+            env: run_command.env,
+            fixed_pty_size: run_command.fixed_pty_size,
+            run_in_shell: None,
         }
     }
 }
 
+impl RunCommandAction {
+    /// Run this command through `shell` as a login shell (`shell -lc "command args"`), so that
+    /// the user's shell profile is sourced before it runs
+    pub fn wrap_in_login_shell(&mut self, shell: PathBuf) {
+        let command_line = std::iter::once(self.command.to_string_lossy().to_string())
+            .chain(self.args.drain(..))
+            .map(|word| shell_quote(&word))
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.command = shell;
+        self.args = vec!["-lc".to_owned(), command_line];
+    }
+}
+
+// Quote a word so that the shell passes it to the command as-is
+fn shell_quote(word: &str) -> String {
+    let is_safe = |c: char| c.is_ascii_alphanumeric() || "_-./=:,@%+".contains(c);
+    if !word.is_empty() && word.chars().all(is_safe) {
+        word.to_owned()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
 impl RunCommand {
     pub fn new(command: PathBuf) -> Self {
         RunCommand {
@@ -42,6 +42,17 @@ impl Keybinds {
             .cloned()
             .unwrap_or_else(|| vec![self.default_action_for_mode(mode, raw_bytes)])
     }
+    pub fn keybinds_for_mode(&self, mode: &InputMode) -> Vec<(Key, Vec<Action>)> {
+        self.0
+            .get(mode)
+            .map(|mode_binds| {
+                mode_binds
+                    .iter()
+                    .map(|(key, actions)| (key.clone(), actions.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
     pub fn get_input_mode_mut(&mut self, input_mode: &InputMode) -> &mut HashMap<Key, Vec<Action>> {
         self.0.entry(*input_mode).or_insert_with(HashMap::new)
     }
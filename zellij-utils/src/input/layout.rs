@@ -212,6 +212,8 @@ pub struct RunPlugin {
     #[serde(default)]
     pub _allow_exec_host_cmd: bool,
     pub location: RunPluginLocation,
+    #[serde(default)]
+    pub configuration: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
@@ -307,6 +309,7 @@ pub type SwapFloatingLayout = (
 pub struct Layout {
     pub tabs: Vec<(Option<String>, TiledPaneLayout, Vec<FloatingPaneLayout>)>,
     pub focused_tab_index: Option<usize>,
+    pub pinned_tab_indices: Vec<usize>,
     pub template: Option<(TiledPaneLayout, Vec<FloatingPaneLayout>)>,
     pub swap_layouts: Vec<(TiledPaneLayout, Vec<FloatingPaneLayout>)>,
     pub swap_tiled_layouts: Vec<SwapTiledLayout>,
@@ -372,6 +375,7 @@ pub struct FloatingPaneLayout {
     pub y: Option<PercentOrFixed>,
     pub run: Option<Run>,
     pub focus: Option<bool>,
+    pub pinned: Option<bool>,
 }
 
 impl FloatingPaneLayout {
@@ -841,6 +845,10 @@ impl Layout {
         self.focused_tab_index
     }
 
+    pub fn pinned_tab_indices(&self) -> Vec<usize> {
+        self.pinned_tab_indices.clone()
+    }
+
     fn swap_layout_and_path(path: &Path) -> Option<(String, String)> {
         // Option<path, stringified_swap_layout>
         let mut swap_layout_path = PathBuf::from(path);
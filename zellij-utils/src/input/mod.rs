@@ -39,6 +39,7 @@ mod not_wasm {
             style: attributes.style,
             capabilities,
             session_name,
+            is_sync_session_active: false,
         }
     }
 
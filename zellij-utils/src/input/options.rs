@@ -124,6 +124,16 @@ pub struct Options {
     #[clap(long, value_parser)]
     #[serde(default)]
     pub auto_layout: Option<bool>,
+
+    /// Whether a keypress cancels or extends a timed mode switch (cancel or extend)
+    #[clap(long, arg_enum, hide_possible_values = true, value_parser)]
+    #[serde(default)]
+    pub timed_mode_keypress: Option<TimedModeKeypress>,
+
+    /// How many resize increments each key press makes while in a resize loop (EnterResizeLoop)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub resize_loop_step: Option<usize>,
 }
 
 #[derive(ArgEnum, Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
@@ -151,6 +161,31 @@ impl FromStr for Clipboard {
     }
 }
 
+#[derive(ArgEnum, Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum TimedModeKeypress {
+    #[serde(alias = "cancel")]
+    Cancel,
+    #[serde(alias = "extend")]
+    Extend,
+}
+
+impl Default for TimedModeKeypress {
+    fn default() -> Self {
+        Self::Extend
+    }
+}
+
+impl FromStr for TimedModeKeypress {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Cancel" | "cancel" => Ok(Self::Cancel),
+            "Extend" | "extend" => Ok(Self::Extend),
+            _ => Err(format!("No such timed mode keypress behaviour: {}", s)),
+        }
+    }
+}
+
 impl Options {
     pub fn from_yaml(from_yaml: Option<Options>) -> Options {
         if let Some(opts) = from_yaml {
@@ -187,6 +222,8 @@ impl Options {
         let attach_to_session = other
             .attach_to_session
             .or_else(|| self.attach_to_session.clone());
+        let timed_mode_keypress = other.timed_mode_keypress.or(self.timed_mode_keypress);
+        let resize_loop_step = other.resize_loop_step.or(self.resize_loop_step);
 
         Options {
             simplified_ui,
@@ -209,6 +246,8 @@ impl Options {
             session_name,
             attach_to_session,
             auto_layout,
+            timed_mode_keypress,
+            resize_loop_step,
         }
     }
 
@@ -252,6 +291,8 @@ impl Options {
         let attach_to_session = other
             .attach_to_session
             .or_else(|| self.attach_to_session.clone());
+        let timed_mode_keypress = other.timed_mode_keypress.or(self.timed_mode_keypress);
+        let resize_loop_step = other.resize_loop_step.or(self.resize_loop_step);
 
         Options {
             simplified_ui,
@@ -274,6 +315,8 @@ impl Options {
             session_name,
             attach_to_session,
             auto_layout,
+            timed_mode_keypress,
+            resize_loop_step,
         }
     }
 
@@ -333,6 +376,8 @@ impl From<CliOptions> for Options {
             session_name: opts.session_name,
             attach_to_session: opts.attach_to_session,
             auto_layout: opts.auto_layout,
+            timed_mode_keypress: opts.timed_mode_keypress,
+            resize_loop_step: opts.resize_loop_step,
             ..Default::default()
         }
     }
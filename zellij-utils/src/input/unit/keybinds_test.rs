@@ -1,7 +1,10 @@
 use super::super::actions::*;
 use super::super::keybinds::*;
-use crate::data::{self, CharOrArrow, Direction, Key};
+use crate::data::{self, CharOrArrow, Direction, Key, PluginTag};
+use crate::input::command::RunCommandAction;
 use crate::input::config::Config;
+use crate::input::layout::{RunPlugin, RunPluginLocation};
+use crate::position::Position;
 use insta::assert_snapshot;
 use strum::IntoEnumIterator;
 
@@ -77,6 +80,72 @@ fn can_define_series_of_actions_for_same_keybinding() {
     );
 }
 
+#[test]
+fn can_define_sequence_of_actions_as_a_single_action() {
+    let config_contents = r#"
+        keybinds {
+            normal {
+                bind "Ctrl g" {
+                    Sequence {
+                        NewPane "Right"
+                        Sequence { SwitchToMode "Locked"; }
+                    }
+                }
+                bind "Ctrl h" { Sequence; }
+            }
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    let ctrl_g_normal_mode_action = config
+        .keybinds
+        .get_actions_for_key_in_mode(&InputMode::Normal, &Key::Ctrl('g'));
+    let ctrl_h_normal_mode_action = config
+        .keybinds
+        .get_actions_for_key_in_mode(&InputMode::Normal, &Key::Ctrl('h'));
+    assert_eq!(
+        ctrl_g_normal_mode_action,
+        Some(&vec![Action::Sequence(vec![
+            Action::NewPane(Some(Direction::Right), None),
+            Action::SwitchToMode(InputMode::Locked),
+        ])]),
+        "Nested sequence flattened into its parent"
+    );
+    assert_eq!(
+        ctrl_h_normal_mode_action,
+        Some(&vec![Action::NoOp]),
+        "Empty sequence is a NoOp"
+    );
+}
+
+#[test]
+fn can_define_scrolling_by_several_lines() {
+    let config_contents = r#"
+        keybinds {
+            scroll {
+                bind "u" { ScrollUpBy 5; }
+                bind "d" { ScrollDownBy 5; }
+            }
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    let u_in_scroll_mode = config
+        .keybinds
+        .get_actions_for_key_in_mode(&InputMode::Scroll, &Key::Char('u'));
+    let d_in_scroll_mode = config
+        .keybinds
+        .get_actions_for_key_in_mode(&InputMode::Scroll, &Key::Char('d'));
+    assert_eq!(
+        u_in_scroll_mode,
+        Some(&vec![Action::ScrollUpBy(5)]),
+        "Scroll up by lines defined"
+    );
+    assert_eq!(
+        d_in_scroll_mode,
+        Some(&vec![Action::ScrollDownBy(5)]),
+        "Scroll down by lines defined"
+    );
+}
+
 #[test]
 fn keybindings_bind_order_is_preserved() {
     let config_contents = r#"
@@ -516,6 +585,90 @@ fn keybindings_unbinds_happen_after_binds() {
     assert_eq!(z_in_pane_mode, None, "Key was ultimately unbound");
 }
 
+#[test]
+fn can_bind_show_mode_help() {
+    let config_contents = r#"
+        keybinds {
+            normal {
+                bind "?" { ShowModeHelp "Pane"; }
+            }
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    let question_mark_in_normal_mode = config
+        .keybinds
+        .get_actions_for_key_in_mode(&InputMode::Normal, &Key::Char('?'));
+    assert_eq!(
+        question_mark_in_normal_mode,
+        Some(&vec![Action::ShowModeHelp(InputMode::Pane)]),
+        "ShowModeHelp parsed with its mode"
+    );
+}
+
+#[test]
+fn name_input_of_a_lone_zero_clears_the_name() {
+    let config_contents = r#"
+        keybinds {
+            normal {
+                bind "c" { SwitchToMode "RenamePane"; PaneNameInput 0; }
+                bind "t" { SwitchToMode "RenameTab"; TabNameInput 0; }
+            }
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    assert_eq!(
+        config
+            .keybinds
+            .get_actions_for_key_in_mode(&InputMode::Normal, &Key::Char('c')),
+        Some(&vec![
+            Action::SwitchToMode(InputMode::RenamePane),
+            Action::ClearPaneName
+        ]),
+        "old PaneNameInput 0 binding clears the pane name"
+    );
+    assert_eq!(
+        config
+            .keybinds
+            .get_actions_for_key_in_mode(&InputMode::Normal, &Key::Char('t')),
+        Some(&vec![
+            Action::SwitchToMode(InputMode::RenameTab),
+            Action::ClearTabName
+        ]),
+        "old TabNameInput 0 binding clears the tab name"
+    );
+}
+
+#[test]
+fn can_bind_tab_tour() {
+    let config_contents = r#"
+        keybinds {
+            normal {
+                bind "t" { StartTabTour "2000 1 2 3"; }
+            }
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    let t_in_normal_mode = config
+        .keybinds
+        .get_actions_for_key_in_mode(&InputMode::Normal, &Key::Char('t'));
+    assert_eq!(
+        t_in_normal_mode,
+        Some(&vec![Action::StartTabTour(vec![1, 2, 3], 2000)]),
+        "StartTabTour parsed with its delay and tab indices"
+    );
+    let config_contents = r#"
+        keybinds {
+            normal {
+                bind "t" { StartTabTour "2000"; }
+            }
+        }
+    "#;
+    assert!(
+        Config::from_kdl(config_contents, None).is_err(),
+        "a tour without tabs is an error"
+    );
+}
+
 #[test]
 fn error_received_on_unknown_input_mode() {
     let config_contents = r#"
@@ -542,3 +695,289 @@ fn error_received_on_unknown_key_instruction() {
     let config_error = Config::from_kdl(config_contents, None).unwrap_err();
     assert_snapshot!(format!("{:?}", config_error));
 }
+
+#[test]
+fn actions_serialized_to_kdl_are_parsed_back_into_the_same_actions() {
+    let search_options = [
+        SearchOption::CaseSensitivity,
+        SearchOption::WholeWord,
+        SearchOption::Wrap,
+        SearchOption::Regex,
+        SearchOption::VisibleOnly,
+    ];
+    let mut actions = vec![
+        Action::Quit,
+        Action::Write(vec![27, 91, 65]),
+        Action::Write(vec![]),
+        Action::WriteChars("echo \"hi\" \\ there\n".into()),
+        Action::PasteText("pasted".into()),
+        Action::CopyThrough("sort | uniq".into()),
+        Action::SearchToQuickfix(vec![102, 111, 111]),
+        Action::SearchJumpToMatch(12),
+        Action::DetachWhenIdle(600),
+        Action::MoveFloatingPaneTo { x: 10, y: 5 },
+        Action::ResizeFloatingPaneTo { cols: 80, rows: 20 },
+        Action::SwitchToModeTimed(InputMode::Locked, 2000),
+        Action::ShowModeHelp(InputMode::Pane),
+        Action::StartTabTour(vec![1, 3, 2], 1500),
+        Action::Resize(data::Resize::Increase, None),
+        Action::Resize(data::Resize::Decrease, Some(Direction::Up)),
+        Action::FocusNextPane,
+        Action::FocusPreviousPane,
+        Action::FocusNextFloatingPane,
+        Action::FocusPreviousFloatingPane,
+        Action::SwitchFocus,
+        Action::MoveFocus(Direction::Left),
+        Action::MoveFocusOrTab(Direction::Right),
+        Action::CycleFocusByCommand("ssh".into()),
+        Action::MovePane(None),
+        Action::MovePane(Some(Direction::Down)),
+        Action::MovePaneBackwards,
+        Action::RotatePanesForward,
+        Action::RotatePanesBackward,
+        Action::ClearScreen,
+        Action::ClearScrollback,
+        Action::SelectAll(false),
+        Action::SelectAll(true),
+        Action::EnterResizeLoop,
+        Action::ExitResizeLoop,
+        Action::SetScrollbackLines(5000),
+        Action::DumpScreen("/tmp/dump".into(), false, false, false),
+        Action::DumpScreenSvg("/tmp/dump.svg".into(), false),
+        Action::DumpScreenJsonl("/tmp/dump.jsonl".into(), false),
+        Action::DumpScreenHtml("/tmp/dump.html".into(), false),
+        Action::EditScrollback,
+        Action::ScrollUp,
+        Action::ScrollUpBy(5),
+        Action::ScrollDown,
+        Action::ScrollDownBy(3),
+        Action::ScrollToBottom,
+        Action::ScrollToTop,
+        Action::ScrollToLine(1200),
+        Action::PageScrollUp,
+        Action::PageScrollDown,
+        Action::HalfPageScrollUp,
+        Action::HalfPageScrollDown,
+        Action::MoveToPreviousPrompt,
+        Action::MoveToNextPrompt,
+        Action::TogglePaneLineWrap,
+        Action::ToggleFocusFullscreen,
+        Action::TogglePaneFrames,
+        Action::ToggleFocusedPaneFrame,
+        Action::ToggleRuler,
+        Action::ToggleZenMode,
+        Action::ToggleActiveSyncTab,
+        Action::ToggleActiveSyncSession,
+        Action::RestartPaneCommand,
+        Action::NewPane(None, None),
+        Action::NewPane(Some(Direction::Right), None),
+        Action::DuplicatePane(None),
+        Action::DuplicatePane(Some(Direction::Down)),
+        Action::DuplicateFloatingPane,
+        Action::TogglePaneEmbedOrFloating,
+        Action::EmbedAllFloatingPanes,
+        Action::FloatAllTiledPanes,
+        Action::PeekPaneFloating,
+        Action::TogglePanePinned,
+        Action::StopTabTour,
+        Action::FocusMostRecentlyActivePane,
+        Action::ToggleDefaultSplitDirection,
+        Action::BalancePanes,
+        Action::BalancePanesAxis(true),
+        Action::BalancePanesAxis(false),
+        Action::ToggleFloatingPanes,
+        Action::CloseFocus,
+        Action::CloseFocusSmart,
+        Action::PaneNameInput(vec![97, 98]),
+        Action::ClearPaneName,
+        Action::UndoRenamePane,
+        Action::NewTab(None, vec![], None, None, None, false, false),
+        Action::NewTab(None, vec![], None, None, None, true, false),
+        Action::NewTab(None, vec![], None, None, Some("logs".into()), true, false),
+        Action::SetClientLayout("/tmp/layout.kdl".into()),
+        Action::SetModeReportSocket("/tmp/modes.sock".into()),
+        Action::NoOp,
+        Action::GoToNextTab,
+        Action::GoToPreviousTab,
+        Action::MoveTabLeft,
+        Action::MoveTabRight,
+        Action::MoveTabToIndex(2),
+        Action::BreakPane,
+        Action::BreakPaneLeft,
+        Action::BreakPaneRight,
+        Action::MovePaneToTab(2),
+        Action::ToggleOverlayTab(2),
+        Action::GoToSwapLayout(1),
+        Action::ApplySwapLayoutByName("horizontal".into()),
+        Action::CloseTab,
+        Action::TogglePinTab,
+        Action::GoToTab(3),
+        Action::SetRepeatCount(4),
+        Action::ToggleTab,
+        Action::RestoreTabFocus,
+        Action::TabNameInput(vec![99]),
+        Action::ClearTabName,
+        Action::UndoRenameTab,
+        Action::Run(RunCommandAction {
+            command: "htop".into(),
+            ..Default::default()
+        }),
+        Action::Run(RunCommandAction {
+            command: "tail".into(),
+            args: vec!["-f".into(), "/var/log/syslog".into()],
+            cwd: Some("/var/log".into()),
+            direction: Some(Direction::Down),
+            hold_on_close: true,
+            hold_on_start: true,
+            ..Default::default()
+        }),
+        Action::Detach,
+        Action::LaunchOrFocusPlugin(
+            RunPlugin {
+                _allow_exec_host_cmd: false,
+                location: RunPluginLocation::Zellij(PluginTag::new("strider")),
+                configuration: Default::default(),
+            },
+            true,
+        ),
+        Action::LaunchOrFocusPlugin(
+            RunPlugin {
+                _allow_exec_host_cmd: false,
+                location: RunPluginLocation::File("/path/to/plugin.wasm".into()),
+                configuration: Default::default(),
+            },
+            false,
+        ),
+        Action::Copy,
+        Action::CopyNormalized,
+        Action::CopyLastCommandOutput,
+        Action::Confirm,
+        Action::Deny,
+        Action::SearchInput(vec![100]),
+        Action::Search(SearchDirection::Up),
+        Action::Search(SearchDirection::Down),
+        Action::ToggleMouseMode,
+        Action::PreviousSwapLayout,
+        Action::NextSwapLayout,
+        Action::Sequence(vec![
+            Action::NewPane(None, None),
+            Action::ToggleFloatingPanes,
+        ]),
+    ];
+    actions.extend(InputMode::iter().map(Action::SwitchToMode));
+    actions.extend(search_options.into_iter().map(Action::SearchToggleOption));
+
+    for action in actions {
+        let kdl_action = action
+            .to_kdl()
+            .unwrap_or_else(|| panic!("{:?} was not serialized", action));
+        let config_contents = format!(
+            r#"
+            keybinds {{
+                normal {{
+                    bind "a" {{ {}; }}
+                }}
+            }}
+            "#,
+            kdl_action
+        );
+        let config = Config::from_kdl(&config_contents, None)
+            .unwrap_or_else(|e| panic!("failed to parse {}: {:?}", kdl_action, e));
+        let parsed_actions = config
+            .keybinds
+            .get_actions_for_key_in_mode(&InputMode::Normal, &Key::Char('a'))
+            .unwrap_or_else(|| panic!("{} was not bound", kdl_action));
+        assert_eq!(
+            parsed_actions.len(),
+            1,
+            "{} was parsed into one action",
+            kdl_action
+        );
+        assert!(
+            parsed_actions[0].shallow_eq(&action),
+            "{} was parsed back into {:?}, not {:?}",
+            kdl_action,
+            parsed_actions[0],
+            action
+        );
+    }
+}
+
+#[test]
+fn actions_that_cannot_be_configured_are_not_serialized_to_kdl() {
+    assert_eq!(Action::GoToTab(256).to_kdl(), None);
+    assert_eq!(
+        Action::DumpScreen("/tmp/dump".into(), true, false, false).to_kdl(),
+        None
+    );
+    assert_eq!(Action::LeftClick(Position::new(1, 1)).to_kdl(), None);
+    assert_eq!(
+        Action::NewPane(None, Some("logs".to_owned())).to_kdl(),
+        None
+    );
+    assert_eq!(Action::QueryTabNames.to_kdl(), None);
+    assert_eq!(Action::RenameSession("new-name".to_owned()).to_kdl(), None);
+}
+
+#[test]
+fn input_carrying_actions_are_shallow_eq_regardless_of_their_input() {
+    assert!(Action::PaneNameInput(vec![97]).shallow_eq(&Action::PaneNameInput(vec![98, 99])));
+    assert!(Action::TabNameInput(vec![0]).shallow_eq(&Action::TabNameInput(vec![97])));
+    assert!(Action::SearchInput(vec![97]).shallow_eq(&Action::SearchInput(vec![])));
+    assert!(Action::Write(vec![1]).shallow_eq(&Action::Write(vec![2])));
+    assert!(!Action::PaneNameInput(vec![97]).shallow_eq(&Action::TabNameInput(vec![97])));
+}
+
+#[test]
+fn actions_with_different_arguments_are_not_shallow_eq() {
+    assert!(!Action::GoToTab(1).shallow_eq(&Action::GoToTab(2)));
+    assert!(Action::GoToTab(1).shallow_eq(&Action::GoToTab(1)));
+    assert!(!Action::MoveFocus(Direction::Left).shallow_eq(&Action::MoveFocus(Direction::Right)));
+}
+
+#[test]
+fn actions_are_parsed_from_strings() {
+    assert_eq!("Quit".parse(), Ok(Action::Quit));
+    assert_eq!("  ToggleZenMode ".parse(), Ok(Action::ToggleZenMode));
+    assert_eq!("Clear".parse(), Ok(Action::ClearScreen));
+    assert_eq!(
+        "MoveFocus Left".parse(),
+        Ok(Action::MoveFocus(Direction::Left))
+    );
+    assert_eq!(
+        "Resize Increase Up".parse(),
+        Ok(Action::Resize(data::Resize::Increase, Some(Direction::Up)))
+    );
+    assert_eq!(
+        "Resize -".parse(),
+        Ok(Action::Resize(data::Resize::Decrease, None))
+    );
+    assert_eq!("GoToTab 3".parse(), Ok(Action::GoToTab(3)));
+    assert_eq!(
+        "SwitchToMode locked".parse(),
+        Ok(Action::SwitchToMode(InputMode::Locked))
+    );
+}
+
+#[test]
+fn unknown_or_malformed_actions_are_not_parsed_from_strings() {
+    for malformed_action in [
+        "",
+        "Frobnicate",
+        "Quit now",
+        "MoveFocus",
+        "MoveFocus Sideways",
+        "MoveFocus Left Right",
+        "Resize Up",
+        "Resize Bigger",
+        "GoToTab 0",
+        "GoToTab first",
+        "SwitchToMode Sleepy",
+    ] {
+        assert!(
+            malformed_action.parse::<Action>().is_err(),
+            "{:?} was not parsed",
+            malformed_action
+        );
+    }
+}
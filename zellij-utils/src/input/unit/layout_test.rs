@@ -515,6 +515,7 @@ fn layout_with_plugin_panes() {
                         run: Some(Run::Plugin(RunPlugin {
                             location: RunPluginLocation::Zellij(PluginTag::new("tab-bar")),
                             _allow_exec_host_cmd: false,
+                            configuration: Default::default(),
                         })),
                         ..Default::default()
                     },
@@ -524,6 +525,7 @@ fn layout_with_plugin_panes() {
                                 "/path/to/my/plugin.wasm",
                             )),
                             _allow_exec_host_cmd: false,
+                            configuration: Default::default(),
                         })),
                         ..Default::default()
                     },
@@ -666,6 +668,29 @@ fn layout_with_focused_tab() {
     assert_eq!(layout, expected_layout);
 }
 
+#[test]
+fn layout_with_pinned_tabs() {
+    let kdl_layout = r#"
+        layout {
+            tab pinned=true
+            tab
+            tab pinned=true
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    let expected_layout = Layout {
+        tabs: vec![
+            (None, TiledPaneLayout::default(), vec![]),
+            (None, TiledPaneLayout::default(), vec![]),
+            (None, TiledPaneLayout::default(), vec![]),
+        ],
+        template: Some((TiledPaneLayout::default(), vec![])),
+        pinned_tab_indices: vec![0, 2],
+        ..Default::default()
+    };
+    assert_eq!(layout, expected_layout);
+}
+
 #[test]
 fn layout_with_tab_templates() {
     let kdl_layout = r#"
@@ -2016,6 +2041,7 @@ fn run_plugin_location_parsing() {
                         run: Some(Run::Plugin(RunPlugin {
                             _allow_exec_host_cmd: false,
                             location: RunPluginLocation::Zellij(PluginTag::new("tab-bar")),
+                            configuration: Default::default(),
                         })),
                         ..Default::default()
                     },
@@ -2025,6 +2051,7 @@ fn run_plugin_location_parsing() {
                             location: RunPluginLocation::File(PathBuf::from(
                                 "/path/to/my/plugin.wasm",
                             )),
+                            configuration: Default::default(),
                         })),
                         ..Default::default()
                     },
@@ -2032,6 +2059,7 @@ fn run_plugin_location_parsing() {
                         run: Some(Run::Plugin(RunPlugin {
                             _allow_exec_host_cmd: false,
                             location: RunPluginLocation::File(PathBuf::from("plugin.wasm")),
+                            configuration: Default::default(),
                         })),
                         ..Default::default()
                     },
@@ -2041,6 +2069,7 @@ fn run_plugin_location_parsing() {
                             location: RunPluginLocation::File(PathBuf::from(
                                 "relative/with space/plugin.wasm",
                             )),
+                            configuration: Default::default(),
                         })),
                         ..Default::default()
                     },
@@ -2050,6 +2079,7 @@ fn run_plugin_location_parsing() {
                             location: RunPluginLocation::File(PathBuf::from(
                                 "/absolute/with space/plugin.wasm",
                             )),
+                            configuration: Default::default(),
                         })),
                         ..Default::default()
                     },
@@ -2059,6 +2089,7 @@ fn run_plugin_location_parsing() {
                             location: RunPluginLocation::File(PathBuf::from(
                                 "c:/absolute/windows/plugin.wasm",
                             )),
+                            configuration: Default::default(),
                         })),
                         ..Default::default()
                     },
@@ -2128,3 +2159,66 @@ fn env_var_missing() {
     let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None);
     assert!(layout.is_err(), "invalid env var lookup should fail");
 }
+
+#[test]
+fn layout_with_tabs_round_trips_through_kdl() {
+    let kdl_layout = r#"
+        layout {
+            tab name="my \"first\" tab" split_direction="vertical" {
+                pane size=1 borderless=true {
+                    plugin location="zellij:tab-bar"
+                }
+                pane split_direction="vertical" {
+                    pane command="htop" cwd="/tmp" size="60%" focus=true {
+                        args "-d" "10"
+                    }
+                    pane cwd="/home"
+                }
+                pane edit="/tmp/my_file.txt"
+                floating_panes {
+                    pane command="tail" close_on_exit=true start_suspended=true {
+                        x 10
+                        y "20%"
+                        width 50
+                        height 20
+                        args "-f" "/var/log/syslog"
+                    }
+                    pane {
+                        plugin location="file:/path/to/my/plugin.wasm"
+                    }
+                }
+            }
+            tab name="second tab" focus=true pinned=true {
+                pane stacked=true {
+                    pane
+                    pane expanded=true
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    let serialized_layout = layout.to_kdl();
+    let reparsed_layout =
+        Layout::from_kdl(&serialized_layout, "layout_file_name".into(), None, None).unwrap();
+    assert_eq!(layout, reparsed_layout, "{}", serialized_layout);
+}
+
+#[test]
+fn layout_without_tabs_round_trips_through_kdl() {
+    let kdl_layout = r#"
+        layout {
+            pane split_direction="vertical" {
+                pane
+                pane command="htop"
+            }
+            floating_panes {
+                pane x="10%" y=1 width="50%" height=10
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    let serialized_layout = layout.to_kdl();
+    let reparsed_layout =
+        Layout::from_kdl(&serialized_layout, "layout_file_name".into(), None, None).unwrap();
+    assert_eq!(layout, reparsed_layout, "{}", serialized_layout);
+}
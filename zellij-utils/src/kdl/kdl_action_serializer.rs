@@ -0,0 +1,321 @@
+use crate::input::actions::Action;
+use crate::input::command::RunCommandAction;
+use crate::input::layout::{RunPlugin, RunPluginLocation};
+
+// we use debug print for strings and paths throughout so that quotes and backslashes will be
+// escaped
+
+impl Action {
+    /// Serialize this action to the KDL node it would be bound with in the config (eg.
+    /// `SwitchToMode "Locked"`), which the config parser reads back into the same action.
+    ///
+    /// Returns `None` for actions that cannot be expressed in the config, such as mouse events,
+    /// or whose config form loses information, such as a `NewTab` with an already loaded layout.
+    pub fn to_kdl(&self) -> Option<String> {
+        let kdl_action = match self {
+            Action::Quit => "Quit".to_owned(),
+            Action::FocusNextPane => "FocusNextPane".to_owned(),
+            Action::FocusPreviousPane => "FocusPreviousPane".to_owned(),
+            Action::FocusNextFloatingPane => "FocusNextFloatingPane".to_owned(),
+            Action::FocusPreviousFloatingPane => "FocusPreviousFloatingPane".to_owned(),
+            Action::SwitchFocus => "SwitchFocus".to_owned(),
+            Action::EditScrollback => "EditScrollback".to_owned(),
+            Action::ScrollUp => "ScrollUp".to_owned(),
+            Action::ScrollDown => "ScrollDown".to_owned(),
+            Action::ScrollToBottom => "ScrollToBottom".to_owned(),
+            Action::ScrollToTop => "ScrollToTop".to_owned(),
+            Action::PageScrollUp => "PageScrollUp".to_owned(),
+            Action::PageScrollDown => "PageScrollDown".to_owned(),
+            Action::MoveToPreviousPrompt => "MoveToPreviousPrompt".to_owned(),
+            Action::MoveToNextPrompt => "MoveToNextPrompt".to_owned(),
+            Action::TogglePaneLineWrap => "TogglePaneLineWrap".to_owned(),
+            Action::HalfPageScrollUp => "HalfPageScrollUp".to_owned(),
+            Action::HalfPageScrollDown => "HalfPageScrollDown".to_owned(),
+            Action::ToggleFocusFullscreen => "ToggleFocusFullscreen".to_owned(),
+            Action::TogglePaneFrames => "TogglePaneFrames".to_owned(),
+            Action::ToggleFocusedPaneFrame => "ToggleFocusedPaneFrame".to_owned(),
+            Action::ToggleRuler => "ToggleRuler".to_owned(),
+            Action::ToggleZenMode => "ToggleZenMode".to_owned(),
+            Action::ToggleActiveSyncTab => "ToggleActiveSyncTab".to_owned(),
+            Action::ToggleActiveSyncSession => "ToggleActiveSyncSession".to_owned(),
+            Action::RestartPaneCommand => "RestartPaneCommand".to_owned(),
+            Action::TogglePaneEmbedOrFloating => "TogglePaneEmbedOrFloating".to_owned(),
+            Action::EmbedAllFloatingPanes => "EmbedAllFloatingPanes".to_owned(),
+            Action::FloatAllTiledPanes => "FloatAllTiledPanes".to_owned(),
+            Action::StopTabTour => "StopTabTour".to_owned(),
+            Action::FocusMostRecentlyActivePane => "FocusMostRecentlyActivePane".to_owned(),
+            Action::ToggleDefaultSplitDirection => "ToggleDefaultSplitDirection".to_owned(),
+            Action::PeekPaneFloating => "PeekPaneFloating".to_owned(),
+            Action::TogglePanePinned => "TogglePanePinned".to_owned(),
+            Action::BalancePanes => "BalancePanes".to_owned(),
+            Action::ToggleFloatingPanes => "ToggleFloatingPanes".to_owned(),
+            Action::CloseFocus => "CloseFocus".to_owned(),
+            Action::CloseFocusSmart => "CloseFocusSmart".to_owned(),
+            Action::ClearPaneName => "ClearPaneName".to_owned(),
+            Action::UndoRenamePane => "UndoRenamePane".to_owned(),
+            Action::NoOp => "NoOp".to_owned(),
+            Action::GoToNextTab => "GoToNextTab".to_owned(),
+            Action::GoToPreviousTab => "GoToPreviousTab".to_owned(),
+            Action::MoveTabLeft => "MoveTabLeft".to_owned(),
+            Action::MoveTabRight => "MoveTabRight".to_owned(),
+            Action::BreakPane => "BreakPane".to_owned(),
+            Action::BreakPaneLeft => "BreakPaneLeft".to_owned(),
+            Action::BreakPaneRight => "BreakPaneRight".to_owned(),
+            Action::CloseTab => "CloseTab".to_owned(),
+            Action::TogglePinTab => "TogglePinTab".to_owned(),
+            Action::ToggleTab => "ToggleTab".to_owned(),
+            Action::RestoreTabFocus => "RestoreTabFocus".to_owned(),
+            Action::ClearTabName => "ClearTabName".to_owned(),
+            Action::UndoRenameTab => "UndoRenameTab".to_owned(),
+            Action::Detach => "Detach".to_owned(),
+            Action::MoveFloatingPaneTo { x, y } => format!("MoveFloatingPaneTo \"{} {}\"", x, y),
+            Action::ResizeFloatingPaneTo { cols, rows } => {
+                format!("ResizeFloatingPaneTo \"{} {}\"", cols, rows)
+            },
+            Action::DetachWhenIdle(timeout_secs) => format!("DetachWhenIdle \"{}\"", timeout_secs),
+            Action::SetScrollbackLines(lines) => format!("SetScrollbackLines \"{}\"", lines),
+            Action::ScrollToLine(line) => format!("ScrollToLine \"{}\"", line),
+            Action::SearchJumpToMatch(match_index) => {
+                format!("SearchJumpToMatch \"{}\"", match_index)
+            },
+            Action::Copy => "Copy".to_owned(),
+            Action::CopyNormalized => "CopyNormalized".to_owned(),
+            Action::SelectAll(false) => "SelectAll".to_owned(),
+            Action::SelectAll(true) => "SelectAll \"viewport\"".to_owned(),
+            Action::CopyLastCommandOutput => "CopyLastCommandOutput".to_owned(),
+            Action::Confirm => "Confirm".to_owned(),
+            Action::Deny => "Deny".to_owned(),
+            Action::ToggleMouseMode => "ToggleMouseMode".to_owned(),
+            Action::PreviousSwapLayout => "PreviousSwapLayout".to_owned(),
+            Action::NextSwapLayout => "NextSwapLayout".to_owned(),
+            Action::ClearScreen => "Clear".to_owned(),
+            Action::ClearScrollback => "ClearScrollback".to_owned(),
+            Action::EnterResizeLoop => "EnterResizeLoop".to_owned(),
+            Action::ExitResizeLoop => "ExitResizeLoop".to_owned(),
+            Action::MovePaneBackwards => "MovePaneBackwards".to_owned(),
+            Action::RotatePanesForward => "RotatePanesForward".to_owned(),
+            Action::RotatePanesBackward => "RotatePanesBackward".to_owned(),
+            Action::Write(bytes) => stringify_bytes("Write", bytes),
+            Action::PaneNameInput(bytes) => stringify_bytes("PaneNameInput", bytes),
+            Action::TabNameInput(bytes) => stringify_bytes("TabNameInput", bytes),
+            Action::SearchInput(bytes) => stringify_bytes("SearchInput", bytes),
+            Action::SearchToQuickfix(bytes) => stringify_bytes("SearchToQuickfix", bytes),
+            Action::GoToTab(tab_index) => stringify_byte("GoToTab", *tab_index as usize)?,
+            Action::MoveTabToIndex(tab_index) => {
+                stringify_byte("MoveTabToIndex", *tab_index as usize)?
+            },
+            Action::MovePaneToTab(tab_index) => {
+                stringify_byte("MovePaneToTab", *tab_index as usize)?
+            },
+            Action::ToggleOverlayTab(tab_index) => {
+                stringify_byte("ToggleOverlayTab", *tab_index as usize)?
+            },
+            Action::GoToSwapLayout(index) => stringify_byte("GoToSwapLayout", *index)?,
+            Action::SetRepeatCount(count) => stringify_byte("SetRepeatCount", *count as usize)?,
+            Action::ScrollUpBy(lines) => stringify_byte("ScrollUpBy", *lines)?,
+            Action::ScrollDownBy(lines) => stringify_byte("ScrollDownBy", *lines)?,
+            Action::WriteChars(chars) => format!("WriteChars {:?}", chars),
+            Action::PasteText(text) => format!("PasteText {:?}", text),
+            Action::CopyThrough(command) => format!("CopyThrough {:?}", command),
+            Action::CycleFocusByCommand(command) => format!("CycleFocusByCommand {:?}", command),
+            Action::ApplySwapLayoutByName(name) => format!("ApplySwapLayoutByName {:?}", name),
+            Action::SwitchToMode(input_mode) => format!("SwitchToMode \"{:?}\"", input_mode),
+            // the config needs at least one tab to tour
+            Action::StartTabTour(tab_indices, _) if tab_indices.is_empty() => return None,
+            Action::StartTabTour(tab_indices, delay_ms) => {
+                let mut stringified = format!("StartTabTour \"{}", delay_ms);
+                for tab_index in tab_indices {
+                    stringified.push_str(&format!(" {}", tab_index));
+                }
+                stringified.push('"');
+                stringified
+            },
+            Action::ShowModeHelp(input_mode) => format!("ShowModeHelp \"{:?}\"", input_mode),
+            Action::SwitchToModeTimed(input_mode, timeout_ms) => {
+                format!("SwitchToModeTimed \"{:?} {}\"", input_mode, timeout_ms)
+            },
+            Action::Resize(resize, Some(direction)) => {
+                format!("Resize \"{:?} {:?}\"", resize, direction)
+            },
+            Action::Resize(resize, None) => format!("Resize \"{:?}\"", resize),
+            Action::MoveFocus(direction) => format!("MoveFocus \"{:?}\"", direction),
+            Action::MoveFocusOrTab(direction) => format!("MoveFocusOrTab \"{:?}\"", direction),
+            Action::MovePane(Some(direction)) => format!("MovePane \"{:?}\"", direction),
+            Action::MovePane(None) => "MovePane".to_owned(),
+            Action::NewPane(Some(direction), None) => format!("NewPane \"{:?}\"", direction),
+            Action::NewPane(None, None) => "NewPane".to_owned(),
+            Action::DuplicatePane(Some(direction)) => {
+                format!("DuplicatePane \"{:?}\"", direction)
+            },
+            Action::DuplicatePane(None) => "DuplicatePane".to_owned(),
+            Action::DuplicateFloatingPane => "DuplicateFloatingPane".to_owned(),
+            Action::DumpScreen(path, false, false, false) => format!("DumpScreen {:?}", path),
+            Action::DumpScreenSvg(path, false) => format!("DumpScreenSvg {:?}", path),
+            Action::DumpScreenJsonl(path, false) => format!("DumpScreenJsonl {:?}", path),
+            Action::DumpScreenHtml(path, false) => format!("DumpScreenHtml {:?}", path),
+            Action::SetClientLayout(layout_path) => format!("SetClientLayout {:?}", layout_path),
+            Action::SetModeReportSocket(socket_path) => {
+                format!("SetModeReportSocket {:?}", socket_path)
+            },
+            Action::Search(search_direction) => format!("Search \"{:?}\"", search_direction),
+            Action::SearchToggleOption(search_option) => {
+                format!("SearchToggleOption \"{:?}\"", search_option)
+            },
+            Action::BalancePanesAxis(true) => "BalancePanesAxis \"vertical\"".to_owned(),
+            Action::BalancePanesAxis(false) => "BalancePanesAxis \"horizontal\"".to_owned(),
+            // a tab with a layout is parsed by loading the layout, which we cannot refer back to
+            Action::NewTab(None, floating_panes, None, None, None, false, false)
+                if floating_panes.is_empty() =>
+            {
+                "NewTab".to_owned()
+            },
+            Action::NewTab(None, floating_panes, None, None, name, true, false)
+                if floating_panes.is_empty() =>
+            {
+                match name {
+                    Some(name) => format!("NewTab {{ name {:?}; empty true; }}", name),
+                    None => "NewTab { empty true; }".to_owned(),
+                }
+            },
+            Action::Run(run_command_action) => stringify_run(run_command_action)?,
+            Action::LaunchOrFocusPlugin(run_plugin, should_float) => {
+                stringify_launch_or_focus_plugin(run_plugin, *should_float)?
+            },
+            Action::Sequence(actions) => {
+                let actions = actions
+                    .iter()
+                    .map(|action| {
+                        action
+                            .to_kdl()
+                            .map(|kdl_action| format!("{}; ", kdl_action))
+                    })
+                    .collect::<Option<String>>()?;
+                format!("Sequence {{ {}}}", actions)
+            },
+            // the config has no way to name a pane, dump to stdout or keep the styles of a dump,
+            // or refer to an already loaded layout
+            Action::NewPane(_, Some(_))
+            | Action::DumpScreen(..)
+            | Action::DumpScreenSvg(..)
+            | Action::DumpScreenJsonl(..)
+            | Action::DumpScreenHtml(..)
+            | Action::NewTab(..) => return None,
+            // mouse events and the actions at a position of the screen they turn into
+            Action::LeftClick(_)
+            | Action::LeftDoubleClick(_)
+            | Action::LeftTripleClick(_)
+            | Action::RightClick(_)
+            | Action::MiddleClick(_)
+            | Action::LeftMouseRelease(_)
+            | Action::RightMouseRelease(_)
+            | Action::MiddleMouseRelease(_)
+            | Action::MouseHoldLeft(_)
+            | Action::MouseHoldRight(_)
+            | Action::MouseHoldMiddle(_)
+            | Action::ScrollUpAt(_)
+            | Action::ScrollDownAt(_)
+            | Action::CopyUrlUnderCursor(_)
+            | Action::OpenUrlUnderCursor(_) => return None,
+            // queries answer the cli client that sent them
+            Action::QueryTabNames
+            | Action::QueryPaneList(_)
+            | Action::QuerySearchMatchCount
+            | Action::QuerySessionVar(_)
+            | Action::QueryClipboardInfo
+            | Action::DumpLayout(_) => return None,
+            // these are only sent by the cli, plugins or the server itself
+            Action::WriteToPanesMatching(..)
+            | Action::SwitchModeForAllClients(_)
+            | Action::SetPaneSize(..)
+            | Action::SetPaneSizeConstraints(..)
+            | Action::ResizePaneToPercent { .. }
+            | Action::FocusPaneWithId(..)
+            | Action::FocusPaneByName(..)
+            | Action::FocusPaneAtIndex(_)
+            | Action::ToggleFullscreenForPane(_)
+            | Action::EditFile(..)
+            | Action::NewFloatingPane(..)
+            | Action::NewTiledPane(..)
+            | Action::NewPaneAtFraction(..)
+            | Action::GoToTabName(..)
+            | Action::GoToTabOfPane(_)
+            | Action::SkipConfirm(_)
+            | Action::SetSessionVar(..)
+            | Action::RenameSession(_)
+            | Action::NewTiledPluginPane(..)
+            | Action::NewFloatingPluginPane(..)
+            | Action::StartOrReloadPlugin(_)
+            | Action::Repeat(..)
+            | Action::ResetFloatingPaneGeometry(_) => return None,
+        };
+        Some(kdl_action)
+    }
+}
+
+fn stringify_bytes(action_name: &str, bytes: &[u8]) -> String {
+    let mut stringified = action_name.to_owned();
+    for byte in bytes {
+        stringified.push_str(&format!(" {}", byte));
+    }
+    stringified
+}
+
+// these arguments are parsed as a single byte, so larger values cannot be represented
+fn stringify_byte(action_name: &str, value: usize) -> Option<String> {
+    u8::try_from(value)
+        .ok()
+        .map(|byte| stringify_bytes(action_name, &[byte]))
+}
+
+fn stringify_run(run_command_action: &RunCommandAction) -> Option<String> {
+    if !run_command_action.watch_paths.is_empty()
+        || run_command_action.output_socket.is_some()
+        || run_command_action.pin_matching.is_some()
+        || run_command_action.cwd_from_focus
+        || run_command_action.no_frame
+        || run_command_action.capture_exit.is_some()
+        || run_command_action.fixed_pty_size.is_some()
+        || run_command_action.run_in_shell.is_some()
+        || !run_command_action.env.is_empty()
+    {
+        // these can only be given on the command line
+        return None;
+    }
+    let mut stringified = format!("Run {:?}", run_command_action.command);
+    for arg in &run_command_action.args {
+        stringified.push_str(&format!(" {:?}", arg));
+    }
+    let mut children = String::new();
+    if let Some(cwd) = &run_command_action.cwd {
+        children.push_str(&format!("cwd {:?}; ", cwd));
+    }
+    if let Some(direction) = &run_command_action.direction {
+        children.push_str(&format!("direction \"{:?}\"; ", direction));
+    }
+    if !run_command_action.hold_on_close {
+        children.push_str("close_on_exit true; ");
+    }
+    if run_command_action.hold_on_start {
+        children.push_str("start_suspended true; ");
+    }
+    if !children.is_empty() {
+        stringified.push_str(&format!(" {{ {}}}", children));
+    }
+    Some(stringified)
+}
+
+fn stringify_launch_or_focus_plugin(run_plugin: &RunPlugin, should_float: bool) -> Option<String> {
+    if run_plugin._allow_exec_host_cmd || !run_plugin.configuration.is_empty() {
+        // these can only be given in layouts
+        return None;
+    }
+    let location = match &run_plugin.location {
+        RunPluginLocation::File(path) => format!("file:{}", path.display()),
+        RunPluginLocation::Zellij(tag) => format!("zellij:{}", tag),
+    };
+    let mut stringified = format!("LaunchOrFocusPlugin {:?}", location);
+    if should_float {
+        stringified.push_str(" { floating true; }");
+    }
+    Some(stringified)
+}
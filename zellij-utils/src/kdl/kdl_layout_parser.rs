@@ -109,9 +109,11 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "y"
             || property_name == "width"
             || property_name == "height"
+            || property_name == "pinned"
     }
     fn is_a_valid_tab_property(&self, property_name: &str) -> bool {
         property_name == "focus"
+            || property_name == "pinned"
             || property_name == "name"
             || property_name == "split_direction"
             || property_name == "cwd"
@@ -308,6 +310,7 @@ impl<'a> KdlLayoutParser<'a> {
         Ok(Some(Run::Plugin(RunPlugin {
             _allow_exec_host_cmd,
             location,
+            configuration: Default::default(),
         })))
     }
     fn parse_args(&self, pane_node: &KdlNode) -> Result<Option<Vec<String>>, ConfigError> {
@@ -379,6 +382,14 @@ impl<'a> KdlLayoutParser<'a> {
                 cwd,
                 hold_on_close,
                 hold_on_start,
+                watch_paths: vec![],
+                output_socket: None,
+                pin_matching: None,
+                cwd_from_focus: false,
+                no_frame: false,
+                capture_exit: None,
+                env: BTreeMap::new(),
+                fixed_pty_size: None,
             }))),
             (None, Some(edit), Some(cwd)) => {
                 Ok(Some(Run::EditFile(cwd.join(edit), None, Some(cwd))))
@@ -510,6 +521,7 @@ impl<'a> KdlLayoutParser<'a> {
         let y = self.parse_percent_or_fixed(kdl_node, "y", true)?;
         let run = self.parse_command_plugin_or_edit_block(kdl_node)?;
         let focus = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "focus");
+        let pinned = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "pinned");
         let name = kdl_get_string_property_or_child_value_with_error!(kdl_node, "name")
             .map(|name| name.to_string());
         self.assert_no_mixed_children_and_properties(kdl_node)?;
@@ -521,7 +533,7 @@ impl<'a> KdlLayoutParser<'a> {
             y,
             run,
             focus,
-            ..Default::default()
+            pinned,
         })
     }
     fn insert_children_to_pane_template(
@@ -1042,6 +1054,7 @@ impl<'a> KdlLayoutParser<'a> {
         kdl_node: &KdlNode,
     ) -> Result<
         (
+            bool,
             bool,
             Option<String>,
             TiledPaneLayout,
@@ -1049,12 +1062,13 @@ impl<'a> KdlLayoutParser<'a> {
         ),
         ConfigError,
     > {
-        // (is_focused, Option<tab_name>, PaneLayout, Vec<FloatingPaneLayout>)
+        // (is_focused, is_pinned, Option<tab_name>, PaneLayout, Vec<FloatingPaneLayout>)
         self.assert_valid_tab_properties(kdl_node)?;
         let tab_name =
             kdl_get_string_property_or_child_value!(kdl_node, "name").map(|s| s.to_string());
         let tab_cwd = self.parse_path(kdl_node, "cwd")?;
         let is_focused = kdl_get_bool_property_or_child_value!(kdl_node, "focus").unwrap_or(false);
+        let is_pinned = kdl_get_bool_property_or_child_value!(kdl_node, "pinned").unwrap_or(false);
         let children_split_direction = self.parse_split_direction(kdl_node)?;
         let mut child_floating_panes = vec![];
         let children = match kdl_children_nodes!(kdl_node) {
@@ -1076,7 +1090,13 @@ impl<'a> KdlLayoutParser<'a> {
         if let Some(cwd_prefix) = &self.cwd_prefix(tab_cwd.as_ref())? {
             pane_layout.add_cwd_to_layout(&cwd_prefix);
         }
-        Ok((is_focused, tab_name, pane_layout, child_floating_panes))
+        Ok((
+            is_focused,
+            is_pinned,
+            tab_name,
+            pane_layout,
+            child_floating_panes,
+        ))
     }
     fn parse_child_pane_nodes_for_tab(
         &self,
@@ -1439,6 +1459,7 @@ impl<'a> KdlLayoutParser<'a> {
         tab_layout_kdl_node: &KdlNode,
     ) -> Result<
         (
+            bool,
             bool,
             Option<String>,
             TiledPaneLayout,
@@ -1446,11 +1467,12 @@ impl<'a> KdlLayoutParser<'a> {
         ),
         ConfigError,
     > {
-        // (is_focused, Option<tab_name>, PaneLayout, Vec<FloatingPaneLayout>)
+        // (is_focused, is_pinned, Option<tab_name>, PaneLayout, Vec<FloatingPaneLayout>)
         let tab_name =
             kdl_get_string_property_or_child_value!(kdl_node, "name").map(|s| s.to_string());
         let tab_cwd = self.parse_path(kdl_node, "cwd")?;
         let is_focused = kdl_get_bool_property_or_child_value!(kdl_node, "focus").unwrap_or(false);
+        let is_pinned = kdl_get_bool_property_or_child_value!(kdl_node, "pinned").unwrap_or(false);
         let children_split_direction = self.parse_split_direction(kdl_node)?;
         match kdl_children_nodes!(kdl_node) {
             Some(children) => {
@@ -1485,6 +1507,7 @@ impl<'a> KdlLayoutParser<'a> {
         tab_layout.external_children_index = None;
         Ok((
             is_focused,
+            is_pinned,
             tab_name,
             tab_layout,
             tab_template_floating_panes,
@@ -1939,7 +1962,7 @@ impl<'a> KdlLayoutParser<'a> {
             should_mark_external_children_index,
             &tab_template_kdl_node,
         )?;
-        Ok(layout.2)
+        Ok(layout.3)
     }
     fn populate_one_swap_floating_layout(
         &self,
@@ -1965,12 +1988,13 @@ impl<'a> KdlLayoutParser<'a> {
             should_mark_external_children_index,
             &tab_template_kdl_node,
         )?;
-        Ok(layout.3)
+        Ok(layout.4)
     }
     fn layout_with_tabs(
         &self,
         tabs: Vec<(Option<String>, TiledPaneLayout, Vec<FloatingPaneLayout>)>,
         focused_tab_index: Option<usize>,
+        pinned_tab_indices: Vec<usize>,
         swap_tiled_layouts: Vec<SwapTiledLayout>,
         swap_floating_layouts: Vec<SwapFloatingLayout>,
     ) -> Result<Layout, ConfigError> {
@@ -2036,6 +2060,7 @@ impl<'a> KdlLayoutParser<'a> {
         &mut self,
         child: &KdlNode,
         child_tabs: &mut Vec<(
+            bool,
             bool,
             Option<String>,
             TiledPaneLayout,
@@ -2279,7 +2304,7 @@ impl<'a> KdlLayoutParser<'a> {
         if !child_tabs.is_empty() {
             let has_more_than_one_focused_tab = child_tabs
                 .iter()
-                .filter(|(is_focused, _, _, _)| *is_focused)
+                .filter(|(is_focused, _, _, _, _)| *is_focused)
                 .count()
                 > 1;
             if has_more_than_one_focused_tab {
@@ -2291,12 +2316,24 @@ impl<'a> KdlLayoutParser<'a> {
             }
             let focused_tab_index = child_tabs
                 .iter()
-                .position(|(is_focused, _, _, _)| *is_focused);
+                .position(|(is_focused, _, _, _, _)| *is_focused);
+            let pinned_tab_indices = child_tabs
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, is_pinned, _, _, _))| *is_pinned)
+                .map(|(index, _)| index)
+                .collect();
             let child_tabs: Vec<(Option<String>, TiledPaneLayout, Vec<FloatingPaneLayout>)> =
                 child_tabs
                     .drain(..)
                     .map(
-                        |(_is_focused, tab_name, pane_layout, floating_panes_layout)| {
+                        |(
+                            _is_focused,
+                            _is_pinned,
+                            tab_name,
+                            pane_layout,
+                            floating_panes_layout,
+                        )| {
                             (tab_name, pane_layout, floating_panes_layout)
                         },
                     )
@@ -2304,6 +2341,7 @@ impl<'a> KdlLayoutParser<'a> {
             self.layout_with_tabs(
                 child_tabs,
                 focused_tab_index,
+                pinned_tab_indices,
                 swap_tiled_layouts,
                 swap_floating_layouts,
             )
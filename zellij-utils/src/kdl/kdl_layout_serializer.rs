@@ -0,0 +1,216 @@
+use crate::input::layout::{
+    FloatingPaneLayout, Layout, PercentOrFixed, Run, RunPluginLocation, SplitDirection, SplitSize,
+    TiledPaneLayout,
+};
+
+// we use debug print for strings and paths throughout so that quotes and backslashes will be
+// escaped
+
+impl Layout {
+    /// Serialize this layout to KDL that [`Layout::from_kdl`] parses back into the same layout.
+    ///
+    /// Only the tabs (or the template, if there are no tabs) are serialized, pane and tab
+    /// templates as well as swap layouts are not.
+    pub fn to_kdl(&self) -> String {
+        let indentation = "    ";
+        let mut kdl_layout = String::from("layout {\n");
+        if self.tabs.is_empty() {
+            if let Some((tiled_panes, floating_panes)) = &self.template {
+                kdl_layout.push_str(&stringify_tiled_pane_children(tiled_panes, indentation));
+                kdl_layout.push_str(&stringify_floating_panes(floating_panes, indentation));
+            }
+        }
+        for (tab_index, (tab_name, tiled_panes, floating_panes)) in self.tabs.iter().enumerate() {
+            let mut tab_line = String::from("tab");
+            if let Some(tab_name) = tab_name {
+                tab_line.push_str(&format!(" name={:?}", tab_name));
+            }
+            if self.focused_tab_index == Some(tab_index) {
+                tab_line.push_str(" focus=true");
+            }
+            if self.pinned_tab_indices.contains(&tab_index) {
+                tab_line.push_str(" pinned=true");
+            }
+            if tiled_panes.children_split_direction == SplitDirection::Vertical {
+                tab_line.push_str(" split_direction=\"vertical\"");
+            }
+            let child_indentation = format!("{}    ", indentation);
+            let tab_children = format!(
+                "{}{}",
+                stringify_tiled_pane_children(tiled_panes, &child_indentation),
+                stringify_floating_panes(floating_panes, &child_indentation),
+            );
+            kdl_layout.push_str(&stringify_node(&tab_line, &tab_children, indentation));
+        }
+        kdl_layout.push_str("}\n");
+        kdl_layout
+    }
+}
+
+fn stringify_node(node_line: &str, children: &str, indentation: &str) -> String {
+    if children.is_empty() {
+        format!("{}{}\n", indentation, node_line)
+    } else {
+        format!(
+            "{}{} {{\n{}{}}}\n",
+            indentation, node_line, children, indentation
+        )
+    }
+}
+
+fn stringify_tiled_pane_children(pane_layout: &TiledPaneLayout, indentation: &str) -> String {
+    let mut stringified = String::new();
+    for (index, child) in pane_layout.children.iter().enumerate() {
+        if pane_layout.external_children_index == Some(index) {
+            stringified.push_str(&format!("{}children\n", indentation));
+        }
+        stringified.push_str(&stringify_tiled_pane(child, indentation));
+    }
+    if pane_layout.external_children_index == Some(pane_layout.children.len()) {
+        stringified.push_str(&format!("{}children\n", indentation));
+    }
+    stringified
+}
+
+fn stringify_tiled_pane(pane_layout: &TiledPaneLayout, indentation: &str) -> String {
+    let child_indentation = format!("{}    ", indentation);
+    let mut pane_line = String::from("pane");
+    if let Some(name) = &pane_layout.name {
+        pane_line.push_str(&format!(" name={:?}", name));
+    }
+    match pane_layout.split_size {
+        Some(SplitSize::Percent(percent)) => pane_line.push_str(&format!(" size=\"{}%\"", percent)),
+        Some(SplitSize::Fixed(fixed)) => pane_line.push_str(&format!(" size={}", fixed)),
+        None => {},
+    }
+    if pane_layout.borderless {
+        pane_line.push_str(" borderless=true");
+    }
+    if let Some(focus) = pane_layout.focus {
+        pane_line.push_str(&format!(" focus={}", focus));
+    }
+    if pane_layout.children_split_direction == SplitDirection::Vertical
+        && (!pane_layout.children.is_empty() || pane_layout.external_children_index.is_some())
+    {
+        pane_line.push_str(" split_direction=\"vertical\"");
+    }
+    if pane_layout.children_are_stacked {
+        pane_line.push_str(" stacked=true");
+    }
+    if pane_layout.is_expanded_in_stack {
+        pane_line.push_str(" expanded=true");
+    }
+    if let Some(exclude_from_sync) = pane_layout.exclude_from_sync {
+        pane_line.push_str(&format!(" exclude_from_sync={}", exclude_from_sync));
+    }
+    let (run_properties, run_children) = stringify_run(&pane_layout.run, &child_indentation);
+    pane_line.push_str(&run_properties);
+    let pane_children = format!(
+        "{}{}",
+        run_children,
+        stringify_tiled_pane_children(pane_layout, &child_indentation)
+    );
+    stringify_node(&pane_line, &pane_children, indentation)
+}
+
+fn stringify_floating_panes(floating_panes: &[FloatingPaneLayout], indentation: &str) -> String {
+    if floating_panes.is_empty() {
+        return String::new();
+    }
+    let child_indentation = format!("{}    ", indentation);
+    let mut stringified_panes = String::new();
+    for floating_pane in floating_panes {
+        let mut pane_line = String::from("pane");
+        if let Some(name) = &floating_pane.name {
+            pane_line.push_str(&format!(" name={:?}", name));
+        }
+        if let Some(focus) = floating_pane.focus {
+            pane_line.push_str(&format!(" focus={}", focus));
+        }
+        if let Some(pinned) = floating_pane.pinned {
+            pane_line.push_str(&format!(" pinned={}", pinned));
+        }
+        let (run_properties, run_children) =
+            stringify_run(&floating_pane.run, &format!("{}    ", child_indentation));
+        pane_line.push_str(&run_properties);
+        let mut pane_children = String::new();
+        for (name, value) in [
+            ("x", &floating_pane.x),
+            ("y", &floating_pane.y),
+            ("width", &floating_pane.width),
+            ("height", &floating_pane.height),
+        ] {
+            match value {
+                Some(PercentOrFixed::Percent(percent)) => pane_children.push_str(&format!(
+                    "{}    {} \"{}%\"\n",
+                    child_indentation, name, percent
+                )),
+                Some(PercentOrFixed::Fixed(fixed)) => pane_children
+                    .push_str(&format!("{}    {} {}\n", child_indentation, name, fixed)),
+                None => {},
+            }
+        }
+        pane_children.push_str(&run_children);
+        stringified_panes.push_str(&stringify_node(
+            &pane_line,
+            &pane_children,
+            &child_indentation,
+        ));
+    }
+    stringify_node("floating_panes", &stringified_panes, indentation)
+}
+
+// returns the properties to add to the pane line and the children nodes to place inside it
+fn stringify_run(run: &Option<Run>, child_indentation: &str) -> (String, String) {
+    let mut properties = String::new();
+    let mut children = String::new();
+    match run {
+        Some(Run::Command(run_command)) => {
+            properties.push_str(&format!(" command={:?}", run_command.command));
+            if let Some(cwd) = &run_command.cwd {
+                properties.push_str(&format!(" cwd={:?}", cwd));
+            }
+            if !run_command.hold_on_close {
+                properties.push_str(" close_on_exit=true");
+            }
+            if run_command.hold_on_start {
+                properties.push_str(" start_suspended=true");
+            }
+            if !run_command.args.is_empty() {
+                children.push_str(&format!(
+                    "{}args {}\n",
+                    child_indentation,
+                    run_command
+                        .args
+                        .iter()
+                        .map(|arg| format!("{:?}", arg))
+                        .collect::<Vec<String>>()
+                        .join(" ")
+                ));
+            }
+        },
+        Some(Run::EditFile(file_to_edit, _line_number, cwd)) => {
+            // line numbers cannot be specified in layouts
+            properties.push_str(&format!(" edit={:?}", file_to_edit));
+            if let Some(cwd) = cwd {
+                properties.push_str(&format!(" cwd={:?}", cwd));
+            }
+        },
+        Some(Run::Cwd(cwd)) => {
+            properties.push_str(&format!(" cwd={:?}", cwd));
+        },
+        Some(Run::Plugin(run_plugin)) => {
+            let location = match &run_plugin.location {
+                RunPluginLocation::File(path) => format!("file:{}", path.display()),
+                RunPluginLocation::Zellij(tag) => format!("zellij:{}", tag),
+            };
+            let mut plugin_line = format!("plugin location={:?}", location);
+            if run_plugin._allow_exec_host_cmd {
+                plugin_line.push_str(" _allow_exec_host_cmd=true");
+            }
+            children.push_str(&format!("{}{}\n", child_indentation, plugin_line));
+        },
+        None => {},
+    }
+    (properties, children)
+}
@@ -1,15 +1,17 @@
+mod kdl_action_serializer;
 mod kdl_layout_parser;
+mod kdl_layout_serializer;
 use crate::data::{Direction, InputMode, Key, Palette, PaletteColor, Resize};
 use crate::envs::EnvironmentVariables;
 use crate::input::config::{Config, ConfigError, KdlError};
 use crate::input::keybinds::Keybinds;
 use crate::input::layout::{Layout, RunPlugin, RunPluginLocation};
-use crate::input::options::{Clipboard, OnForceClose, Options};
+use crate::input::options::{Clipboard, OnForceClose, Options, TimedModeKeypress};
 use crate::input::plugins::{PluginConfig, PluginTag, PluginType, PluginsConfig};
 use crate::input::theme::{FrameConfig, Theme, Themes, UiConfig};
 use crate::setup::{find_default_config_dir, get_layout_dir};
 use kdl_layout_parser::KdlLayoutParser;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use strum::IntoEnumIterator;
 
 use miette::NamedSource;
@@ -36,6 +38,8 @@ macro_rules! parse_kdl_action_arguments {
                 "Quit" => Ok(Action::Quit),
                 "FocusNextPane" => Ok(Action::FocusNextPane),
                 "FocusPreviousPane" => Ok(Action::FocusPreviousPane),
+                "FocusNextFloatingPane" => Ok(Action::FocusNextFloatingPane),
+                "FocusPreviousFloatingPane" => Ok(Action::FocusPreviousFloatingPane),
                 "SwitchFocus" => Ok(Action::SwitchFocus),
                 "EditScrollback" => Ok(Action::EditScrollback),
                 "ScrollUp" => Ok(Action::ScrollUp),
@@ -43,30 +47,64 @@ macro_rules! parse_kdl_action_arguments {
                 "ScrollToBottom" => Ok(Action::ScrollToBottom),
                 "ScrollToTop" => Ok(Action::ScrollToTop),
                 "PageScrollUp" => Ok(Action::PageScrollUp),
+                "MoveToPreviousPrompt" => Ok(Action::MoveToPreviousPrompt),
+                "MoveToNextPrompt" => Ok(Action::MoveToNextPrompt),
+                "TogglePaneLineWrap" => Ok(Action::TogglePaneLineWrap),
                 "PageScrollDown" => Ok(Action::PageScrollDown),
                 "HalfPageScrollUp" => Ok(Action::HalfPageScrollUp),
                 "HalfPageScrollDown" => Ok(Action::HalfPageScrollDown),
                 "ToggleFocusFullscreen" => Ok(Action::ToggleFocusFullscreen),
                 "TogglePaneFrames" => Ok(Action::TogglePaneFrames),
+                "ToggleFocusedPaneFrame" => Ok(Action::ToggleFocusedPaneFrame),
+                "ToggleRuler" => Ok(Action::ToggleRuler),
+                "ToggleZenMode" => Ok(Action::ToggleZenMode),
                 "ToggleActiveSyncTab" => Ok(Action::ToggleActiveSyncTab),
+                "ToggleActiveSyncSession" => Ok(Action::ToggleActiveSyncSession),
+                "RestartPaneCommand" => Ok(Action::RestartPaneCommand),
                 "TogglePaneEmbedOrFloating" => Ok(Action::TogglePaneEmbedOrFloating),
+                "EmbedAllFloatingPanes" => Ok(Action::EmbedAllFloatingPanes),
+                "FloatAllTiledPanes" => Ok(Action::FloatAllTiledPanes),
+                "StopTabTour" => Ok(Action::StopTabTour),
+                "FocusMostRecentlyActivePane" => Ok(Action::FocusMostRecentlyActivePane),
+                "ToggleDefaultSplitDirection" => Ok(Action::ToggleDefaultSplitDirection),
+                "PeekPaneFloating" => Ok(Action::PeekPaneFloating),
+                "TogglePanePinned" => Ok(Action::TogglePanePinned),
+                "BalancePanes" => Ok(Action::BalancePanes),
                 "ToggleFloatingPanes" => Ok(Action::ToggleFloatingPanes),
                 "CloseFocus" => Ok(Action::CloseFocus),
+                "CloseFocusSmart" => Ok(Action::CloseFocusSmart),
+                "ClearPaneName" => Ok(Action::ClearPaneName),
                 "UndoRenamePane" => Ok(Action::UndoRenamePane),
                 "NoOp" => Ok(Action::NoOp),
                 "GoToNextTab" => Ok(Action::GoToNextTab),
                 "GoToPreviousTab" => Ok(Action::GoToPreviousTab),
+                "MoveTabLeft" => Ok(Action::MoveTabLeft),
+                "MoveTabRight" => Ok(Action::MoveTabRight),
+                "BreakPane" => Ok(Action::BreakPane),
+                "BreakPaneLeft" => Ok(Action::BreakPaneLeft),
+                "BreakPaneRight" => Ok(Action::BreakPaneRight),
                 "CloseTab" => Ok(Action::CloseTab),
+                "TogglePinTab" => Ok(Action::TogglePinTab),
                 "ToggleTab" => Ok(Action::ToggleTab),
+                "RestoreTabFocus" => Ok(Action::RestoreTabFocus),
+                "ClearTabName" => Ok(Action::ClearTabName),
                 "UndoRenameTab" => Ok(Action::UndoRenameTab),
                 "Detach" => Ok(Action::Detach),
                 "Copy" => Ok(Action::Copy),
+                "CopyNormalized" => Ok(Action::CopyNormalized),
+                "CopyLastCommandOutput" => Ok(Action::CopyLastCommandOutput),
                 "Confirm" => Ok(Action::Confirm),
                 "Deny" => Ok(Action::Deny),
                 "ToggleMouseMode" => Ok(Action::ToggleMouseMode),
                 "PreviousSwapLayout" => Ok(Action::PreviousSwapLayout),
                 "NextSwapLayout" => Ok(Action::NextSwapLayout),
                 "Clear" => Ok(Action::ClearScreen),
+                "ClearScrollback" => Ok(Action::ClearScrollback),
+                "RotatePanesForward" => Ok(Action::RotatePanesForward),
+                "RotatePanesBackward" => Ok(Action::RotatePanesBackward),
+                "EnterResizeLoop" => Ok(Action::EnterResizeLoop),
+                "ExitResizeLoop" => Ok(Action::ExitResizeLoop),
+                "DuplicateFloatingPane" => Ok(Action::DuplicateFloatingPane),
                 _ => Err(ConfigError::new_kdl_error(
                     format!("Unsupported action: {:?}", $action_name),
                     $action_node.span().offset(),
@@ -358,9 +396,13 @@ impl Action {
     ) -> Result<Self, ConfigError> {
         match action_name {
             "Write" => Ok(Action::Write(bytes)),
+            // a lone 0 used to clear the name, it is still found in configs written back then
+            "PaneNameInput" if bytes == [0] => Ok(Action::ClearPaneName),
+            "TabNameInput" if bytes == [0] => Ok(Action::ClearTabName),
             "PaneNameInput" => Ok(Action::PaneNameInput(bytes)),
             "TabNameInput" => Ok(Action::TabNameInput(bytes)),
             "SearchInput" => Ok(Action::SearchInput(bytes)),
+            "SearchToQuickfix" => Ok(Action::SearchToQuickfix(bytes)),
             "GoToTab" => {
                 let tab_index = *bytes.get(0).ok_or_else(|| {
                     ConfigError::new_kdl_error(
@@ -371,6 +413,70 @@ impl Action {
                 })? as u32;
                 Ok(Action::GoToTab(tab_index))
             },
+            "MoveTabToIndex" => {
+                let tab_index = *bytes.get(0).ok_or_else(|| {
+                    ConfigError::new_kdl_error(
+                        format!("Missing tab index"),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )
+                })? as u32;
+                Ok(Action::MoveTabToIndex(tab_index))
+            },
+            "MovePaneToTab" => {
+                let tab_index = *bytes.get(0).ok_or_else(|| {
+                    ConfigError::new_kdl_error(
+                        format!("Missing tab index"),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )
+                })? as u32;
+                Ok(Action::MovePaneToTab(tab_index))
+            },
+            "ToggleOverlayTab" => {
+                let tab_index = *bytes.get(0).ok_or_else(|| {
+                    ConfigError::new_kdl_error(
+                        format!("Missing tab index"),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )
+                })? as u32;
+                Ok(Action::ToggleOverlayTab(tab_index))
+            },
+            "GoToSwapLayout" => {
+                let index = *bytes.get(0).ok_or_else(|| {
+                    ConfigError::new_kdl_error(
+                        format!("Missing swap layout index"),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )
+                })? as usize;
+                Ok(Action::GoToSwapLayout(index))
+            },
+            "SetRepeatCount" => {
+                let count = *bytes.get(0).ok_or_else(|| {
+                    ConfigError::new_kdl_error(
+                        format!("Missing repeat count"),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )
+                })? as u32;
+                Ok(Action::SetRepeatCount(count))
+            },
+            "ScrollUpBy" | "ScrollDownBy" => {
+                let lines = *bytes.get(0).ok_or_else(|| {
+                    ConfigError::new_kdl_error(
+                        format!("Missing amount of lines to scroll"),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )
+                })? as usize;
+                if action_name == "ScrollUpBy" {
+                    Ok(Action::ScrollUpBy(lines))
+                } else {
+                    Ok(Action::ScrollDownBy(lines))
+                }
+            },
             _ => Err(ConfigError::new_kdl_error(
                 "Failed to parse action".into(),
                 action_node.span().offset(),
@@ -385,6 +491,9 @@ impl Action {
     ) -> Result<Self, ConfigError> {
         match action_name {
             "WriteChars" => Ok(Action::WriteChars(string)),
+            "PasteText" => Ok(Action::PasteText(string)),
+            "CycleFocusByCommand" => Ok(Action::CycleFocusByCommand(string)),
+            "ApplySwapLayoutByName" => Ok(Action::ApplySwapLayoutByName(string)),
             "SwitchToMode" => match InputMode::from_str(string.as_str()) {
                 Ok(input_mode) => Ok(Action::SwitchToMode(input_mode)),
                 Err(_e) => {
@@ -395,6 +504,16 @@ impl Action {
                     ))
                 },
             },
+            "ShowModeHelp" => match InputMode::from_str(string.as_str()) {
+                Ok(input_mode) => Ok(Action::ShowModeHelp(input_mode)),
+                Err(_e) => {
+                    return Err(ConfigError::new_kdl_error(
+                        format!("Unknown InputMode '{}'", string),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    ))
+                },
+            },
             "Resize" => {
                 let mut resize: Option<Resize> = None;
                 let mut direction: Option<Direction> = None;
@@ -454,7 +573,25 @@ impl Action {
                 }
             },
             "MovePaneBackwards" => Ok(Action::MovePaneBackwards),
-            "DumpScreen" => Ok(Action::DumpScreen(string, false)),
+            "SelectAll" => match string.as_str() {
+                "" => Ok(Action::SelectAll(false)),
+                "viewport" => Ok(Action::SelectAll(true)),
+                _ => Err(ConfigError::new_kdl_error(
+                    format!(
+                        "SelectAll expects no argument or \"viewport\", found: '{}'",
+                        string
+                    ),
+                    action_node.span().offset(),
+                    action_node.span().len(),
+                )),
+            },
+            "DumpScreen" => Ok(Action::DumpScreen(string, false, false, false)),
+            "DumpScreenSvg" => Ok(Action::DumpScreenSvg(string, false)),
+            "DumpScreenJsonl" => Ok(Action::DumpScreenJsonl(string, false)),
+            "DumpScreenHtml" => Ok(Action::DumpScreenHtml(string, false)),
+            "SetClientLayout" => Ok(Action::SetClientLayout(PathBuf::from(string))),
+            "SetModeReportSocket" => Ok(Action::SetModeReportSocket(PathBuf::from(string))),
+            "CopyThrough" => Ok(Action::CopyThrough(string)),
             "NewPane" => {
                 if string.is_empty() {
                     return Ok(Action::NewPane(None, None));
@@ -469,6 +606,20 @@ impl Action {
                     Ok(Action::NewPane(Some(direction), None))
                 }
             },
+            "DuplicatePane" => {
+                if string.is_empty() {
+                    return Ok(Action::DuplicatePane(None));
+                } else {
+                    let direction = Direction::from_str(string.as_str()).map_err(|_| {
+                        ConfigError::new_kdl_error(
+                            format!("Invalid direction: '{}'", string),
+                            action_node.span().offset(),
+                            action_node.span().len(),
+                        )
+                    })?;
+                    Ok(Action::DuplicatePane(Some(direction)))
+                }
+            },
             "SearchToggleOption" => {
                 let toggle_option = SearchOption::from_str(string.as_str()).map_err(|_| {
                     ConfigError::new_kdl_error(
@@ -490,6 +641,113 @@ impl Action {
                     })?;
                 Ok(Action::Search(search_direction))
             },
+            "BalancePanesAxis" => match string.as_str() {
+                "Vertical" | "vertical" => Ok(Action::BalancePanesAxis(true)),
+                "Horizontal" | "horizontal" => Ok(Action::BalancePanesAxis(false)),
+                _ => Err(ConfigError::new_kdl_error(
+                    format!("Invalid axis: '{}'", string),
+                    action_node.span().offset(),
+                    action_node.span().len(),
+                )),
+            },
+            "MoveFloatingPaneTo" | "ResizeFloatingPaneTo" => {
+                // eg. MoveFloatingPaneTo "10 5" or ResizeFloatingPaneTo "80 20"
+                let mut numbers = string
+                    .split_whitespace()
+                    .map(|number| number.parse::<usize>().ok());
+                match (numbers.next(), numbers.next(), numbers.next()) {
+                    (Some(Some(x)), Some(Some(y)), None) if action_name == "MoveFloatingPaneTo" => {
+                        Ok(Action::MoveFloatingPaneTo { x, y })
+                    },
+                    (Some(Some(cols)), Some(Some(rows)), None) => {
+                        Ok(Action::ResizeFloatingPaneTo { cols, rows })
+                    },
+                    _ => Err(ConfigError::new_kdl_error(
+                        format!("{} expects two numbers, found: '{}'", action_name, string),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )),
+                }
+            },
+            "SearchJumpToMatch" => match string.parse::<usize>() {
+                Ok(match_index) => Ok(Action::SearchJumpToMatch(match_index)),
+                Err(_) => Err(ConfigError::new_kdl_error(
+                    format!(
+                        "SearchJumpToMatch expects the index of a match, found: '{}'",
+                        string
+                    ),
+                    action_node.span().offset(),
+                    action_node.span().len(),
+                )),
+            },
+            "ScrollToLine" => match string.parse::<usize>() {
+                Ok(line) => Ok(Action::ScrollToLine(line)),
+                Err(_) => Err(ConfigError::new_kdl_error(
+                    format!("ScrollToLine expects a line number, found: '{}'", string),
+                    action_node.span().offset(),
+                    action_node.span().len(),
+                )),
+            },
+            "SetScrollbackLines" => match string.parse::<usize>() {
+                Ok(lines) => Ok(Action::SetScrollbackLines(lines)),
+                Err(_) => Err(ConfigError::new_kdl_error(
+                    format!(
+                        "SetScrollbackLines expects a number of lines, found: '{}'",
+                        string
+                    ),
+                    action_node.span().offset(),
+                    action_node.span().len(),
+                )),
+            },
+            "DetachWhenIdle" => match string.parse::<u64>() {
+                Ok(timeout_secs) => Ok(Action::DetachWhenIdle(timeout_secs)),
+                Err(_) => Err(ConfigError::new_kdl_error(
+                    format!(
+                        "DetachWhenIdle expects a timeout in seconds, found: '{}'",
+                        string
+                    ),
+                    action_node.span().offset(),
+                    action_node.span().len(),
+                )),
+            },
+            "SwitchToModeTimed" => {
+                // eg. SwitchToModeTimed "locked 2000"
+                let mut words = string.split_whitespace();
+                let input_mode = words.next().and_then(|w| InputMode::from_str(w).ok());
+                let timeout_ms = words.next().and_then(|w| w.parse::<u64>().ok());
+                match (input_mode, timeout_ms, words.next()) {
+                    (Some(input_mode), Some(timeout_ms), None) => {
+                        Ok(Action::SwitchToModeTimed(input_mode, timeout_ms))
+                    },
+                    _ => Err(ConfigError::new_kdl_error(
+                        format!(
+                            "SwitchToModeTimed expects an input mode and a timeout in ms, found: '{}'",
+                            string
+                        ),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )),
+                }
+            },
+            "StartTabTour" => {
+                // eg. StartTabTour "3000 1 2 3" (the delay in ms, then the tab indices)
+                let mut words = string.split_whitespace();
+                let delay_ms = words.next().and_then(|w| w.parse::<u64>().ok());
+                let tab_indices: Option<Vec<u32>> = words.map(|w| w.parse::<u32>().ok()).collect();
+                match (delay_ms, tab_indices) {
+                    (Some(delay_ms), Some(tab_indices)) if !tab_indices.is_empty() => {
+                        Ok(Action::StartTabTour(tab_indices, delay_ms))
+                    },
+                    _ => Err(ConfigError::new_kdl_error(
+                        format!(
+                            "StartTabTour expects a delay in ms followed by tab indices, found: '{}'",
+                            string
+                        ),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )),
+                }
+            },
             _ => Err(ConfigError::new_kdl_error(
                 format!("Unsupported action: {}", action_name),
                 action_node.span().offset(),
@@ -630,6 +888,12 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
             "FocusPreviousPane" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
+            "FocusNextFloatingPane" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "FocusPreviousFloatingPane" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "SwitchFocus" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
             "EditScrollback" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
@@ -648,6 +912,15 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
             "PageScrollDown" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
+            "MoveToPreviousPrompt" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "TogglePaneLineWrap" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "MoveToNextPrompt" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "HalfPageScrollUp" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
@@ -660,16 +933,59 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
             "TogglePaneFrames" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
+            "ToggleFocusedPaneFrame" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "ToggleRuler" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "ToggleZenMode" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "ToggleActiveSyncTab" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
+            "ToggleActiveSyncSession" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "RestartPaneCommand" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "TogglePaneEmbedOrFloating" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
+            "EmbedAllFloatingPanes" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "FloatAllTiledPanes" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "PeekPaneFloating" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "TogglePanePinned" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "StopTabTour" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
+            "FocusMostRecentlyActivePane" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "BalancePanes" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "ToggleDefaultSplitDirection" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "ToggleFloatingPanes" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
             "CloseFocus" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
+            "CloseFocusSmart" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "ClearPaneName" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "UndoRenamePane" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
@@ -678,8 +994,28 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
             "GoToPreviousTab" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
+            "MoveTabLeft" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
+            "MoveTabRight" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "BreakPane" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
+            "BreakPaneLeft" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "BreakPaneRight" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "CloseTab" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
+            "TogglePinTab" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "ToggleTab" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
+            "RestoreTabFocus" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "ClearTabName" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "UndoRenameTab" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
@@ -688,7 +1024,31 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
             },
             "Detach" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
             "Copy" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
+            "CopyNormalized" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "CopyLastCommandOutput" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "Clear" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
+            "ClearScrollback" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "RotatePanesForward" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "RotatePanesBackward" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "EnterResizeLoop" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "DuplicateFloatingPane" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "ExitResizeLoop" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "Confirm" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
             "Deny" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
             "Write" => parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action),
@@ -697,11 +1057,31 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 action_arguments,
                 kdl_action
             ),
+            "PasteText" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "CycleFocusByCommand" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "ApplySwapLayoutByName" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
             "SwitchToMode" => parse_kdl_action_char_or_string_arguments!(
                 action_name,
                 action_arguments,
                 kdl_action
             ),
+            "ShowModeHelp" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
             "Search" => parse_kdl_action_char_or_string_arguments!(
                 action_name,
                 action_arguments,
@@ -732,6 +1112,11 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 action_arguments,
                 kdl_action
             ),
+            "SelectAll" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
             "MovePaneBackwards" => parse_kdl_action_char_or_string_arguments!(
                 action_name,
                 action_arguments,
@@ -742,18 +1127,53 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 action_arguments,
                 kdl_action
             ),
+            "DumpScreenSvg" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "DumpScreenJsonl" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "DumpScreenHtml" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "SetClientLayout" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "SetModeReportSocket" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "CopyThrough" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
             "NewPane" => parse_kdl_action_char_or_string_arguments!(
                 action_name,
                 action_arguments,
                 kdl_action
             ),
+            "DuplicatePane" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
             "PaneNameInput" => {
                 parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action)
             },
             "NewTab" => {
                 let command_metadata = action_children.iter().next();
                 if command_metadata.is_none() {
-                    return Ok(Action::NewTab(None, vec![], None, None, None));
+                    return Ok(Action::NewTab(None, vec![], None, None, None, false, false));
                 }
 
                 let current_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
@@ -769,6 +1189,12 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 let name = command_metadata
                     .and_then(|c_m| kdl_child_string_value_for_entry(c_m, "name"))
                     .map(|name_string| name_string.to_string());
+                let empty = command_metadata
+                    .and_then(|c_m| kdl_child_bool_value_for_entry(c_m, "empty"))
+                    .unwrap_or(false);
+                if empty {
+                    return Ok(Action::NewTab(None, vec![], None, None, name, true, false));
+                }
 
                 let layout_dir = config_options
                     .layout_dir
@@ -816,6 +1242,8 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                         None,
                         None,
                         name,
+                        false,
+                        false,
                     ))
                 } else {
                     let (layout, floating_panes_layout) = layout.new_tab();
@@ -826,21 +1254,89 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                         None,
                         None,
                         name,
+                        false,
+                        false,
                     ))
                 }
             },
             "GoToTab" => parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action),
+            "MoveTabToIndex" => {
+                parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "MovePaneToTab" => {
+                parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "ToggleOverlayTab" => {
+                parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "ScrollUpBy" => {
+                parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "SetRepeatCount" => {
+                parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "GoToSwapLayout" => {
+                parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "ScrollDownBy" => {
+                parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action)
+            },
             "TabNameInput" => {
                 parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action)
             },
             "SearchInput" => {
                 parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action)
             },
+            "SearchToQuickfix" => {
+                parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action)
+            },
             "SearchToggleOption" => parse_kdl_action_char_or_string_arguments!(
                 action_name,
                 action_arguments,
                 kdl_action
             ),
+            "BalancePanesAxis" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "MoveFloatingPaneTo" | "ResizeFloatingPaneTo" => {
+                parse_kdl_action_char_or_string_arguments!(
+                    action_name,
+                    action_arguments,
+                    kdl_action
+                )
+            },
+            "SearchJumpToMatch" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "ScrollToLine" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "SetScrollbackLines" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "DetachWhenIdle" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "SwitchToModeTimed" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
+            "StartTabTour" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
             "Run" => {
                 let arguments = action_arguments.iter().copied();
                 let mut args = kdl_arguments_that_are_strings(arguments)?;
@@ -873,6 +1369,15 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                     direction,
                     hold_on_close,
                     hold_on_start,
+                    watch_paths: vec![],
+                    output_socket: None,
+                    pin_matching: None,
+                    cwd_from_focus: false,
+                    no_frame: false,
+                    capture_exit: None,
+                    env: BTreeMap::new(),
+                    fixed_pty_size: None,
+                    run_in_shell: None,
                 };
                 Ok(Action::Run(run_command_action))
             },
@@ -897,11 +1402,20 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 let run_plugin = RunPlugin {
                     location,
                     _allow_exec_host_cmd: false,
+                    configuration: Default::default(),
                 };
                 Ok(Action::LaunchOrFocusPlugin(run_plugin, should_float))
             },
             "PreviousSwapLayout" => Ok(Action::PreviousSwapLayout),
             "NextSwapLayout" => Ok(Action::NextSwapLayout),
+            "Sequence" => {
+                let actions = action_children
+                    .iter()
+                    .flat_map(|children| children.nodes())
+                    .map(|kdl_action| Action::try_from((kdl_action, config_options)))
+                    .collect::<Result<Vec<Action>, ConfigError>>()?;
+                Ok(Action::sequence(actions))
+            },
             _ => Err(ConfigError::new_kdl_error(
                 format!("Unsupported action: {}", action_name).into(),
                 kdl_action.span().offset(),
@@ -1377,6 +1891,21 @@ impl Options {
         let attach_to_session =
             kdl_property_first_arg_as_bool_or_error!(kdl_options, "attach_to_session")
                 .map(|(v, _)| v);
+        let timed_mode_keypress =
+            match kdl_property_first_arg_as_string_or_error!(kdl_options, "timed_mode_keypress") {
+                Some((string, entry)) => {
+                    Some(TimedModeKeypress::from_str(string).map_err(|_| {
+                        kdl_parsing_error!(
+                            format!("Invalid value for timed_mode_keypress: '{}'", string),
+                            entry
+                        )
+                    })?)
+                },
+                None => None,
+            };
+        let resize_loop_step =
+            kdl_property_first_arg_as_i64_or_error!(kdl_options, "resize_loop_step")
+                .map(|(resize_loop_step, _entry)| resize_loop_step as usize);
         Ok(Options {
             simplified_ui,
             theme,
@@ -1398,6 +1927,8 @@ impl Options {
             session_name,
             attach_to_session,
             auto_layout,
+            timed_mode_keypress,
+            resize_loop_step,
         })
     }
 }
@@ -1423,6 +1954,7 @@ impl RunPlugin {
         Ok(RunPlugin {
             _allow_exec_host_cmd,
             location,
+            configuration: Default::default(),
         })
     }
 }
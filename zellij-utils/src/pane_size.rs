@@ -42,6 +42,16 @@ pub struct SizeInPixels {
     pub width: usize,
 }
 
+/// Bounds that a pane's size is kept within when it is resized or laid out, None meaning the
+/// pane is unbounded in that direction. Minimum sizes take precedence over maximum ones.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct SizeConstraints {
+    pub min_cols: Option<usize>,
+    pub max_cols: Option<usize>,
+    pub min_rows: Option<usize>,
+    pub max_rows: Option<usize>,
+}
+
 #[derive(Eq, Clone, Copy, PartialEq, Debug, Serialize, Deserialize, Hash)]
 pub struct Dimension {
     pub constraint: Constraint,